@@ -53,6 +53,12 @@ pub fn surreal_object_trait_derive(input: TokenStream) -> TokenStream {
     models::object::generate_fields_getter_trait(input)
 }
 
+#[proc_macro_derive(View, attributes(orm))]
+pub fn surreal_view_trait_derive(input: TokenStream) -> TokenStream {
+    #[allow(clippy::all)]
+    models::view::generate_fields_getter_trait(input)
+}
+
 #[proc_macro_derive(TableResources, attributes(orm))]
 pub fn surreal_table_resources_derive(input: TokenStream) -> TokenStream {
     #[allow(clippy::all)]
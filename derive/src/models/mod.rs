@@ -7,3 +7,4 @@
 pub mod edge;
 pub mod node;
 pub mod object;
+pub mod view;
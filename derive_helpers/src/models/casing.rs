@@ -53,3 +53,40 @@ impl std::ops::Deref for StructLevelCasing {
         &self.0
     }
 }
+
+impl CaseString {
+    /// Renames an enum variant the way serde's `rename_all` does e.g
+    /// `SoftDeleted` becomes `soft_deleted` with `snake_case`.
+    pub fn apply_to_variant(&self, variant: &str) -> String {
+        match self {
+            CaseString::None | CaseString::Pascal => variant.to_string(),
+            CaseString::Lower => variant.to_ascii_lowercase(),
+            CaseString::Upper => variant.to_ascii_uppercase(),
+            CaseString::Camel => {
+                let mut chars = variant.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_ascii_lowercase().to_string() + chars.as_str()
+                })
+            }
+            CaseString::Snake => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            CaseString::ScreamingSnake => CaseString::Snake
+                .apply_to_variant(variant)
+                .to_ascii_uppercase(),
+            CaseString::Kebab => CaseString::Snake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+            CaseString::ScreamingKebab => CaseString::ScreamingSnake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+}
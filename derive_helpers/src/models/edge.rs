@@ -43,6 +43,36 @@ impl Deref for EdgeToken {
     }
 }
 
+impl EdgeToken {
+    /// Infers the `TYPE RELATION IN x OUT y` clause of the edge table from the
+    /// node types its `in` and `out` fields link to. It is only rendered in the 2.x dialect.
+    fn get_relation_type_token(
+        &self,
+        table_attrs: &ModelAttributes,
+    ) -> ExtractorResult<Option<TokenStream>> {
+        let crate_name = get_crate_name(false);
+        let casing = table_attrs.casing()?;
+        let mut in_node = None;
+        let mut out_node = None;
+
+        for field_receiver in table_attrs.fields()? {
+            let db_field_name = field_receiver.db_field_name(&casing)?.to_string();
+            match db_field_name.trim_start_matches("r#") {
+                "in" => in_node = field_receiver.link_one.clone(),
+                "out" => out_node = field_receiver.link_one.clone(),
+                _ => {}
+            }
+        }
+
+        Ok(in_node.zip(out_node).map(|(in_node, out_node)| {
+            quote!(.type_relation(
+                <#in_node as #crate_name::Model>::table(),
+                <#out_node as #crate_name::Model>::table()
+            ))
+        }))
+    }
+}
+
 impl ToTokens for EdgeToken {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let crate_name = get_crate_name(false);
@@ -70,13 +100,37 @@ impl ToTokens for EdgeToken {
             _____struct_marker_ident,
             ..
         } = VariablesModelMacro::new();
-        let table_definitions = match self.get_table_definition_token() {
+        let table_attrs = ModelAttributes::from_edge(self);
+        let relation_type = match self.get_relation_type_token(&table_attrs) {
+            Ok(relation_type) => relation_type,
+            Err(err) => return tokens.extend(err.write_errors()),
+        };
+        let table_definitions = match self
+            .get_table_definition_token_with_methods(relation_type.into_iter().collect())
+        {
             Ok(table_definitions) => table_definitions,
             Err(err) => return tokens.extend(err.write_errors()),
         };
-
-        let table_attrs = ModelAttributes::from_edge(self);
         let explicit_generics = table_attrs.explicit_fully_qualified_generics_path();
+        let tenant_key = match table_attrs.tenant_key_token() {
+            Ok(tenant_key) => tenant_key,
+            Err(err) => return tokens.extend(err.write_errors()),
+        };
+        let old_table = match table_attrs.old_table_token() {
+            Ok(old_table) => old_table,
+            Err(err) => return tokens.extend(err.write_errors()),
+        };
+        let define_indexes = match table_attrs.define_indexes_token() {
+            Ok(define_indexes) => define_indexes,
+            Err(err) => return tokens.extend(err.write_errors()),
+        };
+        let define_events = match table_attrs.define_events_token() {
+            Ok(define_events) => define_events,
+            Err(err) => return tokens.extend(err.write_errors()),
+        };
+        if let Err(err) = table_attrs.repository_token() {
+            return tokens.extend(err.write_errors());
+        }
         let code_gen = match Codegen::parse_fields(&table_attrs) {
             Ok(props) => props,
             Err(err) => return tokens.extend(err.write_errors()),
@@ -92,7 +146,6 @@ impl ToTokens for EdgeToken {
             connection_with_field_appended,
             record_link_fields_methods,
             schema_struct_fields_names_kv_empty,
-            field_definitions,
             serialized_ident_struct_partial_init_fields,
             serialized_fmt_db_field_names_instance: serializable_fields,
             linked_fields,
@@ -104,7 +157,6 @@ impl ToTokens for EdgeToken {
             struct_partial_associated_functions,
             renamed_serialized_fields_kv,
             table_id_type,
-            field_metadata,
             ..
         } = &code_gen;
 
@@ -137,6 +189,15 @@ impl ToTokens for EdgeToken {
         } = code_gen.common_idents();
 
         let serializable_fields_count = serializable_fields.len();
+        let field_lists = code_gen.field_lists_with_flattened(quote!(Self::table()));
+        let define_referential_actions = code_gen.define_referential_actions_token();
+        let field_name_consts = code_gen.field_name_consts_token();
+        let reverse_links = code_gen.reverse_links_token();
+        let schema_reverse_method =
+            code_gen.schema_reverse_method_token(quote!(super::#struct_name_ident #ty_generics));
+        let serializable_fields_list = &field_lists.serializable_fields;
+        let field_definitions_list = &field_lists.field_definitions;
+        let field_metadata_list = &field_lists.field_metadata;
         let struct_partial_ident = struct_name_ident.partial_ident();
         let struct_partial_builder_ident = struct_name_ident.partial_builder_ident();
 
@@ -146,6 +207,7 @@ impl ToTokens for EdgeToken {
 
                 impl #impl_generics #struct_name_ident #ty_generics #where_clause {
                       // pub const ALLOWED_FIELDS: [&'static str; 2] = ["name", "strength"];
+                    pub const TABLE: &'static str = #table_str;
                     pub const fn __get_serializable_field_names() -> [&'static str; #serializable_fields_count] {
                         // this is used for static checking, so, we dont need the fields
                         [#( stringify!(#serializable_fields)), *]
@@ -241,7 +303,7 @@ impl ToTokens for EdgeToken {
                     }
 
                     fn get_serializable_fields() -> ::std::vec::Vec<#crate_name::Field> {
-                        return ::std::vec![#( #serializable_fields), *]
+                        return #serializable_fields_list
                     }
 
                     fn get_linked_fields() -> ::std::vec::Vec<#crate_name::Field> {
@@ -269,19 +331,29 @@ impl ToTokens for EdgeToken {
                     }
 
                     fn define_fields() -> ::std::vec::Vec<#crate_name::Raw> {
-                        ::std::vec![
-                           #( #field_definitions), *
-                        ]
+                        #field_definitions_list
                     }
 
                     fn get_field_meta() -> ::std::vec::Vec<#crate_name::FieldMetadata> {
-                        return ::std::vec![#( #field_metadata), *]
+                        return #field_metadata_list
                     }
+
+                    #tenant_key
+
+                    #old_table
+
+                    #define_indexes
+
+                    #define_events
+
+                    #define_referential_actions
                 }
 
                 #[allow(non_snake_case)]
                 pub mod #module_name_rexported {
                     pub use super::#module_name_internal::#_____schema_def::__Schema__ as Schema;
+
+                    #field_name_consts
                 }
 
 
@@ -430,6 +502,8 @@ impl ToTokens for EdgeToken {
                         }
 
                         #( #record_link_fields_methods) *
+
+                        #schema_reverse_method
                     }
                 }
 
@@ -438,6 +512,8 @@ impl ToTokens for EdgeToken {
             fn #test_function_name #impl_generics() #where_clause {
                 #( #static_assertions) *
             }
+
+            #reverse_links
         ));
     }
 }
@@ -0,0 +1,46 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use proc_macros_helpers::get_crate_name;
+use quote::{quote, ToTokens};
+
+/// The referential action of a link field e.g `#[orm(link_one = Company, on_delete = "cascade")]`
+#[derive(Debug, Clone, Copy)]
+pub enum OnDelete {
+    Cascade,
+    Restrict,
+    SetNull,
+    Unset,
+}
+
+impl FromMeta for OnDelete {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "cascade" => Ok(Self::Cascade),
+            "restrict" => Ok(Self::Restrict),
+            "set_null" => Ok(Self::SetNull),
+            "unset" => Ok(Self::Unset),
+            _ => Err(darling::Error::custom(format!(
+                "Unknown on_delete action `{value}`. Expected one of cascade, restrict, set_null or unset"
+            ))),
+        }
+    }
+}
+
+impl ToTokens for OnDelete {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let crate_name = get_crate_name(false);
+        let action = match self {
+            OnDelete::Cascade => quote!(Cascade),
+            OnDelete::Restrict => quote!(Restrict),
+            OnDelete::SetNull => quote!(SetNull),
+            OnDelete::Unset => quote!(Unset),
+        };
+        tokens.extend(quote!(#crate_name::OnDelete::#action));
+    }
+}
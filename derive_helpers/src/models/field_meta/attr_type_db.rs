@@ -125,7 +125,7 @@ impl FieldTypeDb {
             FieldType::Bytes => {
                 quote!(#crate_name::validators::assert_type_is_bytes::<#field_type>();)
             }
-            FieldType::Union(_) => quote!(),
+            FieldType::Union(_) | FieldType::Literal(_) => quote!(),
             FieldType::Option(_) => {
                 quote!(#crate_name::validators::assert_type_is_option::<#field_type>();)
             }
@@ -180,7 +180,7 @@ impl AttributeValue {
             FieldType::Null => quote!(
                 #crate_name::validators::assert_value_is_null(#value_expr)
             ),
-            FieldType::Union(_) => quote!(),
+            FieldType::Union(_) | FieldType::Literal(_) => quote!(),
             FieldType::Option(_) => quote!(
                 #crate_name::validators::assert_value_is_option(#value_expr)
             ),
@@ -653,6 +653,25 @@ impl CustomType {
         }
     }
 
+    pub fn raw_type_is_offset_datetime(&self) -> bool {
+        self.raw_type_last_segment_is("OffsetDateTime")
+    }
+
+    pub fn raw_type_is_uuid(&self) -> bool {
+        self.raw_type_last_segment_is("Uuid")
+    }
+
+    fn raw_type_last_segment_is(&self, ident: &str) -> bool {
+        match self.into_inner_ref() {
+            syn::Type::Path(type_path) => type_path
+                .path
+                .segments
+                .last()
+                .is_some_and(|last_segment| last_segment.ident == ident),
+            _ => false,
+        }
+    }
+
     pub fn raw_type_is_geometry(&self) -> bool {
         let ty = &self.into_inner_ref();
         match ty {
@@ -5,7 +5,7 @@
 */
 
 use proc_macros_helpers::get_crate_name;
-use quote::{format_ident, quote, ToTokens};
+use quote::{quote, ToTokens};
 use surreal_query_builder::FieldType;
 use syn::{self, spanned::Spanned, Expr};
 
@@ -17,6 +17,9 @@ pub struct FieldTypeInference<'a> {
     pub db_field_name: &'a DbFieldName,
     pub field_ty: &'a CustomType,
     pub relation_type: &'a RelationType,
+    pub nest_enum: Option<&'a NestEnumAttrType>,
+    /// The module of the serde `with` attribute of the field
+    pub serde_with: Option<&'a str>,
     pub model_attrs: &'a ModelAttributes<'a>,
 }
 
@@ -207,32 +210,13 @@ impl<'a> FieldTypeInference<'a> {
                 field_type_db_token: quote!(#crate_name::FieldType::Object).into(),
                 static_assertion_token: quote!(#crate_name::validators::assert_impl_one!(#ty: ::std::convert::Into<#crate_name::sql::Object>);).into(),
             }
-        } else if field_ty.raw_type_is_duration() {
+        } else if self.references_a_nested_enum(field_ty) {
+            // The variants of the enum are only known by its `ObjectEnum` implementation
             DbFieldTypeAstMeta {
-                field_type_db_original: FieldType::Duration,
-                field_type_db_token: quote!(#crate_name::FieldType::Duration).into(),
+                field_type_db_original: FieldType::Any,
+                field_type_db_token: quote!(<#ty as #crate_name::ObjectEnum>::field_type()).into(),
                 static_assertion_token:
-                    quote!(#crate_name::validators::assert_type_is_duration::<#ty>();).into(),
-            }
-        } else if field_ty.raw_type_is_datetime() {
-            DbFieldTypeAstMeta {
-                field_type_db_original: FieldType::Datetime,
-                field_type_db_token: quote!(#crate_name::FieldType::Datetime).into(),
-                static_assertion_token:
-                    quote!(#crate_name::validators::assert_type_is_datetime::<#ty>();).into(),
-            }
-        }
-        // else if field_ty.raw_type_is_geometry() {
-        else if let Some(geo_kind) = field_ty.raw_type_geometry_kind() {
-            // We are intentionally using debug string representation of the geometry kind
-            let geo_kind_name_from_debug_print = format!("{:?}", &geo_kind);
-            let geo_kind_ident = format_ident!("{geo_kind_name_from_debug_print}");
-
-            DbFieldTypeAstMeta {
-                field_type_db_original: FieldType::Geometry(vec![geo_kind]),
-                field_type_db_token: quote!(#crate_name::FieldType::Geometry(::std::vec![#crate_name::GeometryType::#geo_kind_ident])).into(),
-                static_assertion_token:
-                    quote!(#crate_name::validators::assert_type_is_geometry::<#ty>();).into(),
+                    quote!(#crate_name::validators::assert_type_is_enum::<#ty>();).into(),
             }
         } else if let Some(foreign_type) = self.refereces_a_nested_object(&relation_type)? {
             // Guess if its a foreign Table type by comparing the type path segment with the foreign
@@ -250,20 +234,122 @@ impl<'a> FieldTypeInference<'a> {
                     .into(),
                 }
             } else {
-                return Err(syn::Error::new(
-                    ty.span(),
-                    "Could not infer the database type for the field based on the field type in rust provided. Specify by using e.g ty = \"array\"",
-                ).into());
+                self.based_on_surreal_type_impl(field_ty, ty)?
             }
         } else {
+            self.based_on_surreal_type_impl(field_ty, ty)?
+        };
+
+        Ok(meta)
+    }
+
+    /// Defers to the `SurrealType` implementation of the field type. This resolves type aliases,
+    /// re-exports, third party types and user newtypes whose database type cannot be known
+    /// from the tokens alone e.g `chrono::DateTime<Utc>`, `geo::Point` or `rust_decimal::Decimal`.
+    fn based_on_surreal_type_impl(
+        &self,
+        field_ty: &CustomType,
+        ty: &syn::Type,
+    ) -> ExtractorResult<DbFieldTypeAstMeta> {
+        let crate_name = get_crate_name(false);
+
+        if !self.defers_to_surreal_type() {
             return Err(syn::Error::new(
                 ty.span(),
                 "Could not infer the database type for the field based on the field type in rust provided. Specify by using e.g ty = \"array\"",
             )
             .into());
+        }
+
+        let (field_type_db_original, known_type_static_assertion) =
+            self.based_on_known_surreal_type(field_ty, ty)?;
+
+        Ok(DbFieldTypeAstMeta {
+            field_type_db_original,
+            field_type_db_token: quote!(<#ty as #crate_name::SurrealType>::field_type()).into(),
+            static_assertion_token: quote!(
+                #crate_name::validators::assert_type_is_surreal_type::<#ty>();
+                #known_type_static_assertion
+            )
+            .into(),
+        })
+    }
+
+    /// The `SurrealType` implementation is only resolved when the generated code runs. The
+    /// database type of the well known types it is implemented for is also resolved here, so that
+    /// the field type and `value` attribute static assertions still apply to them. Every other
+    /// type is `any` at this point.
+    fn based_on_known_surreal_type(
+        &self,
+        field_ty: &CustomType,
+        ty: &syn::Type,
+    ) -> ExtractorResult<(FieldType, proc_macro2::TokenStream)> {
+        let crate_name = get_crate_name(false);
+
+        let known_type = if field_ty.raw_type_is_offset_datetime() {
+            // `OffsetDateTime` is serialized as a tuple unless an RFC 3339 string is asked for
+            let is_rfc3339 = self
+                .serde_with
+                .is_some_and(|module| module.starts_with("time::serde::rfc3339"));
+
+            if !is_rfc3339 {
+                return Err(syn::Error::new(
+                    ty.span(),
+                    "`time::OffsetDateTime` fields must be serialized as a string. Use #[serde(with = \"time::serde::rfc3339\")]",
+                )
+                .into());
+            }
+
+            (FieldType::String, quote!())
+        } else if field_ty.raw_type_is_datetime() {
+            (
+                FieldType::Datetime,
+                quote!(#crate_name::validators::assert_type_is_datetime::<#ty>();),
+            )
+        } else if field_ty.raw_type_is_duration() {
+            // `chrono::Duration` does not convert to a surrealdb duration so it is not asserted
+            (FieldType::Duration, quote!())
+        } else if field_ty.raw_type_is_uuid() {
+            (
+                FieldType::Uuid,
+                quote!(#crate_name::validators::assert_type_is_uuid::<#ty>();),
+            )
+        } else if let Some(geo_kind) = field_ty.raw_type_geometry_kind() {
+            // `geo::Geometry` and `geo::GeometryCollection` do not convert to a surrealdb geometry either
+            (FieldType::Geometry(vec![geo_kind]), quote!())
+        } else {
+            (FieldType::Any, quote!())
         };
 
-        Ok(meta)
+        Ok(known_type)
+    }
+
+    /// Links, relations and the reserved `id`, `in` and `out` fields are inferred from their
+    /// attributes and names rather than from the `SurrealType` implementation of the field type.
+    fn defers_to_surreal_type(&self) -> bool {
+        let is_reserved_field = self.db_field_name.is_id()
+            || self
+                .db_field_name
+                .is_in_or_out_edge_node(&self.model_attrs.to_data_type());
+
+        !is_reserved_field
+            && matches!(
+                self.relation_type,
+                RelationType::None
+                    | RelationType::List(_)
+                    | RelationType::NestObject(_)
+                    | RelationType::NestArray(_)
+            )
+    }
+
+    /// Checks if the field type is the enum specified with the `nest_enum` attribute
+    fn references_a_nested_enum(&self, field_ty: &CustomType) -> bool {
+        self.nest_enum.is_some_and(|enum_type| {
+            matches!(
+                (field_ty.type_ident(), enum_type.into_inner_ref().type_ident()),
+                (Ok(field_ident), Ok(enum_ident)) if field_ident == enum_ident
+            )
+        })
     }
 
     /// Gets out the foreign/nested object type if the field references a foreign struct
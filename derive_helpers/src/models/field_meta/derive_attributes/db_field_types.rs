@@ -109,6 +109,8 @@ impl MyFieldReceiver {
                 FieldTypeInference {
                     db_field_name: field_name,
                     relation_type: &self.to_relation_type(model_attributes),
+                    nest_enum: self.nest_enum.as_ref(),
+                    serde_with: self.with.as_deref(),
                     field_ty: &field_ty.into_inner(),
                     model_attrs: model_attributes,
                 }
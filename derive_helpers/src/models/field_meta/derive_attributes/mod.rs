@@ -22,6 +22,7 @@ create_ident_wrapper!(FieldIdentNormalized);
 create_ident_wrapper!(FieldNamePascalized);
 create_ident_wrapper!(FieldIdentOriginal);
 create_ident_wrapper!(OldFieldName);
+create_ident_wrapper!(ReverseLinkName);
 
 #[allow(dead_code)]
 #[derive(Clone, Debug, FromField)]
@@ -63,12 +64,23 @@ pub struct MyFieldReceiver {
     #[darling(default)]
     pub(crate) link_many: Option<LinkManyAttrType>,
 
+    /// The action on the linking records when the linked record is deleted
+    #[darling(default)]
+    pub(crate) on_delete: Option<OnDelete>,
+
+    /// The name of the accessor generated on the linked model for the records linking to it
+    #[darling(default)]
+    pub(crate) reverse: Option<ReverseLinkName>,
+
     #[darling(default)]
     pub(crate) nest_array: Option<NestArrayAttrType>,
 
     #[darling(default)]
     pub(crate) nest_object: Option<NestObjectAttrType>,
 
+    #[darling(default)]
+    pub(crate) nest_enum: Option<NestEnumAttrType>,
+
     #[darling(default, rename = "ty")]
     pub(crate) field_type_db: Option<FieldTypeDb>,
 
@@ -87,6 +99,18 @@ pub struct MyFieldReceiver {
     #[darling(default)]
     pub(crate) item_assert: Option<AttributeItemAssert>,
 
+    /// Generates a `DEFINE INDEX` statement for the field
+    #[darling(default)]
+    pub(crate) index: bool,
+
+    /// Generates a `DEFINE INDEX ... UNIQUE` statement for the field
+    #[darling(default)]
+    pub(crate) unique: bool,
+
+    /// The projection of a view field e.g `#[orm(expr = "math::sum(amount)")]`
+    #[darling(default)]
+    pub(crate) expr: Option<ExprOrPath>,
+
     // Serde attributes
     #[darling(default)]
     pub(crate) skip_serializing: bool,
@@ -97,11 +121,14 @@ pub struct MyFieldReceiver {
     #[darling(default)]
     pub(crate) skip: bool,
 
+    #[darling(default)]
+    pub(crate) flatten: bool,
+
     #[darling(default)]
     skip_serializing_if: ::darling::util::Ignored,
 
     #[darling(default)]
-    with: ::darling::util::Ignored,
+    pub(crate) with: Option<String>,
 
     #[darling(default)]
     deserialize_with: ::darling::util::Ignored,
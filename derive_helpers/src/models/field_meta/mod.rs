@@ -4,6 +4,7 @@
  * Copyright (c) 2023 Oyelowo Oyedayo
  */
 
+mod attr_on_delete;
 mod attr_permissions;
 mod attr_relate;
 mod attr_type_db;
@@ -23,6 +24,7 @@ mod token_stream_hashable;
 mod token_wrappers;
 mod type_stripper;
 
+pub use attr_on_delete::*;
 pub use attr_permissions::*;
 pub use attr_relate::*;
 pub use attr_type_db::*;
@@ -97,4 +97,5 @@ create_custom_type_wrapper!(LinkOneAttrType);
 create_custom_type_wrapper!(LinkManyAttrType);
 create_custom_type_wrapper!(NestObjectAttrType);
 create_custom_type_wrapper!(NestArrayAttrType);
+create_custom_type_wrapper!(NestEnumAttrType);
 create_custom_type_wrapper!(ArrayItemType);
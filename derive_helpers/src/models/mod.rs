@@ -11,10 +11,12 @@ pub(crate) mod field_meta;
 pub(crate) mod keywords;
 pub(crate) mod node;
 pub(crate) mod object;
+pub(crate) mod object_enum;
 pub(crate) mod table_meta;
 pub(crate) mod token_codegen;
 pub(crate) mod utils;
 pub(crate) mod variables;
+pub(crate) mod view;
 
 pub use casing::*;
 pub use edge::*;
@@ -23,10 +25,12 @@ pub use field_meta::*;
 pub use keywords::*;
 pub use node::*;
 pub use object::*;
+pub use object_enum::*;
 pub use table_meta::*;
 pub use token_codegen::*;
 pub use utils::*;
 pub use variables::*;
+pub use view::*;
 
 #[derive(Debug, Copy, Clone)]
 pub enum DataType {
@@ -56,6 +56,26 @@ impl ToTokens for NodeToken {
         } = VariablesModelMacro::new();
         let table_attrs = ModelAttributes::from_node(self);
         let explicit_generics = table_attrs.explicit_fully_qualified_generics_path();
+        let tenant_key = match table_attrs.tenant_key_token() {
+            Ok(tenant_key) => tenant_key,
+            Err(err) => return tokens.extend(err.write_errors()),
+        };
+        let old_table = match table_attrs.old_table_token() {
+            Ok(old_table) => old_table,
+            Err(err) => return tokens.extend(err.write_errors()),
+        };
+        let define_indexes = match table_attrs.define_indexes_token() {
+            Ok(define_indexes) => define_indexes,
+            Err(err) => return tokens.extend(err.write_errors()),
+        };
+        let define_events = match table_attrs.define_events_token() {
+            Ok(define_events) => define_events,
+            Err(err) => return tokens.extend(err.write_errors()),
+        };
+        let repository = match table_attrs.repository_token() {
+            Ok(repository) => repository,
+            Err(err) => return tokens.extend(err.write_errors()),
+        };
         let code_gen = match Codegen::parse_fields(&table_attrs) {
             Ok(props) => props,
             Err(err) => return tokens.extend(err.write_errors()),
@@ -86,13 +106,11 @@ impl ToTokens for NodeToken {
             link_self_fields,
             link_one_and_self_fields,
             link_many_fields,
-            field_definitions,
             fields_relations_aliased,
             struct_partial_fields,
             struct_partial_associated_functions,
             renamed_serialized_fields_kv,
             table_id_type,
-            field_metadata,
             ..
         } = &code_gen;
 
@@ -112,6 +130,15 @@ impl ToTokens for NodeToken {
         let struct_partial_builder_ident = struct_name_ident.partial_builder_ident();
 
         let serializable_fields_count = serializable_fields.len();
+        let field_lists = code_gen.field_lists_with_flattened(quote!(Self::table()));
+        let define_referential_actions = code_gen.define_referential_actions_token();
+        let reverse_links = code_gen.reverse_links_token();
+        let schema_reverse_method = code_gen
+            .schema_reverse_method_token(quote!(super::#struct_name_ident #struct_ty_generics));
+        let field_name_consts = code_gen.field_name_consts_token();
+        let serializable_fields_list = &field_lists.serializable_fields;
+        let field_definitions_list = &field_lists.field_definitions;
+        let field_metadata_list = &field_lists.field_metadata;
         let table_definitions = match self.get_table_definition_token() {
             Ok(table_definitions) => table_definitions,
             Err(err) => return tokens.extend(err.write_errors()),
@@ -170,7 +197,7 @@ impl ToTokens for NodeToken {
 
                     #module_name_internal::#struct_name_ident #explicit_generics ::#__________connect_node_to_graph_traversal_string(
                                 #module_name_internal::#struct_name_ident #explicit_generics ::empty(),
-                                clause.with_table(#table_str),
+                                clause.with_table(#table_str).tenant_scoped::<Self>(),
                     )
                 }
                 //
@@ -232,6 +259,7 @@ impl ToTokens for NodeToken {
 
             impl #struct_impl_generics #struct_name_ident #struct_ty_generics #struct_where_clause {
                   // pub const ALLOWED_FIELDS: [&'static str; 2] = ["name", "strength"];
+                pub const TABLE: &'static str = #table_str;
 
                 pub const fn __get_serializable_field_names() -> [&'static str; #serializable_fields_count] {
                     [#( stringify!(#serializable_fields)), *]
@@ -255,7 +283,7 @@ impl ToTokens for NodeToken {
                 }
 
                 fn get_serializable_fields() -> ::std::vec::Vec<#crate_name::Field> {
-                    return ::std::vec![#( #serializable_fields), *]
+                    return #serializable_fields_list
                 }
 
                 fn get_linked_fields() -> ::std::vec::Vec<#crate_name::Field> {
@@ -279,23 +307,33 @@ impl ToTokens for NodeToken {
                 }
 
                 fn get_field_meta() -> ::std::vec::Vec<#crate_name::FieldMetadata> {
-                    return ::std::vec![#( #field_metadata), *]
+                    return #field_metadata_list
                 }
 
+                #tenant_key
+
+                #old_table
+
+                #define_indexes
+
+                #define_events
+
+                #define_referential_actions
+
                 fn define_table() -> #crate_name::Raw {
                     #table_definitions
                 }
 
                 fn define_fields() -> ::std::vec::Vec<#crate_name::Raw> {
-                    ::std::vec![
-                       #( #field_definitions), *
-                    ]
+                    #field_definitions_list
                 }
             }
 
             #[allow(non_snake_case)]
             pub mod #module_name_rexported {
                 pub use super::#module_name_internal::#_____schema_def::__Schema__ as Schema;
+
+                #field_name_consts
             }
 
             #[allow(non_snake_case)]
@@ -460,6 +498,7 @@ impl ToTokens for NodeToken {
 
                     #( #record_link_fields_methods) *
 
+                    #schema_reverse_method
                 }
 
                 #node_edge_metadata
@@ -472,6 +511,10 @@ impl ToTokens for NodeToken {
             fn #test_function_name #struct_impl_generics() #struct_where_clause {
                 #( #static_assertions) *
             }
+
+            #reverse_links
+
+            #repository
 ));
     }
 }
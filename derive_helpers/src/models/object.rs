@@ -5,7 +5,7 @@
  */
 
 use crate::models::*;
-use darling::{ast, util, FromDeriveInput, ToTokens};
+use darling::{ast, FromDeriveInput, ToTokens};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::Ident;
@@ -17,12 +17,22 @@ pub struct ObjectToken {
     #[allow(dead_code)]
     pub(crate) attrs: Vec<syn::Attribute>,
     pub(crate) generics: StructGenerics,
-    /// Receives the body of the struct or enum. We don't care about
-    /// struct fields because we previously told darling we only accept structs.
-    pub data: ast::Data<util::Ignored, MyFieldReceiver>,
+    /// Receives the body of the struct or enum. Enums are stored as literal types.
+    pub data: ast::Data<ObjectVariantReceiver, MyFieldReceiver>,
 
     #[darling(default)]
     pub(crate) rename_all: ::std::option::Option<Rename>,
+
+    /// The tag field of an internally or adjacently tagged enum
+    #[darling(default)]
+    pub(crate) tag: ::std::option::Option<String>,
+
+    /// The content field of an adjacently tagged enum
+    #[darling(default)]
+    pub(crate) content: ::std::option::Option<String>,
+
+    #[darling(default)]
+    pub(crate) untagged: bool,
 }
 
 impl ObjectToken {
@@ -37,6 +47,13 @@ impl ObjectToken {
 
 impl ToTokens for ObjectToken {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        if self.data.is_enum() {
+            return match self.enum_to_tokens() {
+                Ok(enum_tokens) => tokens.extend(enum_tokens),
+                Err(err) => tokens.extend(err.write_errors()),
+            };
+        }
+
         let crate_name = get_crate_name(false);
         let struct_name_ident = &self.ident();
         let (impl_generics, ty_generics, where_clause) = self.generics().split_for_impl();
@@ -72,6 +89,11 @@ impl ToTokens for ObjectToken {
             serialized_ident_struct_partial_init_fields,
             ..
         } = &code_gen;
+        let field_lists = code_gen.field_lists_with_flattened(quote!(table.clone()));
+        let field_name_consts = code_gen.field_name_consts_token();
+        let serializable_fields_list = &field_lists.serializable_fields;
+        let field_definitions_list = &field_lists.field_definitions;
+        let field_metadata_list = &field_lists.field_metadata;
 
         let imports_referenced_node_schema =
             imports_referenced_node_schema.iter().collect::<Vec<_>>();
@@ -117,6 +139,22 @@ impl ToTokens for ObjectToken {
             }
 
             impl #impl_generics #crate_name::Object for #struct_name_ident #ty_generics #where_clause {
+                fn get_serializable_fields() -> ::std::vec::Vec<#crate_name::Field> {
+                    return #serializable_fields_list
+                }
+
+                #[allow(unused_variables)]
+                fn define_fields_on(table: #crate_name::Table) -> ::std::vec::Vec<#crate_name::Raw> {
+                    use ::std::str::FromStr as _;
+                    #field_definitions_list
+                }
+
+                #[allow(unused_variables)]
+                fn get_field_meta_on(table: #crate_name::Table) -> ::std::vec::Vec<#crate_name::FieldMetadata> {
+                    use ::std::str::FromStr as _;
+                    return #field_metadata_list
+                }
+
                 // type Schema = #module_name::#struct_name_ident;
 
                 // fn schema() -> Self::Schema {
@@ -124,6 +162,12 @@ impl ToTokens for ObjectToken {
                 // }
             }
 
+            impl #impl_generics #crate_name::SurrealType for #struct_name_ident #ty_generics #where_clause {
+                fn field_type() -> #crate_name::FieldType {
+                    #crate_name::FieldType::Object
+                }
+            }
+
             #[allow(non_snake_case)]
             #[derive(#crate_name::serde::Serialize, Debug, Clone, Default)]
             pub struct  #struct_partial_ident #impl_generics #where_clause {
@@ -153,6 +197,8 @@ impl ToTokens for ObjectToken {
             #[allow(non_snake_case)]
             pub mod #module_name_rexported {
                 pub use super::#module_name_internal::#_____schema_def::__Schema__ as Schema;
+
+                #field_name_consts
             }
 
 
@@ -0,0 +1,226 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use std::str::FromStr;
+
+use darling::{ast, FromVariant};
+use quote::format_ident;
+use syn::Ident;
+
+use crate::models::*;
+
+/// A variant of an enum deriving `Object`.
+#[derive(Debug, Clone, FromVariant)]
+#[darling(attributes(orm, serde), forward_attrs(allow, doc, cfg))]
+pub struct ObjectVariantReceiver {
+    pub(crate) ident: Ident,
+    #[allow(dead_code)]
+    pub(crate) attrs: Vec<syn::Attribute>,
+    pub(crate) fields: ast::Fields<MyFieldReceiver>,
+
+    #[darling(default)]
+    pub(crate) rename: ::std::option::Option<Rename>,
+
+    #[darling(default)]
+    pub(crate) rename_all: ::std::option::Option<Rename>,
+
+    #[darling(default)]
+    pub(crate) skip: bool,
+
+    #[darling(default)]
+    pub(crate) skip_serializing: bool,
+}
+
+impl ObjectVariantReceiver {
+    fn casing(&self) -> ExtractorResult<StructLevelCasing> {
+        casing_from_rename(self.rename_all.as_ref())
+    }
+
+    /// The serialized name of the variant
+    fn serialized_name(&self, enum_casing: &CaseString) -> String {
+        self.rename
+            .as_ref()
+            .and_then(|rename| rename.serialize.clone())
+            .unwrap_or_else(|| enum_casing.apply_to_variant(&self.ident.to_string()))
+    }
+}
+
+pub(crate) fn casing_from_rename(
+    rename_all: Option<&Rename>,
+) -> ExtractorResult<StructLevelCasing> {
+    let casing = match rename_all.and_then(|case| case.serialize.clone()) {
+        Some(case) => CaseString::from_str(case.as_str())
+            .map_err(|e| darling::Error::custom(e.to_string()))?,
+        None => CaseString::None,
+    };
+    Ok(casing.into())
+}
+
+impl ObjectToken {
+    /// Generates the `ObjectEnum` implementation of an enum. Unit enums are stored as a union of
+    /// string literals e.g `"active" | "banned"`, internally and adjacently tagged enums as a
+    /// union of object literals e.g `{ type: "circle", radius: float }`.
+    pub(crate) fn enum_to_tokens(&self) -> ExtractorResult<TokenStream> {
+        let crate_name = get_crate_name(false);
+        let enum_ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics().split_for_impl();
+        let variants = self
+            .data
+            .as_ref()
+            .take_enum()
+            .ok_or(darling::Error::custom("Expected an enum"))?;
+
+        if self.untagged {
+            return Err(syn::Error::new_spanned(
+                enum_ident,
+                "Untagged enums are not supported. Use a unit enum or an internally or adjacently \
+                tagged enum e.g #[serde(tag = \"type\")]",
+            )
+            .into());
+        }
+
+        let model_attributes = ModelAttributes::from_object(self);
+        let enum_casing = casing_from_rename(self.rename_all.as_ref())?;
+        let mut variant_types = vec![];
+        let mut static_assertions = vec![];
+
+        for variant in variants
+            .into_iter()
+            .filter(|variant| !variant.skip && !variant.skip_serializing)
+        {
+            let name = variant.serialized_name(&enum_casing);
+            let name_literal = quote!(
+                #crate_name::FieldType::Literal(#crate_name::LiteralType::String(#name.to_string()))
+            );
+
+            let Some(tag) = self.tag.as_ref() else {
+                if !variant.fields.is_unit() {
+                    return Err(syn::Error::new_spanned(
+                        &variant.ident,
+                        "Only unit variants are supported in externally tagged enums. \
+                        Tag the enum e.g #[serde(tag = \"type\")] to store variants with data",
+                    )
+                    .into());
+                }
+                variant_types.push(name_literal);
+                continue;
+            };
+
+            let mut object_fields = vec![quote!((#tag.to_string(), #name_literal))];
+
+            match (variant.fields.style, self.content.as_ref()) {
+                (ast::Style::Unit, _) => {}
+                (ast::Style::Struct, None) => {
+                    let (fields, assertions) = variant_fields(variant, &model_attributes)?;
+                    object_fields.extend(fields);
+                    static_assertions.extend(assertions);
+                }
+                (ast::Style::Struct, Some(content)) => {
+                    let (fields, assertions) = variant_fields(variant, &model_attributes)?;
+                    static_assertions.extend(assertions);
+                    object_fields.push(quote!((
+                        #content.to_string(),
+                        #crate_name::FieldType::Literal(#crate_name::LiteralType::Object(::std::vec![#(#fields),*]))
+                    )));
+                }
+                (ast::Style::Tuple, _) if variant.fields.len() != 1 => {
+                    return Err(syn::Error::new_spanned(
+                        &variant.ident,
+                        "Tuple variants are not supported in tagged enums. Use a struct variant",
+                    )
+                    .into());
+                }
+                // The fields of an internally tagged newtype variant come from its inner struct
+                (ast::Style::Tuple, None) => {
+                    variant_types.push(quote!(#crate_name::FieldType::Object));
+                    continue;
+                }
+                (ast::Style::Tuple, Some(content)) => {
+                    let field_type = variant
+                        .fields
+                        .iter()
+                        .next()
+                        .and_then(|field| field.field_type_db.as_ref())
+                        .map_or_else(
+                            || quote!(#crate_name::FieldType::Any),
+                            |field_type| quote!(#field_type),
+                        );
+                    object_fields.push(quote!((#content.to_string(), #field_type)));
+                }
+            }
+
+            variant_types.push(quote!(
+                #crate_name::FieldType::Literal(#crate_name::LiteralType::Object(::std::vec![#(#object_fields),*]))
+            ));
+        }
+
+        let field_type = match variant_types.as_slice() {
+            [] => {
+                return Err(syn::Error::new_spanned(
+                    enum_ident,
+                    "An enum deriving Object must have at least one serialized variant",
+                )
+                .into())
+            }
+            [variant_type] => quote!(#variant_type),
+            _ => quote!(#crate_name::FieldType::Union(::std::vec![#(#variant_types),*])),
+        };
+
+        let test_function_name =
+            format_ident!("_________test_{enum_ident}_static_funcs_name__________");
+
+        Ok(quote!(
+            impl #impl_generics #crate_name::ObjectEnum for #enum_ident #ty_generics #where_clause {
+                fn field_type() -> #crate_name::FieldType {
+                    use ::std::str::FromStr as _;
+                    #field_type
+                }
+            }
+
+            impl #impl_generics #crate_name::SurrealType for #enum_ident #ty_generics #where_clause {
+                fn field_type() -> #crate_name::FieldType {
+                    <Self as #crate_name::ObjectEnum>::field_type()
+                }
+            }
+
+            #[allow(non_snake_case)]
+            #[allow(unreachable_code)]
+            fn #test_function_name #impl_generics() #where_clause {
+                #( #static_assertions) *
+            }
+        ))
+    }
+}
+
+/// The serialized fields of a struct variant and their static assertions
+fn variant_fields(
+    variant: &ObjectVariantReceiver,
+    model_attributes: &ModelAttributes,
+) -> ExtractorResult<(Vec<TokenStream>, Vec<StaticAssertionToken>)> {
+    let casing = variant.casing()?;
+    let mut fields = vec![];
+    let mut static_assertions = vec![];
+
+    for field in variant
+        .fields
+        .iter()
+        .filter(|field| !field.skip && !field.skip_serializing)
+    {
+        let field_name = field.db_field_name(&casing)?.to_string();
+        let DbFieldTypeAstMeta {
+            field_type_db_token,
+            static_assertion_token,
+            ..
+        } = field
+            .field_type_db_with_static_assertions(model_attributes)?
+            .unwrap_or_default();
+
+        fields.push(quote!((#field_name.to_string(), #field_type_db_token)));
+        static_assertions.push(static_assertion_token);
+    }
+
+    Ok((fields, static_assertions))
+}
@@ -16,6 +16,7 @@ use crate::models::*;
 create_ident_wrapper!(StructIdent);
 create_ident_wrapper!(StructPartialIdent);
 create_ident_wrapper!(StructPartialBuilderIdent);
+create_ident_wrapper!(TenantKeyIdent);
 
 impl StructIdent {
     pub fn partial_ident(&self) -> StructPartialIdent {
@@ -39,6 +40,7 @@ impl StructIdent {
 #[darling(attributes(orm, serde), forward_attrs(allow, doc, cfg))]
 pub struct TableDeriveAttributes {
     pub(crate) ident: Ident,
+    pub(crate) vis: syn::Visibility,
     // pub(crate) attrs: Vec<syn::Attribute>,
     pub(crate) generics: StructGenerics,
     /// Receives the body of the struct or enum. We don't care about
@@ -54,6 +56,10 @@ pub struct TableDeriveAttributes {
     #[darling(default)]
     pub(crate) relax_table: Option<bool>,
 
+    /// The previous name of the table e.g `#[orm(table = customer, old_table = client)]`
+    #[darling(default)]
+    pub(crate) old_table: Option<TableNameIdent>,
+
     #[darling(default)]
     pub(crate) schemafull: Option<bool>,
 
@@ -70,6 +76,25 @@ pub struct TableDeriveAttributes {
 
     #[darling(default)]
     pub(crate) define: Option<AttributeDefine>,
+
+    #[darling(default)]
+    pub(crate) tenant_key: Option<TenantKeyIdent>,
+
+    /// Composite indexes e.g `#[orm(index(name = "species_speed_idx", fields(species, velocity), unique))]`
+    #[darling(multiple, rename = "index")]
+    pub(crate) indexes: Vec<TableIndexAttr>,
+
+    /// Events e.g `#[orm(event(name = "audit_update", when = "$event = 'UPDATE'", then = audit_update))]`
+    #[darling(multiple, rename = "event")]
+    pub(crate) events: Vec<TableEventAttr>,
+
+    /// The source of a view e.g `#[orm(view(from = Order, group_by = customer))]`
+    #[darling(default)]
+    pub(crate) view: Option<TableViewAttr>,
+
+    /// Generates a repository of the node e.g `UserRepo<C>`
+    #[darling(default)]
+    pub(crate) repository: bool,
 }
 
 impl TableDeriveAttributes {
@@ -91,6 +116,16 @@ impl TableDeriveAttributes {
     }
 
     pub fn get_table_definition_token(&self) -> ExtractorResult<TableDefinitions> {
+        self.get_table_definition_token_with_methods(vec![])
+    }
+
+    /// Like `get_table_definition_token` but appends extra builder methods
+    /// e.g the `TYPE RELATION` clause inferred from an edge's `in`/`out` fields.
+    /// The extra methods are not applied to an explicit `define` attribute.
+    pub fn get_table_definition_token_with_methods(
+        &self,
+        extra_methods: Vec<TokenStream>,
+    ) -> ExtractorResult<TableDefinitions> {
         let TableDeriveAttributes {
             ref drop,
             ref flexible,
@@ -98,11 +133,20 @@ impl TableDeriveAttributes {
             ref as_,
             ref permissions,
             ref define,
+            ref view,
             ..
         } = *self;
 
         let crate_name = get_crate_name(false);
 
+        if view.is_some() {
+            return Err(syn::Error::new_spanned(
+                self.ident.clone(),
+                "The `view` attribute is only supported with `#[derive(View)]`",
+            )
+            .into());
+        }
+
         if (define.is_some())
             && (drop.is_some()
                 || as_.is_some()
@@ -151,6 +195,8 @@ impl TableDeriveAttributes {
             define_table_methods.push(permissions.to_token_stream());
         }
 
+        define_table_methods.extend(extra_methods);
+
         Ok(define_table
             .unwrap_or_else(|| {
                 quote!(
@@ -0,0 +1,72 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use proc_macros_helpers::get_crate_name;
+use quote::quote;
+use syn::{Expr, ExprLit, Lit};
+
+use crate::models::*;
+
+/// An event of a table e.g
+/// `#[orm(event(name = "audit_update", when = "$event = 'UPDATE'", then = audit_update))]`.
+/// `when` and `then` are either raw SurrealQL strings, expressions or functions
+/// returning a condition and a statement respectively.
+#[derive(Debug, Clone, FromMeta)]
+pub struct TableEventAttr {
+    pub(crate) name: String,
+    pub(crate) when: ExprOrPath,
+    pub(crate) then: ExprOrPath,
+}
+
+impl ModelAttributes<'_> {
+    /// Overrides `Model::define_events` with the table level `#[orm(event(...))]` attributes.
+    /// The events are bound to `Self::table()` so they follow a table rename.
+    pub fn define_events_token(&self) -> ExtractorResult<TokenStream> {
+        let table_events = match self {
+            ModelAttributes::Node(node) => &node.0.events,
+            ModelAttributes::Edge(edge) => &edge.0.events,
+            ModelAttributes::Object(_object) => return Ok(quote!()),
+        };
+
+        if table_events.is_empty() {
+            return Ok(quote!());
+        }
+
+        let crate_name = get_crate_name(false);
+        let event_definitions = table_events
+            .iter()
+            .map(|TableEventAttr { name, when, then }| {
+                let when = raw_if_string_literal(when);
+                let then = raw_if_string_literal(then);
+                quote!(
+                    #crate_name::statements::define_event(#name)
+                        .on_table(Self::table())
+                        .when(#when)
+                        .then(#then)
+                        .to_raw()
+                )
+            });
+
+        Ok(quote!(
+            fn define_events() -> ::std::vec::Vec<#crate_name::Raw> {
+                ::std::vec![#( #event_definitions ),*]
+            }
+        ))
+    }
+}
+
+/// A string literal is taken as raw SurrealQL e.g `"$event = 'UPDATE'"`
+pub(crate) fn raw_if_string_literal(expr_or_path: &ExprOrPath) -> TokenStream {
+    let crate_name = get_crate_name(false);
+    match expr_or_path {
+        ExprOrPath::Expr(Expr::Lit(ExprLit {
+            lit: Lit::Str(raw), ..
+        })) => quote!(#crate_name::Raw::new(#raw)),
+        _ => quote!(#expr_or_path),
+    }
+}
@@ -0,0 +1,132 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use darling::{util::PathList, FromMeta};
+use proc_macro2::TokenStream;
+use proc_macros_helpers::get_crate_name;
+use quote::quote;
+
+use crate::models::*;
+
+/// A composite index of a table e.g
+/// `#[orm(index(name = "species_speed_idx", fields(species, velocity), unique))]`.
+#[derive(Debug, Clone, FromMeta)]
+pub struct TableIndexAttr {
+    /// Defaults to `<table>_<field1>_<field2>_idx`
+    #[darling(default)]
+    pub(crate) name: Option<String>,
+
+    /// The rust field names of the indexed fields
+    pub(crate) fields: PathList,
+
+    #[darling(default)]
+    pub(crate) unique: bool,
+}
+
+struct IndexDefinition {
+    name: String,
+    db_field_names: Vec<DbFieldName>,
+    unique: bool,
+}
+
+impl ModelAttributes<'_> {
+    /// Overrides `Model::define_indexes` with the indexes of the fields marked with
+    /// `#[orm(index)]` or `#[orm(unique)]` and of the table level `#[orm(index(...))]` attributes.
+    pub fn define_indexes_token(&self) -> ExtractorResult<TokenStream> {
+        let (table, table_indexes) = match self {
+            ModelAttributes::Node(node) => (node.table()?, &node.0.indexes),
+            ModelAttributes::Edge(edge) => (edge.table()?, &edge.0.indexes),
+            ModelAttributes::Object(_object) => return Ok(quote!()),
+        };
+        let table = table.to_string();
+        let casing = self.casing()?;
+        let fields = self.fields()?;
+        let mut index_definitions = vec![];
+
+        for field in &fields {
+            if !field.index && !field.unique {
+                continue;
+            }
+            let db_field_name = field.db_field_name(&casing)?;
+            index_definitions.push(IndexDefinition {
+                name: format!("{table}_{db_field_name}_idx"),
+                db_field_names: vec![db_field_name],
+                unique: field.unique,
+            });
+        }
+
+        for table_index in table_indexes {
+            let mut db_field_names = vec![];
+            for path in table_index.fields.iter() {
+                let field_name = path
+                    .get_ident()
+                    .ok_or_else(|| syn::Error::new_spanned(path, "Expected a field name"))?
+                    .to_string();
+                let field = fields
+                    .iter()
+                    .find(|field| {
+                        field
+                            .ident()
+                            .is_ok_and(|ident| ident.to_string() == field_name)
+                    })
+                    .ok_or_else(|| {
+                        syn::Error::new_spanned(
+                            path,
+                            format!("index field `{field_name}` is not a field of the struct"),
+                        )
+                    })?;
+                db_field_names.push(field.db_field_name(&casing)?);
+            }
+
+            if db_field_names.is_empty() {
+                return Err(darling::Error::custom("An index must have at least one field").into());
+            }
+
+            let name = table_index.name.clone().unwrap_or_else(|| {
+                let joined_fields = db_field_names
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("_");
+                format!("{table}_{joined_fields}_idx")
+            });
+
+            index_definitions.push(IndexDefinition {
+                name,
+                db_field_names,
+                unique: table_index.unique,
+            });
+        }
+
+        if index_definitions.is_empty() {
+            return Ok(quote!());
+        }
+
+        let crate_name = get_crate_name(false);
+        let index_definitions = index_definitions.into_iter().map(
+            |IndexDefinition {
+                 name,
+                 db_field_names,
+                 unique,
+             }| {
+                let unique = unique.then(|| quote!(.unique()));
+                quote!(
+                    #crate_name::statements::define_index(#name)
+                        .on_table(Self::table())
+                        .fields(::std::vec![#( #crate_name::Field::new(#db_field_names) ),*])
+                        #unique
+                        .to_raw()
+                )
+            },
+        );
+
+        Ok(quote!(
+            fn define_indexes() -> ::std::vec::Vec<#crate_name::Raw> {
+                ::std::vec![#( #index_definitions ),*]
+            }
+        ))
+    }
+}
@@ -4,8 +4,15 @@
  * Copyright (c) 2024 Oyelowo Oyedayo
  */
 mod derive_attributes;
+mod events;
+mod indexes;
 mod model_attributes;
+mod repository;
 mod table;
+mod view;
 
 pub use derive_attributes::*;
+pub use events::*;
+pub use indexes::*;
 pub use model_attributes::*;
+pub use view::*;
@@ -106,13 +106,11 @@ impl<'a> ModelAttributes<'a> {
 
     pub fn fields(&self) -> ExtractorResult<Vec<&MyFieldReceiver>> {
         let fields = match self {
-            ModelAttributes::Node(node) => &node.0.data,
-            ModelAttributes::Edge(edge) => &edge.0.data,
-            ModelAttributes::Object(object) => &object.data,
+            ModelAttributes::Node(node) => node.0.data.as_ref().take_struct(),
+            ModelAttributes::Edge(edge) => edge.0.data.as_ref().take_struct(),
+            ModelAttributes::Object(object) => object.data.as_ref().take_struct(),
         };
         Ok(fields
-            .as_ref()
-            .take_struct()
             .ok_or(darling::Error::custom("Expected a struct"))?
             .fields)
     }
@@ -152,6 +150,75 @@ impl<'a> ModelAttributes<'a> {
         Ok(table_name)
     }
 
+    /// The db name of the field set in `#[orm(tenant_key = field)]`.
+    fn tenant_key_db_name(&self) -> ExtractorResult<Option<String>> {
+        let tenant_key = match self {
+            ModelAttributes::Node(node) => node.0.tenant_key.as_ref(),
+            ModelAttributes::Edge(edge) => edge.0.tenant_key.as_ref(),
+            ModelAttributes::Object(_object) => None,
+        };
+        let Some(tenant_key) = tenant_key else {
+            return Ok(None);
+        };
+
+        let tenant_key_name = tenant_key.to_string();
+        let casing = self.casing()?;
+        for field in self.fields()? {
+            if field.ident()?.to_string() == tenant_key_name {
+                return Ok(Some(field.db_field_name(&casing)?.to_string()));
+            }
+        }
+
+        Err(syn::Error::new(
+            tenant_key.span(),
+            format!("tenant_key `{tenant_key_name}` is not a field of the struct"),
+        )
+        .into())
+    }
+
+    /// Overrides `Model::tenant_key` with the db name of the field set in
+    /// `#[orm(tenant_key = field)]`.
+    pub fn tenant_key_token(&self) -> ExtractorResult<TokenStream> {
+        let Some(db_field_name) = self.tenant_key_db_name()? else {
+            return Ok(quote!());
+        };
+
+        let crate_name = get_crate_name(false);
+        Ok(quote!(
+            fn tenant_key() -> ::std::option::Option<#crate_name::Field> {
+                ::std::option::Option::Some(#crate_name::Field::new(#db_field_name))
+            }
+        ))
+    }
+
+    /// Overrides `Model::old_table` with the table set in `#[orm(old_table = client)]`.
+    pub fn old_table_token(&self) -> ExtractorResult<TokenStream> {
+        let (old_table, table) = match self {
+            ModelAttributes::Node(node) => (node.0.old_table.as_ref(), node.table()?),
+            ModelAttributes::Edge(edge) => (edge.0.old_table.as_ref(), edge.table()?),
+            ModelAttributes::Object(_object) => return Ok(quote!()),
+        };
+        let Some(old_table) = old_table else {
+            return Ok(quote!());
+        };
+
+        let old_table_name = old_table.to_string();
+        if old_table_name == table.to_string() {
+            return Err(syn::Error::new(
+                old_table.span(),
+                "old_table must be different from the current table name",
+            )
+            .into());
+        }
+
+        let crate_name = get_crate_name(false);
+        Ok(quote!(
+            fn old_table() -> ::std::option::Option<#crate_name::Table> {
+                ::std::option::Option::Some(#crate_name::Table::new(#old_table_name))
+            }
+        ))
+    }
+
     pub fn explicit_fully_qualified_generics_path(&self) -> ExplicitFullyQualifiedGenericsPath {
         let (_struct_impl_generics, struct_ty_generics, _struct_where_clause) =
             &self.generics().split_for_impl();
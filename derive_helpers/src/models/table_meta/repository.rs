@@ -0,0 +1,225 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use proc_macro2::TokenStream;
+use proc_macros_helpers::get_crate_name;
+use quote::{format_ident, quote};
+
+use crate::models::*;
+
+impl ModelAttributes<'_> {
+    /// Generates the repository of a node marked with `#[orm(repository)]` e.g `UserRepo<C>`
+    /// holding a `Surreal<C>` with async `get`, `list`, `create`, `update_partial`, `delete`
+    /// and `exists` methods, and `UserRepoTransaction` queueing the writes into a transaction.
+    pub fn repository_token(&self) -> ExtractorResult<TokenStream> {
+        let node = match self {
+            ModelAttributes::Node(node) => node,
+            ModelAttributes::Edge(edge) => {
+                if edge.0.repository {
+                    return Err(syn::Error::new_spanned(
+                        &edge.0.ident,
+                        "repository is only supported on nodes",
+                    )
+                    .into());
+                }
+                return Ok(quote!());
+            }
+            ModelAttributes::Object(_object) => return Ok(quote!()),
+        };
+
+        if !node.0.repository {
+            return Ok(quote!());
+        }
+
+        if !node.generics().params().is_empty() {
+            return Err(syn::Error::new_spanned(
+                &node.0.ident,
+                "repository is not supported on generic structs",
+            )
+            .into());
+        }
+
+        let crate_name = get_crate_name(false);
+        let vis = &node.0.vis;
+        let node_ident = &node.0.ident;
+        let repo_ident = format_ident!("{node_ident}Repo");
+        let repo_transaction_ident = format_ident!("{node_ident}RepoTransaction");
+        let repo_doc = format!(
+            "The repository of `{node_ident}`. Statements run on the connection. Use \
+            `with_transaction` to queue the writes into a transaction instead."
+        );
+        let repo_transaction_doc = format!(
+            "Queues the creates, updates and deletes of `{node_ident}` into a transaction. \
+            Nothing is written and no record is returned until the transaction is run."
+        );
+
+        Ok(quote!(
+            #[doc = #repo_doc]
+            #vis struct #repo_ident<C: #crate_name::internal_tools::surrealdb::Connection> {
+                db: #crate_name::internal_tools::surrealdb::Surreal<C>,
+            }
+
+            impl<C: #crate_name::internal_tools::surrealdb::Connection> ::std::clone::Clone
+                for #repo_ident<C>
+            {
+                fn clone(&self) -> Self {
+                    Self {
+                        db: self.db.clone(),
+                    }
+                }
+            }
+
+            #[doc = #repo_transaction_doc]
+            #[derive(Debug, Clone)]
+            #vis struct #repo_transaction_ident {
+                transaction: #crate_name::statements::TransactionHandle,
+            }
+
+            #[allow(dead_code)]
+            impl<C: #crate_name::internal_tools::surrealdb::Connection> #repo_ident<C> {
+                /// Creates a repository running its statements on the connection
+                #vis fn new(db: #crate_name::internal_tools::surrealdb::Surreal<C>) -> Self {
+                    Self { db }
+                }
+
+                /// Queues the creates, updates and deletes into the transaction instead of
+                /// running them on the connection
+                #vis fn with_transaction(
+                    &self,
+                    transaction: #crate_name::statements::TransactionHandle,
+                ) -> #repo_transaction_ident {
+                    #repo_transaction_ident { transaction }
+                }
+
+                /// The connection of the repository
+                #vis fn db(&self) -> &#crate_name::internal_tools::surrealdb::Surreal<C> {
+                    &self.db
+                }
+
+                /// Gets a record by id
+                #vis async fn get(
+                    &self,
+                    id: impl ::std::convert::Into<#crate_name::sql::Thing>,
+                ) -> #crate_name::SurrealOrmResult<::std::option::Option<#node_ident>> {
+                    use #crate_name::{ReturnableStandard as _, SurrealCrud as _};
+
+                    #node_ident::find_by_id(id).return_one(self.db.clone()).await
+                }
+
+                /// Gets a record by id with the given link fields fetched
+                #vis async fn get_with_links(
+                    &self,
+                    id: impl ::std::convert::Into<#crate_name::sql::Thing>,
+                    linked_fields: ::std::vec::Vec<impl ::std::convert::Into<#crate_name::ValueLike>>,
+                ) -> #crate_name::SurrealOrmResult<::std::option::Option<#node_ident>> {
+                    use #crate_name::{ReturnableStandard as _, SurrealCrud as _};
+
+                    #node_ident::find_by_id(id)
+                        .load_links(linked_fields)?
+                        .return_one(self.db.clone())
+                        .await
+                }
+
+                /// Lists all records
+                #vis async fn list(
+                    &self,
+                ) -> #crate_name::SurrealOrmResult<::std::vec::Vec<#node_ident>> {
+                    use #crate_name::{ReturnableStandard as _, SurrealCrud as _};
+
+                    #node_ident::find_all().return_many(self.db.clone()).await
+                }
+
+                /// Lists the records matching the filter
+                #vis async fn list_where(
+                    &self,
+                    filter: impl #crate_name::Conditional + ::std::clone::Clone,
+                ) -> #crate_name::SurrealOrmResult<::std::vec::Vec<#node_ident>> {
+                    use #crate_name::{ReturnableStandard as _, SurrealCrud as _};
+
+                    #node_ident::find_where(filter).return_many(self.db.clone()).await
+                }
+
+                /// Whether a record of the id exists
+                #vis async fn exists(
+                    &self,
+                    id: impl ::std::convert::Into<#crate_name::sql::Thing>,
+                ) -> #crate_name::SurrealOrmResult<bool> {
+                    ::std::result::Result::Ok(self.get(id).await?.is_some())
+                }
+
+                /// Creates a record
+                #vis async fn create(
+                    &self,
+                    record: #node_ident,
+                ) -> #crate_name::SurrealOrmResult<::std::option::Option<#node_ident>> {
+                    use #crate_name::{ReturnableStandard as _, SurrealCrudNode as _};
+
+                    record.create().return_one(self.db.clone()).await
+                }
+
+                /// Updates the fields set on the partial record built with `partial_builder()`
+                #vis async fn update_partial(
+                    &self,
+                    id: impl ::std::convert::Into<#crate_name::sql::Thing>,
+                    partial: <#node_ident as #crate_name::PartialUpdater>::StructPartial,
+                ) -> #crate_name::SurrealOrmResult<::std::option::Option<#node_ident>> {
+                    use #crate_name::ReturnableStandard as _;
+
+                    #crate_name::statements::update::<#node_ident>(id.into())
+                        .merge(partial)
+                        .return_one(self.db.clone())
+                        .await
+                }
+
+                /// Deletes a record by id
+                #vis async fn delete(
+                    &self,
+                    id: impl ::std::convert::Into<#crate_name::sql::Thing>,
+                ) -> #crate_name::SurrealOrmResult<()> {
+                    use #crate_name::{ReturnableStandard as _, SurrealCrud as _};
+
+                    #node_ident::delete_by_id(id).return_none(self.db.clone()).await
+                }
+            }
+
+            #[allow(dead_code)]
+            impl #repo_transaction_ident {
+                /// The transaction the writes are queued into
+                #vis fn transaction(&self) -> &#crate_name::statements::TransactionHandle {
+                    &self.transaction
+                }
+
+                /// Queues the creation of a record
+                #vis fn create(&self, record: #node_ident) -> #crate_name::SurrealOrmResult<()> {
+                    use #crate_name::SurrealCrudNode as _;
+
+                    self.transaction.queue(record.create())
+                }
+
+                /// Queues the update of the fields set on the partial record built with
+                /// `partial_builder()`
+                #vis fn update_partial(
+                    &self,
+                    id: impl ::std::convert::Into<#crate_name::sql::Thing>,
+                    partial: <#node_ident as #crate_name::PartialUpdater>::StructPartial,
+                ) -> #crate_name::SurrealOrmResult<()> {
+                    self.transaction
+                        .queue(#crate_name::statements::update::<#node_ident>(id.into()).merge(partial))
+                }
+
+                /// Queues the deletion of a record by id
+                #vis fn delete(
+                    &self,
+                    id: impl ::std::convert::Into<#crate_name::sql::Thing>,
+                ) -> #crate_name::SurrealOrmResult<()> {
+                    use #crate_name::SurrealCrud as _;
+
+                    self.transaction.queue(#node_ident::delete_by_id(id))
+                }
+            }
+        ))
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use darling::{ast::NestedMeta, util::PathList, FromMeta};
+use syn::{Expr, Ident};
+
+use crate::models::*;
+
+/// The source of a view e.g
+/// `#[orm(view(from = Order, where_ = "amount > 0", group_by(customer, status)))]`.
+/// `where_` is either a raw SurrealQL string or an expression returning a condition.
+#[derive(Debug, Clone, FromMeta)]
+pub struct TableViewAttr {
+    /// The model the view selects from
+    pub(crate) from: syn::Path,
+
+    #[darling(default)]
+    pub(crate) where_: Option<ExprOrPath>,
+
+    /// The rust field names of the view grouped by
+    #[darling(default)]
+    pub(crate) group_by: Option<ViewGroupBy>,
+
+    #[darling(default)]
+    pub(crate) group_all: bool,
+}
+
+/// The fields of a view grouped by e.g `group_by = customer`, `group_by = [customer, status]`
+/// or `group_by(customer, status)`
+#[derive(Debug, Clone)]
+pub struct ViewGroupBy(pub(crate) Vec<Ident>);
+
+impl FromMeta for ViewGroupBy {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        PathList::from_list(items)?
+            .iter()
+            .map(|path| {
+                path.get_ident()
+                    .cloned()
+                    .ok_or_else(|| darling::Error::custom("Expected a field name").with_span(path))
+            })
+            .collect::<darling::Result<_>>()
+            .map(Self)
+    }
+
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        let field_ident = |expr: &Expr| match expr {
+            Expr::Path(expr_path) => expr_path
+                .path
+                .get_ident()
+                .cloned()
+                .ok_or_else(|| darling::Error::custom("Expected a field name").with_span(expr)),
+            _ => Err(darling::Error::custom("Expected a field name").with_span(expr)),
+        };
+
+        match expr {
+            Expr::Array(array) => array
+                .elems
+                .iter()
+                .map(field_ident)
+                .collect::<darling::Result<_>>()
+                .map(Self),
+            _ => Ok(Self(vec![field_ident(expr)?])),
+        }
+    }
+}
@@ -4,6 +4,7 @@
  * Copyright (c) 2024 Oyelowo Oyedayo
  */
 
+use proc_macro2::TokenStream;
 use proc_macros_helpers::get_crate_name;
 use quote::{quote, ToTokens};
 
@@ -40,6 +41,7 @@ impl Codegen<'_> {
         let casing = model_attributes.casing()?;
         let db_field_name = field_receiver.db_field_name(&casing)?;
         let field_type_in_db_token = field_receiver.field_type_db_token(model_attributes)?;
+        let on_table = self.define_field_on_table_token();
 
         let mut define_field_methods = vec![];
         let mut define_array_field_item_methods = vec![];
@@ -63,7 +65,7 @@ impl Codegen<'_> {
 
         let main_field_def = quote!(
             #crate_name::statements::define_field(#crate_name::Field::new(#db_field_name))
-            .on_table(#crate_name::Table::from(Self::table()))
+            .on_table(#on_table)
             .type_(#field_type_in_db_token)
             #( # define_field_methods) *
             .to_raw()
@@ -75,7 +77,7 @@ impl Codegen<'_> {
             let array_field_item_str = quote!(#db_field_name.*).to_string();
             let array_item_definition = quote!(
                 #crate_name::statements::define_field(#crate_name::Field::new(#array_field_item_str))
-                                        .on_table(#crate_name::Table::from(Self::table()))
+                                        .on_table(#on_table)
                                         #( # define_array_field_item_methods) *
                                         .to_raw()
 
@@ -90,6 +92,18 @@ impl Codegen<'_> {
         Ok(all_field_defintions)
     }
 
+    /// Objects have no table of their own. Their fields are defined on the table of the node or
+    /// edge they are flattened into, which is passed in as `table`.
+    fn define_field_on_table_token(&self) -> TokenStream {
+        let crate_name = get_crate_name(false);
+
+        if self.data_type().is_object() {
+            quote!(#crate_name::Table::from(table.clone()))
+        } else {
+            quote!(#crate_name::Table::from(Self::table()))
+        }
+    }
+
     fn static_assertion_field_value(
         &self,
         model_attrs: &ModelAttributes,
@@ -4,7 +4,8 @@
  * Copyright (c) 2024 Oyelowo Oyedayo
  */
 
-use quote::quote;
+use convert_case::{Case, Casing};
+use quote::{format_ident, quote};
 
 use crate::models::*;
 
@@ -43,6 +44,17 @@ impl Codegen<'_> {
             quote!(pub #field_ident_normalized: #_____field_names::#field_name_pascalized, ).into(),
         );
 
+        let field_name_const = format_ident!(
+            "{}",
+            field_receiver
+                .ident()?
+                .to_string()
+                .trim_start_matches("r#")
+                .to_case(Case::UpperSnake)
+        );
+        self.field_name_consts
+            .push(quote!(pub const #field_name_const: &str = #field_name_serialized;).into());
+
         self.schema_struct_fields_names_kv
             .push(quote!(#field_ident_normalized: #field_name_serialized.into(),).into());
 
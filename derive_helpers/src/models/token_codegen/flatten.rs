@@ -0,0 +1,183 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::models::*;
+
+use super::Codegen;
+
+create_tokenstream_wrapper!(
+/// The type of an object flattened into its parent with `#[serde(flatten)]` e.g `Audit`
+=>
+FlattenedObjectType
+);
+
+/// The lists of the fields of a struct including the fields of its flattened objects
+pub(crate) struct FieldListsWithFlattened {
+    pub serializable_fields: TokenStream,
+    pub field_definitions: TokenStream,
+    pub field_metadata: TokenStream,
+}
+
+impl Codegen<'_> {
+    /// A flattened object has no field of its own in the database. Its fields are expanded into
+    /// the parent's field definitions and serializable fields at runtime through its `Object`
+    /// implementation, and its schema is accessed with a method of the same name as the field.
+    pub fn create_flattened_object_tokens(&mut self) -> ExtractorResult<()> {
+        let crate_name = get_crate_name(false);
+        let model_attributes = self.table_derive_attributes();
+        let field_receiver = self.field_receiver();
+        self.validate_flattened_field_attributes()?;
+
+        let object_type = field_receiver
+            .ty()
+            .replace_self_with_current_struct_concrete_type(model_attributes)?;
+        let object_type_turbo_fished = object_type.turbo_fishize()?;
+        let field_ident_original = field_receiver.ident()?;
+        let field_ident_normalized =
+            field_receiver.field_ident_normalized(&model_attributes.casing()?)?;
+
+        self.flattened_objects.push(quote!(#object_type).into());
+
+        self.static_assertions
+            .push(quote!(#crate_name::validators::assert_type_is_object::<#object_type>();).into());
+
+        // e.g type Audit = <super::Audit as SchemaGetter>::Schema;
+        self.imports_referenced_node_schema.insert(
+            quote!(type #object_type = <super::#object_type as #crate_name::SchemaGetter>::Schema;)
+                .into(),
+        );
+
+        self.record_link_fields_methods.push(
+            quote!(
+                pub fn #field_ident_normalized(&self) -> #object_type {
+                    if self.build().is_empty() {
+                        #object_type_turbo_fished::new()
+                    } else {
+                        #object_type_turbo_fished::new_prefixed(
+                            #crate_name::Field::new(self.build()).with_bindings(self.get_bindings())
+                        )
+                    }
+                }
+            )
+            .into(),
+        );
+
+        let maybe_fn_path = format!("{crate_name}::Maybe::is_none");
+        let struct_partial_type =
+            quote!(<#object_type as #crate_name::PartialUpdater>::StructPartial);
+        self.struct_partial_fields.push(
+            quote!(
+                #[serde(flatten, skip_serializing_if = #maybe_fn_path)]
+                pub #field_ident_original: #crate_name::Maybe<#struct_partial_type>
+            )
+            .into(),
+        );
+        self.struct_partial_associated_functions.push(
+            quote!(
+                pub fn #field_ident_original(mut self, value: #struct_partial_type) -> Self {
+                    self.0.#field_ident_original = #crate_name::Maybe::Some(value);
+                    self
+                }
+            )
+            .into(),
+        );
+        self.serialized_ident_struct_partial_init_fields.push(
+            SerializedIdentStructPartialInitFields::new(quote!(#field_ident_original)),
+        );
+
+        Ok(())
+    }
+
+    fn validate_flattened_field_attributes(&self) -> ExtractorResult<()> {
+        let MyFieldReceiver {
+            relate,
+            link_one,
+            link_self,
+            link_many,
+            nest_array,
+            nest_enum,
+            field_type_db,
+            assert: assert_,
+            define,
+            value,
+            permissions,
+            item_assert,
+            old_name,
+            ..
+        } = self.field_receiver();
+
+        let has_field_level_attributes = relate.is_some()
+            || link_one.is_some()
+            || link_self.is_some()
+            || link_many.is_some()
+            || nest_array.is_some()
+            || nest_enum.is_some()
+            || field_type_db.is_some()
+            || assert_.is_some()
+            || define.is_some()
+            || value.is_some()
+            || permissions.is_some()
+            || item_assert.is_some()
+            || old_name.is_some();
+
+        if has_field_level_attributes {
+            return Err(syn::Error::new_spanned(
+                self.field_receiver().ident()?,
+                r#"A flattened object field has no database field of its own and only allows the nest_object attribute.
+Specify the attributes on the fields of the object instead"#,
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// The serializable fields, field definitions and field metadata of the struct with those of
+    /// its flattened objects defined on `table` appended.
+    pub(crate) fn field_lists_with_flattened(&self, table: TokenStream) -> FieldListsWithFlattened {
+        let crate_name = get_crate_name(false);
+        let Self {
+            serialized_fmt_db_field_names_instance: serializable_fields,
+            field_definitions,
+            field_metadata,
+            ..
+        } = self;
+
+        FieldListsWithFlattened {
+            serializable_fields: self.with_flattened_objects(
+                quote!(::std::vec![#( #serializable_fields), *]),
+                |object| quote!(<#object as #crate_name::Object>::get_serializable_fields()),
+            ),
+            field_definitions: self.with_flattened_objects(
+                quote!(::std::vec![#( #field_definitions), *]),
+                |object| quote!(<#object as #crate_name::Object>::define_fields_on(#table)),
+            ),
+            field_metadata: self.with_flattened_objects(
+                quote!(::std::vec![#( #field_metadata), *]),
+                |object| quote!(<#object as #crate_name::Object>::get_field_meta_on(#table)),
+            ),
+        }
+    }
+
+    /// Appends the items of flattened objects to a list of the items of the current struct
+    /// e.g `[vec![name.into()], <Audit as Object>::get_serializable_fields()].concat()`.
+    /// Returns the list as is when there are no flattened objects.
+    fn with_flattened_objects(
+        &self,
+        list: TokenStream,
+        object_items: impl Fn(&FlattenedObjectType) -> TokenStream,
+    ) -> TokenStream {
+        if self.flattened_objects.is_empty() {
+            return list;
+        }
+
+        let flattened_items = self.flattened_objects.iter().map(object_items);
+        quote!([#list, #( #flattened_items ),*].concat())
+    }
+}
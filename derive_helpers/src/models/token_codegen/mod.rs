@@ -10,8 +10,12 @@ mod field_connection_build;
 mod field_metadata;
 mod field_type_assertions;
 mod field_value_setter;
+mod flatten;
 mod link_methods;
+mod name_consts;
+mod referential_actions;
 mod relate;
+mod reverse_links;
 mod serialized_field_fmts;
 mod simple;
 mod struct_partial;
@@ -24,8 +28,12 @@ use crate::models::*;
 
 use self::{
     field_value_setter::FieldSetterImplTokens,
+    flatten::FlattenedObjectType,
     link_methods::{ForeignNodeSchemaImport, LinkFieldTraversalMethodToken},
+    name_consts::FieldNameConst,
+    referential_actions::ReferentialActionToken,
     relate::NodeEdgeMetadataLookupTable,
+    reverse_links::ReverseLink,
 };
 
 create_ident_wrapper!(ModuleNameInternalIdent);
@@ -224,6 +232,15 @@ pub struct Codegen<'a> {
     pub struct_partial_associated_functions: Vec<StructPartialMethods>,
     pub renamed_serialized_fields_kv: Vec<RenamedSerializedFields>,
     pub table_id_type: TableIdType,
+    /// The types of the objects flattened into the struct with `#[serde(flatten)]`.
+    /// Their fields are appended to the fields of the struct at runtime.
+    pub flattened_objects: Vec<FlattenedObjectType>,
+    /// The events implementing the `on_delete` actions of the link fields
+    pub referential_actions: Vec<ReferentialActionToken>,
+    /// The `reverse` attributes of the link fields generating accessors on the linked models
+    pub reverse_links: Vec<ReverseLink>,
+    /// The constants of the database names of the fields
+    pub field_name_consts: Vec<FieldNameConst>,
 
     struct_attributes_data: StructAttributesData<'a>,
 }
@@ -295,6 +312,11 @@ impl<'a> Codegen<'a> {
         for field_receiver in model_attributes.fields()? {
             tokens_generator.set_field_receiver(field_receiver);
 
+            if field_receiver.flatten {
+                tokens_generator.create_flattened_object_tokens()?;
+                continue;
+            }
+
             tokens_generator.create_table_id_type_token()?;
             tokens_generator.create_field_definitions()?;
             tokens_generator.create_db_field_names_token()?;
@@ -305,6 +327,8 @@ impl<'a> Codegen<'a> {
             tokens_generator.create_relation_connection_tokenstream()?;
             tokens_generator.create_db_fields_for_links_and_loaders()?;
             tokens_generator.create_link_methods()?;
+            tokens_generator.create_referential_action_token()?;
+            tokens_generator.create_reverse_link_token()?;
             tokens_generator.create_relation_aliases_struct_fields_types_kv()?;
             tokens_generator.create_struct_partial_metadata()?;
         }
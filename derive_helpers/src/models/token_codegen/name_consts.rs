@@ -0,0 +1,32 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::models::*;
+
+use super::Codegen;
+
+create_tokenstream_wrapper!(
+/// The constant of the database name of a field e.g `pub const FIRST_NAME: &str = "firstName";`
+=>
+FieldNameConst
+);
+
+impl Codegen<'_> {
+    /// The module of the constants of the database names of the fields e.g `user::fields::EMAIL`
+    /// for splicing into raw queries with `concat_const!` or `query!`
+    pub(crate) fn field_name_consts_token(&self) -> TokenStream {
+        let field_name_consts = &self.field_name_consts;
+
+        quote!(
+            pub mod fields {
+                #( #field_name_consts )*
+            }
+        )
+    }
+}
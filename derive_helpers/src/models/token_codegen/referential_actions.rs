@@ -0,0 +1,79 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::models::*;
+
+use super::{Codegen, RelationType};
+
+create_tokenstream_wrapper!(
+/// The `DEFINE EVENT` of the `on_delete` action of a link field e.g
+/// `OnDelete::Cascade.define_event(Self::table(), Field::new("company"), <Company as Model>::table(), false)`
+=>
+ReferentialActionToken
+);
+
+impl Codegen<'_> {
+    pub fn create_referential_action_token(&mut self) -> ExtractorResult<()> {
+        let crate_name = get_crate_name(false);
+        let table_derive_attrs = self.table_derive_attributes();
+        let field_receiver = self.field_receiver();
+        let Some(on_delete) = field_receiver.on_delete else {
+            return Ok(());
+        };
+        let db_field_name = field_receiver.db_field_name(&table_derive_attrs.casing()?)?;
+
+        let (linked_table, is_link_many) = match field_receiver.to_relation_type(table_derive_attrs)
+        {
+            RelationType::LinkOne(link_one) => {
+                (quote!(<#link_one as #crate_name::Model>::table()), false)
+            }
+            RelationType::LinkSelf(_link_self) => (quote!(Self::table()), false),
+            RelationType::LinkMany(link_many) => {
+                (quote!(<#link_many as #crate_name::Model>::table()), true)
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    field_receiver.ident()?,
+                    "on_delete can only be used with link_one, link_self or link_many fields",
+                )
+                .into())
+            }
+        };
+
+        self.referential_actions.push(
+            quote!(
+                #on_delete.define_event(
+                    Self::table(),
+                    #crate_name::Field::new(#db_field_name),
+                    #linked_table,
+                    #is_link_many,
+                )
+            )
+            .into(),
+        );
+
+        Ok(())
+    }
+
+    /// Overrides `Model::define_referential_actions` when a link field has an `on_delete` action
+    pub(crate) fn define_referential_actions_token(&self) -> TokenStream {
+        let crate_name = get_crate_name(false);
+        let referential_actions = &self.referential_actions;
+
+        if referential_actions.is_empty() {
+            return quote!();
+        }
+
+        quote!(
+            fn define_referential_actions() -> ::std::vec::Vec<#crate_name::Raw> {
+                ::std::vec![#( #referential_actions ),*]
+            }
+        )
+    }
+}
@@ -104,7 +104,10 @@ impl Codegen<'_> {
             EdgeToForeignNodeMethod(quote!(
                 pub fn #foreign_node_table(self, clause: impl ::std::convert::Into<#crate_name::NodeClause>) -> #foreign_node_schema_type_alias_with_generics {
                     let clause: #crate_name::NodeClause = clause.into();
-                    let clause = clause.with_arrow(#arrow).with_table(#destination_node_table_str);
+                    let clause = clause
+                        .with_arrow(#arrow)
+                        .with_table(#destination_node_table_str)
+                        .tenant_scoped::<<super::super::#edge_type as #crate_name::Edge>::#foreign_node_in_or_out>();
 
                     #foreign_node_schema_type_alias_with_generics_turbofishized::#__________connect_node_to_graph_traversal_string(
                                 self,
@@ -533,7 +536,10 @@ impl ToTokens for NodeEdgeMetadata<'_> {
                     clause: impl ::std::convert::Into<#crate_name::EdgeClause>,
                 ) -> #edge_inner_module_name::#edge_name_as_struct_with_direction_ident {
                     let clause: #crate_name::EdgeClause = clause.into();
-                    let clause = clause.with_arrow(#arrow).with_table(#edge_table_str);
+                    let clause = clause
+                        .with_arrow(#arrow)
+                        .with_table(#edge_table_str)
+                        .tenant_scoped::<super::#edge_relation_model_selected_ident #edge_type_ty_generics>();
 
                     // i.e Edge to Node
                      // TODO: Use type over mere ident. include potential generics
@@ -619,7 +625,10 @@ impl ToTokens for NodeEdgeMetadata<'_> {
                         clause: impl ::std::convert::Into<#crate_name::EdgeClause>,
                     ) -> #edge_name_as_struct_with_direction_ident #edge_type_ty_generics {
                         let clause: #crate_name::EdgeClause = clause.into();
-                        let clause = clause.with_arrow(#arrow).with_table(#edge_table_str);
+                        let clause = clause
+                            .with_arrow(#arrow)
+                            .with_table(#edge_table_str)
+                            .tenant_scoped::<super::super::#edge_relation_model_selected_ident #edge_type_ty_generics>();
 
                         // i.e Edge to Edge
                         #edge_name_as_struct_original_ident::#__________connect_edge_to_graph_traversal_string(
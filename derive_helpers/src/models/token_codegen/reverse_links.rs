@@ -0,0 +1,270 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Ident, PathArguments, Type};
+
+use crate::models::*;
+
+use super::{Codegen, RelationType};
+
+/// A `reverse` attribute of a link field e.g `#[orm(link_one = User, reverse = posts)]`
+#[derive(Debug, Clone)]
+pub struct ReverseLink {
+    /// The linked model e.g `User`
+    linked_type: Type,
+    /// The name of the linked model used in the generated idents e.g `User`
+    linked_ident: Ident,
+    /// The name of the reverse link e.g `posts`
+    name: String,
+    /// The field of the struct of the reverse links on the linked model e.g
+    /// `pub posts: Alias`
+    field: TokenStream,
+    /// The initialization of the field e.g `posts: select(All).from(..).__as__("posts")`
+    field_value: TokenStream,
+    /// The method of the loader trait on the linked model e.g
+    /// `async fn load_posts(&self, db) -> SurrealOrmResult<Vec<Post>>`
+    load_method: TokenStream,
+    /// The signature of the method of the loader trait
+    load_signature: TokenStream,
+}
+
+impl Codegen<'_> {
+    pub fn create_reverse_link_token(&mut self) -> ExtractorResult<()> {
+        let crate_name = get_crate_name(false);
+        let table_derive_attrs = self.table_derive_attributes();
+        let field_receiver = self.field_receiver();
+        let Some(reverse) = field_receiver.reverse.as_ref() else {
+            return Ok(());
+        };
+        let db_field_name = field_receiver.db_field_name(&table_derive_attrs.casing()?)?;
+        let current_struct = table_derive_attrs.ident();
+        let current_struct_name = current_struct.to_string();
+
+        let (linked_type, is_link_many) = match field_receiver.to_relation_type(table_derive_attrs)
+        {
+            RelationType::LinkOne(link_one) => (link_one.into_inner(), false),
+            RelationType::LinkSelf(link_self) => (
+                link_self
+                    .to_linkone_attr_type(table_derive_attrs)?
+                    .into_inner(),
+                false,
+            ),
+            RelationType::LinkMany(link_many) => (link_many.into_inner(), true),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    reverse,
+                    "reverse can only be used with link_one, link_self or link_many fields",
+                )
+                .into())
+            }
+        };
+
+        if !table_derive_attrs.generics().params().is_empty() {
+            return Err(syn::Error::new_spanned(
+                reverse,
+                "reverse is not supported on generic structs",
+            )
+            .into());
+        }
+
+        let linked_ident = linked_model_ident(&linked_type, reverse)?;
+        let linked_type = linked_type.into_inner();
+        let reverse_name = reverse.to_string();
+
+        let is_duplicate = self
+            .reverse_links
+            .iter()
+            .any(|link| link.linked_ident == linked_ident && link.name == reverse_name);
+        if is_duplicate {
+            return Err(syn::Error::new_spanned(
+                reverse,
+                format!(
+                    "The reverse link `{reverse_name}` is already declared by another field of \
+                    `{current_struct_name}` linking to `{linked_ident}`. Use another name"
+                ),
+            )
+            .into());
+        }
+
+        let load_reverse = format_ident!("load_{reverse_name}");
+        let (condition, operator) = if is_link_many {
+            (
+                format!("{db_field_name} CONTAINS $parent.id"),
+                quote!(contains),
+            )
+        } else {
+            (format!("{db_field_name} = $parent.id"), quote!(eq))
+        };
+        let reverse_doc = format!(
+            "The `{current_struct_name}` records whose `{db_field_name}` links to the record as a \
+            subquery aliased `{reverse_name}`"
+        );
+        let load_reverse_doc = format!(
+            "Fetches the `{current_struct_name}` records whose `{db_field_name}` links to the record"
+        );
+        let load_signature = quote!(
+            fn #load_reverse(
+                &self,
+                db: #crate_name::internal_tools::surrealdb::Surreal<
+                    impl #crate_name::internal_tools::surrealdb::Connection
+                >,
+            )
+        );
+
+        self.reverse_links.push(ReverseLink {
+            linked_type,
+            linked_ident,
+            name: reverse_name.clone(),
+            field: quote!(
+                #[doc = #reverse_doc]
+                pub #reverse: #crate_name::Alias
+            ),
+            field_value: quote!(
+                #reverse: #crate_name::statements::select(#crate_name::All)
+                    .from(<#current_struct as #crate_name::Model>::table())
+                    .where_(#crate_name::Raw::new(#condition))
+                    .__as__(#reverse_name)
+            ),
+            load_method: quote!(
+                async #load_signature -> #crate_name::SurrealOrmResult<::std::vec::Vec<#current_struct>> {
+                    use #crate_name::{Model as _, Operatable as _, ReturnableSelect as _};
+
+                    #crate_name::statements::select(#crate_name::All)
+                        .from(<#current_struct as #crate_name::Model>::table())
+                        .where_(
+                            #crate_name::Field::new(#db_field_name)
+                                .#operator(self.get_id_as_thing())
+                        )
+                        .return_many(db)
+                        .await
+                }
+            ),
+            load_signature: quote!(
+                #[doc = #load_reverse_doc]
+                #load_signature -> impl ::std::future::Future<
+                    Output = #crate_name::SurrealOrmResult<::std::vec::Vec<#current_struct>>
+                >;
+            ),
+        });
+
+        Ok(())
+    }
+
+    /// The struct of the reverse links of the current struct on each linked model e.g
+    /// `PostReverseLinksOnUser { pub posts: Alias }` implementing `ReverseLinks<Post>` for
+    /// `User`, and the trait loading them e.g `LoadPostReverseLinksOnUser::load_posts`.
+    /// They are generated next to the current struct, so the linked model can live in
+    /// another module or crate.
+    pub(crate) fn reverse_links_token(&self) -> TokenStream {
+        let crate_name = get_crate_name(false);
+        let current_struct = self.table_derive_attributes().ident();
+
+        let mut linked_idents: Vec<&Ident> = vec![];
+        for link in &self.reverse_links {
+            if !linked_idents.contains(&&link.linked_ident) {
+                linked_idents.push(&link.linked_ident);
+            }
+        }
+
+        let groups = linked_idents.into_iter().map(|linked_ident| {
+            let links = self
+                .reverse_links
+                .iter()
+                .filter(|link| &link.linked_ident == linked_ident)
+                .collect::<Vec<_>>();
+            let linked_type = &links[0].linked_type;
+            let fields = links.iter().map(|link| &link.field);
+            let field_values = links.iter().map(|link| &link.field_value);
+            let load_signatures = links.iter().map(|link| &link.load_signature);
+            let load_methods = links.iter().map(|link| &link.load_method);
+
+            let links_struct = format_ident!("{current_struct}ReverseLinksOn{linked_ident}");
+            let loader_trait = format_ident!("Load{links_struct}");
+            let links_struct_doc =
+                format!("The reverse links of `{current_struct}` on `{linked_ident}`");
+            let loader_trait_doc =
+                format!("Loads the reverse links of `{current_struct}` on `{linked_ident}`");
+
+            quote!(
+                #[doc = #links_struct_doc]
+                #[derive(Debug, Clone)]
+                pub struct #links_struct {
+                    #( #fields ),*
+                }
+
+                impl #crate_name::ReverseLinks<#current_struct> for #linked_type {
+                    type Links = #links_struct;
+
+                    fn reverse_links() -> Self::Links {
+                        use #crate_name::Aliasable as _;
+
+                        #links_struct {
+                            #( #field_values ),*
+                        }
+                    }
+                }
+
+                #[doc = #loader_trait_doc]
+                pub trait #loader_trait {
+                    #( #load_signatures )*
+                }
+
+                impl #loader_trait for #linked_type {
+                    #( #load_methods )*
+                }
+            )
+        });
+
+        quote!(#( #groups )*)
+    }
+
+    /// The method of the schema struct accessing the reverse links declared by other models
+    /// on their links to `model` e.g `User::schema().reverse_links::<Post>().posts`
+    pub(crate) fn schema_reverse_method_token(&self, model: TokenStream) -> TokenStream {
+        let crate_name = get_crate_name(false);
+
+        quote!(
+            /// The reverse links declared by the model `LinkingModel` on its links to this model
+            pub fn reverse_links<LinkingModel>(
+                &self,
+            ) -> <#model as #crate_name::ReverseLinks<LinkingModel>>::Links
+            where
+                #model: #crate_name::ReverseLinks<LinkingModel>,
+            {
+                <#model as #crate_name::ReverseLinks<LinkingModel>>::reverse_links()
+            }
+        )
+    }
+}
+
+/// The name of a linked model e.g `User` for `crate::models::User`
+fn linked_model_ident(
+    linked_type: &CustomType,
+    reverse: &ReverseLinkName,
+) -> ExtractorResult<Ident> {
+    let Type::Path(type_path) = linked_type.into_inner_ref() else {
+        return Err(syn::Error::new_spanned(
+            linked_type,
+            "reverse requires the linked model to be a struct",
+        )
+        .into());
+    };
+    let Some(last_segment) = type_path.path.segments.last() else {
+        return Err(syn::Error::new_spanned(linked_type, "Expected a linked model").into());
+    };
+
+    if !matches!(last_segment.arguments, PathArguments::None) {
+        return Err(syn::Error::new_spanned(
+            reverse,
+            "reverse is not supported for generic linked models",
+        )
+        .into());
+    }
+
+    Ok(last_segment.ident.clone())
+}
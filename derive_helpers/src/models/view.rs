@@ -0,0 +1,218 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use std::ops::Deref;
+
+use crate::models::*;
+use darling::{FromDeriveInput, ToTokens};
+use proc_macro2::TokenStream;
+use proc_macros_helpers::get_crate_name;
+use quote::quote;
+
+#[derive(Clone, Debug, FromDeriveInput)]
+#[darling(attributes(orm, serde), forward_attrs(allow, doc, cfg))]
+pub struct ViewToken(pub TableDeriveAttributes);
+
+impl Deref for ViewToken {
+    type Target = TableDeriveAttributes;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl ToTokens for ViewToken {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self.view_to_tokens() {
+            Ok(view) => tokens.extend(view),
+            Err(err) => tokens.extend(err.write_errors()),
+        }
+    }
+}
+
+impl ViewToken {
+    /// Generates the `View` and `ViewResources` implementations of a view. The `AS SELECT`
+    /// projections are the fields of the view aliased to their serialized names: a field with
+    /// an `expr` attribute is the expression, any other field is the field of the same name
+    /// on the source model, whose serialized name can differ with `rename_all`.
+    fn view_to_tokens(&self) -> ExtractorResult<TokenStream> {
+        let crate_name = get_crate_name(false);
+        let TableDeriveAttributes {
+            ident: view_ident,
+            rename_all,
+            schemafull,
+            drop,
+            flexible,
+            as_,
+            permissions,
+            define,
+            tenant_key,
+            old_table,
+            indexes,
+            events,
+            view,
+            ..
+        } = self.deref();
+
+        let Some(view) = view else {
+            return Err(syn::Error::new_spanned(
+                view_ident,
+                "A View requires the source of its records e.g \
+                #[orm(view(from = Order, group_by = customer))]",
+            )
+            .into());
+        };
+
+        if as_.is_some()
+            || define.is_some()
+            || schemafull.is_some()
+            || flexible.is_some()
+            || tenant_key.is_some()
+            || old_table.is_some()
+            || !indexes.is_empty()
+            || !events.is_empty()
+        {
+            return Err(syn::Error::new_spanned(
+                view_ident,
+                "Invalid combination. A View only supports the table, relax_table, drop, \
+                permissions and view attributes because its definition is generated from \
+                the view attribute and the expr attributes of its fields",
+            )
+            .into());
+        }
+
+        if !self.generics().params().is_empty() {
+            return Err(
+                syn::Error::new_spanned(view_ident, "A View cannot be a generic struct").into(),
+            );
+        }
+
+        let table = self.table()?.as_string();
+        let casing = casing_from_rename(rename_all.as_ref())?;
+        let from = &view.from;
+        let fields = self
+            .data
+            .as_ref()
+            .take_struct()
+            .ok_or_else(|| darling::Error::custom("A View must be a struct with named fields"))?
+            .fields;
+
+        let mut projections = vec![];
+        let mut serializable_fields = vec![];
+
+        for field in &fields {
+            if field.skip || field.skip_serializing {
+                continue;
+            }
+
+            if field.relate.is_some() || field.flatten {
+                return Err(syn::Error::new_spanned(
+                    field.ident()?,
+                    "relate and flatten are not supported on the fields of a View",
+                )
+                .into());
+            }
+
+            let db_field_name = field.db_field_name(&casing)?;
+            let field_ident_normalized = field.field_ident_normalized(&casing)?;
+
+            match &field.expr {
+                Some(expr) => {
+                    let expr = raw_if_string_literal(expr);
+                    projections.push(quote!(
+                        #crate_name::ValueLike::from((#expr).__as__(#db_field_name))
+                    ));
+                }
+                // The record id of a view is generated by the database
+                None if db_field_name.is_id() => {}
+                None => projections.push(quote!(
+                    #crate_name::ValueLike::from(
+                        #crate_name::Field::from(
+                            <#from as #crate_name::SchemaGetter>::schema().#field_ident_normalized
+                        )
+                        .__as__(#db_field_name)
+                    )
+                )),
+            }
+
+            serializable_fields.push(db_field_name);
+        }
+
+        let where_ = view.where_.as_ref().map(|where_| {
+            let where_ = raw_if_string_literal(where_);
+            quote!(.where_(#where_))
+        });
+
+        let group_by = match &view.group_by {
+            Some(group_by) => {
+                let mut grouped_fields = vec![];
+                for group_by_ident in &group_by.0 {
+                    let field = fields
+                        .iter()
+                        .find(|field| {
+                            field
+                                .ident()
+                                .is_ok_and(|ident| ident.to_string() == group_by_ident.to_string())
+                        })
+                        .ok_or_else(|| {
+                            syn::Error::new_spanned(
+                                group_by_ident,
+                                format!(
+                                    "group_by field `{group_by_ident}` is not a field of the View"
+                                ),
+                            )
+                        })?;
+                    grouped_fields.push(field.db_field_name(&casing)?);
+                }
+                Some(quote!(
+                    .group_by(::std::vec![#( #crate_name::Field::new(#grouped_fields) ),*])
+                ))
+            }
+            None => None,
+        };
+
+        let group_all = view.group_all.then(|| quote!(.group_all()));
+
+        let drop = drop.map(|_drop| quote!(.drop()));
+        let permissions = permissions
+            .as_ref()
+            .map(|permissions| permissions.to_token_stream());
+
+        Ok(quote!(
+            impl #crate_name::View for #view_ident {
+                fn table() -> #crate_name::Table {
+                    #crate_name::Table::new(#table)
+                }
+
+                fn view_statement() -> #crate_name::statements::SelectStatement {
+                    use #crate_name::Aliasable as _;
+
+                    #crate_name::statements::select(::std::vec![#( #projections ),*])
+                        .from(<#from as #crate_name::Model>::table())
+                        #where_
+                        #group_by
+                        #group_all
+                }
+
+                fn define_table() -> #crate_name::Raw {
+                    use #crate_name::ToRaw as _;
+
+                    #crate_name::statements::define_table(Self::table())
+                        #drop
+                        .as_(Self::view_statement())
+                        #permissions
+                        .to_raw()
+                }
+
+                fn get_serializable_fields() -> ::std::vec::Vec<#crate_name::Field> {
+                    ::std::vec![#( #crate_name::Field::new(#serializable_fields) ),*]
+                }
+            }
+
+            impl #crate_name::ViewResources for #view_ident {}
+        ))
+    }
+}
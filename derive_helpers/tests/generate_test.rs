@@ -155,3 +155,90 @@ fn test_edge_trait_derive() {
         format!("{:#}", node_token.to_token_stream())
     );
 }
+
+#[test_case(quote!(), false)]
+#[test_case(quote!(#[serde(with = "time::serde::rfc3339")]), true)]
+#[test_case(quote!(#[serde(with = "time::serde::rfc3339::option")]), true)]
+fn test_offset_datetime_field_must_be_serialized_as_rfc3339(
+    serde_with_attr: proc_macro2::TokenStream,
+    is_valid: bool,
+) {
+    let input = quote!(
+        #[derive(Node, Serialize, Deserialize)]
+        #[orm(table = "subscriber")]
+        pub struct Subscriber {
+            id: SurrealSimpleId<Self>,
+
+            #serde_with_attr
+            renewed_at: Option<time::OffsetDateTime>,
+        }
+    );
+
+    let derive_input = syn::parse2(input).unwrap();
+    let node_token = NodeToken::from_derive_input(&derive_input).unwrap();
+    let node_token = node_token.to_token_stream().to_string();
+
+    let has_error =
+        node_token.contains("`time::OffsetDateTime` fields must be serialized as a string");
+    assert_eq!(has_error, !is_valid);
+    assert_eq!(
+        node_token.contains("impl surreal_orm :: Node for Subscriber "),
+        is_valid
+    );
+}
+
+#[test]
+fn test_surreal_type_fields_keep_static_assertions_of_known_types() {
+    let input = quote!(
+        #[derive(Node, Serialize, Deserialize)]
+        #[orm(table = "subscriber")]
+        pub struct Subscriber {
+            id: SurrealSimpleId<Self>,
+
+            #[orm(value = default_last_seen())]
+            last_seen: chrono::DateTime<chrono::Utc>,
+
+            #[orm(value = default_tracking_id())]
+            tracking_id: uuid::Uuid,
+        }
+    );
+
+    let derive_input = syn::parse2(input).unwrap();
+    let node_token = NodeToken::from_derive_input(&derive_input).unwrap();
+    let node_token = node_token.to_token_stream().to_string();
+
+    assert!(node_token.contains(
+        "surreal_orm :: validators :: assert_type_is_datetime :: < chrono :: DateTime < chrono :: Utc > > ()"
+    ));
+    assert!(node_token
+        .contains("surreal_orm :: validators :: assert_value_is_datetime (default_last_seen ())"));
+    assert!(node_token
+        .contains("surreal_orm :: validators :: assert_type_is_uuid :: < uuid :: Uuid > ()"));
+    assert!(node_token
+        .contains("surreal_orm :: validators :: assert_value_is_uuid (default_tracking_id ())"));
+}
+
+#[test]
+fn test_duplicate_reverse_links_to_the_same_model_are_rejected() {
+    let input = quote!(
+        #[derive(Node, Serialize, Deserialize)]
+        #[orm(table = "post")]
+        pub struct Post {
+            id: SurrealSimpleId<Self>,
+
+            #[orm(link_one = User, reverse = posts)]
+            author: LinkOne<User>,
+
+            #[orm(link_many = User, reverse = posts)]
+            editors: LinkMany<User>,
+        }
+    );
+
+    let derive_input = syn::parse2(input).unwrap();
+    let node_token = NodeToken::from_derive_input(&derive_input).unwrap();
+    let node_token = node_token.to_token_stream().to_string();
+
+    assert!(node_token.contains(
+        "compile_error ! { \"The reverse link `posts` is already declared by another field of `Post` linking to `User`. Use another name\" }"
+    ));
+}
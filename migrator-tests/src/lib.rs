@@ -12,7 +12,7 @@ use surreal_models::migrations::{
 };
 use surreal_orm::{
     migrator::{
-        Basename, Checksum, DatabaseConnection, DbInfo, Down, Extension, FastForwardDelta,
+        Basename, Checksum, DatabaseConnection, DbInfo, Down, Drift, Extension, FastForwardDelta,
         Generate, Init, List, Migration, MigrationFilename, MigrationFlag, Migrator, MockPrompter,
         Mode, Prune, RenameOrDelete, Reset, RollbackStrategyStruct, Status, SubCommand, Up,
     },
@@ -357,6 +357,13 @@ impl TestConfig {
         self
     }
 
+    pub async fn run_drift(&mut self) -> &mut Self {
+        self.set_cmd(Drift::default())
+            .run(None::<MockEmptyResource>, MockPrompter::default())
+            .await;
+        self
+    }
+
     pub async fn run_prune(&mut self) -> &mut Self {
         self.set_cmd(Prune)
             .run(None::<MockEmptyResource>, MockPrompter::default())
@@ -0,0 +1,155 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use migrator_tests::{current_function, TestConfig};
+use pretty_assertions::assert_eq;
+use surreal_models::migrations::{Planet, Student};
+use surreal_orm::{
+    create_table_resources,
+    migrator::{
+        Generate, Init, Migration, MigrationError, MigrationFlag, MigrationRunner, MockPrompter,
+        Mode, RenameOrDelete, RightDatabase,
+    },
+    DbResources,
+};
+use tempfile::tempdir;
+use test_case::test_case;
+
+#[derive(Debug, Clone)]
+pub struct Resources;
+impl DbResources for Resources {
+    create_table_resources!(Planet, Student);
+}
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_init_baselines_existing_database_schema(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    let db = conf.migrator.db();
+    db.query(RightDatabase::get_codebase_schema_queries(&Resources))
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+    db.query("CREATE planet:earth SET firstName = 'Earth', population = 8, createdAt = time::now(), updatedAt = time::now(), labels = [];")
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+    let mock_prompter = MockPrompter::builder()
+        .allow_empty_migrations_gen(false)
+        .rename_or_delete_single_field_change(RenameOrDelete::Rename)
+        .build();
+    conf.run_init(
+        Init::builder()
+            .reversible(reversible)
+            .name("baseline".into())
+            .run(false)
+            .baseline_from_db(true)
+            .build(),
+        Resources,
+        mock_prompter,
+    )
+    .await;
+
+    let migration_files = conf.read_migrations_from_dir_sorted_asc();
+    assert_eq!(migration_files.len(), if reversible { 2 } else { 1 });
+    let up = migration_files
+        .iter()
+        .find(|f| !f.is_down())
+        .expect("Baseline migration exists");
+    let up_content = std::fs::read_to_string(migration_dir.join(up.to_string()))
+        .expect("Failed to read baseline migration");
+    assert!(up_content.contains("DEFINE TABLE planet"));
+    assert!(up_content.contains("DEFINE FIELD firstName ON planet"));
+    assert!(up_content.contains("DEFINE TABLE student"));
+    assert!(!up_content.contains("migration_lock"));
+
+    let applied = Migration::get_all_desc(db.clone()).await;
+    assert_eq!(
+        applied.iter().map(|m| m.name.clone()).collect::<Vec<_>>(),
+        vec![up.to_string()]
+    );
+    let planets: Option<i64> = db
+        .query("RETURN count(SELECT * FROM planet);")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    assert_eq!(planets, Some(1), "Baselining leaves the data untouched");
+
+    // The codebase matches the baseline so there is nothing to generate
+    conf.run_gen(
+        Generate::builder()
+            .name("after baseline".into())
+            .run(false)
+            .build(),
+        Resources,
+        mock_prompter,
+    )
+    .await;
+    assert_eq!(
+        conf.read_migrations_from_dir_sorted_asc().len(),
+        migration_files.len()
+    );
+}
+
+#[tokio::test]
+async fn test_cannot_baseline_database_with_applied_migrations() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+    conf.run_init(
+        Init::builder()
+            .reversible(true)
+            .name("migration init".into())
+            .run(true)
+            .build(),
+        Resources,
+        MockPrompter::default(),
+    )
+    .await;
+
+    let file_manager = conf.migrator.file_manager().set_flag(MigrationFlag::TwoWay);
+    let err =
+        MigrationRunner::baseline_from_db(conf.migrator.db(), &file_manager, &"baseline".into())
+            .await
+            .unwrap_err();
+    assert!(
+        matches!(err, MigrationError::BaselineDatabaseHasMigrations),
+        "Unexpected error: {err}"
+    );
+}
+
+#[tokio::test]
+async fn test_failed_baseline_write_registers_nothing() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    // A file in place of the parent of the migration directory makes writing the baseline fail
+    let not_a_dir = temp_dir.path().join("not-a-directory");
+    std::fs::write(&not_a_dir, "").unwrap();
+    let migration_dir = &not_a_dir.join("migrations-tests");
+    let conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+    let db = conf.migrator.db();
+    db.query(RightDatabase::get_codebase_schema_queries(&Resources))
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+    let file_manager = conf.migrator.file_manager().set_flag(MigrationFlag::TwoWay);
+    let err = MigrationRunner::baseline_from_db(db.clone(), &file_manager, &"baseline".into())
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, MigrationError::IoError(_)),
+        "Unexpected error: {err}"
+    );
+    assert!(Migration::get_all_desc(db.clone()).await.is_empty());
+}
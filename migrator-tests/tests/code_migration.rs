@@ -0,0 +1,240 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::fs;
+
+use migrator_tests::{current_function, TestConfig};
+use pretty_assertions::assert_eq;
+use surreal_orm::migrator::{
+    async_trait, CodeMigration, CodeMigrations, FastForwardDelta, Migration, MigrationConfig,
+    MigrationError, MigrationResult, Mode, RollbackStrategyStruct, UpdateStrategy,
+};
+use surrealdb::{
+    engine::{
+        any::Any,
+        local::{Db, Mem},
+    },
+    Surreal,
+};
+use tempfile::tempdir;
+use test_case::test_case;
+
+struct BackfillHandles;
+
+#[async_trait]
+impl CodeMigration for BackfillHandles {
+    fn name(&self) -> &'static str {
+        "20240102000000000_backfill_handles"
+    }
+
+    async fn up(&self, db: &Surreal<Any>) -> MigrationResult<()> {
+        let names: Vec<String> = db.query("SELECT VALUE name FROM account").await?.take(0)?;
+        for name in names {
+            db.query("UPDATE account SET handle = $handle WHERE name = $name")
+                .bind(("handle", name.to_lowercase()))
+                .bind(("name", name))
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn reversible(&self) -> bool {
+        true
+    }
+
+    async fn down(&self, db: &Surreal<Any>) -> MigrationResult<()> {
+        db.query("UPDATE account UNSET handle").await?;
+        Ok(())
+    }
+}
+
+struct NotReversible;
+
+#[async_trait]
+impl CodeMigration for NotReversible {
+    fn name(&self) -> &'static str {
+        "20240104000000000_not_reversible"
+    }
+
+    async fn up(&self, _db: &Surreal<Any>) -> MigrationResult<()> {
+        Ok(())
+    }
+}
+
+const CODE_MIGRATIONS: CodeMigrations = CodeMigrations::new(&[&BackfillHandles]);
+const CODE_MIGRATIONS_NOT_REVERSIBLE: CodeMigrations =
+    CodeMigrations::new(&[&BackfillHandles, &NotReversible]);
+
+fn write_migration_files(migration_dir: &std::path::Path) {
+    fs::create_dir_all(migration_dir).expect("Failed to create migration directory");
+    let files = [
+        (
+            "20240101000000000_create_account.up.surql",
+            "DEFINE TABLE account SCHEMALESS;\nCREATE account:1 SET name = 'Oyelowo';",
+        ),
+        (
+            "20240101000000000_create_account.down.surql",
+            "REMOVE TABLE account;",
+        ),
+        (
+            "20240103000000000_snapshot_handles.up.surql",
+            "CREATE snapshot:1 SET handles = (SELECT VALUE handle FROM account);",
+        ),
+        (
+            "20240103000000000_snapshot_handles.down.surql",
+            "REMOVE TABLE snapshot;",
+        ),
+    ];
+    for (name, content) in files {
+        fs::write(migration_dir.join(name), content).expect("Failed to write migration file");
+    }
+}
+
+async fn registered_migration_names(db: Surreal<Any>) -> Vec<String> {
+    Migration::get_all_desc(db)
+        .await
+        .into_iter()
+        .map(|m| m.name)
+        .collect()
+}
+
+async fn local_db() -> Surreal<Db> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    db
+}
+
+async fn local_registered_migration_names(db: Surreal<Db>) -> Vec<String> {
+    db.query("SELECT VALUE name FROM migration ORDER BY name")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap()
+}
+
+#[test_case(Mode::Strict; "Strict")]
+#[test_case(Mode::Lax; "Lax")]
+#[tokio::test]
+async fn test_code_migrations_are_interleaved_with_files_in_timestamp_order(mode: Mode) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    write_migration_files(migration_dir);
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    conf.migrator.set_code_migrations(CODE_MIGRATIONS);
+    let db = conf.migrator.db().clone();
+
+    conf.run_up(&FastForwardDelta::default()).await;
+
+    assert_eq!(
+        registered_migration_names(db.clone()).await,
+        vec![
+            "20240103000000000_snapshot_handles.up.surql",
+            "20240102000000000_backfill_handles.up.surql",
+            "20240101000000000_create_account.up.surql",
+        ]
+    );
+    let handles: Option<Vec<String>> = db
+        .query("SELECT VALUE handles FROM ONLY snapshot:1")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    assert_eq!(
+        handles,
+        Some(vec!["oyelowo".to_string()]),
+        "The file after the code migration sees its backfill"
+    );
+
+    conf.run_down(&RollbackStrategyStruct::default(), false)
+        .await;
+    conf.run_down(&RollbackStrategyStruct::default(), false)
+        .await;
+
+    assert_eq!(
+        registered_migration_names(db.clone()).await,
+        vec!["20240101000000000_create_account.up.surql"]
+    );
+    let handles: Vec<Option<String>> = db
+        .query("SELECT VALUE handle FROM account")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    assert_eq!(handles, vec![None], "The code migration is rolled back");
+
+    conf.run_up(&FastForwardDelta::builder().number(1).build())
+        .await;
+
+    assert_eq!(
+        registered_migration_names(db.clone()).await,
+        vec![
+            "20240102000000000_backfill_handles.up.surql",
+            "20240101000000000_create_account.up.surql",
+        ]
+    );
+}
+
+#[tokio::test]
+#[should_panic(expected = "is not reversible")]
+async fn test_cannot_rollback_code_migration_without_down() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    write_migration_files(migration_dir);
+    let mut conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+    conf.migrator
+        .set_code_migrations(CODE_MIGRATIONS_NOT_REVERSIBLE);
+
+    conf.run_up(&FastForwardDelta::default()).await;
+    conf.run_down(&RollbackStrategyStruct::default(), false)
+        .await;
+}
+
+#[tokio::test]
+async fn test_file_migrations_run_on_local_connection() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    write_migration_files(migration_dir);
+    let db = local_db().await;
+
+    MigrationConfig::new()
+        .set_custom_path(migration_dir)
+        .two_way()
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        local_registered_migration_names(db).await,
+        vec![
+            "20240101000000000_create_account.up.surql",
+            "20240103000000000_snapshot_handles.up.surql",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_code_migrations_are_refused_on_local_connection() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    write_migration_files(migration_dir);
+    let db = local_db().await;
+
+    let applied = MigrationConfig::new()
+        .set_custom_path(migration_dir)
+        .set_code_migrations(CODE_MIGRATIONS)
+        .two_way()
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+        .await;
+
+    assert!(matches!(
+        applied,
+        Err(MigrationError::CodeMigrationRequiresAnyConnection(name))
+            if name == "20240102000000000_backfill_handles.up.surql"
+    ));
+    assert!(
+        local_registered_migration_names(db).await.is_empty(),
+        "No migration is applied before the code migration is refused"
+    );
+}
@@ -0,0 +1,112 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::{fs, path::Path, time::Duration};
+
+use clap::CommandFactory;
+use pretty_assertions::assert_eq;
+use surreal_orm::migrator::{ConfigFile, MigrationError, Migrator};
+use tempfile::tempdir;
+
+const CONFIG: &str = r#"
+dir = "migrations"
+url = "ws://localhost:8000"
+ns = "app"
+db = "app"
+
+[env.staging]
+url = "wss://staging.example.com"
+user = "${SURREAL_ORM_TEST_STAGING_USER}"
+lock_timeout = 300
+"#;
+
+fn parse(args: &[&str]) -> Result<Migrator, MigrationError> {
+    let matches = Migrator::command().get_matches_from(
+        std::iter::once("migrator")
+            .chain(args.iter().copied())
+            .chain(std::iter::once("up")),
+    );
+    Migrator::from_matches_with_config(&matches)
+}
+
+fn path_arg(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_environment_settings_override_top_level_settings_and_flags_override_both() {
+    let config_dir = tempdir().expect("Failed to create temp directory");
+    let config_path = config_dir.path().join(ConfigFile::FILENAME);
+    fs::write(&config_path, CONFIG).expect("Failed to write config file");
+    std::env::set_var("SURREAL_ORM_TEST_STAGING_USER", "migrator");
+
+    let cli = parse(&[
+        "--config",
+        &path_arg(&config_path),
+        "--env",
+        "staging",
+        "--db",
+        "from_flag",
+    ])
+    .unwrap();
+
+    assert_eq!(cli.dir, Some(config_dir.path().join("migrations")));
+    assert_eq!(cli.lock_timeout(), Duration::from_secs(300));
+    let connection = cli.db_connection();
+    assert_eq!(connection.url().to_string(), "wss://staging.example.com");
+    assert_eq!(connection.namespace(), "app");
+    assert_eq!(connection.database(), "from_flag");
+    assert_eq!(connection.user(), "migrator");
+
+    let cli = parse(&["--config", &path_arg(&config_path)]).unwrap();
+    let connection = cli.db_connection();
+    assert_eq!(connection.url().to_string(), "ws://localhost:8000");
+    assert_eq!(connection.user(), "root");
+    assert_eq!(cli.lock_timeout(), Duration::from_secs(60));
+}
+
+#[test]
+fn test_config_is_read_from_cargo_manifest_metadata() {
+    let config_dir = tempdir().expect("Failed to create temp directory");
+    let manifest_path = config_dir.path().join("Cargo.toml");
+    fs::write(
+        &manifest_path,
+        r#"
+[package]
+name = "app"
+version = "0.1.0"
+
+[package.metadata.surreal_orm]
+ns = "from_manifest"
+"#,
+    )
+    .expect("Failed to write Cargo.toml");
+
+    let cli = parse(&["--config", &path_arg(&manifest_path)]).unwrap();
+    assert_eq!(cli.db_connection().namespace(), "from_manifest");
+}
+
+#[test]
+fn test_unknown_environment_and_unset_env_var_are_errors() {
+    let config_dir = tempdir().expect("Failed to create temp directory");
+    let config_path = config_dir.path().join(ConfigFile::FILENAME);
+    fs::write(
+        &config_path,
+        format!("{CONFIG}\n[env.production]\npass = \"${{SURREAL_ORM_TEST_UNSET_PASS}}\"\n"),
+    )
+    .expect("Failed to write config file");
+
+    let err = parse(&["--config", &path_arg(&config_path), "--env", "qa"]).unwrap_err();
+    assert!(
+        matches!(&err, MigrationError::ConfigEnvironmentDoesNotExist { env } if env == "qa"),
+        "Unexpected error: {err}"
+    );
+
+    let err = parse(&["--config", &path_arg(&config_path), "--env", "production"]).unwrap_err();
+    assert!(
+        matches!(&err, MigrationError::ConfigEnvVarNotSet(var) if var == "SURREAL_ORM_TEST_UNSET_PASS"),
+        "Unexpected error: {err}"
+    );
+}
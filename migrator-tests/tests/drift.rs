@@ -0,0 +1,162 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::{fs, path::Path};
+
+use migrator_tests::{current_function, TestConfig};
+use pretty_assertions::assert_eq;
+use surreal_orm::migrator::{
+    DatabaseConnection, DriftKind, DriftReport, DriftResource, FastForwardDelta, MigrationConfig,
+    Mode, UpdateStrategy,
+};
+use tempfile::tempdir;
+use test_case::test_case;
+
+fn write_migration_files(migration_dir: &Path) {
+    fs::create_dir_all(migration_dir).expect("Failed to create migration directory");
+    let files = [
+        (
+            "20240101000000000_create_account.up.surql",
+            "DEFINE TABLE account SCHEMAFULL;\nDEFINE FIELD name ON account TYPE string;",
+        ),
+        (
+            "20240101000000000_create_account.down.surql",
+            "REMOVE TABLE account;",
+        ),
+        (
+            "20240102000000000_create_org.up.surql",
+            "DEFINE TABLE org SCHEMAFULL;\nDEFINE FIELD name ON org TYPE string;",
+        ),
+        (
+            "20240102000000000_create_org.down.surql",
+            "REMOVE TABLE org;",
+        ),
+    ];
+    for (name, content) in files {
+        fs::write(migration_dir.join(name), content).expect("Failed to write migration file");
+    }
+}
+
+fn write_oneway_migration_files(migration_dir: &Path) {
+    fs::create_dir_all(migration_dir).expect("Failed to create migration directory");
+    let files = [
+        (
+            "20240101000000000_create_account.surql",
+            "DEFINE TABLE account SCHEMAFULL;\nDEFINE FIELD name ON account TYPE string;",
+        ),
+        (
+            "20240102000000000_create_org.surql",
+            "DEFINE TABLE org SCHEMAFULL;\nDEFINE FIELD name ON org TYPE string;",
+        ),
+    ];
+    for (name, content) in files {
+        fs::write(migration_dir.join(name), content).expect("Failed to write migration file");
+    }
+}
+
+#[tokio::test]
+async fn test_drift_reports_definitions_changed_outside_migrations() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = migration_dir.path().join("migrations-tests");
+    write_migration_files(&migration_dir);
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+    let two_way = MigrationConfig::new()
+        .set_custom_path(migration_dir.clone())
+        .two_way();
+    two_way
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Number(1))
+        .await
+        .unwrap();
+
+    let report = DriftReport::detect(db.clone(), &two_way).await.unwrap();
+    assert!(
+        !report.has_drift(),
+        "Pending migrations are not drift: {report}"
+    );
+
+    db.query(
+        "DEFINE FIELD age ON account TYPE int;\n\
+        REMOVE FIELD name ON account;\n\
+        DEFINE INDEX account_age ON account FIELDS age;",
+    )
+    .await
+    .unwrap()
+    .check()
+    .unwrap();
+
+    let report = DriftReport::detect(db.clone(), &two_way).await.unwrap();
+    let drifts = report
+        .drifts
+        .iter()
+        .map(|d| (d.kind, d.resource, d.table.as_deref(), d.name.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        drifts,
+        vec![
+            (
+                DriftKind::Extra,
+                DriftResource::Field,
+                Some("account"),
+                "age"
+            ),
+            (
+                DriftKind::Missing,
+                DriftResource::Field,
+                Some("account"),
+                "name"
+            ),
+            (
+                DriftKind::Extra,
+                DriftResource::Index,
+                Some("account"),
+                "account_age"
+            ),
+        ]
+    );
+    assert!(report.drifts[1]
+        .expected
+        .as_ref()
+        .is_some_and(|def| def.starts_with("DEFINE FIELD name ON account TYPE string")));
+    assert_eq!(report.drifts[1].actual, None);
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(json["drifts"][0]["kind"], "extra");
+    assert_eq!(json["drifts"][0]["resource"], "field");
+    assert_eq!(json["drifts"][0]["table"], "account");
+}
+
+#[test_case(Mode::Strict; "Strict")]
+#[test_case(Mode::Lax; "Lax")]
+#[tokio::test]
+#[should_panic(expected = "Schema drift detected")]
+async fn test_drift_command_fails_when_database_drifts(mode: Mode) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    write_migration_files(migration_dir);
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    conf.run_up(&FastForwardDelta::default()).await;
+    conf.run_drift().await;
+
+    conf.migrator
+        .db()
+        .query("DEFINE TABLE hotfix SCHEMALESS;")
+        .await
+        .unwrap();
+
+    conf.run_drift().await;
+}
+
+#[test_case(write_migration_files; "TwoWay")]
+#[test_case(write_oneway_migration_files; "OneWay")]
+#[tokio::test]
+async fn test_drift_command_passes_when_database_matches_migrations(write_files: fn(&Path)) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    write_files(migration_dir);
+    let mut conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+
+    conf.run_up(&FastForwardDelta::default()).await;
+    conf.run_drift().await;
+}
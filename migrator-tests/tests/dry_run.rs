@@ -0,0 +1,159 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::{fs, path::Path};
+
+use pretty_assertions::assert_eq;
+use surreal_models::migrations::Resources;
+use surreal_orm::migrator::{
+    DatabaseConnection, Migration, MigrationConfig, MigrationPlan, MockPrompter, PlanAction,
+    PlannedMigrationKind, RollbackOptions, UpdateStrategy,
+};
+use tempfile::tempdir;
+
+fn write_migration_files(migration_dir: &Path) {
+    fs::create_dir_all(migration_dir).expect("Failed to create migration directory");
+    let files = [
+        (
+            "20240101000000000_create_account.up.surql",
+            "DEFINE TABLE account SCHEMALESS;",
+        ),
+        (
+            "20240101000000000_create_account.down.surql",
+            "REMOVE TABLE account;",
+        ),
+        (
+            "20240102000000000_create_org.up.surql",
+            "CREATE org:1 SET name = 'Codebreather';",
+        ),
+        ("20240102000000000_create_org.down.surql", "DELETE org:1;"),
+    ];
+    for (name, content) in files {
+        fs::write(migration_dir.join(name), content).expect("Failed to write migration file");
+    }
+}
+
+fn planned_names(plan: &MigrationPlan) -> Vec<String> {
+    plan.migrations.iter().map(|m| m.name.clone()).collect()
+}
+
+#[tokio::test]
+async fn test_dry_run_up_plans_pending_migrations_without_applying_them() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = migration_dir.path().join("migrations-tests");
+    write_migration_files(&migration_dir);
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+    let two_way = MigrationConfig::new()
+        .set_custom_path(migration_dir.clone())
+        .two_way();
+
+    let plan = two_way
+        .plan_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+        .await
+        .unwrap();
+
+    assert_eq!(plan.action, PlanAction::Apply);
+    assert_eq!(
+        planned_names(&plan),
+        vec![
+            "20240101000000000_create_account.up.surql",
+            "20240102000000000_create_org.up.surql",
+        ]
+    );
+    assert_eq!(plan.migrations[0].kind, PlannedMigrationKind::File);
+    assert!(plan.migrations[0]
+        .statements
+        .starts_with("DEFINE TABLE account SCHEMALESS;"));
+    assert!(
+        plan.migrations[0].statements.contains("CREATE migration"),
+        "The plan includes registering the migration"
+    );
+    assert!(Migration::get_all_desc(db.clone()).await.is_empty());
+
+    let json: serde_json::Value = serde_json::from_str(&plan.to_json().unwrap()).unwrap();
+    assert_eq!(json["action"], "apply");
+    assert_eq!(
+        json["migrations"][1]["name"],
+        "20240102000000000_create_org.up.surql"
+    );
+    assert_eq!(json["migrations"][1]["kind"], "file");
+
+    let plan = two_way
+        .plan_up_pending_migrations(db.clone(), UpdateStrategy::Number(1))
+        .await
+        .unwrap();
+    assert_eq!(
+        planned_names(&plan),
+        vec!["20240101000000000_create_account.up.surql"]
+    );
+}
+
+#[tokio::test]
+async fn test_dry_run_down_plans_rollback_without_rolling_back() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = migration_dir.path().join("migrations-tests");
+    write_migration_files(&migration_dir);
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+    let two_way = MigrationConfig::new()
+        .set_custom_path(migration_dir.clone())
+        .two_way();
+    two_way
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+        .await
+        .unwrap();
+
+    let plan = two_way
+        .plan_down_migrations(db.clone(), RollbackOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(plan.action, PlanAction::Rollback);
+    assert_eq!(
+        planned_names(&plan),
+        vec!["20240102000000000_create_org.down.surql"]
+    );
+    assert!(plan.migrations[0].statements.starts_with("DELETE org:1;"));
+    assert!(
+        plan.migrations[0].statements.contains("DELETE migration:"),
+        "The plan includes removing the migration from the migration table"
+    );
+    assert_eq!(Migration::get_all_desc(db.clone()).await.len(), 2);
+    assert!(plan.to_string().contains("-- 1 migration(s) to roll back"));
+}
+
+#[tokio::test]
+async fn test_generate_print_does_not_write_migration_files() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = migration_dir.path().join("migrations-tests");
+    write_migration_files(&migration_dir);
+    let two_way = MigrationConfig::new()
+        .set_custom_path(migration_dir.clone())
+        .two_way();
+
+    let plan = two_way
+        .plan_migration(&"add resources".into(), Resources, MockPrompter::default())
+        .await
+        .unwrap();
+
+    assert_eq!(plan.action, PlanAction::Generate);
+    assert_eq!(plan.migrations.len(), 2);
+    assert!(plan.migrations[0].name.ends_with("_add_resources.up.surql"));
+    assert!(plan.migrations[1]
+        .name
+        .ends_with("_add_resources.down.surql"));
+    assert!(!plan.migrations[0].statements.trim().is_empty());
+    let lint = plan.lint.as_ref().expect("The plan includes the lint");
+    assert!(!lint.findings.is_empty());
+    assert!(plan.to_string().contains(&format!(
+        "-- Lint: {}",
+        lint.to_string().lines().next().unwrap()
+    )));
+    assert!(plan.to_json().unwrap().contains("\"lint\""));
+    assert_eq!(
+        fs::read_dir(&migration_dir).unwrap().count(),
+        4,
+        "No migration file is written"
+    );
+}
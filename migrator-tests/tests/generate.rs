@@ -6,7 +6,8 @@
 
 use migrator_tests::{current_function, AssertionArg, TestConfig};
 use surreal_models::migrations::{
-    invalid_cases, Animal, AnimalV2, Planet, PlanetV2, Resources, ResourcesV2,
+    invalid_cases, Animal, AnimalV2, Anthology, AnthologyV2, Author, Client, Customer, Novel,
+    NovelV2, Planet, PlanetPopulation, PlanetV2, Resources, ResourcesV2, Writer,
 };
 use surreal_orm::{
     create_table_resources,
@@ -210,6 +211,7 @@ async fn test_successfully_handles_renaming(
         Generate::builder()
             .name("migration gen 1".into())
             .run(false)
+            .allow_destructive(true)
             .build(),
         ResourcesV2,
         mock_prompter,
@@ -345,6 +347,162 @@ async fn test_successfully_handles_renaming(
     assert!(migration_dir.exists());
 }
 
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[tokio::test]
+async fn test_successfully_handles_table_renaming(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    #[derive(Debug, Clone)]
+    pub struct ResourcesV1;
+    impl DbResources for ResourcesV1 {
+        create_table_resources!(Client);
+    }
+    #[derive(Debug, Clone)]
+    pub struct ResourcesV2;
+    impl DbResources for ResourcesV2 {
+        create_table_resources!(Customer);
+    }
+
+    let mock_prompter = MockPrompter::builder()
+        .allow_empty_migrations_gen(true)
+        .rename_or_delete_single_field_change(RenameOrDelete::Rename)
+        .build();
+
+    conf.run_init(
+        Init::builder()
+            .reversible(reversible)
+            .name("migration init".into())
+            .run(false)
+            .build(),
+        ResourcesV1,
+        mock_prompter,
+    )
+    .await;
+    conf.run_gen(
+        Generate::builder()
+            .name("migration gen 1".into())
+            .run(false)
+            .build(),
+        ResourcesV2,
+        mock_prompter,
+    )
+    .await;
+
+    let read_gen_migration = |is_down: bool| {
+        let filename = conf
+            .read_migrations_from_dir_sorted_asc()
+            .into_iter()
+            .find(|f| f.basename().to_string() == "migration_gen_1" && f.is_down() == is_down)
+            .expect("Generated migration file exists");
+        std::fs::read_to_string(migration_dir.join(filename.to_string()))
+            .expect("Failed to read migration file")
+    };
+
+    let up = read_gen_migration(false);
+    assert!(up.contains(
+        "-- Rename table client to customer\
+        \nDEFINE TABLE customer SCHEMAFULL PERMISSIONS NONE;"
+    ));
+    assert!(up.contains("DEFINE FIELD email ON customer TYPE string PERMISSIONS FULL;"));
+    assert!(up.contains("customer_email_idx"));
+    assert!(
+        up.contains(
+            "INSERT INTO customer (SELECT *, meta::id(id) AS id FROM client);\
+            \nREMOVE TABLE client;"
+        ),
+        "Copies the records of the old table before removing it"
+    );
+    assert!(!up.contains("REMOVE TABLE customer;"));
+
+    if reversible {
+        let down = read_gen_migration(true);
+        assert!(down.contains("DEFINE TABLE client SCHEMAFULL PERMISSIONS NONE;"));
+        assert!(down.contains("DEFINE FIELD email ON client TYPE string PERMISSIONS FULL;"));
+        assert!(
+            down.contains(
+                "INSERT INTO client (SELECT *, meta::id(id) AS id FROM customer);\
+                \nREMOVE TABLE customer;"
+            ),
+            "Copies the records back to the old table before removing the new one"
+        );
+    }
+}
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_generates_view_table_definitions(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    #[derive(Debug, Clone)]
+    pub struct ResourcesV1;
+    impl DbResources for ResourcesV1 {
+        create_table_resources!(Planet);
+    }
+    #[derive(Debug, Clone)]
+    pub struct ResourcesV2;
+    impl DbResources for ResourcesV2 {
+        create_table_resources!(Planet; views: PlanetPopulation);
+    }
+
+    conf.run_init(
+        Init::builder()
+            .reversible(reversible)
+            .name("migration init".into())
+            .run(true)
+            .build(),
+        ResourcesV1,
+        MockPrompter::default(),
+    )
+    .await;
+    conf.run_gen(
+        Generate::builder()
+            .name("migration gen 1".into())
+            .run(true)
+            .build(),
+        ResourcesV2,
+        MockPrompter::default(),
+    )
+    .await;
+
+    let read_gen_migration = |is_down: bool| {
+        let filename = conf
+            .read_migrations_from_dir_sorted_asc()
+            .into_iter()
+            .find(|f| f.basename().to_string() == "migration_gen_1" && f.is_down() == is_down)
+            .expect("Generated migration file exists");
+        std::fs::read_to_string(migration_dir.join(filename.to_string()))
+            .expect("Failed to read migration file")
+    };
+
+    let up = read_gen_migration(false);
+    assert!(up.contains("DEFINE TABLE planet_population"), "{up}");
+    assert!(
+        up.contains(
+            "SELECT firstName, math::sum(population) AS population FROM planet GROUP BY firstName"
+        ),
+        "The view is defined from the select of its derive: {up}"
+    );
+    assert!(!up.contains("DEFINE FIELD population ON planet_population"));
+
+    if reversible {
+        let down = read_gen_migration(true);
+        assert!(down.contains("REMOVE TABLE planet_population;"));
+    }
+
+    conf.assert_with_db_instance(AssertionArg {
+        expected_mig_files_count: 2,
+        expected_db_mig_meta_count: 2,
+        expected_latest_migration_file_basename_normalized: Some("migration_gen_1".into()),
+        expected_latest_db_migration_meta_basename_normalized: Some("migration_gen_1".into()),
+        code_origin_line: std::line!(),
+    })
+    .await;
+}
+
 #[test_case(Mode::Strict, true; "Reversible Strict")]
 #[test_case(Mode::Lax, true; "Reversible Lax")]
 #[test_case(Mode::Strict, false; "Non-Reversible Strict")]
@@ -393,8 +551,16 @@ async fn test_can_generate_after_first_initializing_no_db_run(mode: Mode, revers
         assert!(snapshot.contains("DEFINE FIELD velocity ON animal TYPE int PERMISSIONS FULL;"));
         assert!(snapshot
             .contains("DEFINE INDEX species_speed_idx ON animal FIELDS species, velocity UNIQUE;"));
-        assert!(snapshot.contains("DEFINE EVENT event1 ON animal WHEN (species = 'Homo Erectus') AND (velocity > 545) THEN (SELECT * FROM crop);"));
-        assert!(snapshot.contains("DEFINE EVENT event2 ON animal WHEN (species = 'Homo Sapien') AND (velocity < 10) THEN (SELECT * FROM eats);"));
+        assert!(snapshot.contains(
+            "DEFINE EVENT event1 ON animal
+    WHEN (species = 'Homo Erectus') AND (velocity > 545)
+    THEN (SELECT * FROM crop);"
+        ));
+        assert!(snapshot.contains(
+            "DEFINE EVENT event2 ON animal
+    WHEN (species = 'Homo Sapien') AND (velocity < 10)
+    THEN (SELECT * FROM eats);"
+        ));
 
         assert!(snapshot.contains("DEFINE TABLE crop SCHEMAFULL PERMISSIONS NONE;"));
         assert!(snapshot.contains("DEFINE FIELD color ON crop TYPE string PERMISSIONS FULL;"));
@@ -775,6 +941,7 @@ async fn should_panic_if_same_field_renaming_twice(mode: Mode, reversible: bool)
         Generate::builder()
             .name("migration 2 gen".into())
             .run(true)
+            .allow_destructive(true)
             .build(),
         ResourcesV2,
         MockPrompter::default(),
@@ -795,6 +962,7 @@ async fn should_panic_if_same_field_renaming_twice(mode: Mode, reversible: bool)
         Generate::builder()
             .name("migration 3 gen".into())
             .run(true)
+            .allow_destructive(true)
             .build(),
         ResourcesV2,
         MockPrompter::default(),
@@ -922,3 +1090,144 @@ async fn test_should_panic_if_renaming_from_non_existing_field_in_migration_dire
     .await;
     assert!(migration_dir.exists());
 }
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_rewrites_links_to_renamed_table(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    #[derive(Debug, Clone)]
+    pub struct ResourcesV1;
+    impl DbResources for ResourcesV1 {
+        create_table_resources!(Author, Novel);
+    }
+    #[derive(Debug, Clone)]
+    pub struct ResourcesV2;
+    impl DbResources for ResourcesV2 {
+        create_table_resources!(Writer, NovelV2);
+    }
+
+    conf.run_init(
+        Init::builder()
+            .reversible(reversible)
+            .name("migration init".into())
+            .run(true)
+            .build(),
+        ResourcesV1,
+        MockPrompter::default(),
+    )
+    .await;
+    let db = conf.migrator.db().clone();
+    db.query("CREATE author:oyelowo SET fullName = 'Oyelowo Oyedayo';")
+        .query("CREATE novel:sky SET title = 'Sky', author = author:oyelowo;")
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+    conf.run_gen(
+        Generate::builder()
+            .name("migration gen 1".into())
+            .run(true)
+            .build(),
+        ResourcesV2,
+        MockPrompter::default(),
+    )
+    .await;
+
+    let read_gen_migration = |is_down: bool| {
+        let filename = conf
+            .read_migrations_from_dir_sorted_asc()
+            .into_iter()
+            .find(|f| f.basename().to_string() == "migration_gen_1" && f.is_down() == is_down)
+            .expect("Generated migration file exists");
+        let content = std::fs::read_to_string(migration_dir.join(filename.to_string()))
+            .expect("Failed to read migration file");
+        // Long statements are split into clauses on separate lines
+        content.split_whitespace().collect::<Vec<_>>().join(" ")
+    };
+
+    let up = read_gen_migration(false);
+    assert!(
+        up.contains(
+            "INSERT INTO writer (SELECT *, meta::id(id) AS id, fullName AS name OMIT fullName FROM author);"
+        ),
+        "Moves the renamed field while copying the records: {up}"
+    );
+    assert!(
+        up.contains(
+            "UPDATE novel SET author = type::thing('writer', meta::id(author)) \
+            WHERE type::is::record(author, 'author');"
+        ),
+        "Rewrites the links to the old table: {up}"
+    );
+    assert!(up.contains("REMOVE TABLE author;"));
+
+    if reversible {
+        let down = read_gen_migration(true);
+        assert!(down.contains(
+            "INSERT INTO author (SELECT *, meta::id(id) AS id, name AS fullName OMIT name FROM writer);"
+        ));
+        assert!(down.contains(
+            "UPDATE novel SET author = type::thing('author', meta::id(author)) \
+            WHERE type::is::record(author, 'writer');"
+        ));
+    }
+
+    let author: Option<String> = db
+        .query("SELECT VALUE type::string(author) FROM ONLY novel:sky")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    assert_eq!(author, Some("writer:oyelowo".to_string()));
+    let name: Option<String> = db
+        .query("SELECT VALUE name FROM ONLY writer:oyelowo")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    assert_eq!(name, Some("Oyelowo Oyedayo".to_string()));
+}
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+#[should_panic(expected = "Failed to generate migrations")]
+async fn test_should_panic_if_renamed_table_is_linked_in_array(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    #[derive(Debug, Clone)]
+    pub struct ResourcesV1;
+    impl DbResources for ResourcesV1 {
+        create_table_resources!(Author, Anthology);
+    }
+    #[derive(Debug, Clone)]
+    pub struct ResourcesV2;
+    impl DbResources for ResourcesV2 {
+        create_table_resources!(Writer, AnthologyV2);
+    }
+
+    conf.run_init(
+        Init::builder()
+            .reversible(reversible)
+            .name("migration init".into())
+            .run(true)
+            .build(),
+        ResourcesV1,
+        MockPrompter::default(),
+    )
+    .await;
+    conf.run_gen(
+        Generate::builder()
+            .name("migration gen 1".into())
+            .run(true)
+            .build(),
+        ResourcesV2,
+        MockPrompter::default(),
+    )
+    .await;
+}
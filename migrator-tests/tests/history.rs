@@ -0,0 +1,186 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::{fs, path::Path};
+
+use migrator_tests::{current_function, TestConfig};
+use pretty_assertions::assert_eq;
+use surreal_models::migrations::Resources;
+use surreal_orm::{
+    migrator::{
+        DatabaseConnection, FileContent, Init, Migration, MigrationConfig, MigrationDirection,
+        MigrationFailure, MigrationFilename, MigrationFlag, MigrationHistory, MockPrompter, Mode,
+        UpdateStrategy,
+    },
+    statements::begin_transaction,
+    Runnable,
+};
+use tempfile::tempdir;
+use test_case::test_case;
+
+fn write_migration_files(migration_dir: &Path, files: &[(&str, &str)]) {
+    fs::create_dir_all(migration_dir).expect("Failed to create migration directory");
+    for (name, content) in files {
+        fs::write(migration_dir.join(name), content).expect("Failed to write migration file");
+    }
+}
+
+fn assert_history_recorded(applied: &Migration) {
+    assert!(applied.applied_at.is_some());
+    assert!(applied
+        .applied_by
+        .as_ref()
+        .is_some_and(|by| by.contains('@')));
+    assert_eq!(
+        applied.migrator_version.as_deref(),
+        Some(env!("CARGO_PKG_VERSION"))
+    );
+}
+
+#[tokio::test]
+async fn test_applied_migrations_record_history_after_upgrading_the_migration_table() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = migration_dir.path().join("migrations-tests");
+    write_migration_files(
+        &migration_dir,
+        &[
+            (
+                "20240101000000000_create_account.up.surql",
+                "DEFINE TABLE account SCHEMALESS;",
+            ),
+            (
+                "20240101000000000_create_account.down.surql",
+                "REMOVE TABLE account;",
+            ),
+        ],
+    );
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+
+    // The migration table as defined by a migrator without the history fields
+    let mut old_migration_table = begin_transaction().query(Migration::define_table());
+    for def in Migration::define_fields(MigrationFlag::TwoWay) {
+        old_migration_table = old_migration_table.query(def);
+    }
+    old_migration_table
+        .commit_transaction()
+        .run(db.clone())
+        .await
+        .unwrap();
+
+    MigrationConfig::new()
+        .set_custom_path(migration_dir.clone())
+        .two_way()
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+        .await
+        .unwrap();
+
+    let history = MigrationHistory::get(db.clone()).await.unwrap();
+    assert_eq!(history.applied.len(), 1);
+    let applied = &history.applied[0];
+    assert_eq!(applied.name, "20240101000000000_create_account.up.surql");
+    assert_history_recorded(applied);
+    assert!(applied.duration_ms.is_some());
+    assert!(history.failures.is_empty());
+}
+
+#[tokio::test]
+async fn test_aborted_migrations_are_recorded_as_failures() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = migration_dir.path().join("migrations-tests");
+    write_migration_files(
+        &migration_dir,
+        &[
+            (
+                "20240101000000000_create_account.up.surql",
+                "DEFINE TABLE account SCHEMALESS;",
+            ),
+            (
+                "20240101000000000_create_account.down.surql",
+                "REMOVE TABLE account;",
+            ),
+            (
+                "20240102000000000_create_org.up.surql",
+                "CREATE org:1 SET name = 'Codebreather';\nCREATE org:1 SET name = 'Oyelowo';",
+            ),
+            ("20240102000000000_create_org.down.surql", "DELETE org:1;"),
+        ],
+    );
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+
+    let err = MigrationConfig::new()
+        .set_custom_path(migration_dir.clone())
+        .two_way()
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+        .await
+        .unwrap_err();
+
+    assert!(Migration::get_all_desc(db.clone()).await.is_empty());
+    let failures = MigrationFailure::get_all_desc(db.clone()).await.unwrap();
+    assert_eq!(failures.len(), 1);
+    let failure = &failures[0];
+    assert_eq!(failure.direction, MigrationDirection::Up);
+    assert_eq!(
+        failure.migrations,
+        vec![
+            "20240101000000000_create_account.up.surql",
+            "20240102000000000_create_org.up.surql",
+        ]
+    );
+    assert_eq!(failure.error, err.to_string());
+    assert!(failure.error.contains("already exists"));
+
+    let history = MigrationHistory::get(db.clone()).await.unwrap();
+    assert!(history.to_string().contains("1 failed attempt(s)"));
+}
+
+#[test_case(true; "Reversible")]
+#[test_case(false; "Non-Reversible")]
+#[tokio::test]
+async fn test_init_records_history_of_the_first_migration(reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+
+    conf.run_init(
+        Init::builder()
+            .name("migration_init".into())
+            .reversible(reversible)
+            .run(true)
+            .build(),
+        Resources,
+        MockPrompter::default(),
+    )
+    .await;
+
+    let history = MigrationHistory::get(conf.migrator.db()).await.unwrap();
+    assert_eq!(history.applied.len(), 1);
+    let extension = if reversible { "up.surql" } else { "surql" };
+    assert!(history.applied[0]
+        .name
+        .ends_with(&format!("_migration_init.{extension}")));
+    assert_history_recorded(&history.applied[0]);
+}
+
+#[tokio::test]
+async fn test_reinitialized_migration_table_keeps_history_fields() {
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+    let filename: MigrationFilename = "20240101000000000_migration_init.up.surql"
+        .to_string()
+        .try_into()
+        .unwrap();
+    let checksum = FileContent::from("DEFINE TABLE account SCHEMALESS;".to_string())
+        .as_checksum()
+        .unwrap();
+
+    Migration::create_reinitialize_table_raw_tx(&filename, &checksum, None, MigrationFlag::TwoWay)
+        .run(db.clone())
+        .await
+        .unwrap();
+
+    let history = MigrationHistory::get(db.clone()).await.unwrap();
+    assert_eq!(history.applied.len(), 1);
+    assert_eq!(history.applied[0].name, filename.to_string());
+    assert_history_recorded(&history.applied[0]);
+}
@@ -0,0 +1,192 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use migrator_tests::{current_function, TestConfig};
+use pretty_assertions::assert_eq;
+use surreal_models::migrations::{Planet, PlanetV2, PlanetV3};
+use surreal_orm::{
+    create_table_resources,
+    migrator::{
+        Generate, Init, LintSeverity, MigrationFlag, MigratorDatabase, MockPrompter, Mode,
+        RenameOrDelete,
+    },
+    DbResources,
+};
+use tempfile::tempdir;
+
+#[derive(Debug, Clone)]
+pub struct ResourcesV1;
+impl DbResources for ResourcesV1 {
+    create_table_resources!(Planet);
+}
+
+#[derive(Debug, Clone)]
+pub struct ResourcesV2;
+impl DbResources for ResourcesV2 {
+    create_table_resources!(PlanetV2);
+}
+
+#[derive(Debug, Clone)]
+pub struct ResourcesV3;
+impl DbResources for ResourcesV3 {
+    create_table_resources!(PlanetV3);
+}
+
+fn delete_prompter() -> MockPrompter {
+    MockPrompter::builder()
+        .allow_empty_migrations_gen(true)
+        .rename_or_delete_single_field_change(RenameOrDelete::Delete)
+        .build()
+}
+
+async fn init(conf: &mut TestConfig) {
+    conf.run_init(
+        Init::builder()
+            .reversible(true)
+            .name("migration init".into())
+            .run(true)
+            .build(),
+        ResourcesV1,
+        delete_prompter(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_lint_classifies_changes_and_counts_violating_records() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+    init(&mut conf).await;
+    conf.migrator
+        .db()
+        .query(
+            "CREATE planet CONTENT { firstName: 'Earth', population: 8, \
+            createdAt: time::now(), updatedAt: time::now(), labels: [] };",
+        )
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+    let file_manager = conf.migrator.file_manager().set_flag(MigrationFlag::TwoWay);
+    let (_, mut lint) = MigratorDatabase::plan_and_lint_migration(
+        &"migration gen 1".into(),
+        &file_manager,
+        ResourcesV2,
+        &delete_prompter(),
+    )
+    .await
+    .unwrap();
+    lint.count_violations(conf.migrator.db()).await.unwrap();
+
+    assert!(lint.has_destructive());
+    let remove_field = lint
+        .findings
+        .iter()
+        .find(|f| {
+            f.statement
+                .starts_with("REMOVE FIELD firstName ON TABLE planet")
+        })
+        .expect("Removing the field is linted");
+    assert_eq!(remove_field.severity, LintSeverity::Destructive);
+
+    let new_field = lint
+        .findings
+        .iter()
+        .find(|f| f.statement.starts_with("DEFINE FIELD newName ON planet"))
+        .expect("Defining the field is linted");
+    assert_eq!(new_field.severity, LintSeverity::Warning);
+    assert_eq!(new_field.violations, Some(1));
+}
+
+#[tokio::test]
+async fn test_lint_counts_records_violating_new_assertion() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+    init(&mut conf).await;
+    conf.migrator
+        .db()
+        .query(
+            "CREATE planet CONTENT { firstName: 'Earth', population: 8, \
+            createdAt: time::now(), updatedAt: time::now(), labels: [] };\
+            CREATE planet CONTENT { firstName: 'firstName', population: 0, \
+            createdAt: time::now(), updatedAt: time::now(), labels: [] };\
+            CREATE planet CONTENT { firstName: '', population: 0, \
+            createdAt: time::now(), updatedAt: time::now(), labels: [] };",
+        )
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+    let file_manager = conf.migrator.file_manager().set_flag(MigrationFlag::TwoWay);
+    let (_, mut lint) = MigratorDatabase::plan_and_lint_migration(
+        &"migration gen 1".into(),
+        &file_manager,
+        ResourcesV3,
+        &delete_prompter(),
+    )
+    .await
+    .unwrap();
+    lint.count_violations(conf.migrator.db()).await.unwrap();
+
+    let assertion = lint
+        .findings
+        .iter()
+        .find(|f| f.statement.starts_with("DEFINE FIELD firstName ON planet"))
+        .expect("Adding the assertion is linted");
+    assert_eq!(assertion.severity, LintSeverity::Warning);
+    // Only the empty name violates it. The '$value' string of the assertion is
+    // not the value of the field.
+    assert_eq!(assertion.violations, Some(1));
+}
+
+#[tokio::test]
+#[should_panic(expected = "Failed to generate migrations")]
+async fn test_generate_refuses_destructive_migration_without_allow_destructive() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+    init(&mut conf).await;
+
+    conf.run_gen(
+        Generate::builder()
+            .name("migration gen 1".into())
+            .allow_destructive(false)
+            .build(),
+        ResourcesV2,
+        delete_prompter(),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_generate_writes_destructive_migration_with_allow_destructive() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+    init(&mut conf).await;
+
+    conf.run_gen(
+        Generate::builder()
+            .name("migration gen 1".into())
+            .allow_destructive(true)
+            .build(),
+        ResourcesV2,
+        delete_prompter(),
+    )
+    .await;
+
+    let up = conf
+        .read_migrations_from_dir_sorted_asc()
+        .into_iter()
+        .find(|f| f.basename().to_string() == "migration_gen_1" && !f.is_down())
+        .expect("Generated migration file exists");
+    let up = std::fs::read_to_string(migration_dir.join(up.to_string()))
+        .expect("Failed to read migration file");
+    assert!(up.contains("REMOVE FIELD firstName ON TABLE planet"));
+}
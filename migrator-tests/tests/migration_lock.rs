@@ -0,0 +1,182 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::{fs, path::Path, time::Duration};
+
+use pretty_assertions::assert_eq;
+use surreal_orm::migrator::{
+    DatabaseConnection, Migration, MigrationConfig, MigrationError, MigrationLock, UpdateStrategy,
+};
+use tempfile::tempdir;
+
+fn write_migration_files(migration_dir: &Path) {
+    fs::create_dir_all(migration_dir).expect("Failed to create migration directory");
+    let files = [
+        (
+            "20240101000000000_create_account.up.surql",
+            "DEFINE TABLE account SCHEMALESS;\nCREATE account:1 SET name = 'Oyelowo';",
+        ),
+        (
+            "20240101000000000_create_account.down.surql",
+            "REMOVE TABLE account;",
+        ),
+        (
+            "20240102000000000_create_org.up.surql",
+            "CREATE org:1 SET name = 'Codebreather';",
+        ),
+        ("20240102000000000_create_org.down.surql", "DELETE org:1;"),
+    ];
+    for (name, content) in files {
+        fs::write(migration_dir.join(name), content).expect("Failed to write migration file");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_runners_apply_each_migration_once() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = migration_dir.path().join("migrations-tests");
+    write_migration_files(&migration_dir);
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+
+    let runners = (0..4)
+        .map(|_| {
+            let db = db.clone();
+            let two_way = MigrationConfig::new()
+                .set_custom_path(migration_dir.clone())
+                .two_way();
+            tokio::spawn(async move {
+                two_way
+                    .run_up_pending_migrations(db, UpdateStrategy::Latest)
+                    .await
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for runner in runners {
+        runner
+            .await
+            .expect("Runner panicked")
+            .expect("Runner failed");
+    }
+
+    let migration_names = Migration::get_all_desc(db.clone())
+        .await
+        .into_iter()
+        .map(|m| m.name)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        migration_names,
+        vec![
+            "20240102000000000_create_org.up.surql",
+            "20240101000000000_create_account.up.surql",
+        ]
+    );
+    assert!(
+        MigrationLock::get(db.clone()).await.unwrap().is_none(),
+        "The lock is released after the run"
+    );
+}
+
+#[tokio::test]
+async fn test_runner_times_out_while_lock_is_held_until_force_unlocked() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = migration_dir.path().join("migrations-tests");
+    write_migration_files(&migration_dir);
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+    let two_way = MigrationConfig::new()
+        .set_custom_path(migration_dir.clone())
+        .set_lock_timeout(Duration::from_millis(500))
+        .two_way();
+
+    let stale_lock = MigrationLock::acquire(db.clone(), Duration::from_secs(1))
+        .await
+        .unwrap();
+
+    let err = two_way
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            MigrationError::MigrationLockTimeout { holder, .. } if holder == stale_lock.holder()
+        ),
+        "Unexpected error: {err}"
+    );
+    assert!(Migration::get_all_desc(db.clone()).await.is_empty());
+
+    let released = MigrationLock::force_unlock(db.clone()).await.unwrap();
+    assert_eq!(
+        released.map(|lock| lock.holder),
+        Some(stale_lock.holder().to_string())
+    );
+
+    two_way
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+        .await
+        .unwrap();
+    assert_eq!(Migration::get_all_desc(db.clone()).await.len(), 2);
+}
+
+#[tokio::test]
+async fn test_acquire_fails_on_errors_other_than_contention() {
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+    db.query(
+        "DEFINE TABLE migration_lock SCHEMAFULL; DEFINE FIELD holder ON migration_lock TYPE int;",
+    )
+    .await
+    .unwrap()
+    .check()
+    .unwrap();
+
+    let err = MigrationLock::acquire(db.clone(), Duration::from_secs(5))
+        .await
+        .err()
+        .expect("The lock cannot be created");
+    assert!(
+        !matches!(err, MigrationError::MigrationLockTimeout { .. }),
+        "The error is returned instead of waiting for the lock: {err}"
+    );
+}
+
+#[tokio::test]
+async fn test_migration_is_aborted_when_lease_is_lost() {
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+    let lock = MigrationLock::acquire_with_lease(
+        db.clone(),
+        Duration::from_secs(1),
+        Duration::from_millis(300),
+    )
+    .await
+    .unwrap();
+    let holder = lock.holder().to_string();
+
+    // Another migrator took the lock e.g after this one could not renew it in time
+    db.query("UPDATE migration_lock:singleton SET holder = 'another-migrator';")
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+    let err = lock
+        .run(async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Ok(())
+        })
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, MigrationError::MigrationLockLost { holder: lost } if *lost == holder),
+        "Unexpected error: {err}"
+    );
+    assert_eq!(
+        MigrationLock::get(db.clone())
+            .await
+            .unwrap()
+            .map(|lock| lock.holder),
+        Some("another-migrator".to_string()),
+        "The lock of the other migrator is kept"
+    );
+}
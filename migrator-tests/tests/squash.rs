@@ -0,0 +1,250 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::{fs, path::Path};
+
+use pretty_assertions::assert_eq;
+use surreal_orm::migrator::{
+    DatabaseConnection, Migration, MigrationConfig, MigrationError, MigrationFilename, Mode,
+    RollbackOptions, SquashOptions, UpdateStrategy,
+};
+use surrealdb::{engine::any::Any, Surreal};
+use tempfile::tempdir;
+use test_case::test_case;
+
+fn write_migration_files(migration_dir: &Path) {
+    fs::create_dir_all(migration_dir).expect("Failed to create migration directory");
+    let files = [
+        (
+            "20240101000000000_create_account.up.surql",
+            "DEFINE TABLE account SCHEMALESS;",
+        ),
+        (
+            "20240101000000000_create_account.down.surql",
+            "REMOVE TABLE account;",
+        ),
+        (
+            "20240102000000000_create_org.up.surql",
+            "DEFINE TABLE org SCHEMALESS;",
+        ),
+        (
+            "20240102000000000_create_org.down.surql",
+            "REMOVE TABLE org;",
+        ),
+        (
+            "20240103000000000_create_project.up.surql",
+            "DEFINE TABLE project SCHEMALESS;",
+        ),
+        (
+            "20240103000000000_create_project.down.surql",
+            "REMOVE TABLE project;",
+        ),
+    ];
+    for (name, content) in files {
+        fs::write(migration_dir.join(name), content).expect("Failed to write migration file");
+    }
+}
+
+fn migration_name(name: &str) -> MigrationFilename {
+    name.to_string().try_into().unwrap()
+}
+
+fn read_dir_sorted(migration_dir: &Path) -> Vec<String> {
+    let mut files = fs::read_dir(migration_dir)
+        .unwrap()
+        .map(|f| f.unwrap().file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+async fn registered_migration_names(db: Surreal<Any>) -> Vec<String> {
+    Migration::get_all_desc(db)
+        .await
+        .into_iter()
+        .map(|m| m.name)
+        .collect()
+}
+
+#[test_case(Mode::Strict; "Strict")]
+#[test_case(Mode::Lax; "Lax")]
+#[tokio::test]
+async fn test_squash_collapses_applied_migrations_into_a_baseline(mode: Mode) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = migration_dir.path().join("migrations-tests");
+    write_migration_files(&migration_dir);
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+    let two_way = MigrationConfig::new()
+        .set_custom_path(migration_dir.clone())
+        .set_mode(mode)
+        .two_way();
+    two_way
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+        .await
+        .unwrap();
+
+    two_way
+        .squash_migrations(
+            db.clone(),
+            SquashOptions::new(migration_name("20240102000000000_create_org.up.surql"))
+                .basename("baseline".into())
+                .mode(mode),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        read_dir_sorted(&migration_dir),
+        vec![
+            "20240102000000000_baseline.down.surql",
+            "20240102000000000_baseline.up.surql",
+            "20240103000000000_create_project.down.surql",
+            "20240103000000000_create_project.up.surql",
+        ]
+    );
+    assert_eq!(
+        fs::read_to_string(migration_dir.join("20240102000000000_baseline.up.surql")).unwrap(),
+        "DEFINE TABLE account SCHEMALESS;\n\nDEFINE TABLE org SCHEMALESS;"
+    );
+    assert_eq!(
+        fs::read_to_string(migration_dir.join("20240102000000000_baseline.down.surql")).unwrap(),
+        "REMOVE TABLE org;\n\nREMOVE TABLE account;"
+    );
+    assert_eq!(
+        registered_migration_names(db.clone()).await,
+        vec![
+            "20240103000000000_create_project.up.surql",
+            "20240102000000000_baseline.up.surql",
+        ]
+    );
+
+    two_way
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+        .await
+        .expect("The baseline is not applied again");
+    assert_eq!(registered_migration_names(db.clone()).await.len(), 2);
+
+    for _ in 0..2 {
+        two_way
+            .run_down_migrations(db.clone(), RollbackOptions::default().mode(mode))
+            .await
+            .unwrap();
+    }
+    assert!(registered_migration_names(db.clone()).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_cannot_squash_migrations_after_since_marker() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = migration_dir.path().join("migrations-tests");
+    write_migration_files(&migration_dir);
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+    let two_way = MigrationConfig::new()
+        .set_custom_path(migration_dir.clone())
+        .two_way();
+    two_way
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+        .await
+        .unwrap();
+
+    let err = two_way
+        .squash_migrations(
+            db.clone(),
+            SquashOptions::new(migration_name("20240101000000000_create_account.up.surql"))
+                .till(migration_name("20240103000000000_create_project.up.surql")),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(
+            &err,
+            MigrationError::SquashIncludesUnappliedMigration { migration, .. }
+                if migration.to_string() == "20240102000000000_create_org.up.surql"
+        ),
+        "Unexpected error: {err}"
+    );
+    assert_eq!(read_dir_sorted(&migration_dir).len(), 6);
+}
+
+#[tokio::test]
+async fn test_cannot_squash_migrations_not_applied_to_the_database() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = migration_dir.path().join("migrations-tests");
+    write_migration_files(&migration_dir);
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+    let two_way = MigrationConfig::new()
+        .set_custom_path(migration_dir.clone())
+        .two_way();
+    two_way
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Number(1))
+        .await
+        .unwrap();
+
+    let err = two_way
+        .squash_migrations(
+            db.clone(),
+            SquashOptions::new(migration_name("20240102000000000_create_org.up.surql")),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(&err, MigrationError::SquashMigrationNotApplied(_)),
+        "Unexpected error: {err}"
+    );
+    assert_eq!(
+        registered_migration_names(db.clone()).await,
+        vec!["20240101000000000_create_account.up.surql"]
+    );
+}
+
+#[tokio::test]
+async fn test_failed_baseline_write_leaves_migrations_untouched() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = migration_dir.path().join("migrations-tests");
+    write_migration_files(&migration_dir);
+    let db = DatabaseConnection::default().setup().await.db().unwrap();
+    let two_way = MigrationConfig::new()
+        .set_custom_path(migration_dir.clone())
+        .two_way();
+    two_way
+        .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+        .await
+        .unwrap();
+    let files_before = read_dir_sorted(&migration_dir);
+    let registered_before = registered_migration_names(db.clone()).await;
+
+    // A directory in place of the baseline up file makes writing it fail
+    // after the down file has been written.
+    let blocker = migration_dir.join("20240102000000000_baseline.up.surql");
+    fs::create_dir(&blocker).unwrap();
+    let squash_options = || {
+        SquashOptions::new(migration_name("20240102000000000_create_org.up.surql"))
+            .basename("baseline".into())
+    };
+
+    let err = two_way
+        .squash_migrations(db.clone(), squash_options())
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(&err, MigrationError::IoError(_)),
+        "Unexpected error: {err}"
+    );
+    fs::remove_dir(&blocker).unwrap();
+    assert_eq!(read_dir_sorted(&migration_dir), files_before);
+    assert_eq!(
+        registered_migration_names(db.clone()).await,
+        registered_before
+    );
+
+    two_way
+        .squash_migrations(db.clone(), squash_options())
+        .await
+        .expect("Squash succeeds once the baseline can be written");
+    assert_eq!(registered_migration_names(db.clone()).await.len(), 2);
+}
@@ -61,6 +61,7 @@ pub struct DatabaseConnection {
     #[arg(
             global = true,
             long,
+            env = "SURREAL_URL",
             // value_name = "URL",
             default_value = "ws://localhost:8000",
             // default_value = "memory",
@@ -77,16 +78,28 @@ pub struct DatabaseConnection {
         )]
     pub(crate) url: UrlDb,
 
-    #[arg(global = true, long, default_value_t = String::from("test"), help = "Database name")]
+    #[arg(
+        global = true,
+        long,
+        env = "SURREAL_DB",
+        default_value_t = String::from("test"),
+        help = "Database name"
+    )]
     #[builder(default = "test".into())]
     pub(crate) db: String,
 
-    #[arg(global = true, long, default_value_t = String::from("test"), help = "Namespace name")]
+    #[arg(
+        global = true,
+        long,
+        env = "SURREAL_NS",
+        default_value_t = String::from("test"),
+        help = "Namespace name"
+    )]
     #[builder(default = "test".into())]
     pub(crate) ns: String,
 
     /// users scope
-    #[arg(global = true, long, help = "Scope")]
+    #[arg(global = true, long, env = "SURREAL_SC", help = "Scope")]
     #[builder(default, setter(strip_option))]
     pub(crate) sc: Option<String>,
 
@@ -94,13 +107,22 @@ pub struct DatabaseConnection {
         global = true,
         short,
         long,
+        env = "SURREAL_USER",
         default_value_t = String::from("root"),
         help = "User name"
     )]
     #[builder(default = "root".into())]
     pub(crate) user: String,
 
-    #[arg(global = true, short, long, default_value_t = String::from("root"), help = "Password")]
+    #[arg(
+        global = true,
+        short,
+        long,
+        env = "SURREAL_PASS",
+        hide_env_values = true,
+        default_value_t = String::from("root"),
+        help = "Password"
+    )]
     #[builder(default = "root".into())]
     pub(crate) pass: String,
 
@@ -162,6 +184,22 @@ impl DatabaseConnection {
     pub fn db(&self) -> Option<Surreal<Any>> {
         self.db_connection.clone()
     }
+
+    pub fn url(&self) -> &UrlDb {
+        &self.url
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.ns
+    }
+
+    pub fn database(&self) -> &str {
+        &self.db
+    }
+
+    pub fn user(&self) -> &str {
+        &self.user
+    }
 }
 
 impl Default for DatabaseConnection {
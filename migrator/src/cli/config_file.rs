@@ -0,0 +1,209 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::*;
+
+/// Settings from the config file. Any of them can be overridden by an environment
+/// and string values can reference environment variables e.g `pass = "${SURREAL_PASS}"`.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigSettings {
+    /// Relative to the directory of the config file
+    pub dir: Option<PathBuf>,
+    pub url: Option<String>,
+    pub ns: Option<String>,
+    pub db: Option<String>,
+    pub sc: Option<String>,
+    pub user: Option<String>,
+    pub pass: Option<String>,
+    pub lock_timeout: Option<u64>,
+}
+
+impl ConfigSettings {
+    /// Settings of the other take precedence
+    fn merge(self, other: Self) -> Self {
+        Self {
+            dir: other.dir.or(self.dir),
+            url: other.url.or(self.url),
+            ns: other.ns.or(self.ns),
+            db: other.db.or(self.db),
+            sc: other.sc.or(self.sc),
+            user: other.user.or(self.user),
+            pass: other.pass.or(self.pass),
+            lock_timeout: other.lock_timeout.or(self.lock_timeout),
+        }
+    }
+
+    fn interpolate_env_vars(self) -> MigrationResult<Self> {
+        let interpolate =
+            |value: Option<String>| value.map(|v| interpolate_env_vars(&v)).transpose();
+
+        Ok(Self {
+            dir: self.dir,
+            url: interpolate(self.url)?,
+            ns: interpolate(self.ns)?,
+            db: interpolate(self.db)?,
+            sc: interpolate(self.sc)?,
+            user: interpolate(self.user)?,
+            pass: interpolate(self.pass)?,
+            lock_timeout: self.lock_timeout,
+        })
+    }
+}
+
+/// Replaces `${NAME}` with the value of the environment variable `NAME`
+fn interpolate_env_vars(value: &str) -> MigrationResult<String> {
+    let env_var = Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}")?;
+    let mut interpolated = String::new();
+    let mut last_end = 0;
+
+    for captures in env_var.captures_iter(value) {
+        let (Some(reference), Some(name)) = (captures.get(0), captures.get(1)) else {
+            continue;
+        };
+        let env_value = std::env::var(name.as_str())
+            .map_err(|_| MigrationError::ConfigEnvVarNotSet(name.as_str().to_string()))?;
+        interpolated.push_str(&value[last_end..reference.start()]);
+        interpolated.push_str(&env_value);
+        last_end = reference.end();
+    }
+    interpolated.push_str(&value[last_end..]);
+
+    Ok(interpolated)
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+struct ConfigFileContent {
+    #[serde(flatten)]
+    settings: ConfigSettings,
+    #[serde(default)]
+    env: BTreeMap<String, ConfigSettings>,
+}
+
+#[derive(Deserialize)]
+struct CargoManifest {
+    package: Option<CargoPackage>,
+}
+
+#[derive(Deserialize)]
+struct CargoPackage {
+    metadata: Option<CargoMetadata>,
+}
+
+#[derive(Deserialize)]
+struct CargoMetadata {
+    surreal_orm: Option<ConfigFileContent>,
+}
+
+/// Migrator settings with named environments from `surreal_orm.toml` or the
+/// `[package.metadata.surreal_orm]` table of `Cargo.toml`.
+///
+/// ```toml
+/// dir = "migrations"
+/// url = "ws://localhost:8000"
+///
+/// [env.staging]
+/// url = "wss://staging.example.com"
+/// user = "migrator"
+/// pass = "${SURREAL_PASS}"
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfigFile {
+    path: PathBuf,
+    content: ConfigFileContent,
+}
+
+impl ConfigFile {
+    pub const FILENAME: &'static str = "surreal_orm.toml";
+    const CARGO_MANIFEST: &'static str = "Cargo.toml";
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the given config file or else the one in the current directory, if any
+    pub fn find(path: Option<&Path>) -> MigrationResult<Option<Self>> {
+        if let Some(path) = path {
+            return Self::load(path)?
+                .ok_or(MigrationError::InvalidConfigFile {
+                    path: path.to_string_lossy().to_string(),
+                    error: "No [package.metadata.surreal_orm] table".into(),
+                })
+                .map(Some);
+        }
+
+        let config_path = Path::new(Self::FILENAME);
+        if config_path.exists() {
+            return Self::load(config_path);
+        }
+
+        let manifest_path = Path::new(Self::CARGO_MANIFEST);
+        if manifest_path.exists() {
+            return Self::load(manifest_path);
+        }
+
+        Ok(None)
+    }
+
+    /// A Cargo.toml without a `[package.metadata.surreal_orm]` table has no settings
+    pub fn load(path: &Path) -> MigrationResult<Option<Self>> {
+        let invalid_config_file = |error: String| MigrationError::InvalidConfigFile {
+            path: path.to_string_lossy().to_string(),
+            error,
+        };
+
+        let file_content =
+            fs::read_to_string(path).map_err(|e| invalid_config_file(e.to_string()))?;
+        let content = if path
+            .file_name()
+            .map_or(false, |name| name == Self::CARGO_MANIFEST)
+        {
+            toml::from_str::<CargoManifest>(&file_content)
+                .map_err(|e| invalid_config_file(e.to_string()))?
+                .package
+                .and_then(|package| package.metadata)
+                .and_then(|metadata| metadata.surreal_orm)
+        } else {
+            Some(
+                toml::from_str::<ConfigFileContent>(&file_content)
+                    .map_err(|e| invalid_config_file(e.to_string()))?,
+            )
+        };
+
+        Ok(content.map(|content| Self {
+            path: path.to_path_buf(),
+            content,
+        }))
+    }
+
+    /// The settings of the environment merged over the top level settings
+    pub fn settings(&self, env: Option<&str>) -> MigrationResult<ConfigSettings> {
+        let settings = self.content.settings.clone();
+        let settings = match env {
+            Some(env) => settings.merge(
+                self.content
+                    .env
+                    .get(env)
+                    .cloned()
+                    .ok_or(MigrationError::ConfigEnvironmentDoesNotExist { env: env.into() })?,
+            ),
+            None => settings,
+        };
+        let mut settings = settings.interpolate_env_vars()?;
+
+        let config_dir = self.path.parent().unwrap_or(Path::new(""));
+        settings.dir = settings.dir.map(|dir| config_dir.join(dir));
+
+        Ok(settings)
+    }
+}
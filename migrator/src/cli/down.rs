@@ -4,6 +4,8 @@
  * Copyright (c) 2023 Oyelowo Oyedayo
  */
 
+use std::path::PathBuf;
+
 use clap::Args;
 use typed_builder::TypedBuilder;
 
@@ -22,6 +24,24 @@ pub struct Down {
         default_value_t = false
     )]
     pub(crate) prune: bool,
+
+    /// Print the migrations and statements that would be rolled back without rolling them back
+    #[arg(
+        long,
+        help = "Print the migrations and statements that would be rolled back without rolling them back"
+    )]
+    #[builder(default)]
+    pub(crate) dry_run: bool,
+
+    /// File to write the dry run plan to instead of printing it
+    #[arg(long, requires = "dry_run", help = "File to write the dry run plan to")]
+    #[builder(default, setter(strip_option))]
+    pub(crate) output: Option<PathBuf>,
+
+    /// Output the dry run plan as JSON
+    #[arg(long, requires = "dry_run", help = "Output the dry run plan as JSON")]
+    #[builder(default)]
+    pub(crate) json: bool,
 }
 
 impl Down {
@@ -106,17 +126,26 @@ impl Down {
             panic!("{err}");
         }
 
-        let rollback_strategy = self.rollback_strategy();
+        let rollback_options = RollbackOptions {
+            rollback_strategy: self.rollback_strategy(),
+            mode: cli.mode,
+        };
+
+        if self.dry_run {
+            let plan = file_manager
+                .two_way()
+                .plan_down_migrations(db.clone(), rollback_options)
+                .await;
+            if let Err(e) = plan.and_then(|plan| plan.output(self.output.as_deref(), self.json)) {
+                log::error!("Failed to plan rollback: {e}");
+                panic!("Failed to plan rollback: {e}");
+            }
+            return;
+        }
 
         let rollback = file_manager
             .two_way()
-            .run_down_migrations(
-                db.clone(),
-                RollbackOptions {
-                    rollback_strategy,
-                    mode: cli.mode,
-                },
-            )
+            .run_down_migrations(db.clone(), rollback_options)
             .await;
 
         if let Err(ref e) = rollback {
@@ -0,0 +1,64 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::path::PathBuf;
+
+use clap::Args;
+use typed_builder::TypedBuilder;
+
+use crate::*;
+
+/// Check the live database for definitions not made by the applied migrations
+/// e.g hotfixes run directly against the database. Exits with status 1 when there is drift.
+/// cargo run -- drift
+/// cargo run -- drift --json --output drift.json
+#[derive(Args, Debug, TypedBuilder, Clone, Default)]
+pub struct Drift {
+    /// File to write the drift report to instead of printing it
+    #[arg(long, help = "File to write the drift report to")]
+    #[builder(default, setter(strip_option))]
+    pub(crate) output: Option<PathBuf>,
+
+    /// Output the drift report as JSON
+    #[arg(long, help = "Output the drift report as JSON")]
+    #[builder(default)]
+    pub(crate) json: bool,
+}
+
+impl Drift {
+    pub async fn run(&self, cli: &mut Migrator) {
+        cli.setup_db().await;
+        let file_manager = cli.file_manager();
+        let db = cli.db().clone();
+
+        let report = match file_manager.detect_migration_type() {
+            Ok(MigrationFlag::TwoWay) => DriftReport::detect(db, &file_manager.two_way()).await,
+            Ok(MigrationFlag::OneWay) => DriftReport::detect(db, &file_manager.one_way()).await,
+            Err(e) => Err(e),
+        };
+
+        let report = match report {
+            Ok(report) => report,
+            Err(e) => {
+                log::error!("Failed to detect schema drift: {e}");
+                panic!("Failed to detect schema drift: {e}");
+            }
+        };
+
+        if let Err(e) = report.output(self.output.as_deref(), self.json) {
+            log::error!("Failed to output drift report: {e}");
+            panic!("Failed to output drift report: {e}");
+        }
+
+        // The report is the output, so drift exits with a failure status for CI instead of
+        // panicking with a backtrace
+        if report.has_drift() {
+            log::error!("Schema drift detected");
+            std::process::exit(1);
+        }
+
+        log::info!("No schema drift detected");
+    }
+}
@@ -0,0 +1,28 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use crate::*;
+use clap::Args;
+
+/// Release the migration lock held by a migrator that is no longer running
+/// cargo run -- force-unlock
+#[derive(Args, Debug, Clone)]
+pub struct ForceUnlock;
+
+impl ForceUnlock {
+    pub async fn run(&self, cli: &mut Migrator) {
+        cli.setup_db().await;
+        let db = cli.db().clone();
+
+        match MigrationLock::force_unlock(db.clone()).await {
+            Ok(Some(lock)) => log::info!("Released migration lock held by {}", lock.holder),
+            Ok(None) => log::info!("Migration lock is not held"),
+            Err(e) => {
+                log::error!("Failed to release migration lock: {e}");
+                panic!("Failed to release migration lock: {e}");
+            }
+        }
+    }
+}
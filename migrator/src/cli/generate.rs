@@ -22,6 +22,37 @@ pub struct Generate {
     #[arg(long, help = "Whether to run the migrations after generation")]
     #[builder(default)]
     pub(crate) run: bool,
+
+    /// Print the migration(s) that would be generated without writing the files
+    #[arg(
+        long,
+        conflicts_with = "run",
+        help = "Print the migration(s) that would be generated without writing the files"
+    )]
+    #[builder(default)]
+    pub(crate) print: bool,
+
+    /// Print the migration(s) as JSON
+    #[arg(long, requires = "print", help = "Print the migration(s) as JSON")]
+    #[builder(default)]
+    pub(crate) json: bool,
+
+    /// Generate the migration even if it removes tables or fields or narrows the
+    /// type of a field
+    #[arg(
+        long,
+        help = "Generate the migration even if it has destructive changes e.g removing a table or narrowing a field type"
+    )]
+    #[builder(default)]
+    pub(crate) allow_destructive: bool,
+
+    /// Count the records of the database that would violate new field types and assertions
+    #[arg(
+        long,
+        help = "Count the records of the database that would violate new field types and assertions"
+    )]
+    #[builder(default)]
+    pub(crate) check_db: bool,
 }
 
 impl Generate {
@@ -31,10 +62,39 @@ impl Generate {
         codebase_resources: impl DbResources,
         prompter: impl Prompter,
     ) {
-        let file_manager = cli.file_manager();
+        let mut file_manager = cli
+            .file_manager()
+            .set_deny_destructive(!self.allow_destructive);
+        if self.check_db {
+            cli.setup_db().await;
+            file_manager = file_manager.set_lint_db(cli.db());
+        }
         let migration_basename = &self.name;
         let mig_type = file_manager.detect_migration_type();
 
+        if self.print {
+            let plan = match mig_type {
+                Ok(MigrationFlag::TwoWay) => {
+                    file_manager
+                        .two_way()
+                        .plan_migration(migration_basename, codebase_resources, prompter)
+                        .await
+                }
+                Ok(MigrationFlag::OneWay) => {
+                    file_manager
+                        .one_way()
+                        .plan_migration(migration_basename, codebase_resources, prompter)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = plan.and_then(|plan| plan.output(None, self.json)) {
+                log::error!("Failed to generate migrations: {e}");
+                panic!("Failed to generate migrations");
+            }
+            return;
+        }
+
         match mig_type {
             Ok(MigrationFlag::TwoWay) => {
                 log::info!("Generating two-way migration");
@@ -83,6 +143,7 @@ impl Generate {
                 number: None,
                 till: None,
             },
+            ..Default::default()
         }
     }
 }
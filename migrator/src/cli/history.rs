@@ -0,0 +1,48 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::path::PathBuf;
+
+use clap::Args;
+use typed_builder::TypedBuilder;
+
+use crate::*;
+
+/// Show the applied migrations with when, how long and by whom they were applied
+/// and the attempts to apply or roll back migrations that failed.
+/// cargo run -- history
+/// cargo run -- history --json --output history.json
+#[derive(Args, Debug, TypedBuilder, Clone, Default)]
+pub struct History {
+    /// File to write the migration history to instead of printing it
+    #[arg(long, help = "File to write the migration history to")]
+    #[builder(default, setter(strip_option))]
+    pub(crate) output: Option<PathBuf>,
+
+    /// Output the migration history as JSON
+    #[arg(long, help = "Output the migration history as JSON")]
+    #[builder(default)]
+    pub(crate) json: bool,
+}
+
+impl History {
+    pub async fn run(&self, cli: &mut Migrator) {
+        cli.setup_db().await;
+        let db = cli.db().clone();
+
+        let history = match MigrationHistory::get(db).await {
+            Ok(history) => history,
+            Err(e) => {
+                log::error!("Failed to get migration history: {e}");
+                panic!("Failed to get migration history: {e}");
+            }
+        };
+
+        if let Err(e) = history.output(self.output.as_deref(), self.json) {
+            log::error!("Failed to output migration history: {e}");
+            panic!("Failed to output migration history: {e}");
+        }
+    }
+}
@@ -28,6 +28,16 @@ pub struct Init {
         help = "Unidirectional(Up only) Bidirectional(up & down) migration(S)"
     )]
     pub(crate) reversible: bool,
+
+    /// Write the schema of the existing database as the first migration and
+    /// register it as applied instead of generating it from the codebase
+    #[arg(
+        long,
+        conflicts_with = "run",
+        help = "Write the schema of the existing database as the first migration and register it as applied"
+    )]
+    #[builder(default)]
+    pub(crate) baseline_from_db: bool,
 }
 
 impl Init {
@@ -61,6 +71,34 @@ impl Init {
             }
         };
 
+        if self.baseline_from_db {
+            cli.setup_db().await;
+            let db = cli.db();
+            let baseline = if self.reversible {
+                file_manager
+                    .two_way()
+                    .baseline_from_db(db, &migration_name)
+                    .await
+            } else {
+                file_manager
+                    .one_way()
+                    .baseline_from_db(db, &migration_name)
+                    .await
+            };
+
+            match baseline {
+                Ok(baseline) => log::info!(
+                    "Successfully initialized migrations with baseline {}",
+                    baseline.name_forward()
+                ),
+                Err(e) => {
+                    log::error!("Failed to baseline the database: {e}");
+                    panic!("Failed to baseline the database: {e}");
+                }
+            }
+            return;
+        }
+
         if self.reversible {
             let gen = file_manager
                 .two_way()
@@ -101,6 +139,7 @@ impl Init {
                 number: None,
                 till: None,
             },
+            ..Default::default()
         }
     }
 }
@@ -5,33 +5,49 @@
  */
 mod arg_parser;
 pub mod config;
+mod config_file;
 mod down;
+mod drift;
+mod force_unlock;
 mod generate;
+mod history;
 mod init;
 mod list;
 mod prune;
 mod reset;
+mod squash;
 mod up;
 
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 pub use arg_parser::*;
+pub use config_file::{ConfigFile, ConfigSettings};
 pub use down::{Down, RollbackStrategy, RollbackStrategyStruct};
+pub use drift::Drift;
+pub use force_unlock::ForceUnlock;
 pub use generate::Generate;
+pub use history::History;
 pub use init::Init;
 pub use list::{List, Status};
 pub use prune::Prune;
 pub use reset::Reset;
+pub use squash::Squash;
 
 use surrealdb::{engine::any::Any, Surreal};
 use typed_builder::TypedBuilder;
 pub use up::{FastForwardDelta, Up, UpdateStrategy};
 
-use clap::{ArgAction, Parser};
+use clap::{
+    error::ErrorKind, parser::ValueSource, ArgAction, ArgMatches, CommandFactory, FromArgMatches,
+    Parser,
+};
 use surreal_query_builder::DbResources;
 
 pub use self::config::DatabaseConnection;
-use crate::{MigrationConfig, MockPrompter, Mode, Prompter, RealPrompter, RenameOrDelete};
+use crate::{
+    CodeMigrations, MigrationConfig, MigrationError, MigrationResult, MockPrompter, Mode, Prompter,
+    RealPrompter, RenameOrDelete,
+};
 
 /// Surreal ORM CLI
 #[derive(Parser, Debug, Clone, TypedBuilder)]
@@ -67,8 +83,44 @@ pub struct Migrator {
     )]
     pub(crate) mode: Mode,
 
+    /// Seconds to wait for another migrator to release the migration lock
+    #[arg(
+        global = true,
+        long,
+        help = "Seconds to wait for another migrator to release the migration lock",
+        default_value_t = 60
+    )]
+    #[builder(default = 60)]
+    pub(crate) lock_timeout: u64,
+
+    /// Config file with the settings of each environment
+    #[arg(
+        global = true,
+        long,
+        env = "SURREAL_ORM_CONFIG",
+        help = "Config file with the settings of each environment. Defaults to surreal_orm.toml \
+            or [package.metadata.surreal_orm] in Cargo.toml of the current directory"
+    )]
+    #[builder(default, setter(strip_option))]
+    pub(crate) config: Option<PathBuf>,
+
+    /// Environment from the config file whose settings are used e.g staging
+    #[arg(
+        global = true,
+        long,
+        env = "SURREAL_ORM_ENV",
+        help = "Environment from the config file whose settings are used e.g staging"
+    )]
+    #[builder(default, setter(strip_option))]
+    pub(crate) env: Option<String>,
+
     #[command(flatten)]
     pub(crate) db_connection: DatabaseConnection,
+
+    /// Rust code migrations run with the migration files by up and down
+    #[arg(skip)]
+    #[builder(default)]
+    pub(crate) code_migrations: CodeMigrations,
 }
 
 impl Migrator {
@@ -80,15 +132,117 @@ impl Migrator {
         self.db_connection.db().expect("Failed to get db")
     }
 
+    pub fn db_connection(&self) -> &DatabaseConnection {
+        &self.db_connection
+    }
+
+    pub fn lock_timeout(&self) -> Duration {
+        Duration::from_secs(self.lock_timeout)
+    }
+
+    /// Parses the command line arguments and the config file. Flags take precedence
+    /// over environment variables which take precedence over the config file.
+    pub fn parse_with_config() -> Self {
+        let matches = Self::command().get_matches();
+        match Self::from_matches_with_config(&matches) {
+            Ok(cli) => cli,
+            Err(e) => Self::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!("Failed to load the config file: {e}"),
+                )
+                .exit(),
+        }
+    }
+
+    pub fn from_matches_with_config(matches: &ArgMatches) -> MigrationResult<Self> {
+        let mut cli = Self::from_arg_matches(matches).unwrap_or_else(|e| e.exit());
+        cli.apply_config_file(matches)?;
+        Ok(cli)
+    }
+
+    /// Sets the settings that were neither passed as flags nor environment
+    /// variables from the config file
+    fn apply_config_file(&mut self, matches: &ArgMatches) -> MigrationResult<()> {
+        let config_file = match ConfigFile::find(self.config.as_deref())? {
+            Some(config_file) => config_file,
+            None => {
+                return match &self.env {
+                    Some(env) => {
+                        Err(MigrationError::ConfigEnvironmentDoesNotExist { env: env.clone() })
+                    }
+                    None => Ok(()),
+                }
+            }
+        };
+        let ConfigSettings {
+            dir,
+            url,
+            ns,
+            db,
+            sc,
+            user,
+            pass,
+            lock_timeout,
+        } = config_file.settings(self.env.as_deref())?;
+
+        let is_unset = |id: &str| {
+            !matches!(
+                matches.value_source(id),
+                Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+            )
+        };
+        let db_connection = &mut self.db_connection;
+
+        if let Some(dir) = dir.filter(|_| is_unset("dir")) {
+            self.dir = Some(dir);
+        }
+        if let Some(url) = url.filter(|_| is_unset("url")) {
+            db_connection.url = url
+                .parse()
+                .map_err(|error| MigrationError::InvalidConfigFile {
+                    path: config_file.path().to_string_lossy().to_string(),
+                    error,
+                })?;
+        }
+        if let Some(ns) = ns.filter(|_| is_unset("ns")) {
+            db_connection.ns = ns;
+        }
+        if let Some(db) = db.filter(|_| is_unset("db")) {
+            db_connection.db = db;
+        }
+        if let Some(sc) = sc.filter(|_| is_unset("sc")) {
+            db_connection.sc = Some(sc);
+        }
+        if let Some(user) = user.filter(|_| is_unset("user")) {
+            db_connection.user = user;
+        }
+        if let Some(pass) = pass.filter(|_| is_unset("pass")) {
+            db_connection.pass = pass;
+        }
+        if let Some(lock_timeout) = lock_timeout.filter(|_| is_unset("lock_timeout")) {
+            self.lock_timeout = lock_timeout;
+        }
+
+        Ok(())
+    }
+
     pub fn set_cmd(&mut self, cmd: SubCommand) -> &mut Self {
         self.subcmd = Some(cmd);
         self
     }
 
+    pub fn set_code_migrations(&mut self, code_migrations: CodeMigrations) -> &mut Self {
+        self.code_migrations = code_migrations;
+        self
+    }
+
     pub fn file_manager(&self) -> MigrationConfig {
         let fm_init = MigrationConfig::builder()
             .custom_path(self.dir.clone())
-            .mode(self.mode);
+            .mode(self.mode)
+            .code_migrations(self.code_migrations.clone())
+            .lock_timeout(Some(self.lock_timeout()));
 
         fm_init.build()
     }
@@ -105,13 +259,38 @@ impl Migrator {
     /// }
     /// ```
     pub async fn run(codebase_resources: impl DbResources) {
-        let mut cli = Self::parse();
+        let mut cli = Self::parse_with_config();
         cli.setup_logging();
         cli.run_fn(codebase_resources, RealPrompter).await;
     }
 
+    /// Run migration cli with Rust code migrations applied in timestamp order
+    /// with the migration files
+    /// # Example
+    /// ```rust, ignore
+    /// use surreal_models::migrations::Resources;
+    /// use surreal_orm::migrator::{CodeMigrations, Migrator};
+    ///
+    /// const CODE_MIGRATIONS: CodeMigrations = CodeMigrations::new(&[&BackfillHandles]);
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     Migrator::run_with_code_migrations(Resources, CODE_MIGRATIONS).await;
+    /// }
+    /// ```
+    pub async fn run_with_code_migrations(
+        codebase_resources: impl DbResources,
+        code_migrations: CodeMigrations,
+    ) {
+        let mut cli = Self::parse_with_config();
+        cli.setup_logging();
+        cli.set_code_migrations(code_migrations)
+            .run_fn(codebase_resources, RealPrompter)
+            .await;
+    }
+
     pub async fn run_test_main(codebase_resources: impl DbResources) {
-        let mut cli = Self::parse();
+        let mut cli = Self::parse_with_config();
         cli.setup_logging();
         cli.run_fn(
             codebase_resources,
@@ -155,6 +334,10 @@ impl Migrator {
                 SubCommand::Down(down) => down.run(self).await,
                 SubCommand::Prune(prune) => prune.run(self).await,
                 SubCommand::List(prune) => prune.run(self).await,
+                SubCommand::ForceUnlock(force_unlock) => force_unlock.run(self).await,
+                SubCommand::Drift(drift) => drift.run(self).await,
+                SubCommand::Squash(squash) => squash.run(self).await,
+                SubCommand::History(history) => history.run(self).await,
                 SubCommand::Reset(reset) => {
                     reset
                         .run(
@@ -182,6 +365,10 @@ impl Migrator {
                 SubCommand::Down(down) => down.run(self).await,
                 SubCommand::Prune(prune) => prune.run(self).await,
                 SubCommand::List(prune) => prune.run(self).await,
+                SubCommand::ForceUnlock(force_unlock) => force_unlock.run(self).await,
+                SubCommand::Drift(drift) => drift.run(self).await,
+                SubCommand::Squash(squash) => squash.run(self).await,
+                SubCommand::History(history) => history.run(self).await,
                 SubCommand::Reset(reset) => reset.run(self, codebase_resources, prompter).await,
             },
         };
@@ -228,6 +415,15 @@ pub enum SubCommand {
     List(List),
     /// Delete Unapplied local migration files that have not been applied to the current database instance
     Prune(Prune),
+    /// Release the migration lock held by a migrator that is no longer running
+    ForceUnlock(ForceUnlock),
+    /// Check the live database for definitions not made by the applied migrations
+    #[clap(alias = "check")]
+    Drift(Drift),
+    /// Squash applied migrations into a single baseline migration
+    Squash(Squash),
+    /// Show when, how and by whom migrations were applied and the attempts that failed
+    History(History),
 }
 
 macro_rules! impl_from {
@@ -246,3 +442,7 @@ impl_from!(Down);
 impl_from!(Reset);
 impl_from!(List);
 impl_from!(Prune);
+impl_from!(ForceUnlock);
+impl_from!(Drift);
+impl_from!(Squash);
+impl_from!(History);
@@ -0,0 +1,86 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use clap::Args;
+use typed_builder::TypedBuilder;
+
+use crate::*;
+
+/// Squash applied migrations into a single baseline migration
+/// cargo run -- squash --since 20240101000000000_create_account.up.surql
+/// cargo run -- squash --since <migration> --till <migration> --name baseline
+#[derive(Args, Debug, TypedBuilder, Clone)]
+pub struct Squash {
+    /// Latest migration applied to every database. Later migrations are not squashed.
+    #[arg(
+        long,
+        value_parser = mig_name_parser,
+        help = "Latest migration applied to every database. Later migrations are not squashed"
+    )]
+    pub(crate) since: MigrationFilename,
+
+    /// Last migration to squash. Defaults to the since migration.
+    #[arg(
+        short,
+        long,
+        value_parser = mig_name_parser,
+        help = "Last migration to squash. Defaults to the since migration"
+    )]
+    #[builder(default, setter(strip_option))]
+    pub(crate) till: Option<MigrationFilename>,
+
+    /// Name of the baseline migration
+    #[arg(
+        long,
+        help = "Name of the baseline migration",
+        default_value = "squashed"
+    )]
+    #[builder(default = "squashed".into())]
+    pub(crate) name: Basename,
+}
+
+impl Squash {
+    pub fn squash_options(&self, mode: Mode) -> SquashOptions {
+        let options = SquashOptions::new(self.since.clone())
+            .basename(self.name.clone())
+            .mode(mode);
+
+        match self.till.clone() {
+            Some(till) => options.till(till),
+            None => options,
+        }
+    }
+
+    pub async fn run(&self, cli: &mut Migrator) {
+        cli.setup_db().await;
+        let file_manager = cli.file_manager();
+        let db = cli.db().clone();
+        let squash_options = self.squash_options(cli.mode);
+
+        let squashed = match file_manager.detect_migration_type() {
+            Ok(MigrationFlag::TwoWay) => {
+                file_manager
+                    .two_way()
+                    .squash_migrations(db, squash_options)
+                    .await
+            }
+            Ok(MigrationFlag::OneWay) => {
+                file_manager
+                    .one_way()
+                    .squash_migrations(db, squash_options)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        match squashed {
+            Ok(baseline) => log::info!("Squashed migrations into {}", baseline.name_forward()),
+            Err(e) => {
+                log::error!("Failed to squash migrations: {e}");
+                panic!("Failed to squash migrations: {e}");
+            }
+        }
+    }
+}
@@ -3,6 +3,8 @@
  * Email: oyelowo.oss@gmail.com
  * Copyright (c) 2023 Oyelowo Oyedayo
  */
+use std::path::PathBuf;
+
 use crate::*;
 
 use clap::Args;
@@ -15,10 +17,29 @@ use typed_builder::TypedBuilder;
 /// cargo run -- up -l
 /// cargo run -- up -n 2
 /// cargo run -- up -t 2021-09-09-xxxxx
+/// cargo run -- up --dry-run --json --output plan.json
 #[derive(Args, Debug, TypedBuilder, Clone, Default)]
 pub struct Up {
     #[command(flatten)]
     pub(crate) fast_forward: FastForwardDelta,
+
+    /// Print the migrations and statements that would run without running them
+    #[arg(
+        long,
+        help = "Print the migrations and statements that would run without running them"
+    )]
+    #[builder(default)]
+    pub(crate) dry_run: bool,
+
+    /// File to write the dry run plan to instead of printing it
+    #[arg(long, requires = "dry_run", help = "File to write the dry run plan to")]
+    #[builder(default, setter(strip_option))]
+    pub(crate) output: Option<PathBuf>,
+
+    /// Output the dry run plan as JSON
+    #[arg(long, requires = "dry_run", help = "Output the dry run plan as JSON")]
+    #[builder(default)]
+    pub(crate) json: bool,
 }
 
 impl Up {
@@ -96,6 +117,29 @@ impl Up {
         let update_strategy = self.update_strategy();
         let db = cli.db().clone();
 
+        if self.dry_run {
+            let plan = match file_manager.detect_migration_type() {
+                Ok(MigrationFlag::TwoWay) => {
+                    file_manager
+                        .two_way()
+                        .plan_up_pending_migrations(db.clone(), update_strategy)
+                        .await
+                }
+                Ok(MigrationFlag::OneWay) => {
+                    file_manager
+                        .one_way()
+                        .plan_pending_migrations(db.clone(), update_strategy)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = plan.and_then(|plan| plan.output(self.output.as_deref(), self.json)) {
+                log::error!("Failed to plan migrations: {e}");
+                panic!("Failed to plan migrations: {e}");
+            }
+            return;
+        }
+
         match file_manager.detect_migration_type() {
             Ok(MigrationFlag::TwoWay) => {
                 log::info!("Running two way migrations");
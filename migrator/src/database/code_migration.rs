@@ -0,0 +1,189 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use async_trait::async_trait;
+use surrealdb::{engine::any::Any, Connection, Surreal};
+
+use crate::*;
+
+/// A migration written in Rust for data changes that need application logic
+/// e.g re-hashing passwords or splitting a field. It is applied in timestamp
+/// order with the migration files and recorded in the migration table like them.
+///
+/// # Example
+/// ```rust, ignore
+/// use surreal_orm::migrator::{async_trait, CodeMigration, CodeMigrations, MigrationResult};
+///
+/// struct BackfillHandles;
+///
+/// #[async_trait]
+/// impl CodeMigration for BackfillHandles {
+///     fn name(&self) -> &'static str {
+///         "20240115093000000_backfill_handles"
+///     }
+///
+///     async fn up(&self, db: &Surreal<Any>) -> MigrationResult<()> {
+///         db.query("UPDATE account SET handle = string::lowercase(name)").await?;
+///         Ok(())
+///     }
+/// }
+///
+/// const CODE_MIGRATIONS: CodeMigrations = CodeMigrations::new(&[&BackfillHandles]);
+///
+/// #[tokio::main]
+/// async fn main() {
+///     Migrator::run_with_code_migrations(Resources, CODE_MIGRATIONS).await;
+/// }
+/// ```
+#[async_trait]
+pub trait CodeMigration: Send + Sync {
+    /// Timestamped name in the format <timestamp>_<migration_name>
+    /// e.g 20240115093000000_backfill_handles
+    fn name(&self) -> &'static str;
+
+    async fn up(&self, db: &Surreal<Any>) -> MigrationResult<()>;
+
+    /// Return true when `down` is implemented. Only reversible code migrations
+    /// can be rolled back.
+    fn reversible(&self) -> bool {
+        false
+    }
+
+    async fn down(&self, _db: &Surreal<Any>) -> MigrationResult<()> {
+        Err(MigrationError::CodeMigrationNotReversible(
+            self.name().to_string(),
+        ))
+    }
+}
+
+#[allow(missing_copy_implementations)]
+#[derive(Clone)]
+pub struct CodeMigrations {
+    migrations: &'static [&'static dyn CodeMigration],
+}
+
+impl Default for CodeMigrations {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+impl std::fmt::Debug for CodeMigrations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.migrations.iter().map(|m| m.name()))
+            .finish()
+    }
+}
+
+impl CodeMigrations {
+    pub const fn new(migrations: &'static [&'static dyn CodeMigration]) -> Self {
+        Self { migrations }
+    }
+
+    pub const fn get_migrations(&self) -> &'static [&'static dyn CodeMigration] {
+        self.migrations
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.migrations.is_empty()
+    }
+
+    /// Code migrations named like the migration files of the given flag
+    /// i.e <timestamp>_<migration_name>.up.surql for two way and
+    /// <timestamp>_<migration_name>.surql for one way migrations.
+    pub fn to_migrations(&self, flag: MigrationFlag) -> MigrationResult<Vec<CodeMigrationMeta>> {
+        let mut migs = self
+            .migrations
+            .iter()
+            .map(|migration| CodeMigrationMeta::new(*migration, flag))
+            .collect::<MigrationResult<Vec<_>>>()?;
+
+        migs.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(migs)
+    }
+}
+
+#[derive(Clone)]
+pub struct CodeMigrationMeta {
+    pub name: MigrationFilename,
+    pub migration: &'static dyn CodeMigration,
+}
+
+impl std::fmt::Debug for CodeMigrationMeta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CodeMigrationMeta")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl CodeMigrationMeta {
+    pub fn new(
+        migration: &'static dyn CodeMigration,
+        flag: MigrationFlag,
+    ) -> MigrationResult<Self> {
+        let name: MigrationFilename = format!("{}.surql", migration.name()).try_into()?;
+        let name = match flag {
+            MigrationFlag::TwoWay => name.to_up(),
+            MigrationFlag::OneWay => name,
+        };
+
+        Ok(Self { name, migration })
+    }
+
+    /// Code migrations have no content so the checksum is of the name.
+    /// It is recorded for both directions of two way migrations.
+    pub fn checksum(&self) -> MigrationResult<Checksum> {
+        FileContent::from(self.name.to_string()).as_checksum()
+    }
+
+    pub fn is_twoway(&self) -> bool {
+        self.name.is_up()
+    }
+
+    /// Code migrations are written against `Surreal<Any>`, so the migrations
+    /// can be run on other connections only while none of them is a code migration.
+    pub fn db<C: Connection>(&self, db: &Surreal<C>) -> MigrationResult<&Surreal<Any>> {
+        (db as &dyn std::any::Any)
+            .downcast_ref::<Surreal<Any>>()
+            .ok_or_else(|| {
+                MigrationError::CodeMigrationRequiresAnyConnection(self.name.to_string())
+            })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum PendingMigration {
+    File(PendingMigrationFile),
+    Code(CodeMigrationMeta),
+}
+
+impl PendingMigration {
+    pub fn name_forward(&self) -> &MigrationFilename {
+        match self {
+            Self::File(m) => m.name_forward(),
+            Self::Code(m) => &m.name,
+        }
+    }
+}
+
+/// A migration that can be rolled back i.e a two way migration file pair or
+/// a code migration.
+#[derive(Debug, Clone)]
+pub enum ReversibleMigration {
+    File(MigrationFileTwoWayPair),
+    Code(CodeMigrationMeta),
+}
+
+impl ReversibleMigration {
+    pub fn up_name(&self) -> &MigrationFilename {
+        match self {
+            Self::File(m) => &m.up.name,
+            Self::Code(m) => &m.name,
+        }
+    }
+}
@@ -3,13 +3,22 @@
  * Email: oyelowo.oss@gmail.com
  * Copyright (c) 2023 Oyelowo Oyedayo
  */
-use std::{collections::BTreeSet, path::PathBuf};
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    time::Instant,
+};
 
 use surreal_query_builder::{statements::*, *};
 use surrealdb::{engine::any::Any, Connection, Surreal};
 
 use crate::*;
 
+enum RollbackStep {
+    Queries(Raw, Vec<MigrationFilename>),
+    Code(CodeMigrationMeta, Option<Raw>),
+}
+
 // pub struct MigrationRunner<C: Connection> {
 pub struct MigrationRunner {
     // db: Surreal<C>,
@@ -50,6 +59,41 @@ impl RollbackOptions {
     }
 }
 
+pub struct SquashOptions {
+    /// Latest migration applied to every database. Later migrations are not squashed.
+    pub since: MigrationFilename,
+    /// Last migration to squash. Defaults to the `since` migration.
+    pub till: Option<MigrationFilename>,
+    pub basename: Basename,
+    pub mode: Mode,
+}
+
+impl SquashOptions {
+    pub fn new(since: MigrationFilename) -> Self {
+        Self {
+            since,
+            till: None,
+            basename: "squashed".into(),
+            mode: Mode::default(),
+        }
+    }
+
+    pub fn till(mut self, till: MigrationFilename) -> Self {
+        self.till = Some(till);
+        self
+    }
+
+    pub fn basename(mut self, basename: Basename) -> Self {
+        self.basename = basename;
+        self
+    }
+
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
 impl MigrationRunner {
     /// Only two way migrations support rollback
     pub async fn rollback_migrations(
@@ -57,14 +101,153 @@ impl MigrationRunner {
         fm: &MigrationConfig,
         rollback_options: RollbackOptions,
     ) -> MigrationResult<()> {
+        MigrationLock::acquire(db.clone(), fm.lock_timeout())
+            .await?
+            .run(Self::rollback_migrations_locked(db, fm, rollback_options))
+            .await
+    }
+
+    async fn rollback_migrations_locked(
+        db: Surreal<Any>,
+        fm: &MigrationConfig,
+        rollback_options: RollbackOptions,
+    ) -> MigrationResult<()> {
+        let (migrations_to_rollback, migrations_from_db) =
+            Self::get_migrations_to_rollback(db.clone(), fm, &rollback_options).await?;
+        let (rollback_steps, _rolledback_file_paths) =
+            Self::generate_rollback_queries_and_filepaths(
+                fm,
+                migrations_to_rollback,
+                migrations_from_db,
+                &rollback_options.mode,
+            )?;
+
+        if rollback_steps.is_empty() {
+            log::info!("No migrations to rollback");
+        } else {
+            for step in rollback_steps {
+                match step {
+                    RollbackStep::Queries(queries, migration_names) => {
+                        let started = Instant::now();
+                        let rolled_back = Self::run_in_transaction(db.clone(), queries).await;
+                        MigrationFailure::record_and_return(
+                            db.clone(),
+                            MigrationDirection::Down,
+                            &migration_names,
+                            started.elapsed(),
+                            rolled_back,
+                        )
+                        .await?;
+                    }
+                    RollbackStep::Code(code_migration, deletion_query) => {
+                        log::info!("Rolling back code migration: {}", code_migration.name);
+                        let started = Instant::now();
+                        let rolled_back = code_migration.migration.down(&db).await;
+                        MigrationFailure::record_and_return(
+                            db.clone(),
+                            MigrationDirection::Down,
+                            &[code_migration.name.clone()],
+                            started.elapsed(),
+                            rolled_back,
+                        )
+                        .await?;
+                        if let Some(deletion_query) = deletion_query {
+                            deletion_query.run(db.clone()).await?;
+                        }
+                    }
+                }
+            }
+
+            log::info!("Rolled back {} migrations", 1);
+        }
+
+        Ok(())
+    }
+
+    /// The migrations and statements `rollback_migrations` would run, without running them
+    pub async fn plan_rollback(
+        db: Surreal<Any>,
+        fm: &MigrationConfig,
+        rollback_options: RollbackOptions,
+    ) -> MigrationResult<MigrationPlan> {
+        let (migrations_to_rollback, migrations_from_db) =
+            Self::get_migrations_to_rollback(db.clone(), fm, &rollback_options).await?;
+        // Validates the rollback the same way it is done before rolling back
+        Self::generate_rollback_queries_and_filepaths(
+            fm,
+            migrations_to_rollback.clone(),
+            migrations_from_db.clone(),
+            &rollback_options.mode,
+        )?;
+
+        let deletion_query = |name: &str| {
+            migrations_from_db
+                .iter()
+                .find(|m| m.name == name)
+                .map(|m| Migration::delete_raw(&m.id).build())
+                .unwrap_or_default()
+        };
+
+        let mut plan = MigrationPlan::new(PlanAction::Rollback);
+        for m in migrations_to_rollback.iter() {
+            let up_name = m.up_name().to_up().to_string();
+            match m {
+                ReversibleMigration::File(m_from_file) => plan.push(
+                    &m_from_file.down.name,
+                    PlannedMigrationKind::File,
+                    format!("{}\n{}", m_from_file.down.content, deletion_query(&up_name)),
+                ),
+                ReversibleMigration::Code(code_migration) => plan.push(
+                    &code_migration.name,
+                    PlannedMigrationKind::Code,
+                    format!(
+                        "-- Runs the Rust code migration down\n{}",
+                        deletion_query(&up_name)
+                    ),
+                ),
+            }
+        }
+
+        // Registered migrations without a counterpart are also deleted
+        for m in migrations_from_db.iter().filter(|m| {
+            !migrations_to_rollback
+                .iter()
+                .any(|mig| mig.up_name().to_up().to_string() == m.name)
+        }) {
+            plan.push(
+                &m.name,
+                PlannedMigrationKind::File,
+                Migration::delete_raw(&m.id).build(),
+            );
+        }
+
+        Ok(plan)
+    }
+
+    async fn get_migrations_to_rollback(
+        db: Surreal<Any>,
+        fm: &MigrationConfig,
+        rollback_options: &RollbackOptions,
+    ) -> MigrationResult<(Vec<ReversibleMigration>, Vec<Migration>)> {
         let RollbackOptions {
             ref rollback_strategy,
-            mode: ref strictness,
+            ..
         } = rollback_options;
 
-        let all_migrations_from_dir = fm.get_two_way_migrations_sorted_desc(false)?;
+        let mut all_migrations_from_dir = fm
+            .get_two_way_migrations_sorted_desc(false)?
+            .into_iter()
+            .map(ReversibleMigration::File)
+            .chain(
+                fm.code_migrations
+                    .to_migrations(MigrationFlag::TwoWay)?
+                    .into_iter()
+                    .map(ReversibleMigration::Code),
+            )
+            .collect::<Vec<_>>();
+        all_migrations_from_dir.sort_by(|a, b| b.up_name().cmp(a.up_name()));
 
-        let (queries_to_run, _rolledback_file_paths) = match rollback_strategy {
+        let migrations_to_rollback = match rollback_strategy {
             RollbackStrategy::Previous => {
                 let latest_migration = Self::get_latest_migration(db.clone()).await?;
                 match latest_migration {
@@ -73,24 +256,14 @@ impl MigrationRunner {
                             latest_migration.name.clone().try_into()?;
                         let migrations_from_dir = all_migrations_from_dir
                             .iter()
-                            .find(|m| m.up.name == latest_migration_name.to_up())
+                            .find(|m| *m.up_name() == latest_migration_name.to_up())
                             .ok_or(MigrationError::RollbackFailed(format!(
                 "The latest migration - {} - does not have a corresponding down migration file",
                 latest_migration_name
             )))?;
-                        Self::generate_rollback_queries_and_filepaths(
-                            fm,
-                            vec![migrations_from_dir.clone()],
-                            vec![latest_migration],
-                            strictness,
-                        )?
+                        (vec![migrations_from_dir.clone()], vec![latest_migration])
                     }
-                    None => Self::generate_rollback_queries_and_filepaths(
-                        fm,
-                        vec![],
-                        vec![],
-                        strictness,
-                    )?,
+                    None => (vec![], vec![]),
                 }
             }
             RollbackStrategy::Number(count) => {
@@ -107,16 +280,11 @@ impl MigrationRunner {
 
                 let migrations_to_rollback = all_migrations_from_dir
                     .into_iter()
-                    .filter(|m| m.up.name.timestamp() <= latest_migration.timestamp)
+                    .filter(|m| m.up_name().timestamp() <= latest_migration.timestamp)
                     .take(*count as usize)
                     .collect::<Vec<_>>();
 
-                Self::generate_rollback_queries_and_filepaths(
-                    fm,
-                    migrations_to_rollback,
-                    migrations_from_db,
-                    strictness,
-                )?
+                (migrations_to_rollback, migrations_from_db)
             }
             RollbackStrategy::Till(file_cursor) => {
                 let MigrationSchema {
@@ -155,48 +323,32 @@ impl MigrationRunner {
                     .clone()
                     .into_iter()
                     .filter(|m| {
-                        let is_latest = m.up.name
+                        let is_latest = *m.up_name()
                             == latest_migration
                                 .name
                                 .clone()
                                 .try_into()
                                 .expect("Invalid migration name");
                         let is_before_db_latest_migration =
-                            m.up.name.timestamp() < latest_migration.timestamp;
+                            m.up_name().timestamp() < latest_migration.timestamp;
 
-                        let is_after_file_cursor = m.up.name.timestamp() > file_cursor.timestamp();
-                        let is_file_cursor = &m.up.name == file_cursor;
+                        let is_after_file_cursor =
+                            m.up_name().timestamp() > file_cursor.timestamp();
+                        let is_file_cursor = m.up_name() == file_cursor;
 
                         (is_before_db_latest_migration || is_latest)
                             && (is_after_file_cursor || is_file_cursor)
                     })
                     .collect::<Vec<_>>();
 
-                Self::generate_rollback_queries_and_filepaths(
-                    fm,
-                    migrations_files_to_rollback,
-                    migrations_from_db,
-                    strictness,
-                )?
+                (migrations_files_to_rollback, migrations_from_db)
             }
         };
 
-        if queries_to_run.build().trim().is_empty() {
-            log::info!("No migrations to rollback");
-        } else {
-            begin_transaction()
-                .query(queries_to_run)
-                .commit_transaction()
-                .run(db.clone())
-                .await?;
-
-            log::info!("Rolled back {} migrations", 1);
-        }
-
-        Ok(())
+        Ok(migrations_to_rollback)
     }
 
-    async fn get_latest_migration(
+    pub(crate) async fn get_latest_migration(
         db: Surreal<impl Connection>,
     ) -> SurrealOrmResult<Option<Migration>> {
         select(All)
@@ -209,22 +361,23 @@ impl MigrationRunner {
 
     pub async fn get_pending_migrations(
         all_migrations: Vec<impl Into<MigrationFile>>,
+        code_migrations: Vec<CodeMigrationMeta>,
         db: Surreal<impl Connection>,
-    ) -> SurrealOrmResult<Vec<PendingMigrationFile>> {
+    ) -> SurrealOrmResult<Vec<PendingMigration>> {
         let latest_migration = Self::get_latest_migration(db.clone()).await?;
 
         let mut pending_migrations = all_migrations
             .into_iter()
             .map(|m| {
                 let m: MigrationFile = m.into();
-                PendingMigrationFile::from(m)
+                PendingMigration::File(PendingMigrationFile::from(m))
             })
+            .chain(code_migrations.into_iter().map(PendingMigration::Code))
             .filter(|m| {
                 latest_migration.as_ref().map_or(true, |latest_migration| {
                     m.name_forward().timestamp() > latest_migration.timestamp
                 })
             })
-            .map(PendingMigrationFile::from)
             .collect::<Vec<_>>();
 
         pending_migrations.sort_by_key(|m| m.name_forward().timestamp());
@@ -288,60 +441,119 @@ impl MigrationRunner {
 
     fn generate_rollback_queries_and_filepaths(
         fm: &MigrationConfig,
-        migrations_to_rollback: Vec<MigrationFileTwoWayPair>,
+        migrations_to_rollback: Vec<ReversibleMigration>,
         migrations_from_db: Vec<Migration>,
         mode: &Mode,
-    ) -> MigrationResult<(Raw, Vec<PathBuf>)> {
+    ) -> MigrationResult<(Vec<RollbackStep>, Vec<PathBuf>)> {
         if mode.is_strict() {
-            for (m_from_file, m_from_db) in
+            for (m_to_rollback, m_from_db) in
                 migrations_to_rollback.iter().zip(migrations_from_db.iter())
             {
                 let db_mig_name: MigrationFilename = m_from_db.name.clone().try_into()?;
 
-                m_from_db
-                    .checksum_up
-                    .verify(&m_from_file.up.name, &m_from_file.up.content)?;
-
-                m_from_db
-                    .clone()
-                    .checksum_down
-                    .ok_or(MigrationError::NoChecksumInDb {
-                        migration_name: m_from_db.name.clone(),
-                    })?
-                    .verify(&m_from_file.down.name, &m_from_file.down.content)?;
+                match m_to_rollback {
+                    ReversibleMigration::File(m_from_file) => {
+                        m_from_db
+                            .checksum_up
+                            .verify(&m_from_file.up.name, &m_from_file.up.content)?;
+
+                        m_from_db
+                            .clone()
+                            .checksum_down
+                            .ok_or(MigrationError::NoChecksumInDb {
+                                migration_name: m_from_db.name.clone(),
+                            })?
+                            .verify(&m_from_file.down.name, &m_from_file.down.content)?;
+                    }
+                    ReversibleMigration::Code(code_migration) => {
+                        m_from_db.checksum_up.verify(
+                            &code_migration.name,
+                            &FileContent::from(code_migration.name.to_string()),
+                        )?;
+                    }
+                }
 
-                if m_from_file.up.name != db_mig_name.to_up() {
+                if *m_to_rollback.up_name() != db_mig_name.to_up() {
                     return Err(MigrationError::MigrationFileVsDbNamesMismatch {
-                        migration_file_name: m_from_file.up.name.to_string(),
+                        migration_file_name: m_to_rollback.up_name().to_string(),
                         migration_db_name: db_mig_name.to_string(),
                     });
                 }
             }
         }
 
+        // Check before rolling back anything so that a code migration without
+        // a down does not leave the rollback half done.
+        for m in migrations_to_rollback.iter() {
+            if let ReversibleMigration::Code(code_migration) = m {
+                if !code_migration.migration.reversible() {
+                    return Err(MigrationError::CodeMigrationNotReversible(
+                        code_migration.name.to_string(),
+                    ));
+                }
+            }
+        }
+
         log::info!("Rolling back {} migration(s)", migrations_to_rollback.len());
 
-        let rollback_queries = migrations_to_rollback
-            .clone()
-            .into_iter()
-            .map(|m| m.down.content.to_string())
-            .collect::<Vec<_>>()
-            .join("\n");
+        // We are deleting by upname because that's how theyre are stored
+        let deletion_query = |name: &MigrationFilename| {
+            migrations_from_db
+                .iter()
+                .find(|m| m.name == name.to_up().to_string())
+                .map(|m| Migration::delete_raw(&m.id).build())
+        };
 
-        let rollbacked_migration_deletion_queries = migrations_from_db
-            .iter()
-            // We are deleting by upname because that's how theyre are stored
-            .map(|m| Migration::delete_raw(&m.id).build())
-            .collect::<Vec<_>>()
-            .join("\n");
+        // File migrations are rolled back together in a transaction until a
+        // code migration which is run on its own.
+        let mut rollback_steps = vec![];
+        let mut queries = vec![];
+        let mut batch_names = vec![];
+        let mut deleted_migration_names = vec![];
+        for m in migrations_to_rollback.iter() {
+            deleted_migration_names.push(m.up_name().to_up().to_string());
+            match m {
+                ReversibleMigration::File(m_from_file) => {
+                    queries.push(m_from_file.down.content.to_string());
+                    queries.extend(deletion_query(&m_from_file.up.name));
+                    batch_names.push(m_from_file.down.name.clone());
+                }
+                ReversibleMigration::Code(code_migration) => {
+                    let batch = queries.join("\n");
+                    queries.clear();
+                    if !batch.trim().is_empty() {
+                        rollback_steps.push(RollbackStep::Queries(
+                            Raw::new(batch),
+                            std::mem::take(&mut batch_names),
+                        ));
+                    }
+                    rollback_steps.push(RollbackStep::Code(
+                        code_migration.clone(),
+                        deletion_query(&code_migration.name).map(Raw::new),
+                    ));
+                }
+            }
+        }
 
-        let all = format!(
-            "{}\n{}",
-            rollback_queries, rollbacked_migration_deletion_queries
+        // Registered migrations without a counterpart are also deleted
+        queries.extend(
+            migrations_from_db
+                .iter()
+                .filter(|m| !deleted_migration_names.contains(&m.name))
+                .map(|m| Migration::delete_raw(&m.id).build()),
         );
 
+        let batch = queries.join("\n");
+        if !batch.trim().is_empty() {
+            rollback_steps.push(RollbackStep::Queries(Raw::new(batch), batch_names));
+        }
+
         let file_paths = migrations_to_rollback
             .iter()
+            .filter_map(|m| match m {
+                ReversibleMigration::File(m) => Some(m),
+                ReversibleMigration::Code(_) => None,
+            })
             .map(|m| {
                 fm.get_migration_dir().map(|d| {
                     vec![
@@ -353,40 +565,123 @@ impl MigrationRunner {
             .collect::<MigrationResult<Vec<_>>>();
 
         Ok((
-            Raw::new(all),
+            rollback_steps,
             file_paths?.iter().flatten().cloned().collect::<Vec<_>>(),
         ))
     }
 
-    async fn run_up_pending_migrations(
-        db: Surreal<impl Connection>,
-        filtered_pending_migrations: Vec<PendingMigrationFile>,
+    async fn run_up_pending_migrations<C: Connection>(
+        db: Surreal<C>,
+        filtered_pending_migrations: Vec<PendingMigration>,
     ) -> MigrationResult<()> {
         let mut migration_queries: Vec<FileContent> = vec![];
         let mut mark_queries_registered_queries: Vec<Raw> = vec![];
+        let mut migration_names: Vec<MigrationFilename> = vec![];
 
-        for mf in filtered_pending_migrations.into_iter() {
-            match mf.into() {
-                MigrationFile::OneWay(m) => {
-                    let created_registered_mig =
-                        Migration::create_raw(m.name(), &m.content().as_checksum()?, None);
+        // Check before applying anything so that a code migration the connection
+        // cannot run does not leave the migrations half applied.
+        for m in filtered_pending_migrations.iter() {
+            if let PendingMigration::Code(code_migration) = m {
+                code_migration.db(&db)?;
+            }
+        }
 
-                    migration_queries.push(m.content().to_owned());
-                    mark_queries_registered_queries.push(created_registered_mig);
-                }
-                MigrationFile::TwoWay(m) => {
-                    let created_registered_mig = Migration::create_raw(
-                        &m.up.name,
-                        &m.up.content.as_checksum()?,
-                        Some(&m.down.content.as_checksum()?),
-                    );
+        for m in filtered_pending_migrations.into_iter() {
+            let registration_query = Self::registration_query(&m)?;
+            let name = m.name_forward().clone();
+            let mf = match m {
+                PendingMigration::File(mf) => mf,
+                PendingMigration::Code(code_migration) => {
+                    // Files before the code migration are applied first so
+                    // that it sees the schema and data it was written against.
+                    if !mark_queries_registered_queries.is_empty() {
+                        Self::run_up_migration_queries(
+                            db.clone(),
+                            &migration_queries,
+                            &mark_queries_registered_queries,
+                            &migration_names,
+                        )
+                        .await?;
+                        migration_queries.clear();
+                        mark_queries_registered_queries.clear();
+                        migration_names.clear();
+                    }
 
-                    migration_queries.push(m.up.content);
-                    mark_queries_registered_queries.push(created_registered_mig);
+                    log::info!("Running code migration: {}", code_migration.name);
+                    let started = Instant::now();
+                    let applied = code_migration.migration.up(code_migration.db(&db)?).await;
+                    MigrationFailure::record_and_return(
+                        db.clone(),
+                        MigrationDirection::Up,
+                        &[code_migration.name.clone()],
+                        started.elapsed(),
+                        applied,
+                    )
+                    .await?;
+                    let duration = started.elapsed();
+                    Self::registration_query(&PendingMigration::Code(code_migration.clone()))?
+                        .run(db.clone())
+                        .await?;
+                    Migration::set_duration_raw(
+                        &[Migration::create_id(&code_migration.name)],
+                        duration,
+                    )
+                    .run(db.clone())
+                    .await?;
+
+                    log::info!("Applied code migration: {}", code_migration.name);
+                    continue;
                 }
+            };
+
+            match mf.into() {
+                MigrationFile::OneWay(m) => migration_queries.push(m.content().to_owned()),
+                MigrationFile::TwoWay(m) => migration_queries.push(m.up.content),
             }
+            mark_queries_registered_queries.push(registration_query);
+            migration_names.push(name);
         }
 
+        Self::run_up_migration_queries(
+            db.clone(),
+            &migration_queries,
+            &mark_queries_registered_queries,
+            &migration_names,
+        )
+        .await
+    }
+
+    /// The query recording a migration as applied in the migration table
+    fn registration_query(migration: &PendingMigration) -> MigrationResult<Raw> {
+        let query = match migration {
+            PendingMigration::File(mf) => match MigrationFile::from(mf.clone()) {
+                MigrationFile::OneWay(m) => {
+                    Migration::create_raw(m.name(), &m.content().as_checksum()?, None)
+                }
+                MigrationFile::TwoWay(m) => Migration::create_raw(
+                    &m.up.name,
+                    &m.up.content.as_checksum()?,
+                    Some(&m.down.content.as_checksum()?),
+                ),
+            },
+            PendingMigration::Code(code_migration) => {
+                let checksum = code_migration.checksum()?;
+                Migration::create_raw(
+                    &code_migration.name,
+                    &checksum,
+                    code_migration.is_twoway().then_some(&checksum),
+                )
+            }
+        };
+        Ok(query)
+    }
+
+    async fn run_up_migration_queries<C: Connection>(
+        db: Surreal<C>,
+        migration_queries: &[FileContent],
+        mark_queries_registered_queries: &[Raw],
+        migration_names: &[MigrationFilename],
+    ) -> MigrationResult<()> {
         let migration_queries_str = migration_queries
             .iter()
             .map(ToString::to_string)
@@ -416,9 +711,22 @@ impl MigrationRunner {
             log::info!("No new migrations to apply");
         } else {
             println!("{}", &all);
-            begin_transaction()
-                .query(Raw::new(all))
-                .commit_transaction()
+            let started = Instant::now();
+            let applied = Self::run_in_transaction(db.clone(), Raw::new(all)).await;
+            MigrationFailure::record_and_return(
+                db.clone(),
+                MigrationDirection::Up,
+                migration_names,
+                started.elapsed(),
+                applied,
+            )
+            .await?;
+
+            let migration_ids = migration_names
+                .iter()
+                .map(Migration::create_id)
+                .collect::<Vec<_>>();
+            Migration::set_duration_raw(&migration_ids, started.elapsed())
                 .run(db.clone())
                 .await?;
 
@@ -428,31 +736,138 @@ impl MigrationRunner {
         Ok(())
     }
 
-    pub async fn apply_pending_migrations(
-        db: Surreal<impl Connection>,
+    /// Runs the queries in a transaction, failing when any of them fails
+    async fn run_in_transaction(db: Surreal<impl Connection>, queries: Raw) -> MigrationResult<()> {
+        let mut response = begin_transaction()
+            .query(queries)
+            .commit_transaction()
+            .run(db)
+            .await?;
+
+        // Every statement of a failed transaction fails, so the error of the
+        // statement that caused it is the one worth reporting.
+        let mut errors = response.take_errors().into_iter().collect::<Vec<_>>();
+        errors.sort_by_key(|(index, _)| *index);
+        let cause = errors
+            .iter()
+            .position(|(_, e)| {
+                !matches!(
+                    e,
+                    surrealdb::Error::Db(surrealdb::error::Db::QueryNotExecuted)
+                )
+            })
+            .unwrap_or(0);
+
+        match errors.into_iter().nth(cause) {
+            Some((_, e)) => Err(e.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Applies pending migrations while holding the migration lock so that
+    /// concurrent migrators do not apply the same migration twice.
+    pub async fn apply_pending_migrations<C: Connection>(
+        db: Surreal<C>,
+        fm: &MigrationConfig,
         all_migrations: Vec<impl Into<MigrationFile> + ::std::fmt::Debug>,
         update_strategy: UpdateStrategy,
+    ) -> MigrationResult<()> {
+        let code_migrations = fm
+            .code_migrations
+            .to_migrations(fm.migration_flag_checked()?)?;
+
+        MigrationLock::acquire(db.clone(), fm.lock_timeout())
+            .await?
+            .run(Self::apply_pending_migrations_locked(
+                db,
+                all_migrations,
+                code_migrations,
+                update_strategy,
+            ))
+            .await
+    }
+
+    async fn apply_pending_migrations_locked<C: Connection>(
+        db: Surreal<C>,
+        all_migrations: Vec<impl Into<MigrationFile> + ::std::fmt::Debug>,
+        code_migrations: Vec<CodeMigrationMeta>,
+        update_strategy: UpdateStrategy,
     ) -> MigrationResult<()> {
         log::info!("Running pending migrations");
+        Migration::upgrade_table(db.clone()).await?;
 
-        let filtered_pending_migrations = match update_strategy {
-            UpdateStrategy::Latest => {
-                Self::get_pending_migrations(all_migrations, db.clone()).await?
-            }
-            UpdateStrategy::Number(count) => {
-                Self::get_pending_migrations(all_migrations, db.clone())
-                    .await?
-                    .into_iter()
-                    .take(count as usize)
-                    .collect::<Vec<_>>()
+        let filtered_pending_migrations = Self::filter_pending_migrations(
+            db.clone(),
+            all_migrations,
+            code_migrations,
+            update_strategy,
+        )
+        .await?;
+
+        Self::run_up_pending_migrations(db.clone(), filtered_pending_migrations).await
+    }
+
+    /// The migrations and statements `apply_pending_migrations` would run, without running them
+    pub async fn plan_pending_migrations<C: Connection>(
+        db: Surreal<C>,
+        fm: &MigrationConfig,
+        all_migrations: Vec<impl Into<MigrationFile> + ::std::fmt::Debug>,
+        update_strategy: UpdateStrategy,
+    ) -> MigrationResult<MigrationPlan> {
+        let code_migrations = fm
+            .code_migrations
+            .to_migrations(fm.migration_flag_checked()?)?;
+        let pending_migrations =
+            Self::filter_pending_migrations(db, all_migrations, code_migrations, update_strategy)
+                .await?;
+
+        let mut plan = MigrationPlan::new(PlanAction::Apply);
+        for m in pending_migrations {
+            let registration_query = Self::registration_query(&m)?.build();
+            match m {
+                PendingMigration::File(mf) => match MigrationFile::from(mf) {
+                    MigrationFile::OneWay(m) => plan.push(
+                        m.name(),
+                        PlannedMigrationKind::File,
+                        format!("{}\n{registration_query}", m.content()),
+                    ),
+                    MigrationFile::TwoWay(m) => plan.push(
+                        &m.up.name,
+                        PlannedMigrationKind::File,
+                        format!("{}\n{registration_query}", m.up.content),
+                    ),
+                },
+                PendingMigration::Code(code_migration) => plan.push(
+                    &code_migration.name,
+                    PlannedMigrationKind::Code,
+                    format!("-- Runs the Rust code migration\n{registration_query}"),
+                ),
             }
-            UpdateStrategy::Till(filecursor) => {
-                let pending_migs = Self::get_pending_migrations(all_migrations, db.clone()).await?;
+        }
 
+        Ok(plan)
+    }
+
+    async fn filter_pending_migrations<C: Connection>(
+        db: Surreal<C>,
+        all_migrations: Vec<impl Into<MigrationFile> + ::std::fmt::Debug>,
+        code_migrations: Vec<CodeMigrationMeta>,
+        update_strategy: UpdateStrategy,
+    ) -> MigrationResult<Vec<PendingMigration>> {
+        let pending_migrations =
+            Self::get_pending_migrations(all_migrations, code_migrations, db.clone()).await?;
+
+        let filtered_pending_migrations = match update_strategy {
+            UpdateStrategy::Latest => pending_migrations,
+            UpdateStrategy::Number(count) => pending_migrations
+                .into_iter()
+                .take(count as usize)
+                .collect::<Vec<_>>(),
+            UpdateStrategy::Till(filecursor) => {
                 let mut migration_found = false;
-                let mut filtered_migs: Vec<PendingMigrationFile> = vec![];
+                let mut filtered_migs: Vec<PendingMigration> = vec![];
 
-                for mig in pending_migs {
+                for mig in pending_migrations {
                     filtered_migs.push(mig.clone());
                     // if *mig.name_forward() == filecursor {
                     if mig.name_forward().to_up() == filecursor.to_up() {
@@ -471,7 +886,305 @@ impl MigrationRunner {
             }
         };
 
-        Self::run_up_pending_migrations(db.clone(), filtered_pending_migrations).await
+        Ok(filtered_pending_migrations)
+    }
+
+    /// Collapses the migration files up to a migration into a single baseline
+    /// migration with the same timestamp as the last squashed one and replaces
+    /// their records with the baseline's so it is not applied again.
+    pub async fn squash_migrations(
+        db: Surreal<Any>,
+        fm: &MigrationConfig,
+        squash_options: SquashOptions,
+    ) -> MigrationResult<MigrationFile> {
+        MigrationLock::acquire(db.clone(), fm.lock_timeout())
+            .await?
+            .run(Self::squash_migrations_locked(db, fm, squash_options))
+            .await
+    }
+
+    async fn squash_migrations_locked(
+        db: Surreal<Any>,
+        fm: &MigrationConfig,
+        squash_options: SquashOptions,
+    ) -> MigrationResult<MigrationFile> {
+        let SquashOptions {
+            since,
+            till,
+            basename,
+            mode,
+        } = squash_options;
+        let basename = basename.normalize_ensure();
+        let till = till.unwrap_or_else(|| since.clone());
+        let till_timestamp = till.timestamp();
+
+        let flag = fm.migration_flag_checked()?;
+        let all_migrations: Vec<MigrationFile> = match flag {
+            MigrationFlag::TwoWay => fm
+                .get_two_way_migrations_sorted_asc(false)?
+                .into_iter()
+                .map(MigrationFile::from)
+                .collect(),
+            MigrationFlag::OneWay => fm
+                .get_oneway_migrations_sorted_asc(false)?
+                .into_iter()
+                .map(MigrationFile::from)
+                .collect(),
+        };
+
+        if !all_migrations
+            .iter()
+            .any(|m| m.name_forward().to_up() == till.to_up())
+        {
+            return Err(MigrationError::MigrationDoesNotExist { filename: till });
+        }
+
+        let migrations_to_squash = all_migrations
+            .into_iter()
+            .filter(|m| m.name_forward().timestamp() <= till_timestamp)
+            .collect::<Vec<_>>();
+
+        if let Some(unapplied) = migrations_to_squash
+            .iter()
+            .find(|m| m.name_forward().timestamp() > since.timestamp())
+        {
+            return Err(MigrationError::SquashIncludesUnappliedMigration {
+                migration: unapplied.name_forward().clone(),
+                since,
+            });
+        }
+
+        if migrations_to_squash.len() < 2 {
+            return Err(MigrationError::NothingToSquash);
+        }
+
+        let MigrationSchema { timestamp, .. } = &Migration::schema();
+        let migrations_from_db = select(All)
+            .from(Migration::table())
+            .where_(timestamp.lte(till_timestamp.clone().into_inner()))
+            .return_many::<Migration>(db.clone())
+            .await?;
+
+        let mut deletion_queries = vec![];
+        for m in migrations_to_squash.iter() {
+            let m_from_db = migrations_from_db
+                .iter()
+                .find(|db_m| db_m.name == m.name_forward().to_string())
+                .ok_or(MigrationError::SquashMigrationNotApplied(
+                    m.name_forward().clone(),
+                ))?;
+
+            if mode.is_strict() {
+                m_from_db
+                    .checksum_up
+                    .verify(m.name_forward(), m.up_content())?;
+            }
+            deletion_queries.push(Migration::delete_raw(&m_from_db.id));
+        }
+
+        let up_content = migrations_to_squash
+            .iter()
+            .map(|m| m.up_content().to_string())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let baseline_name: MigrationFilename =
+            format!("{till_timestamp}_{basename}.surql").try_into()?;
+        let baseline = match flag {
+            MigrationFlag::TwoWay => {
+                let down_content = migrations_to_squash
+                    .iter()
+                    .rev()
+                    .filter_map(|m| m.down_content().map(ToString::to_string))
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                MigrationFile::TwoWay(MigrationFileTwoWayPair {
+                    up: FileMetadata::new(baseline_name.to_up(), up_content.into()),
+                    down: FileMetadata::new(baseline_name.to_down(), down_content.into()),
+                })
+            }
+            MigrationFlag::OneWay => MigrationFile::OneWay(MigrationFileOneWay::new(
+                FileMetadata::new(baseline_name, up_content.into()),
+            )),
+        };
+
+        // The baseline is written and read back before the database is touched,
+        // so a failed write leaves both the files and the registrations as they were.
+        let migration_dir = fm.get_migration_dir()?;
+        let baseline_files = BaselineFiles::write(&baseline, fm)?;
+
+        let mut queries = deletion_queries;
+        queries.push(Self::registration_query(&PendingMigration::File(
+            baseline.clone().into(),
+        ))?);
+        let queries = queries.iter().map(|q| q.build()).collect::<Vec<_>>();
+        let committed = async {
+            Migration::upgrade_table(db.clone()).await?;
+            begin_transaction()
+                .query(Raw::new(queries.join("\n")))
+                .commit_transaction()
+                .run(db.clone())
+                .await?;
+            MigrationResult::Ok(())
+        }
+        .await;
+        if let Err(e) = committed {
+            baseline_files.restore();
+            return Err(e);
+        }
+
+        for m in migrations_to_squash.iter() {
+            let paths = match m {
+                MigrationFile::OneWay(m) => vec![m.name().fullpath(&migration_dir)],
+                MigrationFile::TwoWay(m) => vec![
+                    m.up.name.fullpath(&migration_dir),
+                    m.down.name.fullpath(&migration_dir),
+                ],
+            };
+            for path in paths.iter().filter(|p| !baseline_files.contains(p)) {
+                std::fs::remove_file(path).map_err(|e| {
+                    MigrationError::IoError(format!(
+                        "Failed to delete squashed migration file: {}. Error: {e}",
+                        path.to_string_lossy()
+                    ))
+                })?;
+            }
+        }
+
+        log::info!(
+            "Squashed {} migrations into {}",
+            migrations_to_squash.len(),
+            baseline.name_forward()
+        );
+
+        Ok(baseline)
+    }
+
+    /// Writes the schema of a database that predates the migrator as the first
+    /// migration and registers it as applied, so that later migrations are
+    /// generated from that schema instead of recreating it.
+    pub async fn baseline_from_db(
+        db: Surreal<Any>,
+        fm: &MigrationConfig,
+        basename: &Basename,
+    ) -> MigrationResult<MigrationFile> {
+        MigrationLock::acquire(db.clone(), fm.lock_timeout())
+            .await?
+            .run(Self::baseline_from_db_locked(db, fm, basename))
+            .await
+    }
+
+    async fn baseline_from_db_locked(
+        db: Surreal<Any>,
+        fm: &MigrationConfig,
+        basename: &Basename,
+    ) -> MigrationResult<MigrationFile> {
+        if Self::get_latest_migration(db.clone()).await?.is_some() {
+            return Err(MigrationError::BaselineDatabaseHasMigrations);
+        }
+
+        let flag = fm.migration_flag_checked()?;
+        let resources = MigratorDatabase::get_all_resources_from(db.clone()).await?;
+        let migrator_tables = [
+            Migration::table(),
+            MigrationLock::table(),
+            MigrationFailure::table(),
+        ];
+        let tables = resources
+            .get_tables()
+            .into_iter()
+            .filter(|table| !migrator_tables.contains(table))
+            .collect::<Vec<_>>();
+
+        let top_level_defs = [
+            resources.analyzers().get_all_definitions(),
+            resources.params().get_all_definitions(),
+            resources.functions().get_all_definitions(),
+            resources.scopes().get_all_definitions(),
+            resources.tokens().get_all_definitions(),
+            resources.users().get_all_definitions(),
+        ]
+        .concat();
+
+        let mut table_defs = vec![];
+        for table in tables.iter() {
+            table_defs.extend(
+                resources
+                    .tables()
+                    .get_definition(table.to_string())
+                    .cloned(),
+            );
+            for table_resources in [
+                resources
+                    .get_table_fields(table)
+                    .map(|f| f.get_all_definitions()),
+                resources
+                    .get_table_indexes(table)
+                    .map(|i| i.get_all_definitions()),
+                resources
+                    .get_table_events(table)
+                    .map(|e| e.get_all_definitions()),
+            ] {
+                table_defs.extend(table_resources.unwrap_or_default());
+            }
+        }
+
+        // The migration table is defined by every first migration so that
+        // replaying the migration directory matches the codebase
+        let migration_table_defs = std::iter::once(Migration::define_table().to_raw())
+            .chain(Migration::define_fields(flag))
+            .collect::<Vec<_>>();
+
+        let up_content = top_level_defs
+            .iter()
+            .chain(table_defs.iter())
+            .map(|def| QueryType::Define(def.clone()).to_pretty_string())
+            .chain(migration_table_defs.iter().map(Raw::build))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut down_queries = tables
+            .iter()
+            .chain(std::iter::once(&Migration::table()))
+            .rev()
+            .map(|table| RemoveStatementRaw::from(remove_table(table.clone()).build()))
+            .collect::<Vec<_>>();
+        for def in top_level_defs.iter().rev() {
+            down_queries.push(def.as_remove_statement()?);
+        }
+        let down_content = down_queries
+            .into_iter()
+            .map(|query| QueryType::Remove(query).to_pretty_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let baseline = MigrationFile::new(
+            &basename.normalize_ensure(),
+            &flag,
+            &up_content.into(),
+            &down_content.into(),
+        )?;
+
+        // The baseline is written and read back before it is registered, so that
+        // the database is never registered with a migration missing on disk.
+        let baseline_files = BaselineFiles::write(&baseline, fm)?;
+
+        let mut queries = migration_table_defs;
+        queries.extend(Migration::define_history_fields());
+        queries.push(Self::registration_query(&PendingMigration::File(
+            baseline.clone().into(),
+        ))?);
+        let queries = queries.iter().map(|q| q.build()).collect::<Vec<_>>();
+        if let Err(e) = Self::run_in_transaction(db, Raw::new(queries.join("\n"))).await {
+            baseline_files.restore();
+            return Err(e);
+        }
+
+        log::info!(
+            "Registered {} as the baseline of the database schema",
+            baseline.name_forward()
+        );
+
+        Ok(baseline)
     }
 
     pub(crate) async fn list_migrations(
@@ -545,3 +1258,77 @@ impl MigrationRunner {
         Ok(filtered_local_migrations)
     }
 }
+
+/// The files of a baseline migration that has been written but not yet registered.
+struct BaselineFiles {
+    paths: Vec<PathBuf>,
+    // Previous content of the files the baseline overwrote.
+    overwritten: Vec<(PathBuf, String)>,
+}
+
+impl BaselineFiles {
+    /// Writes the baseline and reads it back to make sure it was written correctly.
+    /// Nothing is left behind if it was not.
+    fn write(baseline: &MigrationFile, fm: &MigrationConfig) -> MigrationResult<Self> {
+        let migration_dir = fm.resolve_migration_directory(true)?;
+        let files = match baseline {
+            MigrationFile::OneWay(m) => vec![(m.name().fullpath(&migration_dir), m.content())],
+            MigrationFile::TwoWay(m) => vec![
+                (m.up.name.fullpath(&migration_dir), &m.up.content),
+                (m.down.name.fullpath(&migration_dir), &m.down.content),
+            ],
+        };
+        let baseline_files = Self {
+            paths: files.iter().map(|(path, _)| path.clone()).collect(),
+            overwritten: files
+                .iter()
+                .filter_map(|(path, _)| {
+                    std::fs::read_to_string(path)
+                        .ok()
+                        .map(|content| (path.clone(), content))
+                })
+                .collect(),
+        };
+
+        let written = baseline.create_file(fm).and_then(|_| {
+            files
+                .iter()
+                .try_for_each(|(path, content)| match std::fs::read_to_string(path) {
+                    Ok(written) if written == content.to_string() => Ok(()),
+                    _ => Err(MigrationError::IoError(format!(
+                        "Baseline migration file was not written correctly: {}",
+                        path.to_string_lossy()
+                    ))),
+                })
+        });
+        match written {
+            Ok(()) => Ok(baseline_files),
+            Err(e) => {
+                baseline_files.restore();
+                Err(e)
+            }
+        }
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.paths.iter().any(|p| p.as_path() == path)
+    }
+
+    /// Undoes the baseline. Files it overwrote get their content back,
+    /// the others are removed.
+    fn restore(self) {
+        for path in &self.paths {
+            let restored = match self.overwritten.iter().find(|(p, _)| p == path) {
+                Some((_, content)) => std::fs::write(path, content),
+                None if path.is_file() => std::fs::remove_file(path),
+                None => Ok(()),
+            };
+            if let Err(e) = restored {
+                log::error!(
+                    "Failed to restore migration file {} after a failed baseline. Error: {e}",
+                    path.to_string_lossy()
+                );
+            }
+        }
+    }
+}
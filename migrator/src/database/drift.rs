@@ -0,0 +1,275 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::{fmt::Display, fs, path::Path};
+
+use serde::Serialize;
+use surreal_query_builder::Table;
+use surrealdb::{engine::any::Any, Surreal};
+
+use crate::{table_events::ComparisonEvents, table_indexes::ComparisonIndexes, *};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftKind {
+    /// Defined in the database but not by the applied migrations
+    Extra,
+    /// Defined by the applied migrations but not in the database
+    Missing,
+    /// Defined differently in the database and by the applied migrations
+    Changed,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftResource {
+    Analyzer,
+    Function,
+    Param,
+    Scope,
+    Token,
+    User,
+    Table,
+    Field,
+    Event,
+    Index,
+}
+
+impl Display for DriftResource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let resource = match self {
+            Self::Analyzer => "analyzer",
+            Self::Function => "function",
+            Self::Param => "param",
+            Self::Scope => "scope",
+            Self::Token => "token",
+            Self::User => "user",
+            Self::Table => "table",
+            Self::Field => "field",
+            Self::Event => "event",
+            Self::Index => "index",
+        };
+        write!(f, "{resource}")
+    }
+}
+
+/// A definition that differs between the live database and the applied migrations
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDrift {
+    pub kind: DriftKind,
+    pub resource: DriftResource,
+    pub table: Option<String>,
+    pub name: String,
+    /// The definition from the applied migrations
+    pub expected: Option<String>,
+    /// The definition in the live database
+    pub actual: Option<String>,
+}
+
+impl Display for SchemaDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            DriftKind::Extra => "Extra",
+            DriftKind::Missing => "Missing",
+            DriftKind::Changed => "Changed",
+        };
+        write!(f, "{kind} {} '{}'", self.resource, self.name)?;
+        if let Some(table) = &self.table {
+            write!(f, " on table '{table}'")?;
+        }
+        if let Some(expected) = &self.expected {
+            write!(f, "\n  expected: {}", expected.trim())?;
+        }
+        if let Some(actual) = &self.actual {
+            write!(f, "\n  actual:   {}", actual.trim())?;
+        }
+        Ok(())
+    }
+}
+
+/// The definitions that differ between the live database and the schema the
+/// applied migrations produce e.g after a hotfix was run directly against
+/// the database.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct DriftReport {
+    pub drifts: Vec<SchemaDrift>,
+}
+
+impl DriftReport {
+    pub fn has_drift(&self) -> bool {
+        !self.drifts.is_empty()
+    }
+
+    pub fn to_json(&self) -> MigrationResult<String> {
+        serde_json::to_string_pretty(self).map_err(|e| MigrationError::IoError(e.to_string()))
+    }
+
+    /// Prints the report or writes it to the output file
+    pub fn output(&self, output: Option<&Path>, json: bool) -> MigrationResult<()> {
+        let report = if json {
+            self.to_json()?
+        } else {
+            self.to_string()
+        };
+
+        match output {
+            Some(path) => fs::write(path, report).map_err(|e| {
+                MigrationError::IoError(format!(
+                    "Failed to write drift report to {}. Error: {e}",
+                    path.to_string_lossy()
+                ))
+            }),
+            None => {
+                println!("{report}");
+                Ok(())
+            }
+        }
+    }
+
+    /// Compares the live database with the migrations registered in it, replayed
+    /// into an in-memory database. The migrator's own tables are not compared.
+    pub async fn detect(db: Surreal<Any>, fm: &MigrationConfig) -> MigrationResult<Self> {
+        let latest_migration = MigrationRunner::get_latest_migration(db.clone()).await?;
+        let applied_migrations = match fm.migration_flag_checked()? {
+            MigrationFlag::TwoWay => fm
+                .get_two_way_migrations_sorted_asc(false)?
+                .into_iter()
+                .map(MigrationFileOneWay::from)
+                .collect::<Vec<_>>(),
+            MigrationFlag::OneWay => fm.get_oneway_migrations_sorted_asc(false)?,
+        }
+        .into_iter()
+        .filter(|m| {
+            latest_migration
+                .as_ref()
+                .map_or(false, |latest| m.name().timestamp() <= latest.timestamp)
+        })
+        .collect::<Vec<_>>();
+
+        let replayed = MigratorDatabase::init().await;
+        LeftDatabase::run_local_dir_oneway_content_migrations(replayed.db(), applied_migrations)
+            .await?;
+
+        let left_resources = LeftFullDbInfo(replayed.get_all_resources().await?);
+        let right_resources =
+            RightFullDbInfo(MigratorDatabase::get_all_resources_from(db.clone()).await?);
+        let init = ComparisonsInit {
+            left_resources: &left_resources,
+            right_resources: &right_resources,
+            prompter: &RealPrompter,
+        };
+
+        let mut drifts = [
+            init.new_analyzers().drift(DriftResource::Analyzer),
+            init.new_functions().drift(DriftResource::Function),
+            init.new_params().drift(DriftResource::Param),
+            init.new_scopes().drift(DriftResource::Scope),
+            init.new_tokens().drift(DriftResource::Token),
+            init.new_users().drift(DriftResource::User),
+            schema_drifts(
+                DriftResource::Table,
+                None,
+                left_resources.tables(),
+                right_resources.tables(),
+            ),
+        ]
+        .concat();
+
+        let mut tables = left_resources.get_tables();
+        tables.extend(right_resources.get_tables());
+        tables.sort();
+        tables.dedup();
+        for table in tables.iter() {
+            drifts.extend(schema_drifts(
+                DriftResource::Field,
+                Some(table),
+                left_resources.get_table_fields(table).unwrap_or_default(),
+                right_resources.get_table_fields(table).unwrap_or_default(),
+            ));
+            drifts.extend(
+                ComparisonEvents {
+                    table,
+                    resources: &init,
+                }
+                .drift(DriftResource::Event),
+            );
+            drifts.extend(
+                ComparisonIndexes {
+                    table,
+                    resources: &init,
+                }
+                .drift(DriftResource::Index),
+            );
+        }
+
+        let migrator_tables = [
+            Migration::table().to_string(),
+            MigrationLock::table().to_string(),
+            MigrationFailure::table().to_string(),
+        ];
+        drifts.retain(|drift| {
+            let table = match drift.resource {
+                DriftResource::Table => Some(&drift.name),
+                _ => drift.table.as_ref(),
+            };
+            !table.map_or(false, |table| migrator_tables.contains(table))
+        });
+
+        Ok(Self { drifts })
+    }
+}
+
+impl Display for DriftReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.drifts.is_empty() {
+            return write!(f, "No schema drift detected");
+        }
+
+        write!(f, "{} schema drift(s) detected", self.drifts.len())?;
+        for drift in &self.drifts {
+            write!(f, "\n\n{drift}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Left is from the applied migrations, right is from the live database
+pub(crate) fn schema_drifts<T: Informational>(
+    resource: DriftResource,
+    table: Option<&Table>,
+    left: T,
+    right: T,
+) -> Vec<SchemaDrift> {
+    let names = left.get_names_as_set();
+    let names = names
+        .union(&right.get_names_as_set())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let def_left = left.get_definition(&name).cloned();
+            let def_right = right.get_definition(&name).cloned();
+            let (kind, expected, actual) = match DeltaTypeResource::from((def_left, def_right)) {
+                DeltaTypeResource::Create { right } => (DriftKind::Extra, None, Some(right)),
+                DeltaTypeResource::Remove { left } => (DriftKind::Missing, Some(left), None),
+                DeltaTypeResource::Update { left, right } => {
+                    (DriftKind::Changed, Some(left), Some(right))
+                }
+                DeltaTypeResource::NoChange | DeltaTypeResource::Rename { .. } => return None,
+            };
+
+            Some(SchemaDrift {
+                kind,
+                resource,
+                table: table.map(ToString::to_string),
+                name,
+                expected: expected.map(|def| def.to_string()),
+                actual: actual.map(|def| def.to_string()),
+            })
+        })
+        .collect()
+}
@@ -4,7 +4,7 @@
  * Copyright (c) 2023 Oyelowo Oyedayo
  */
 
-use surrealdb::{engine::any::Any, Surreal};
+use surrealdb::{Connection, Surreal};
 
 use crate::{
     FileMetadata, MigrationConfig, MigrationFileOneWay, MigrationFileTwoWayPair, MigrationResult,
@@ -30,7 +30,7 @@ impl EmbeddedMigrationsTwoWay {
 
     pub async fn run(
         self,
-        db: Surreal<Any>,
+        db: Surreal<impl Connection>,
         update_strategy: UpdateStrategy,
         mode: Mode,
     ) -> MigrationResult<()> {
@@ -90,7 +90,7 @@ impl EmbeddedMigrationsOneWay {
 
     pub async fn run(
         self,
-        db: Surreal<Any>,
+        db: Surreal<impl Connection>,
         update_strategy: UpdateStrategy,
         mode: Mode,
     ) -> MigrationResult<()> {
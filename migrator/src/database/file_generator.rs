@@ -9,7 +9,7 @@ use std::{collections::BTreeMap, ops::Deref};
 use surreal_query_builder::{statements::info_for, *};
 use surrealdb::{
     engine::local::{Db, Mem},
-    Surreal,
+    Connection, Surreal,
 };
 
 use crate::*;
@@ -87,10 +87,25 @@ impl MigratorDatabase {
     }
 
     pub async fn get_all_resources(&self) -> MigrationResult<FullDbInfo> {
-        let top_level_resources = self.get_db_info().await?;
+        Self::get_all_resources_from(self.db()).await
+    }
+
+    /// All resources of any database e.g the live database migrations are run against
+    pub async fn get_all_resources_from(
+        db: Surreal<impl Connection>,
+    ) -> MigrationResult<FullDbInfo> {
+        let top_level_resources = info_for()
+            .database()
+            .get_data::<DbInfo>(db.clone())
+            .await?
+            .expect("Database not found");
         let mut fields_by_table = BTreeMap::new();
         for table in top_level_resources.tables().get_names() {
-            let table_info = self.get_table_info(table.clone()).await?;
+            let table_info = info_for()
+                .table(table.clone())
+                .get_data::<TableResourcesData>(db.clone())
+                .await?
+                .expect("Table not found");
             fields_by_table.insert(table.into(), table_info);
         }
         let all_resources = FullDbInfo {
@@ -112,6 +127,89 @@ impl MigratorDatabase {
         codebase_resources: impl DbResources,
         prompter: impl Prompter,
     ) -> MigrationResult<()> {
+        let (migration_file, mut lint) = Self::plan_and_lint_migration(
+            migration_basename,
+            file_manager,
+            codebase_resources,
+            &prompter,
+        )
+        .await?;
+
+        if let Some(db) = file_manager.lint_db.clone() {
+            lint.count_violations(db).await?;
+        }
+        if file_manager.deny_destructive && lint.has_destructive() {
+            return Err(MigrationError::DestructiveMigration {
+                count: lint.destructive().count(),
+                lint: lint.to_string(),
+            });
+        }
+        if lint
+            .findings
+            .iter()
+            .any(|finding| finding.severity != LintSeverity::Safe)
+        {
+            log::warn!("Migration lint: {lint}");
+        }
+
+        let query_str = format!(
+            "{}{}",
+            migration_file.up_content(),
+            migration_file
+                .down_content()
+                .map(ToString::to_string)
+                .unwrap_or_default()
+        );
+
+        if query_str.trim().is_empty() {
+            match prompter.prompt_empty_migrations_trigger() {
+                Ok(true) => {
+                    migration_file.create_file(file_manager)?;
+                    log::info!("New migration generated.");
+                }
+                Ok(false) => {
+                    log::info!("No migration created");
+                }
+                Err(e) => {
+                    return Err(MigrationError::PromptError(e));
+                }
+            };
+        } else {
+            migration_file.create_file(file_manager)?;
+            log::info!("New migration generated.");
+        };
+
+        Ok(())
+    }
+
+    /// The migration file(s) that would be generated and their lint, without writing them
+    pub async fn plan_migration(
+        migration_basename: &Basename,
+        file_manager: &MigrationConfig,
+        codebase_resources: impl DbResources,
+        prompter: &impl Prompter,
+    ) -> MigrationResult<MigrationPlan> {
+        let (migration_file, mut lint) = Self::plan_and_lint_migration(
+            migration_basename,
+            file_manager,
+            codebase_resources,
+            prompter,
+        )
+        .await?;
+        if let Some(db) = file_manager.lint_db.clone() {
+            lint.count_violations(db).await?;
+        }
+        Ok(MigrationPlan::from(&migration_file).with_lint(lint))
+    }
+
+    /// The migration file(s) that would be generated and the classification of
+    /// their up statements as safe, warning or destructive
+    pub async fn plan_and_lint_migration(
+        migration_basename: &Basename,
+        file_manager: &MigrationConfig,
+        codebase_resources: impl DbResources,
+        prompter: &impl Prompter,
+    ) -> MigrationResult<(MigrationFile, MigrationLint)> {
         let migration_basename = migration_basename.normalize_ensure();
 
         // Left = migration directory
@@ -134,10 +232,11 @@ impl MigratorDatabase {
         right
             .run_codebase_schema_queries(&codebase_resources, migration_flag)
             .await?;
+        let left_resources = left.resources().await;
         let init = ComparisonsInit {
-            left_resources: &left.resources().await,
+            left_resources: &left_resources,
             right_resources: &right.resources().await,
-            prompter: &prompter,
+            prompter,
         };
 
         let tables = init.new_tables(&codebase_resources).queries()?;
@@ -182,9 +281,11 @@ impl MigratorDatabase {
             }
         }
 
+        let lint = MigrationLint::lint(&up_queries, &left_resources);
+
         let up_queries_str = up_queries
             .iter()
-            .map(ToString::to_string)
+            .map(QueryType::to_pretty_string)
             .collect::<Vec<_>>()
             .join("")
             .trim()
@@ -192,14 +293,12 @@ impl MigratorDatabase {
 
         let down_queries_str = down_queries
             .iter()
-            .map(ToString::to_string)
+            .map(QueryType::to_pretty_string)
             .collect::<Vec<_>>()
             .join("")
             .trim()
             .to_string();
 
-        let query_str = format!("{up_queries_str}{down_queries_str}");
-
         let migration_file = MigrationFile::new(
             &migration_basename,
             &file_manager.migration_flag_checked()?,
@@ -207,25 +306,7 @@ impl MigratorDatabase {
             &down_queries_str.into(),
         )?;
 
-        if query_str.trim().is_empty() {
-            match prompter.prompt_empty_migrations_trigger() {
-                Ok(true) => {
-                    migration_file.create_file(file_manager)?;
-                    log::info!("New migration generated.");
-                }
-                Ok(false) => {
-                    log::info!("No migration created");
-                }
-                Err(e) => {
-                    return Err(MigrationError::PromptError(e));
-                }
-            };
-        } else {
-            migration_file.create_file(file_manager)?;
-            log::info!("New migration generated.");
-        };
-
-        Ok(())
+        Ok((migration_file, lint))
     }
 
     fn get_migration_reset_queries(file_manager: &MigrationConfig) -> MigrationResult<Queries> {
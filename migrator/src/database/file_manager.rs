@@ -6,6 +6,7 @@
 use std::{
     env, fs,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use surreal_query_builder::DbResources;
@@ -34,6 +35,21 @@ pub struct MigrationConfig {
     // After init => Some(MigrationFlag)
     #[builder(default)]
     pub migration_flag: Option<MigrationFlag>,
+    /// Rust code migrations run in timestamp order with the migration files
+    #[builder(default)]
+    pub code_migrations: CodeMigrations,
+    /// How long to wait for another migrator to release the migration lock.
+    /// Defaults to 60 seconds
+    #[builder(default)]
+    pub lock_timeout: Option<Duration>,
+    /// Refuse to generate migrations with destructive changes e.g removing a
+    /// table or narrowing the type of a field
+    #[builder(default)]
+    pub deny_destructive: bool,
+    /// Database to count the records that would violate new field types and
+    /// assertions of a generated migration
+    #[builder(default)]
+    pub lint_db: Option<Surreal<Any>>,
 }
 
 impl MigrationConfig {
@@ -51,6 +67,30 @@ impl MigrationConfig {
             .ok_or(MigrationError::MigrationFlagNotSet)
     }
 
+    pub fn set_code_migrations(mut self, code_migrations: CodeMigrations) -> Self {
+        self.code_migrations = code_migrations;
+        self
+    }
+
+    pub fn set_lock_timeout(mut self, lock_timeout: Duration) -> Self {
+        self.lock_timeout = Some(lock_timeout);
+        self
+    }
+
+    pub fn set_deny_destructive(mut self, deny_destructive: bool) -> Self {
+        self.deny_destructive = deny_destructive;
+        self
+    }
+
+    pub fn set_lint_db(mut self, db: Surreal<Any>) -> Self {
+        self.lint_db = Some(db);
+        self
+    }
+
+    pub fn lock_timeout(&self) -> Duration {
+        self.lock_timeout.unwrap_or(MigrationLock::DEFAULT_TIMEOUT)
+    }
+
     pub fn set_mode(mut self, mode: Mode) -> Self {
         self.mode = mode;
         self
@@ -170,6 +210,9 @@ impl MigrationConfig {
                 ))
             })?;
             let path = migration.path();
+            if !path.is_file() {
+                continue;
+            }
             let filename: MigrationFilename = path
                 .components()
                 .last()
@@ -252,6 +295,17 @@ impl FileManagerUni {
         Ok(())
     }
 
+    /// The migration file that would be generated and its lint, without writing it
+    pub async fn plan_migration(
+        &self,
+        migration_basename: &Basename,
+        codebase_resources: impl DbResources,
+        prompter: impl Prompter,
+    ) -> MigrationResult<MigrationPlan> {
+        MigratorDatabase::plan_migration(migration_basename, self, codebase_resources, &prompter)
+            .await
+    }
+
     /// Runs migrations at runtime against a database
     /// Make sure the migration directory exists when running migrations
     pub async fn run_pending_migrations(
@@ -260,11 +314,40 @@ impl FileManagerUni {
         update_strategy: UpdateStrategy,
     ) -> MigrationResult<()> {
         let migrations = self.get_migrations()?;
-        MigrationRunner::apply_pending_migrations(db, migrations, update_strategy).await?;
+        MigrationRunner::apply_pending_migrations(db, self, migrations, update_strategy).await?;
 
         Ok(())
     }
 
+    /// The pending migrations that would be run, without running them
+    pub async fn plan_pending_migrations(
+        &self,
+        db: Surreal<impl Connection>,
+        update_strategy: UpdateStrategy,
+    ) -> MigrationResult<MigrationPlan> {
+        let migrations = self.get_migrations()?;
+        MigrationRunner::plan_pending_migrations(db, self, migrations, update_strategy).await
+    }
+
+    /// Squash applied migrations into a single baseline migration
+    pub async fn squash_migrations(
+        &self,
+        db: Surreal<Any>,
+        squash_options: SquashOptions,
+    ) -> MigrationResult<MigrationFile> {
+        MigrationRunner::squash_migrations(db, self, squash_options).await
+    }
+
+    /// Writes the schema of an existing database as the first migration and
+    /// registers it as applied
+    pub async fn baseline_from_db(
+        &self,
+        db: Surreal<Any>,
+        migration_basename: &Basename,
+    ) -> MigrationResult<MigrationFile> {
+        MigrationRunner::baseline_from_db(db, self, migration_basename).await
+    }
+
     pub async fn run_embedded_pending_migrations(
         &self,
         db: Surreal<impl Connection>,
@@ -272,7 +355,7 @@ impl FileManagerUni {
         update_strategy: UpdateStrategy,
     ) -> MigrationResult<()> {
         let migrations = one_way_embedded_migrations.to_migrations_one_way()?;
-        MigrationRunner::apply_pending_migrations(db, migrations, update_strategy).await?;
+        MigrationRunner::apply_pending_migrations(db, self, migrations, update_strategy).await?;
 
         Ok(())
     }
@@ -288,6 +371,12 @@ impl FileManagerUni {
             .get_migrations()?
             .into_iter()
             .map(|m| m.name().to_owned())
+            .chain(
+                self.code_migrations
+                    .to_migrations(MigrationFlag::OneWay)?
+                    .into_iter()
+                    .map(|m| m.name),
+            )
             .collect::<Vec<_>>();
 
         let migrations =
@@ -339,6 +428,18 @@ impl FileManagerBi {
         .await
     }
 
+    /// The up and down migration files that would be generated and their lint, without
+    /// writing them
+    pub async fn plan_migration(
+        &self,
+        migration_basename: &Basename,
+        codebase_resources: impl DbResources,
+        prompter: impl Prompter,
+    ) -> MigrationResult<MigrationPlan> {
+        MigratorDatabase::plan_migration(migration_basename, self, codebase_resources, &prompter)
+            .await
+    }
+
     /// Make sure the migration directory exists when running migrations
     pub async fn run_up_pending_migrations(
         &self,
@@ -346,11 +447,22 @@ impl FileManagerBi {
         update_strategy: UpdateStrategy,
     ) -> MigrationResult<()> {
         let migrations = self.get_migrations()?;
-        MigrationRunner::apply_pending_migrations(db.clone(), migrations, update_strategy).await?;
+        MigrationRunner::apply_pending_migrations(db.clone(), self, migrations, update_strategy)
+            .await?;
 
         Ok(())
     }
 
+    /// The pending migrations that would be run, without running them
+    pub async fn plan_up_pending_migrations(
+        &self,
+        db: Surreal<impl Connection>,
+        update_strategy: UpdateStrategy,
+    ) -> MigrationResult<MigrationPlan> {
+        let migrations = self.get_migrations()?;
+        MigrationRunner::plan_pending_migrations(db, self, migrations, update_strategy).await
+    }
+
     /// For running embedded migrations
     pub async fn run_up_embedded_pending_migrations(
         &self,
@@ -359,7 +471,8 @@ impl FileManagerBi {
         update_strategy: UpdateStrategy,
     ) -> MigrationResult<()> {
         let migrations = two_way_embedded_migrations.to_migrations_two_way()?;
-        MigrationRunner::apply_pending_migrations(db.clone(), migrations, update_strategy).await?;
+        MigrationRunner::apply_pending_migrations(db.clone(), self, migrations, update_strategy)
+            .await?;
 
         Ok(())
     }
@@ -375,6 +488,34 @@ impl FileManagerBi {
         Ok(())
     }
 
+    /// The migrations that would be rolled back, without rolling them back
+    pub async fn plan_down_migrations(
+        &self,
+        db: Surreal<Any>,
+        rollback_options: RollbackOptions,
+    ) -> MigrationResult<MigrationPlan> {
+        MigrationRunner::plan_rollback(db, self, rollback_options).await
+    }
+
+    /// Squash applied migrations into a single baseline up and down migration pair
+    pub async fn squash_migrations(
+        &self,
+        db: Surreal<Any>,
+        squash_options: SquashOptions,
+    ) -> MigrationResult<MigrationFile> {
+        MigrationRunner::squash_migrations(db, self, squash_options).await
+    }
+
+    /// Writes the schema of an existing database as the first migration and
+    /// registers it as applied
+    pub async fn baseline_from_db(
+        &self,
+        db: Surreal<Any>,
+        migration_basename: &Basename,
+    ) -> MigrationResult<MigrationFile> {
+        MigrationRunner::baseline_from_db(db, self, migration_basename).await
+    }
+
     /// List all migrations
     pub async fn list_migrations(
         &self,
@@ -386,6 +527,12 @@ impl FileManagerBi {
             .get_migrations()?
             .into_iter()
             .map(|m| m.up.name)
+            .chain(
+                self.code_migrations
+                    .to_migrations(MigrationFlag::TwoWay)?
+                    .into_iter()
+                    .map(|m| m.name),
+            )
             .collect::<Vec<_>>();
 
         let migrations =
@@ -29,6 +29,10 @@ impl TryFrom<MigrationFileTwoWayPair> for Migration {
             timestamp: migration.up.name.timestamp(),
             checksum_up: migration.up.content.as_checksum()?,
             checksum_down: Some(migration.down.content.as_checksum()?),
+            applied_at: None,
+            duration_ms: None,
+            applied_by: None,
+            migrator_version: None,
         })
     }
 }
@@ -77,6 +81,10 @@ impl TryFrom<MigrationFileOneWay> for Migration {
             timestamp: migration.name.timestamp(),
             checksum_up: Checksum::generate_from_content(&migration.content)?,
             checksum_down: None,
+            applied_at: None,
+            duration_ms: None,
+            applied_by: None,
+            migrator_version: None,
         })
     }
 }
@@ -0,0 +1,219 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::{fmt::Display, fs, path::Path, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use surreal_query_builder::Table;
+use surrealdb::{engine::any::Any, sql::Thing, Connection, Surreal};
+
+use crate::*;
+
+/// Version of the migrator recorded with applied and failed migrations
+pub fn migrator_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// `user@host` of the process running the migrator
+pub fn current_operator() -> String {
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".into());
+    let host = std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| {
+            fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|host| host.trim().to_string())
+        })
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "unknown".into());
+
+    format!("{user}@{host}")
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationDirection {
+    Up,
+    Down,
+}
+
+impl Display for MigrationDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let direction = match self {
+            Self::Up => "up",
+            Self::Down => "down",
+        };
+        write!(f, "{direction}")
+    }
+}
+
+/// An attempt to apply or roll back migrations that aborted. File migrations
+/// are run together in a transaction, so all the migrations of the aborted
+/// transaction are recorded. A code migration is recorded on its own and may
+/// have been partially applied.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MigrationFailure {
+    pub id: Thing,
+    pub migrations: Vec<String>,
+    pub direction: MigrationDirection,
+    pub error: String,
+    pub failed_at: surrealdb::sql::Datetime,
+    pub duration_ms: u64,
+    /// `user@host` the migrations were run from
+    pub attempted_by: String,
+    pub migrator_version: String,
+}
+
+impl MigrationFailure {
+    pub fn table() -> Table {
+        Table::new("migration_failure")
+    }
+
+    pub async fn record(
+        db: Surreal<impl Connection>,
+        direction: MigrationDirection,
+        migrations: &[MigrationFilename],
+        error: &MigrationError,
+        duration: Duration,
+    ) -> MigrationResult<()> {
+        let migrations = migrations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        db.query(format!(
+            "CREATE {} SET migrations = $migrations, direction = $direction, error = $error, \
+            failed_at = time::now(), duration_ms = $duration_ms, attempted_by = $attempted_by, \
+            migrator_version = $migrator_version;",
+            Self::table()
+        ))
+        .bind(("migrations", migrations))
+        .bind(("direction", direction))
+        .bind(("error", error.to_string()))
+        .bind(("duration_ms", duration.as_millis() as u64))
+        .bind(("attempted_by", current_operator()))
+        .bind(("migrator_version", migrator_version()))
+        .await?
+        .check()?;
+        Ok(())
+    }
+
+    /// Records the failure and returns the error that caused it. Failing to
+    /// record it is only logged so that the original error is not lost.
+    pub(crate) async fn record_and_return<T>(
+        db: Surreal<impl Connection>,
+        direction: MigrationDirection,
+        migrations: &[MigrationFilename],
+        duration: Duration,
+        result: MigrationResult<T>,
+    ) -> MigrationResult<T> {
+        if let Err(error) = &result {
+            if let Err(e) = Self::record(db, direction, migrations, error, duration).await {
+                log::error!("Failed to record migration failure: {e}");
+            }
+        }
+        result
+    }
+
+    pub async fn get_all_desc(db: Surreal<Any>) -> MigrationResult<Vec<Self>> {
+        let failures: Vec<Self> = db
+            .query(format!(
+                "SELECT * FROM {} ORDER BY failed_at DESC;",
+                Self::table()
+            ))
+            .await?
+            .take(0)?;
+        Ok(failures)
+    }
+}
+
+/// The migrations applied to a database and the attempts that failed
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct MigrationHistory {
+    /// Oldest first
+    pub applied: Vec<Migration>,
+    /// Latest first
+    pub failures: Vec<MigrationFailure>,
+}
+
+impl MigrationHistory {
+    pub async fn get(db: Surreal<Any>) -> MigrationResult<Self> {
+        let mut applied = Migration::get_all_desc(db.clone()).await;
+        applied.reverse();
+        let failures = MigrationFailure::get_all_desc(db).await?;
+
+        Ok(Self { applied, failures })
+    }
+
+    pub fn to_json(&self) -> MigrationResult<String> {
+        serde_json::to_string_pretty(self).map_err(|e| MigrationError::IoError(e.to_string()))
+    }
+
+    /// Prints the history or writes it to the output file
+    pub fn output(&self, output: Option<&Path>, json: bool) -> MigrationResult<()> {
+        let history = if json {
+            self.to_json()?
+        } else {
+            self.to_string()
+        };
+
+        match output {
+            Some(path) => fs::write(path, history).map_err(|e| {
+                MigrationError::IoError(format!(
+                    "Failed to write migration history to {}. Error: {e}",
+                    path.to_string_lossy()
+                ))
+            }),
+            None => {
+                println!("{history}");
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Display for MigrationHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unknown = || "unknown".to_string();
+
+        write!(f, "{} applied migration(s)", self.applied.len())?;
+        for m in &self.applied {
+            write!(
+                f,
+                "\n  {}\n    applied at: {}\n    duration:   {}\n    applied by: {}\n    migrator:   {}",
+                m.name,
+                m.applied_at
+                    .as_ref()
+                    .map_or_else(unknown, ToString::to_string),
+                m.duration_ms
+                    .map_or_else(unknown, |duration| format!("{duration}ms")),
+                m.applied_by.clone().unwrap_or_else(unknown),
+                m.migrator_version.clone().unwrap_or_else(unknown),
+            )?;
+        }
+
+        if self.failures.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, "\n\n{} failed attempt(s)", self.failures.len())?;
+        for failure in &self.failures {
+            write!(
+                f,
+                "\n  {} {}\n    failed at:  {}\n    duration:   {}ms\n    applied by: {}\n    migrator:   {}\n    error:      {}",
+                failure.direction,
+                failure.migrations.join(", "),
+                failure.failed_at,
+                failure.duration_ms,
+                failure.attempted_by,
+                failure.migrator_version,
+                failure.error,
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,526 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::{collections::HashMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+use surreal_query_builder::{
+    sql::{
+        self,
+        statements::{DefineFieldStatement, DefineStatement, RemoveStatement},
+        Array, Cast, Expression, Function, Idiom, Kind, Object, Part, Statement, Subquery, Value,
+    },
+    Field, Table,
+};
+use surrealdb::{engine::any::Any, Surreal};
+
+use crate::*;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    Safe,
+    /// Keeps the data but may fail or change behaviour for existing records
+    Warning,
+    /// Loses data e.g removing a table or narrowing the type of a field
+    Destructive,
+}
+
+impl Display for LintSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self {
+            Self::Safe => "safe",
+            Self::Warning => "warning",
+            Self::Destructive => "destructive",
+        };
+        write!(f, "{severity}")
+    }
+}
+
+/// The classification of a statement of a generated migration
+#[derive(Serialize, Debug, Clone)]
+pub struct LintFinding {
+    pub severity: LintSeverity,
+    pub statement: String,
+    pub reason: String,
+    /// Records in the database that would violate the change, if it was checked
+    pub violations: Option<u64>,
+    /// Counts the records that would violate a new field type or assertion
+    #[serde(skip)]
+    violations_query: Option<String>,
+}
+
+impl Display for LintFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {}\n  {}",
+            self.severity, self.reason, self.statement
+        )?;
+        if let Some(violations) = self.violations {
+            write!(f, "\n  {violations} existing record(s) would violate it")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct Count {
+    count: u64,
+}
+
+/// Classifies the up statements of a generated migration as safe, warning or
+/// destructive so that destructive migrations are not written by accident.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct MigrationLint {
+    pub findings: Vec<LintFinding>,
+}
+
+impl MigrationLint {
+    /// Left is the schema of the migration directory the statements are applied to
+    pub(crate) fn lint(up_queries: &[QueryType], left: &FullDbInfo) -> Self {
+        let mut findings = vec![];
+        // Renames copy the data before removing the old table or field
+        let mut in_rename = false;
+        // Links to the renamed tables are rewritten to their new tables
+        let renamed_tables = up_queries
+            .iter()
+            .filter_map(|query| match query {
+                QueryType::RenameStart(Rename::Table { old, new }) => {
+                    Some((old.to_string(), new.to_string()))
+                }
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        for query in up_queries {
+            let finding = match query {
+                QueryType::RenameStart(_) => {
+                    in_rename = true;
+                    continue;
+                }
+                QueryType::RenameEnd(_) => {
+                    in_rename = false;
+                    continue;
+                }
+                QueryType::Comment(_) | QueryType::NewLine => continue,
+                QueryType::DeleteAll(_) => {
+                    LintFinding::new(LintSeverity::Safe, query, "Resets the migration table")
+                }
+                QueryType::Update(_) => LintFinding::new(
+                    LintSeverity::Warning,
+                    query,
+                    "Rewrites every record of the table",
+                ),
+                QueryType::Insert(_) => LintFinding::new(
+                    LintSeverity::Safe,
+                    query,
+                    "Copies the records of the old table",
+                ),
+                QueryType::Remove(_) => Self::lint_remove(query, in_rename),
+                QueryType::Define(_) => Self::lint_define(query, left, in_rename, &renamed_tables),
+            };
+            findings.push(finding);
+        }
+
+        Self { findings }
+    }
+
+    fn lint_remove(query: &QueryType, in_rename: bool) -> LintFinding {
+        let data_loss_severity = if in_rename {
+            LintSeverity::Warning
+        } else {
+            LintSeverity::Destructive
+        };
+
+        match parse_statement(query) {
+            Some(Statement::Remove(RemoveStatement::Table(_))) => LintFinding::new(
+                data_loss_severity,
+                query,
+                "Removes the table and all its records",
+            ),
+            Some(Statement::Remove(RemoveStatement::Field(_))) => LintFinding::new(
+                data_loss_severity,
+                query,
+                "Removes the field and its value from every record",
+            ),
+            _ => LintFinding::new(
+                LintSeverity::Warning,
+                query,
+                "Removes a definition the database may depend on",
+            ),
+        }
+    }
+
+    fn lint_define(
+        query: &QueryType,
+        left: &FullDbInfo,
+        in_rename: bool,
+        renamed_tables: &HashMap<String, String>,
+    ) -> LintFinding {
+        match parse_statement(query) {
+            Some(Statement::Define(DefineStatement::Field(field))) => {
+                Self::lint_define_field(query, &field, left, in_rename, renamed_tables)
+            }
+            Some(Statement::Define(DefineStatement::Table(table))) => {
+                let was_schemaless = left
+                    .tables()
+                    .get_definition(table.name.to_raw())
+                    .and_then(|def| parse_statement(&QueryType::Define(def.clone())))
+                    .map_or(false, |old| {
+                        matches!(old, Statement::Define(DefineStatement::Table(old)) if !old.full)
+                    });
+
+                if table.full && was_schemaless {
+                    LintFinding::new(
+                        LintSeverity::Warning,
+                        query,
+                        "Makes the table schemafull. Undefined fields are dropped when a record is next written",
+                    )
+                } else {
+                    LintFinding::new(LintSeverity::Safe, query, "Defines the table")
+                }
+            }
+            _ => LintFinding::new(LintSeverity::Safe, query, "Defines a resource"),
+        }
+    }
+
+    fn lint_define_field(
+        query: &QueryType,
+        field: &DefineFieldStatement,
+        left: &FullDbInfo,
+        in_rename: bool,
+        renamed_tables: &HashMap<String, String>,
+    ) -> LintFinding {
+        let table = Table::new(field.what.to_raw());
+        let field_name = field.name.to_string();
+        let old_field = left
+            .get_field_def(table.clone(), Field::new(&field_name))
+            .and_then(|def| match parse_statement(&QueryType::Define(def)) {
+                Some(Statement::Define(DefineStatement::Field(mut old))) => {
+                    old.kind = old
+                        .kind
+                        .map(|kind| with_renamed_tables(kind, renamed_tables));
+                    Some(old)
+                }
+                _ => None,
+            });
+        // The renamed field is populated from the old field in the same migration
+        let table_exists = !in_rename && left.get_tables().contains(&table);
+
+        let type_violations = || {
+            type_check(field.kind.as_ref(), &field_name).map(|check| count_query(&table, &check))
+        };
+        let assert_violations = || {
+            // Nested idioms e.g tags[*] assert each element, not the value of the record
+            if field_name.contains(['[', '*']) {
+                return None;
+            }
+            let check = bind_value(field.assert.clone()?, &field.name)?;
+            Some(count_query(&table, &check.to_string()))
+        };
+
+        let (severity, reason, violations_query) = match old_field {
+            Some(old) if !widens(old.kind.as_ref(), field.kind.as_ref()) => (
+                LintSeverity::Destructive,
+                format!(
+                    "Narrows the type of the field from {} to {}",
+                    kind_name(old.kind.as_ref()),
+                    kind_name(field.kind.as_ref())
+                ),
+                type_violations(),
+            ),
+            Some(old) if field.assert.is_some() && old.assert != field.assert => (
+                LintSeverity::Warning,
+                "Adds an assertion existing records may violate".to_string(),
+                assert_violations(),
+            ),
+            Some(_) => (LintSeverity::Safe, "Redefines the field".to_string(), None),
+            None if table_exists && is_required(field) => (
+                LintSeverity::Warning,
+                "Adds a required field existing records do not have".to_string(),
+                type_violations(),
+            ),
+            None if table_exists && field.assert.is_some() => (
+                LintSeverity::Warning,
+                "Adds a field with an assertion existing records may violate".to_string(),
+                assert_violations(),
+            ),
+            None => (LintSeverity::Safe, "Defines the field".to_string(), None),
+        };
+
+        LintFinding {
+            violations_query,
+            ..LintFinding::new(severity, query, reason)
+        }
+    }
+
+    pub fn has_destructive(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|finding| finding.severity == LintSeverity::Destructive)
+    }
+
+    pub fn destructive(&self) -> impl Iterator<Item = &LintFinding> {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity == LintSeverity::Destructive)
+    }
+
+    /// Counts the records of the database, usually the one the migration will be
+    /// applied to, that would violate new field types and assertions.
+    pub async fn count_violations(&mut self, db: Surreal<Any>) -> MigrationResult<()> {
+        for finding in self.findings.iter_mut() {
+            let Some(query) = &finding.violations_query else {
+                continue;
+            };
+            let count: Option<Count> = match db.query(query.as_str()).await?.take(0) {
+                Ok(count) => count,
+                Err(e) => {
+                    log::warn!("Failed to count violations with {query}. Error: {e}");
+                    continue;
+                }
+            };
+            finding.violations = Some(count.map_or(0, |count| count.count));
+        }
+        Ok(())
+    }
+}
+
+impl Display for MigrationLint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let count = |severity| {
+            self.findings
+                .iter()
+                .filter(|finding| finding.severity == severity)
+                .count()
+        };
+        write!(
+            f,
+            "{} destructive, {} warning(s), {} safe change(s)",
+            count(LintSeverity::Destructive),
+            count(LintSeverity::Warning),
+            count(LintSeverity::Safe)
+        )?;
+        for finding in self
+            .findings
+            .iter()
+            .filter(|finding| finding.severity != LintSeverity::Safe)
+        {
+            write!(f, "\n\n{finding}")?;
+        }
+        Ok(())
+    }
+}
+
+impl LintFinding {
+    fn new(severity: LintSeverity, query: &QueryType, reason: impl Into<String>) -> Self {
+        Self {
+            severity,
+            statement: query.to_string(),
+            reason: reason.into(),
+            violations: None,
+            violations_query: None,
+        }
+    }
+}
+
+fn parse_statement(query: &QueryType) -> Option<Statement> {
+    sql::parse(&query.to_string())
+        .ok()
+        .and_then(|statements| statements.first().cloned())
+}
+
+fn count_query(table: &Table, check: &str) -> String {
+    format!("SELECT count() AS count FROM {table} WHERE !({check}) GROUP ALL;")
+}
+
+fn kind_name(kind: Option<&Kind>) -> String {
+    kind.map_or("any".to_string(), ToString::to_string)
+}
+
+/// Whether every value of the old type is also a value of the new type
+fn widens(old: Option<&Kind>, new: Option<&Kind>) -> bool {
+    match (old, new) {
+        (_, None) | (_, Some(Kind::Any)) => true,
+        (None, Some(_)) | (Some(Kind::Any), Some(_)) => false,
+        (Some(old), Some(new)) => kind_widens(old, new),
+    }
+}
+
+/// The kind with its record links pointing to the new names of the renamed tables
+fn with_renamed_tables(kind: Kind, renamed_tables: &HashMap<String, String>) -> Kind {
+    let rename = |kind: Box<Kind>| Box::new(with_renamed_tables(*kind, renamed_tables));
+    match kind {
+        Kind::Record(tables) => Kind::Record(
+            tables
+                .into_iter()
+                .map(|table| {
+                    renamed_tables
+                        .get(&table.0)
+                        .map_or(table, |new| sql::Table(new.to_string()))
+                })
+                .collect(),
+        ),
+        Kind::Option(kind) => Kind::Option(rename(kind)),
+        Kind::Either(kinds) => Kind::Either(
+            kinds
+                .into_iter()
+                .map(|kind| with_renamed_tables(kind, renamed_tables))
+                .collect(),
+        ),
+        Kind::Array(kind, max) => Kind::Array(rename(kind), max),
+        Kind::Set(kind, max) => Kind::Set(rename(kind), max),
+        kind => kind,
+    }
+}
+
+fn kind_widens(old: &Kind, new: &Kind) -> bool {
+    if old == new {
+        return true;
+    }
+
+    match (old, new) {
+        (_, Kind::Any) => true,
+        (Kind::Option(old), Kind::Option(new)) => kind_widens(old, new),
+        (Kind::Option(_), _) => false,
+        (old, Kind::Option(new)) => kind_widens(old, new),
+        (Kind::Either(olds), new) => olds.iter().all(|old| kind_widens(old, new)),
+        (old, Kind::Either(news)) => news.iter().any(|new| kind_widens(old, new)),
+        (Kind::Int | Kind::Float | Kind::Decimal, Kind::Number) => true,
+        (Kind::Array(old, old_max), Kind::Array(new, new_max))
+        | (Kind::Set(old, old_max), Kind::Set(new, new_max)) => {
+            kind_widens(old, new)
+                && new_max.map_or(true, |new_max| {
+                    old_max.map_or(false, |old_max| old_max <= new_max)
+                })
+        }
+        (Kind::Record(old_tables), Kind::Record(new_tables)) => {
+            new_tables.is_empty()
+                || (!old_tables.is_empty() && old_tables.iter().all(|t| new_tables.contains(t)))
+        }
+        _ => false,
+    }
+}
+
+/// The assertion with `$value` replaced by the field, so that it can be checked
+/// against every record of the table. None when `$value` is used where it
+/// cannot be replaced e.g in a subquery or a block.
+fn bind_value(value: Value, field: &Idiom) -> Option<Value> {
+    let bind = |value: Value| bind_value(value, field);
+    let bind_all = |values: Vec<Value>| values.into_iter().map(bind).collect::<Option<Vec<_>>>();
+
+    let bound = match value {
+        Value::Param(param) if param.as_str() == "value" => Value::Idiom(field.clone()),
+        Value::Idiom(Idiom(parts)) => {
+            let mut bound = Vec::with_capacity(parts.len());
+            for part in parts {
+                match part {
+                    Part::Start(Value::Param(param)) if param.as_str() == "value" => {
+                        bound.extend(field.0.iter().cloned())
+                    }
+                    Part::Start(value) => bound.push(Part::Start(bind(value)?)),
+                    Part::Where(value) => bound.push(Part::Where(bind(value)?)),
+                    Part::Value(value) => bound.push(Part::Value(bind(value)?)),
+                    Part::Method(name, args) => bound.push(Part::Method(name, bind_all(args)?)),
+                    part => bound.push(part),
+                }
+            }
+            Value::Idiom(Idiom(bound))
+        }
+        Value::Expression(expression) => Value::Expression(Box::new(match *expression {
+            Expression::Unary { o, v } => Expression::Unary { o, v: bind(v)? },
+            Expression::Binary { l, o, r } => Expression::Binary {
+                l: bind(l)?,
+                o,
+                r: bind(r)?,
+            },
+        })),
+        Value::Function(function) => Value::Function(Box::new(match *function {
+            Function::Normal(name, args) => Function::Normal(name, bind_all(args)?),
+            Function::Custom(name, args) => Function::Custom(name, bind_all(args)?),
+            Function::Script(..) => return None,
+        })),
+        Value::Cast(cast) => {
+            let Cast(kind, value) = *cast;
+            Value::Cast(Box::new(Cast(kind, bind(value)?)))
+        }
+        Value::Array(Array(values)) => Value::Array(Array(bind_all(values)?)),
+        Value::Object(Object(entries)) => Value::Object(Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| Some((key, bind(value)?)))
+                .collect::<Option<_>>()?,
+        )),
+        Value::Subquery(subquery) => match *subquery {
+            Subquery::Value(value) => Value::Subquery(Box::new(Subquery::Value(bind(value)?))),
+            _ => return None,
+        },
+        value @ (Value::None
+        | Value::Null
+        | Value::Bool(_)
+        | Value::Number(_)
+        | Value::Strand(_)
+        | Value::Duration(_)
+        | Value::Datetime(_)
+        | Value::Uuid(_)
+        | Value::Geometry(_)
+        | Value::Bytes(_)
+        | Value::Thing(_)
+        | Value::Param(_)
+        | Value::Table(_)
+        | Value::Regex(_)
+        | Value::Constant(_)) => value,
+        _ => return None,
+    };
+    Some(bound)
+}
+
+/// A field existing records without a value for would violate
+fn is_required(field: &DefineFieldStatement) -> bool {
+    let optional = matches!(field.kind, None | Some(Kind::Any) | Some(Kind::Option(_)));
+    !optional && field.default.is_none() && field.value.is_none()
+}
+
+/// SurrealQL condition that the value is of the kind
+fn type_check(kind: Option<&Kind>, value: &str) -> Option<String> {
+    // Nested idioms e.g tags[*] cannot be checked with a simple condition
+    if value.contains(['[', '*']) {
+        return None;
+    }
+
+    let check = match kind? {
+        Kind::Any => return None,
+        Kind::Option(kind) => {
+            format!(
+                "type::is::none({value}) OR {}",
+                type_check(Some(kind), value)?
+            )
+        }
+        Kind::Either(kinds) => kinds
+            .iter()
+            .map(|kind| type_check(Some(kind), value))
+            .collect::<Option<Vec<_>>>()?
+            .join(" OR "),
+        Kind::Array(..) | Kind::Set(..) => format!("type::is::array({value})"),
+        Kind::Record(_) => format!("type::is::record({value})"),
+        Kind::Geometry(_) => format!("type::is::geometry({value})"),
+        kind @ (Kind::Bool
+        | Kind::Bytes
+        | Kind::Datetime
+        | Kind::Decimal
+        | Kind::Duration
+        | Kind::Float
+        | Kind::Int
+        | Kind::Number
+        | Kind::Object
+        | Kind::Point
+        | Kind::String
+        | Kind::Uuid) => format!("type::is::{kind}({value})"),
+        _ => return None,
+    };
+
+    Some(check)
+}
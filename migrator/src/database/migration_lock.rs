@@ -0,0 +1,242 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::{
+    future::Future,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use surreal_query_builder::Table;
+use surrealdb::{engine::any::Any, sql::Thing, Connection, Surreal};
+use tokio::{sync::oneshot, task::JoinHandle};
+
+use crate::*;
+
+static NEXT_HOLDER_ID: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MigrationLockRecord {
+    pub id: Thing,
+    pub holder: String,
+    pub expires_at: surrealdb::sql::Datetime,
+}
+
+/// A lease on the `migration_lock:singleton` record so that only one migrator
+/// applies or rolls back migrations at a time. The lease expires unless it is
+/// renewed, so a migrator that is killed only blocks the others until then.
+pub struct MigrationLock<C: Connection = Any> {
+    db: Surreal<C>,
+    holder: String,
+    renewal: JoinHandle<()>,
+    lease_lost: oneshot::Receiver<()>,
+}
+
+impl MigrationLock {
+    /// Default time to wait for another migrator to release the lock
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+    /// How long the lock is held without being renewed
+    pub const LEASE: Duration = Duration::from_secs(30);
+    const RETRY_INTERVAL: Duration = Duration::from_millis(200);
+
+    pub fn table() -> Table {
+        Table::new("migration_lock")
+    }
+
+    pub fn id() -> Thing {
+        Thing {
+            tb: Self::table().to_string(),
+            id: "singleton".into(),
+        }
+    }
+
+    /// Deletes the lock whoever holds it e.g after a migrator was killed
+    pub async fn force_unlock(
+        db: Surreal<impl Connection>,
+    ) -> MigrationResult<Option<MigrationLockRecord>> {
+        let lock: Option<MigrationLockRecord> = db
+            .query(format!("DELETE {} RETURN BEFORE;", Self::id()))
+            .await?
+            .take(0)?;
+        Ok(lock)
+    }
+
+    pub async fn get(db: Surreal<impl Connection>) -> MigrationResult<Option<MigrationLockRecord>> {
+        let lock: Option<MigrationLockRecord> = db
+            .query(format!("SELECT * FROM {};", Self::id()))
+            .await?
+            .take(0)?;
+        Ok(lock)
+    }
+}
+
+impl<C: Connection> MigrationLock<C> {
+    pub fn holder(&self) -> &str {
+        &self.holder
+    }
+
+    /// Waits for the lock until the timeout and renews it in the background
+    /// until it is released.
+    pub async fn acquire(db: Surreal<C>, timeout: Duration) -> MigrationResult<Self> {
+        Self::acquire_with_lease(db, timeout, MigrationLock::LEASE).await
+    }
+
+    /// Same as `acquire` with a lease other than the default
+    pub async fn acquire_with_lease(
+        db: Surreal<C>,
+        timeout: Duration,
+        lease: Duration,
+    ) -> MigrationResult<Self> {
+        let holder = format!(
+            "{}-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_millis(),
+            NEXT_HOLDER_ID.fetch_add(1, Ordering::Relaxed)
+        );
+        let started = Instant::now();
+
+        while !Self::try_acquire(db.clone(), &holder, lease).await? {
+            if started.elapsed() >= timeout {
+                let current_holder = MigrationLock::get(db.clone())
+                    .await?
+                    .map(|lock| lock.holder);
+                return Err(MigrationError::MigrationLockTimeout {
+                    holder: current_holder.unwrap_or_default(),
+                    timeout_secs: timeout.as_secs(),
+                });
+            }
+            tokio::time::sleep(MigrationLock::RETRY_INTERVAL).await;
+        }
+        log::info!("Acquired migration lock as {holder}");
+
+        let (lease_lost_sender, lease_lost) = oneshot::channel();
+        let renewal = tokio::spawn({
+            let db = db.clone();
+            let holder = holder.clone();
+            async move {
+                let mut renewed_at = Instant::now();
+                loop {
+                    tokio::time::sleep(lease / 3).await;
+                    match Self::renew(db.clone(), &holder, lease).await {
+                        Ok(true) => renewed_at = Instant::now(),
+                        Ok(false) => {
+                            log::error!("Migration lock held by {holder} was taken over");
+                            break;
+                        }
+                        Err(e) => {
+                            log::error!("Failed to renew migration lock: {e}");
+                            if renewed_at.elapsed() >= lease {
+                                break;
+                            }
+                        }
+                    }
+                }
+                let _ = lease_lost_sender.send(());
+            }
+        });
+
+        Ok(Self {
+            db,
+            holder,
+            renewal,
+            lease_lost,
+        })
+    }
+
+    /// Takes the lock if it is free or its lease has expired
+    async fn try_acquire(db: Surreal<C>, holder: &str, lease: Duration) -> MigrationResult<bool> {
+        let lock_id = MigrationLock::id();
+        let lease = lease.as_millis();
+        let mut response = db
+            .query(format!(
+                "BEGIN TRANSACTION;\n\
+                DELETE {lock_id} WHERE expires_at < time::now();\n\
+                CREATE {lock_id} SET holder = $holder, expires_at = time::now() + {lease}ms;\n\
+                COMMIT TRANSACTION;"
+            ))
+            .bind(("holder", holder))
+            .await?;
+
+        let mut errors = response.take_errors().into_iter().collect::<Vec<_>>();
+        errors.sort_by_key(|(index, _)| *index);
+        // The create fails, failing the transaction, when another migrator holds the lock
+        let is_held = errors.iter().any(|(_, e)| {
+            matches!(
+                e,
+                surrealdb::Error::Db(surrealdb::error::Db::RecordExists { .. })
+            )
+        });
+        if is_held {
+            return Ok(false);
+        }
+
+        // The other statements of the failed transaction are not executed
+        let cause = errors.into_iter().map(|(_, e)| e).find(|e| {
+            !matches!(
+                e,
+                surrealdb::Error::Db(surrealdb::error::Db::QueryNotExecuted)
+            )
+        });
+        match cause {
+            Some(e) => Err(e.into()),
+            None => Ok(true),
+        }
+    }
+
+    /// Extends the lease, returning false when the lock is no longer held
+    async fn renew(db: Surreal<C>, holder: &str, lease: Duration) -> MigrationResult<bool> {
+        let lock_id = MigrationLock::id();
+        let lease = lease.as_millis();
+        let renewed: Option<MigrationLockRecord> = db
+            .query(format!(
+                "UPDATE {lock_id} SET expires_at = time::now() + {lease}ms WHERE holder = $holder;"
+            ))
+            .bind(("holder", holder))
+            .await?
+            .take(0)?;
+        Ok(renewed.is_some())
+    }
+
+    /// Runs the migration while holding the lock and releases it after. The
+    /// migration is aborted when the lease is lost e.g when the lock could not be
+    /// renewed before it expired. Failing to release the lock is only logged so
+    /// that the result of the migration is returned.
+    pub async fn run<T>(
+        mut self,
+        migration: impl Future<Output = MigrationResult<T>>,
+    ) -> MigrationResult<T> {
+        let result = tokio::select! {
+            result = migration => result,
+            Ok(()) = &mut self.lease_lost => Err(MigrationError::MigrationLockLost {
+                holder: self.holder.clone(),
+            }),
+        };
+
+        if let Err(e) = self.release().await {
+            log::error!("Failed to release migration lock: {e}");
+        }
+
+        result
+    }
+
+    pub async fn release(self) -> MigrationResult<()> {
+        self.renewal.abort();
+        let lock_id = MigrationLock::id();
+        self.db
+            .query(format!("DELETE {lock_id} WHERE holder = $holder;"))
+            .bind(("holder", self.holder.as_str()))
+            .await?
+            .check()?;
+        log::info!("Released migration lock held by {}", self.holder);
+        Ok(())
+    }
+}
+
+impl<C: Connection> Drop for MigrationLock<C> {
+    fn drop(&mut self) {
+        self.renewal.abort();
+    }
+}
@@ -3,7 +3,7 @@
  * Email: oyelowo.oss@gmail.com
  * Copyright (c) 2023 Oyelowo Oyedayo
  */
-use std::fmt::Display;
+use std::{fmt::Display, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use surreal_query_builder::{
@@ -13,7 +13,7 @@ use surreal_query_builder::{
     },
     All, Field, FieldType, Operatable, Raw, ReturnableSelect, Table, ToRaw,
 };
-use surrealdb::{engine::any::Any, sql::Thing, Surreal};
+use surrealdb::{engine::any::Any, sql::Thing, Connection, Surreal};
 
 use crate::*;
 
@@ -28,8 +28,13 @@ pub struct Migration {
     pub timestamp: Timestamp,
     pub checksum_up: Checksum,
     pub checksum_down: Option<Checksum>,
-    // pub timestamp: DateTime<Utc>,
-    // status: String,
+    /// Not recorded for migrations applied by migrators older than the history fields
+    pub applied_at: Option<surrealdb::sql::Datetime>,
+    /// Time taken by the transaction or code migration that applied it
+    pub duration_ms: Option<u64>,
+    /// `user@host` the migration was applied from
+    pub applied_by: Option<String>,
+    pub migrator_version: Option<String>,
 }
 
 impl Ord for Migration {
@@ -86,6 +91,10 @@ pub struct MigrationSchema {
     pub timestamp: Field,
     pub checksum_up: Field,
     pub checksum_down: Field,
+    pub applied_at: Field,
+    pub duration_ms: Field,
+    pub applied_by: Field,
+    pub migrator_version: Field,
 }
 
 impl Migration {
@@ -106,7 +115,10 @@ impl Migration {
             .query(Self::remove_table())
             .query(Self::define_table());
 
-        for def in Self::define_fields(migration_type) {
+        for def in Self::define_fields(migration_type)
+            .into_iter()
+            .chain(Self::define_history_fields())
+        {
             tx = tx.query(def);
         }
 
@@ -126,6 +138,10 @@ impl Migration {
             timestamp: timestamp_field,
             checksum_up: checksum_up_field,
             checksum_down: checksum_down_field,
+            applied_at: applied_at_field,
+            applied_by: applied_by_field,
+            migrator_version: migrator_version_field,
+            ..
         } = Self::schema();
 
         let record_id = Self::create_id(filename);
@@ -135,10 +151,29 @@ impl Migration {
         let checksum_down = checksum_down
             .map(|c| c.to_string())
             .unwrap_or("null".into());
+        let applied_by = surrealdb::sql::Value::from(current_operator());
+        let migrator_version = surrealdb::sql::Value::from(migrator_version());
 
         Raw::new(format!(
             "CREATE {record_id} SET {name_field}='{name}', {timestamp_field}={timestamp}, \
-        {checksum_up_field}='{checksum_up}', {checksum_down_field}='{checksum_down}';"
+        {checksum_up_field}='{checksum_up}', {checksum_down_field}='{checksum_down}', \
+        {applied_at_field}=time::now(), {applied_by_field}={applied_by}, \
+        {migrator_version_field}={migrator_version};"
+        ))
+    }
+
+    /// Records how long applying the migrations took after they have been registered
+    pub fn set_duration_raw(migration_ids: &[Thing], duration: Duration) -> Raw {
+        let migration::Schema { duration_ms, .. } = Self::schema();
+        let ids = migration_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Raw::new(format!(
+            "UPDATE {ids} SET {duration_ms}={};",
+            duration.as_millis()
         ))
     }
 
@@ -154,6 +189,10 @@ impl Migration {
             timestamp: Field::new("timestamp"),
             checksum_up: Field::new("checksum_up"),
             checksum_down: Field::new("checksum_down"),
+            applied_at: Field::new("applied_at"),
+            duration_ms: Field::new("duration_ms"),
+            applied_by: Field::new("applied_by"),
+            migrator_version: Field::new("migrator_version"),
         }
     }
 
@@ -180,6 +219,7 @@ impl Migration {
             timestamp,
             checksum_up,
             checksum_down,
+            ..
         } = Migration::schema();
         let id = define_field(id)
             .type_(FieldType::Record(vec![Self::table()]))
@@ -215,6 +255,46 @@ impl Migration {
         fields
     }
 
+    /// Definitions of the fields added to record when, how and by whom a migration
+    /// was applied. They are optional so that rows registered before are still valid
+    /// and are not part of `define_fields` so that they do not show up in generated
+    /// migrations. They are defined by the migrator itself when it runs.
+    pub fn define_history_fields() -> Vec<Raw> {
+        let migration::Schema {
+            applied_at,
+            duration_ms,
+            applied_by,
+            migrator_version,
+            ..
+        } = Migration::schema();
+
+        [
+            (applied_at, FieldType::Datetime),
+            (duration_ms, FieldType::Int),
+            (applied_by, FieldType::String),
+            (migrator_version, FieldType::String),
+        ]
+        .into_iter()
+        .map(|(field, field_type)| {
+            define_field(field)
+                .type_(FieldType::Option(Box::new(field_type)))
+                .on_table(Self::table())
+                .to_raw()
+        })
+        .collect()
+    }
+
+    /// Upgrades the definition of the migration table of databases migrated by
+    /// an older migrator. Defining the fields again is a no-op.
+    pub async fn upgrade_table(db: Surreal<impl Connection>) -> MigrationResult<()> {
+        let mut tx = begin_transaction();
+        for def in Self::define_history_fields() {
+            tx = tx.query(def);
+        }
+        tx.commit_transaction().run(db).await?;
+        Ok(())
+    }
+
     pub async fn get_latest(db: Surreal<Any>) -> Option<Self> {
         let migration::Schema { timestamp, .. } = Self::schema();
 
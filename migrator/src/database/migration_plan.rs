@@ -0,0 +1,154 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+use std::{fmt::Display, fs, path::Path};
+
+use serde::Serialize;
+
+use crate::*;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanAction {
+    Apply,
+    Rollback,
+    Generate,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlannedMigrationKind {
+    File,
+    Code,
+}
+
+/// A migration in a plan with the statements that would run for it,
+/// including the ones registering it in or removing it from the migration table.
+#[derive(Serialize, Debug, Clone)]
+pub struct PlannedMigration {
+    pub name: String,
+    pub kind: PlannedMigrationKind,
+    pub statements: String,
+}
+
+/// The ordered migrations that `up` or `down` would apply or roll back, or
+/// the files that `generate` would write, without touching the database or
+/// migration directory.
+#[derive(Serialize, Debug, Clone)]
+pub struct MigrationPlan {
+    pub action: PlanAction,
+    pub migrations: Vec<PlannedMigration>,
+    /// The classification of the up statements of a generated migration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lint: Option<MigrationLint>,
+}
+
+impl MigrationPlan {
+    pub fn new(action: PlanAction) -> Self {
+        Self {
+            action,
+            migrations: vec![],
+            lint: None,
+        }
+    }
+
+    pub fn with_lint(mut self, lint: MigrationLint) -> Self {
+        self.lint = Some(lint);
+        self
+    }
+
+    pub fn push(
+        &mut self,
+        name: impl Display,
+        kind: PlannedMigrationKind,
+        statements: impl Display,
+    ) {
+        self.migrations.push(PlannedMigration {
+            name: name.to_string(),
+            kind,
+            statements: statements.to_string(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.migrations.is_empty()
+    }
+
+    pub fn to_json(&self) -> MigrationResult<String> {
+        serde_json::to_string_pretty(self).map_err(|e| MigrationError::IoError(e.to_string()))
+    }
+
+    /// Prints the plan or writes it to the output file
+    pub fn output(&self, output: Option<&Path>, json: bool) -> MigrationResult<()> {
+        let plan = if json {
+            self.to_json()?
+        } else {
+            self.to_string()
+        };
+
+        match output {
+            Some(path) => fs::write(path, plan).map_err(|e| {
+                MigrationError::IoError(format!(
+                    "Failed to write migration plan to {}. Error: {e}",
+                    path.to_string_lossy()
+                ))
+            }),
+            None => {
+                println!("{plan}");
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Display for MigrationPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let action = match self.action {
+            PlanAction::Apply => "apply",
+            PlanAction::Rollback => "roll back",
+            PlanAction::Generate => "generate",
+        };
+
+        if self.migrations.is_empty() {
+            return write!(f, "-- No migrations to {action}");
+        }
+
+        writeln!(f, "-- {} migration(s) to {action}", self.migrations.len())?;
+        for migration in &self.migrations {
+            let kind = match migration.kind {
+                PlannedMigrationKind::File => "file",
+                PlannedMigrationKind::Code => "code",
+            };
+            write!(
+                f,
+                "\n-- {kind}: {}\n{}\n",
+                migration.name, migration.statements
+            )?;
+        }
+        if let Some(lint) = &self.lint {
+            writeln!(f)?;
+            for line in format!("Lint: {lint}").lines() {
+                writeln!(f, "{}", format!("-- {line}").trim_end())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&MigrationFile> for MigrationPlan {
+    fn from(migration_file: &MigrationFile) -> Self {
+        let mut plan = Self::new(PlanAction::Generate);
+        match migration_file {
+            MigrationFile::OneWay(m) => {
+                plan.push(m.name(), PlannedMigrationKind::File, m.content());
+            }
+            MigrationFile::TwoWay(m) => {
+                plan.push(&m.up.name, PlannedMigrationKind::File, &m.up.content);
+                plan.push(&m.down.name, PlannedMigrationKind::File, &m.down.content);
+            }
+        }
+        plan
+    }
+}
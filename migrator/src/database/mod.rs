@@ -5,9 +5,11 @@
  */
 
 mod checksum;
+mod code_migration;
 mod db_left_mig_dir;
 mod db_right_codebase;
 mod db_runner;
+mod drift;
 mod embedded_migration;
 mod file_content;
 mod file_generator;
@@ -15,15 +17,21 @@ mod file_manager;
 mod file_metadata;
 mod file_name;
 mod full_db_info;
+mod migration_history;
+mod migration_lint;
+mod migration_lock;
 mod migration_model;
+mod migration_plan;
 mod prompter;
 mod queries;
 mod settings;
 
 pub use checksum::*;
+pub use code_migration::*;
 pub use db_left_mig_dir::*;
 pub use db_right_codebase::*;
 pub use db_runner::*;
+pub use drift::*;
 pub use embedded_migration::*;
 pub use file_content::*;
 pub use file_generator::*;
@@ -31,7 +39,11 @@ pub use file_manager::*;
 pub use file_metadata::*;
 pub use file_name::*;
 pub use full_db_info::*;
+pub use migration_history::*;
+pub use migration_lint::*;
+pub use migration_lock::*;
 pub use migration_model::*;
+pub use migration_plan::*;
 pub use prompter::*;
 pub use queries::*;
 pub use settings::*;
@@ -24,9 +24,29 @@ pub enum QueryType {
     Define(DefineStatementRaw),
     Remove(RemoveStatementRaw),
     Update(UpdateStatementRaw),
+    Insert(InsertStatementRaw),
     DeleteAll(Raw),
     NewLine,
     Comment(String),
+    /// Starts the statements performing a rename. Written as a comment.
+    RenameStart(Rename),
+    /// Ends the statements performing a rename. Written as a comment.
+    RenameEnd(Rename),
+}
+
+/// A table or field rename, marking the statements that copy the data to the new
+/// name before the old one is removed.
+#[derive(Debug, Clone)]
+pub enum Rename {
+    Table {
+        old: Table,
+        new: Table,
+    },
+    Field {
+        table: Table,
+        old: Field,
+        new: Field,
+    },
 }
 
 impl Display for QueryType {
@@ -35,11 +55,24 @@ impl Display for QueryType {
             QueryType::Define(def) => def.to_string(),
             QueryType::Remove(rem) => rem.to_string(),
             QueryType::Update(upd) => upd.to_string(),
+            QueryType::Insert(ins) => ins.to_string(),
             QueryType::DeleteAll(del) => del.build(),
             QueryType::NewLine => "\n".to_string(),
             QueryType::Comment(comment) => format!("-- {comment}"),
+            QueryType::RenameStart(Rename::Table { old, new }) => {
+                format!("-- Rename table {old} to {new}")
+            }
+            QueryType::RenameStart(Rename::Field { old, new, .. }) => {
+                format!("-- Rename field {old} to {new}")
+            }
+            QueryType::RenameEnd(Rename::Table { .. }) => "-- Rename table ending".to_string(),
+            QueryType::RenameEnd(Rename::Field { .. }) => "-- Rename field ending".to_string(),
         };
-        let end = if let QueryType::NewLine | QueryType::Comment(_) = self {
+        let end = if let QueryType::NewLine
+        | QueryType::Comment(_)
+        | QueryType::RenameStart(_)
+        | QueryType::RenameEnd(_) = self
+        {
             ""
         } else {
             ";"
@@ -48,6 +81,23 @@ impl Display for QueryType {
     }
 }
 
+impl QueryType {
+    /// The readable form of the query written to migration files.
+    pub(crate) fn to_pretty_string(&self) -> String {
+        match self {
+            QueryType::NewLine
+            | QueryType::Comment(_)
+            | QueryType::RenameStart(_)
+            | QueryType::RenameEnd(_) => self.to_string(),
+            QueryType::Define(_)
+            | QueryType::Remove(_)
+            | QueryType::Update(_)
+            | QueryType::Insert(_)
+            | QueryType::DeleteAll(_) => format_surql(&self.to_string(), &PrettyOptions::default()),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct Queries {
     pub(crate) up: Vec<QueryType>,
@@ -130,6 +180,21 @@ impl<T: Into<String>> From<T> for UpdateStatementRaw {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InsertStatementRaw(String);
+
+impl Display for InsertStatementRaw {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<T: Into<String>> From<T> for InsertStatementRaw {
+    fn from(value: T) -> Self {
+        Self(value.into())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RemoveStatementRaw(String);
 impl Display for RemoveStatementRaw {
@@ -40,6 +40,59 @@ pub enum MigrationError {
     #[error("Rollback failed. Error: {0}")]
     RollbackFailed(String),
 
+    #[error(
+        "Timed out after {timeout_secs}s waiting for the migration lock held by {holder}. \
+        If that migrator is no longer running, release the lock with 'cargo run -- force-unlock'"
+    )]
+    MigrationLockTimeout { holder: String, timeout_secs: u64 },
+
+    #[error(
+        "The migration lock held by {holder} was lost before the migration finished. \
+        The migration was aborted so that it does not run alongside another migrator"
+    )]
+    MigrationLockLost { holder: String },
+
+    #[error(
+        "Code migration - {0} - is not reversible. Implement its down migration to roll it back"
+    )]
+    CodeMigrationNotReversible(String),
+
+    #[error(
+        "Code migration - {0} - can only be run on a Surreal<Any> connection. \
+        Connect with surrealdb::engine::any::connect to run it"
+    )]
+    CodeMigrationRequiresAnyConnection(String),
+
+    #[error(
+        "Cannot squash {migration} because it is after {since}, the latest migration applied \
+        to every database"
+    )]
+    SquashIncludesUnappliedMigration {
+        migration: MigrationFilename,
+        since: MigrationFilename,
+    },
+
+    #[error("Cannot squash {0} because it has not been applied to this database")]
+    SquashMigrationNotApplied(MigrationFilename),
+
+    #[error("At least two applied migrations are needed to squash")]
+    NothingToSquash,
+
+    #[error("Cannot baseline a database that already has applied migrations")]
+    BaselineDatabaseHasMigrations,
+
+    #[error("Migration has {count} destructive change(s). Use --allow-destructive to generate it anyway.\n\n{lint}")]
+    DestructiveMigration { count: usize, lint: String },
+
+    #[error("Invalid config file - {path}. Error: {error}")]
+    InvalidConfigFile { path: String, error: String },
+
+    #[error("Environment - {env} - is not defined in a config file")]
+    ConfigEnvironmentDoesNotExist { env: String },
+
+    #[error("Environment variable - {0} - used in the config file is not set")]
+    ConfigEnvVarNotSet(String),
+
     #[error("Migration file name and database name mismatch. Migration file name: {migration_file_name}. Migration database name: {migration_db_name}")]
     MigrationFileVsDbNamesMismatch {
         migration_file_name: String,
@@ -142,6 +195,32 @@ pub enum MigrationError {
         renamables: String,
     },
 
+    #[error(
+        "You are trying to rename the table-'{new_table}' from a table-'{old_table}' \
+        still defined in the codebase. Remove the old table from the codebase resources \
+        or use a different old_table"
+    )]
+    CannotRenameFromOldTableInUse { new_table: Table, old_table: Table },
+
+    #[error(
+        "You are trying to rename the edge table-'{old_table}' to '{new_table}'. \
+        Copying the edges to a new table does not keep them connected to their nodes. \
+        Define the new edge table and relate the nodes again instead"
+    )]
+    CannotRenameEdgeTable { new_table: Table, old_table: Table },
+
+    #[error(
+        "You are trying to rename the table-'{old_table}' to '{new_table}' but the field-'{field}' \
+        of the table-'{table}' links to it in an array, a set or an edge. These links cannot be \
+        rewritten to the new table. Rewrite them in a migration of your own before renaming the table"
+    )]
+    CannotRewriteLinksToRenamedTable {
+        new_table: Table,
+        old_table: Table,
+        table: Table,
+        field: Field,
+    },
+
     #[error(
         "You are trying to use the same old field name - {field} - for new field name - {field}. \
         This is likely not intentional. Use a different name for the new field"
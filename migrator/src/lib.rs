@@ -9,6 +9,7 @@ mod database;
 mod error;
 mod resources;
 
+pub use async_trait::async_trait;
 pub use cli::*;
 pub use database::*;
 pub use error::*;
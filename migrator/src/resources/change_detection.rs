@@ -158,6 +158,13 @@ pub enum DeltaTypeResource {
         left: DefineStatementRaw,
         right: DefineStatementRaw,
     },
+    // Only tables are renamed. Set with the `old_table` table attribute
+    Rename {
+        old_name: Table,
+        new_name: Table,
+        left: DefineStatementRaw,
+        right: DefineStatementRaw,
+    },
 }
 
 impl From<(Option<DefineStatementRaw>, Option<DefineStatementRaw>)> for DeltaTypeResource {
@@ -41,11 +41,16 @@ where
                     queries.add_up(QueryType::Define(right));
                     queries.add_down(QueryType::Define(left));
                 }
-                DeltaTypeResource::NoChange => {}
+                DeltaTypeResource::NoChange | DeltaTypeResource::Rename { .. } => {}
             };
         }
         Ok(queries)
     }
+
+    // Left is from migration dir, right is from the live database
+    fn drift(&self, resource: DriftResource) -> Vec<SchemaDrift> {
+        schema_drifts(resource, None, self.get_left(), self.get_right())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
@@ -139,14 +139,19 @@ impl<'a, R: DbResources> ComparisonFields<'a, R> {
         })?;
 
         // Field name change defs
-        acc.add_comment_to_up(format!("Rename field {old_name} to {new_name}"));
+        let rename = Rename::Field {
+            table: table.clone(),
+            old: old_name.clone(),
+            new: new_name.clone(),
+        };
+        acc.add_up(QueryType::RenameStart(rename.clone()));
         acc.add_up(QueryType::Define(new_field_def.clone()));
         let copy_old_to_new = UpdateStatementRaw::from(
             Raw::new(format!("UPDATE {table} SET {new_name} = {old_name}")).build(),
         );
         acc.add_up(QueryType::Update(copy_old_to_new));
         acc.add_up(QueryType::Remove(old_field_def.as_remove_statement()?));
-        acc.add_comment_to_up("Rename field ending");
+        acc.add_up(QueryType::RenameEnd(rename));
 
         // Field name change reversal defs
         acc.add_comment_to_down(format!(
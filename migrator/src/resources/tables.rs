@@ -9,7 +9,11 @@ use crate::{
     table_indexes::ComparisonIndexes, *,
 };
 use serde::{Deserialize, Serialize};
-use surreal_query_builder::{DbResources, Table};
+use std::collections::HashMap;
+use surreal_query_builder::{
+    sql::{self, statements::DefineStatement, Kind, Statement},
+    Buildable, DbResources, Field, Raw, Table,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct TableResourcesData {
@@ -58,6 +62,7 @@ impl<R: DbResources> DbResourcesMeta<Tables> for ComparisonTables<'_, R> {
         let left = self.get_left().get_names_as_set();
         let right = self.get_right().get_names_as_set();
         let tables = left.union(&right);
+        let renamed_tables = self.renamed_tables()?;
         let mut all_tables_with_sub_resources = vec![];
 
         for table in tables {
@@ -65,6 +70,25 @@ impl<R: DbResources> DbResourcesMeta<Tables> for ComparisonTables<'_, R> {
             let def_left = self.get_left().get_definition(table).cloned();
             let def_right = self.get_right().get_definition(table).cloned();
 
+            // The old table of a renamed table is moved along with the new table
+            if renamed_tables.values().any(|old| old.to_string() == *table) {
+                continue;
+            }
+
+            let delta = match renamed_tables.get(&Table::from(table.clone())) {
+                Some(old_table) => DeltaTypeResource::Rename {
+                    old_name: old_table.to_owned(),
+                    new_name: Table::from(table.clone()),
+                    left: self
+                        .get_left()
+                        .get_definition(old_table.to_string())
+                        .cloned()
+                        .expect("Renamed tables are in the migrations directory"),
+                    right: def_right.expect("Renamed tables are in the codebase"),
+                },
+                None => DeltaTypeResource::from((def_left, def_right)),
+            };
+
             let events = ComparisonEvents {
                 table: &Table::from(table.clone()),
                 resources: self.resources,
@@ -98,7 +122,7 @@ impl<R: DbResources> DbResourcesMeta<Tables> for ComparisonTables<'_, R> {
                 acc.extend_down(&events);
             };
 
-            match DeltaTypeResource::from((def_left, def_right)) {
+            match delta {
                 DeltaTypeResource::NoChange => {
                     extend_table_resources_up(&mut queries);
                     extend_table_resources_down(&mut queries);
@@ -121,10 +145,23 @@ impl<R: DbResources> DbResourcesMeta<Tables> for ComparisonTables<'_, R> {
                     queries.add_down(QueryType::Define(left));
                     extend_table_resources_down(&mut queries);
                 }
+                DeltaTypeResource::Rename {
+                    old_name,
+                    new_name,
+                    left,
+                    right,
+                } => {
+                    self.handle_rename(&mut queries, &old_name, &new_name, left, right)?;
+                }
             };
 
             all_tables_with_sub_resources.push(queries);
         }
+        let links = self.rewrite_links_to_renamed_tables(&renamed_tables)?;
+        if !links.up_is_empty() {
+            all_tables_with_sub_resources.push(links);
+        }
+
         let last_table_index = all_tables_with_sub_resources.len() - 1;
         let spaced_queries = all_tables_with_sub_resources.into_iter().enumerate().fold(
             Queries::default(),
@@ -145,6 +182,321 @@ impl<R: DbResources> DbResourcesMeta<Tables> for ComparisonTables<'_, R> {
     }
 }
 
+impl<R: DbResources> ComparisonTables<'_, R> {
+    /// The old names of the tables renamed with the `old_table` table attribute keyed by
+    /// their new names. A table is only renamed when its old table is in the migrations
+    /// directory and its new table is not yet.
+    fn renamed_tables(&self) -> MigrationResult<HashMap<Table, Table>> {
+        let left = self.get_left();
+        let right = self.get_right();
+        let mut renamed_tables = HashMap::new();
+
+        for (new_table, old_table) in self.codebase_resources.tables_old_names() {
+            let is_renaming = left.get_definition(old_table.to_string()).is_some()
+                && left.get_definition(new_table.to_string()).is_none()
+                && right.get_definition(new_table.to_string()).is_some();
+            if !is_renaming {
+                continue;
+            }
+
+            if right.get_definition(old_table.to_string()).is_some() {
+                return Err(MigrationError::CannotRenameFromOldTableInUse {
+                    new_table,
+                    old_table,
+                });
+            }
+
+            let old_fields = self
+                .resources
+                .left_resources
+                .get_table_field_names_as_set(&old_table);
+            if old_fields.contains("in") && old_fields.contains("out") {
+                return Err(MigrationError::CannotRenameEdgeTable {
+                    new_table,
+                    old_table,
+                });
+            }
+            renamed_tables.insert(new_table, old_table);
+        }
+
+        Ok(renamed_tables)
+    }
+
+    // Defines the new table with its fields and indexes, copies the records of the old
+    // table keeping their ids and moving the values of its renamed fields, defines the
+    // events after the copy so they are not triggered by it and removes the old table.
+    // The down migration does the reverse.
+    fn handle_rename<'q>(
+        &self,
+        acc: &'q mut Queries,
+        old_name: &Table,
+        new_name: &Table,
+        left: DefineStatementRaw,
+        right: DefineStatementRaw,
+    ) -> MigrationResult<&'q mut Queries> {
+        let left_resources = self.resources.left_resources;
+        let right_resources = self.resources.right_resources;
+        let renamed_fields = self.renamed_fields(old_name, new_name);
+        let reverted_fields = renamed_fields
+            .iter()
+            .map(|(old_field, new_field)| (new_field.clone(), old_field.clone()))
+            .collect::<Vec<_>>();
+
+        let rename = Rename::Table {
+            old: old_name.clone(),
+            new: new_name.clone(),
+        };
+        acc.add_up(QueryType::RenameStart(rename.clone()));
+        acc.add_up(QueryType::Define(right.clone()));
+        for def in right_resources
+            .get_table_fields(new_name)
+            .unwrap_or_default()
+            .get_all_definitions()
+        {
+            acc.add_up(QueryType::Define(def));
+        }
+        for def in right_resources
+            .get_table_indexes(new_name)
+            .unwrap_or_default()
+            .get_all_definitions()
+        {
+            acc.add_up(QueryType::Define(def));
+        }
+        acc.add_up(QueryType::Insert(copy_table_records(
+            old_name,
+            new_name,
+            &renamed_fields,
+        )));
+        for def in right_resources
+            .get_table_events(new_name)
+            .unwrap_or_default()
+            .get_all_definitions()
+        {
+            acc.add_up(QueryType::Define(def));
+        }
+        acc.add_up(QueryType::Remove(left.as_remove_statement()?));
+        acc.add_up(QueryType::RenameEnd(rename));
+
+        acc.add_comment_to_down(format!(
+            "Revert table name change. Change table {new_name} back to {old_name}"
+        ));
+        acc.add_down(QueryType::Define(left));
+        for def in left_resources
+            .get_table_fields(old_name)
+            .unwrap_or_default()
+            .get_all_definitions()
+        {
+            acc.add_down(QueryType::Define(def));
+        }
+        for def in left_resources
+            .get_table_indexes(old_name)
+            .unwrap_or_default()
+            .get_all_definitions()
+        {
+            acc.add_down(QueryType::Define(def));
+        }
+        acc.add_down(QueryType::Insert(copy_table_records(
+            new_name,
+            old_name,
+            &reverted_fields,
+        )));
+        for def in left_resources
+            .get_table_events(old_name)
+            .unwrap_or_default()
+            .get_all_definitions()
+        {
+            acc.add_down(QueryType::Define(def));
+        }
+        acc.add_down(QueryType::Remove(right.as_remove_statement()?));
+        acc.add_comment_to_down("Revert table name change ending");
+
+        Ok(acc)
+    }
+
+    /// The fields of a renamed table renamed with the `old_name` field attribute as pairs
+    /// of their old and new names. Only fields defined in the old table are renamed.
+    fn renamed_fields(&self, old_table: &Table, new_table: &Table) -> Vec<(Field, Field)> {
+        let old_fields = self
+            .resources
+            .left_resources
+            .get_table_field_names_as_set(old_table);
+
+        self.codebase_resources
+            .tables_fields_meta()
+            .remove(new_table)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|meta| {
+                let old_name = meta.old_name?;
+                (old_name.to_string() != meta.name.to_string()
+                    && old_fields.contains(&old_name.to_string()))
+                .then_some((old_name, meta.name))
+            })
+            .collect()
+    }
+
+    // Records of other tables keep linking to the old table after it is renamed. Links of
+    // single records are rewritten to the new table once every table has been migrated, so
+    // the renamed tables and fields of the linking tables are in place. Links in arrays, sets
+    // and edges cannot be rewritten with an update and the rename is refused instead.
+    fn rewrite_links_to_renamed_tables(
+        &self,
+        renamed_tables: &HashMap<Table, Table>,
+    ) -> MigrationResult<Queries> {
+        let left_resources = self.resources.left_resources;
+        let right_resources = self.resources.right_resources;
+        let fields_meta = self.codebase_resources.tables_fields_meta();
+        let mut rewrites = Queries::default();
+
+        for (new_table, old_table) in renamed_tables {
+            for left_table in left_resources.get_tables() {
+                let right_table = renamed_tables
+                    .iter()
+                    .find(|(_, old)| **old == left_table)
+                    .map_or(left_table.clone(), |(new, _)| new.clone());
+                let Some(right_fields) = right_resources.get_table_fields(&right_table) else {
+                    continue;
+                };
+
+                for def in left_resources
+                    .get_table_fields(&left_table)
+                    .unwrap_or_default()
+                    .get_all_definitions()
+                {
+                    let Some((left_field, kind)) = parse_field_kind(&def) else {
+                        continue;
+                    };
+                    let old_table_name = old_table.to_string();
+                    let links_many = links_many(&kind, &old_table_name);
+                    if !links_many && !links_one(&kind, &old_table_name) {
+                        continue;
+                    }
+
+                    let right_field = fields_meta
+                        .get(&right_table)
+                        .and_then(|metas| {
+                            metas.iter().find(|meta| {
+                                meta.old_name
+                                    .as_ref()
+                                    .is_some_and(|old| old.to_string() == left_field)
+                            })
+                        })
+                        .map_or(left_field.clone(), |meta| meta.name.to_string());
+                    if right_fields.get_definition(&right_field).is_none() {
+                        continue;
+                    }
+
+                    let is_edge_link = ["in", "out"].contains(&left_field.as_str());
+                    if links_many || is_edge_link || left_field.contains('*') {
+                        return Err(MigrationError::CannotRewriteLinksToRenamedTable {
+                            new_table: new_table.clone(),
+                            old_table: old_table.clone(),
+                            table: left_table,
+                            field: Field::new(left_field),
+                        });
+                    }
+
+                    rewrites.add_up(QueryType::Update(rewrite_links(
+                        &right_table,
+                        &right_field,
+                        old_table,
+                        new_table,
+                    )));
+                    rewrites.add_down(QueryType::Update(rewrite_links(
+                        &left_table,
+                        &left_field,
+                        new_table,
+                        old_table,
+                    )));
+                }
+            }
+        }
+
+        let mut queries = Queries::default();
+        if !rewrites.up_is_empty() {
+            queries.add_comment_to_up("Rewrite links to renamed tables");
+            queries.add_comment_to_down("Revert links to renamed tables");
+            queries.extend_up(&rewrites);
+            queries.extend_down(&rewrites);
+        }
+
+        Ok(queries)
+    }
+}
+
+// The ids are rewritten to the new table by inserting the records with only the
+// key of their old ids e.g client:oyelowo becomes customer:oyelowo. The values of
+// the renamed fields are moved to their new names.
+fn copy_table_records(
+    from: &Table,
+    into: &Table,
+    renamed_fields: &[(Field, Field)],
+) -> InsertStatementRaw {
+    let renames = renamed_fields
+        .iter()
+        .map(|(from_field, into_field)| format!(", {from_field} AS {into_field}"))
+        .collect::<String>();
+    let omitted = renamed_fields
+        .iter()
+        .map(|(from_field, _)| from_field.to_string())
+        .collect::<Vec<_>>();
+    let omit = if omitted.is_empty() {
+        String::new()
+    } else {
+        format!(" OMIT {}", omitted.join(", "))
+    };
+
+    InsertStatementRaw::from(
+        Raw::new(format!(
+            "INSERT INTO {into} (SELECT *, meta::id(id) AS id{renames}{omit} FROM {from})"
+        ))
+        .build(),
+    )
+}
+
+// e.g novel:1 linking to author:oyelowo links to writer:oyelowo after author is renamed to writer
+fn rewrite_links(table: &Table, field: &str, from: &Table, into: &Table) -> UpdateStatementRaw {
+    UpdateStatementRaw::from(
+        Raw::new(format!(
+            "UPDATE {table} SET {field} = type::thing('{into}', meta::id({field})) \
+            WHERE type::is::record({field}, '{from}')"
+        ))
+        .build(),
+    )
+}
+
+fn parse_field_kind(def: &DefineStatementRaw) -> Option<(String, Kind)> {
+    let statement = sql::parse(&def.to_string()).ok()?.first().cloned()?;
+    match statement {
+        Statement::Define(DefineStatement::Field(field)) => {
+            Some((field.name.to_string(), field.kind?))
+        }
+        _ => None,
+    }
+}
+
+/// Whether a value of the kind can be a single link to the table
+fn links_one(kind: &Kind, table: &str) -> bool {
+    match kind {
+        Kind::Record(tables) => tables.iter().any(|t| t.0 == table),
+        Kind::Option(kind) => links_one(kind, table),
+        Kind::Either(kinds) => kinds.iter().any(|kind| links_one(kind, table)),
+        _ => false,
+    }
+}
+
+/// Whether a value of the kind can hold links to the table in an array or a set
+fn links_many(kind: &Kind, table: &str) -> bool {
+    match kind {
+        Kind::Array(kind, _) | Kind::Set(kind, _) => {
+            links_one(kind, table) || links_many(kind, table)
+        }
+        Kind::Option(kind) => links_many(kind, table),
+        Kind::Either(kinds) => kinds.iter().any(|kind| links_many(kind, table)),
+        _ => false,
+    }
+}
+
 pub trait TableResourcesMeta<T>
 where
     T: Informational,
@@ -179,10 +531,20 @@ where
                     queries.add_up(QueryType::Define(right));
                     queries.add_down(QueryType::Define(left));
                 }
-                DeltaTypeResource::NoChange => {}
+                DeltaTypeResource::NoChange | DeltaTypeResource::Rename { .. } => {}
             };
         }
 
         Ok(queries)
     }
+
+    // Left is from migration dir, right is from the live database
+    fn drift(&self, resource: DriftResource) -> Vec<SchemaDrift> {
+        schema_drifts(
+            resource,
+            Some(self.get_table()),
+            self.get_left(),
+            self.get_right(),
+        )
+    }
 }
@@ -9,11 +9,11 @@ use std::ops::Deref;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use surreal_orm::{
-    arr, cond, create_table_resources,
+    cond, create_table_resources,
     functions::crypto,
     statements::{
-        define_analyzer, define_event, define_index, define_param, define_scope, define_token,
-        define_user, select, AnalyzerFilter, SnowballLanguage, Tokenizer, UserRole,
+        define_analyzer, define_param, define_scope, define_token, define_user, select,
+        AnalyzerFilter, SelectStatement, SnowballLanguage, Tokenizer, UserRole,
     },
     SurrealCrudNode, *,
 };
@@ -291,6 +291,19 @@ pub struct PlanetV2 {
     pub labels: Vec<String>,
 }
 
+#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = planet, schemafull, relax_table)]
+pub struct PlanetV3 {
+    pub id: SurrealSimpleId<Self>,
+    #[orm(assert = cond(value().not_equal("$value")).and(value().not_equal("")))]
+    pub first_name: String,
+    pub population: u64,
+    pub created_at: chrono::DateTime<Utc>,
+    pub updated_at: chrono::DateTime<Utc>,
+    pub labels: Vec<String>,
+}
+
 #[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 #[orm(table = student, schemafull)]
@@ -302,6 +315,90 @@ pub struct Student {
     pub created_at: chrono::DateTime<Utc>,
 }
 
+#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = client, schemafull)]
+pub struct Client {
+    pub id: SurrealSimpleId<Self>,
+    #[orm(unique)]
+    pub email: String,
+    pub full_name: String,
+}
+
+#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = customer, old_table = client, schemafull)]
+pub struct Customer {
+    pub id: SurrealSimpleId<Self>,
+    #[orm(unique)]
+    pub email: String,
+    pub full_name: String,
+}
+
+#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = author, schemafull)]
+pub struct Author {
+    pub id: SurrealSimpleId<Self>,
+    pub full_name: String,
+}
+
+#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = writer, old_table = author, schemafull)]
+pub struct Writer {
+    pub id: SurrealSimpleId<Self>,
+    #[orm(old_name = "fullName")]
+    pub name: String,
+}
+
+#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = novel, schemafull)]
+pub struct Novel {
+    pub id: SurrealSimpleId<Self>,
+    pub title: String,
+    #[orm(link_one = Author)]
+    pub author: LinkOne<Author>,
+}
+
+#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = novel, schemafull, relax_table)]
+pub struct NovelV2 {
+    pub id: SurrealSimpleId<Self>,
+    pub title: String,
+    #[orm(link_one = Writer)]
+    pub author: LinkOne<Writer>,
+}
+
+#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = anthology, schemafull)]
+pub struct Anthology {
+    pub id: SurrealSimpleId<Self>,
+    #[orm(link_many = Author)]
+    pub authors: LinkMany<Author>,
+}
+
+#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = anthology, schemafull, relax_table)]
+pub struct AnthologyV2 {
+    pub id: SurrealSimpleId<Self>,
+    #[orm(link_many = Writer)]
+    pub authors: LinkMany<Writer>,
+}
+
+#[derive(View, Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = planet_population, view(from = Planet, group_by = first_name))]
+pub struct PlanetPopulation {
+    pub first_name: String,
+    #[orm(expr = "math::sum(population)")]
+    pub population: u64,
+}
+
 pub mod snake_cases {
     use super::*;
 
@@ -342,9 +439,15 @@ pub mod snake_cases {
     pub type AnimalSnakeCaseEatsCrop = EatsSnakeCase<AnimalSnakeCase, Crop>;
 }
 
-#[derive(Node, Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
-#[orm(table = animal, schemafull)]
+#[orm(
+    table = animal,
+    schemafull,
+    index(name = "species_speed_idx", fields(species, velocity), unique),
+    event(name = "event1", when = fast_homo_erectus, then = select_crops),
+    event(name = "event2", when = slow_homo_sapien, then = select_animals_eating_crops)
+)]
 pub struct Animal {
     pub id: SurrealSimpleId<Self>,
     pub species: String,
@@ -354,44 +457,39 @@ pub struct Animal {
     pub velocity: u64,
 }
 
-impl TableResources for Animal {
-    fn events_definitions() -> Vec<Raw> {
-        let animal::Schema {
-            species, velocity, ..
-        } = Self::schema();
+fn fast_homo_erectus() -> Filter {
+    let animal::Schema {
+        species, velocity, ..
+    } = Animal::schema();
 
-        let event1 = define_event("event1".to_string())
-            .on_table("animal".to_string())
-            .when(cond(species.eq("Homo Erectus")).and(velocity.gt(545)))
-            .then(select(All).from(Crop::table()))
-            .to_raw();
+    cond(species.eq("Homo Erectus")).and(velocity.gt(545))
+}
 
-        let event2 = define_event("event2".to_string())
-            .on_table("animal".to_string())
-            .when(cond(species.eq("Homo Sapien")).and(velocity.lt(10)))
-            .then(select(All).from(AnimalEatsCrop::table()))
-            .to_raw();
-        vec![event1, event2]
-    }
+fn slow_homo_sapien() -> Filter {
+    let animal::Schema {
+        species, velocity, ..
+    } = Animal::schema();
 
-    fn indexes_definitions() -> Vec<Raw> {
-        let animal::Schema {
-            species, velocity, ..
-        } = Self::schema();
+    cond(species.eq("Homo Sapien")).and(velocity.lt(10))
+}
 
-        let idx1 = define_index("species_speed_idx".to_string())
-            .on_table(Self::table())
-            .fields(arr![species, velocity])
-            .unique()
-            .to_raw();
+fn select_crops() -> SelectStatement {
+    select(All).from(Crop::table())
+}
 
-        vec![idx1]
-    }
+fn select_animals_eating_crops() -> SelectStatement {
+    select(All).from(AnimalEatsCrop::table())
 }
 
-#[derive(Node, Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
-#[orm(table = animal, schemafull, relax_table)]
+#[orm(
+    table = animal,
+    schemafull,
+    relax_table,
+    index(name = "species_speed_idx", fields(velocity, characteristics), unique),
+    event(name = "event1", when = fast_homo_habillis, then = select_crops)
+)]
 pub struct AnimalV2 {
     pub id: SurrealSimpleId<Self>,
     pub species: String,
@@ -400,36 +498,12 @@ pub struct AnimalV2 {
     pub velocity: u64,
 }
 
-impl TableResources for AnimalV2 {
-    fn events_definitions() -> Vec<Raw> {
-        let animal_v_2::Schema {
-            species, velocity, ..
-        } = Self::schema();
+fn fast_homo_habillis() -> Filter {
+    let animal_v_2::Schema {
+        species, velocity, ..
+    } = AnimalV2::schema();
 
-        let event1 = define_event("event1".to_string())
-            .on_table("animal".to_string())
-            .when(cond(species.eq("Homo Habillis").and(velocity.gt(545))))
-            .then(select(All).from(Crop::table()))
-            .to_raw();
-
-        vec![event1]
-    }
-
-    fn indexes_definitions() -> Vec<Raw> {
-        let animal_v_2::Schema {
-            characteristics,
-            velocity,
-            ..
-        } = Self::schema();
-
-        let idx1 = define_index("species_speed_idx".to_string())
-            .on_table(Self::table())
-            .fields(arr![velocity, characteristics])
-            .unique()
-            .to_raw();
-
-        vec![idx1]
-    }
+    cond(species.eq("Homo Habillis").and(velocity.gt(545)))
 }
 
 #[derive(Edge, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
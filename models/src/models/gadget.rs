@@ -0,0 +1,53 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use serde::{Deserialize, Serialize};
+use surreal_orm::{Node, Object, SurrealSimpleId};
+
+// Enums are stored as literal types
+#[derive(Node, Serialize, Deserialize, Debug, Clone)]
+#[orm(table = gadget)]
+pub struct Gadget {
+    pub id: SurrealSimpleId<Self>,
+
+    #[orm(nest_enum = "GadgetStatus")]
+    pub status: GadgetStatus,
+
+    #[orm(nest_enum = "GadgetStatus")]
+    pub previous_statuses: Vec<GadgetStatus>,
+
+    #[orm(nest_enum = "GadgetShape")]
+    pub shape: Option<GadgetShape>,
+
+    #[orm(nest_enum = "GadgetPower")]
+    pub power: GadgetPower,
+}
+
+#[derive(Object, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GadgetStatus {
+    #[default]
+    Active,
+    SoftDeleted,
+    #[serde(rename = "banned_forever")]
+    Banned,
+}
+
+#[derive(Object, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum GadgetShape {
+    Circle { radius: f64 },
+    Rectangle { width: f64, height: f64 },
+    Dot,
+}
+
+#[derive(Object, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", content = "data", rename_all = "camelCase")]
+pub enum GadgetPower {
+    Battery { capacity_mah: u32 },
+    Solar(#[orm(ty = "float")] f64),
+    Unplugged,
+}
@@ -8,6 +8,7 @@ pub mod alien;
 pub mod attributes;
 pub mod combined;
 pub mod configuration;
+pub mod gadget;
 pub mod planet;
 pub mod spaceship;
 pub mod user;
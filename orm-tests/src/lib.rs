@@ -3,3 +3,15 @@
  * Email: oyelowo.oss@gmail.com
  * Copyright (c) 2023 Oyelowo Oyedayo
  */
+
+use surreal_orm::{Buildable, ToRaw};
+
+/// Builds the definitions of a model one statement per line e.g
+/// `build_definitions(User::define_fields())`
+pub fn build_definitions(definitions: impl IntoIterator<Item = impl ToRaw>) -> String {
+    definitions
+        .into_iter()
+        .map(|definition| definition.to_raw().build())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,34 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use pretty_assertions::assert_eq;
+use serde::{Deserialize, Serialize};
+use surreal_orm::*;
+use surreal_orm_tests::build_definitions;
+
+fn select_accounts() -> statements::SelectStatement {
+    statements::select(All).from(Table::new("account"))
+}
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone)]
+#[orm(
+    table = "ledger",
+    event(name = "ledger_update", when = "$event = 'UPDATE'", then = select_accounts),
+    event(name = "ledger_delete", when = Ledger::schema().balance.lt(0), then = "DELETE ledger")
+)]
+struct Ledger {
+    id: SurrealSimpleId<Self>,
+    balance: f64,
+}
+
+#[test]
+fn test_node_event_attributes_generate_event_definitions() {
+    assert_eq!(
+        build_definitions(Ledger::define_events()),
+        "DEFINE EVENT ledger_update ON TABLE ledger WHEN $event = 'UPDATE' THEN SELECT * FROM account;
+DEFINE EVENT ledger_delete ON TABLE ledger WHEN balance < 0 THEN DELETE ledger;"
+    );
+}
@@ -21,8 +21,8 @@ use std::time::Duration;
 use surreal_orm::Buildable;
 use surreal_orm::{
     statements::{insert, select},
-    All, Model, Node, Object, Operatable, ReturnableSelect, ReturnableStandard, SchemaGetter,
-    SurrealId, SurrealSimpleId, ToRaw,
+    All, FieldType, GeometryType, Model, Node, Object, Operatable, ReturnableSelect,
+    ReturnableStandard, SchemaGetter, SurrealId, SurrealSimpleId, SurrealType, ToRaw,
 };
 use surrealdb::engine::local::Mem;
 use surrealdb::Surreal;
@@ -156,6 +156,12 @@ pub struct TestGeometrycollection {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GeometryCollection(geo::Geometry);
 
+impl SurrealType for GeometryCollection {
+    fn field_type() -> FieldType {
+        FieldType::Geometry(vec![GeometryType::Collection])
+    }
+}
+
 impl From<GeometryCollection> for surrealdb::sql::Geometry {
     fn from(value: GeometryCollection) -> Self {
         match value.0 {
@@ -0,0 +1,39 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use pretty_assertions::assert_eq;
+use serde::{Deserialize, Serialize};
+use surreal_orm::*;
+use surreal_orm_tests::build_definitions;
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[orm(
+    table = "account",
+    index(fields(first_name, last_name)),
+    index(name = "account_handle_idx", fields(handle, org_id), unique)
+)]
+struct Account {
+    id: SurrealSimpleId<Self>,
+    #[orm(unique)]
+    email: String,
+    #[orm(index)]
+    org_id: String,
+    handle: String,
+    first_name: String,
+    last_name: String,
+}
+
+#[test]
+fn test_node_index_attributes_generate_index_definitions() {
+    assert_eq!(
+        build_definitions(Account::define_indexes()),
+        "DEFINE INDEX account_email_idx ON TABLE account FIELDS email UNIQUE;
+DEFINE INDEX account_orgId_idx ON TABLE account FIELDS orgId;
+DEFINE INDEX account_firstName_lastName_idx ON TABLE account FIELDS firstName, lastName;
+DEFINE INDEX account_handle_idx ON TABLE account FIELDS handle, orgId UNIQUE;"
+    );
+}
@@ -0,0 +1,64 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use pretty_assertions::assert_eq;
+use serde::{Deserialize, Serialize};
+use surreal_orm::*;
+use surrealdb::{engine::local::Mem, Surreal};
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = "account")]
+struct Account {
+    id: SurrealSimpleId<Self>,
+    email: String,
+    org_id: String,
+    handle: String,
+    first_name: String,
+    last_name: String,
+}
+
+const SELECT_ACCOUNT_HANDLES: &str = concat_const!(
+    "SELECT ",
+    account::fields::HANDLE,
+    " FROM ",
+    Account::TABLE,
+    " WHERE ",
+    account::fields::ORG_ID,
+    " = $org_id;"
+);
+
+#[test]
+fn test_node_table_and_field_name_constants() {
+    assert_eq!(Account::TABLE, "account");
+    assert_eq!(account::fields::ID, "id");
+    assert_eq!(account::fields::ORG_ID, "orgId");
+    assert_eq!(account::fields::FIRST_NAME, "firstName");
+    assert_eq!(
+        SELECT_ACCOUNT_HANDLES,
+        "SELECT handle FROM account WHERE orgId = $org_id;"
+    );
+}
+
+#[tokio::test]
+async fn test_node_field_name_constants_spliced_into_query_macro() {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    query!(db, "CREATE account SET handle = 'oye', orgId = $org_id", { org_id: "codebreather" })
+        .await
+        .unwrap();
+    let mut response = query!(
+        db,
+        "SELECT {{account::fields::HANDLE}} FROM {{Account::TABLE}} WHERE {{account::fields::ORG_ID}} = $org_id",
+        { org_id: "codebreather" }
+    )
+    .await
+    .unwrap();
+    let handles: Vec<String> = response.take((0, account::fields::HANDLE)).unwrap();
+
+    assert_eq!(handles, vec!["oye".to_string()]);
+}
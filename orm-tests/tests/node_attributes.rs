@@ -5,11 +5,13 @@
  */
 
 use pretty_assertions::assert_eq;
+use serde::{Deserialize, Serialize};
 use surreal_models::{
-    Alien, AlienWithExplicitAttributes, StudentWithDefineAttr, StudentWithDefineFnAttr,
-    StudentWithGranularAttributes,
+    Alien, AlienWithExplicitAttributes, Gadget, GadgetStatus, StudentWithDefineAttr,
+    StudentWithDefineFnAttr, StudentWithGranularAttributes,
 };
 use surreal_orm::*;
+use surreal_orm_tests::build_definitions;
 use surrealdb::{engine::local::Mem, Surreal};
 
 #[tokio::test]
@@ -23,11 +25,7 @@ async fn test_node_type_atttribute_auto_inferred() -> SurrealOrmResult<()> {
     );
 
     assert_eq!(
-        Alien::define_fields()
-            .iter()
-            .map(|x| x.to_raw().build())
-            .collect::<Vec<_>>()
-            .join("\n"),
+        build_definitions(Alien::define_fields()),
         "DEFINE FIELD id ON TABLE alien TYPE record<alien>;
 DEFINE FIELD name ON TABLE alien TYPE string;
 DEFINE FIELD age ON TABLE alien TYPE int;
@@ -55,11 +53,7 @@ async fn test_node_type_atttribute_explicit() -> SurrealOrmResult<()> {
     );
 
     assert_eq!(
-        AlienWithExplicitAttributes::define_fields()
-            .iter()
-            .map(|x| x.to_raw().build())
-            .collect::<Vec<_>>()
-            .join("\n"),
+        build_definitions(AlienWithExplicitAttributes::define_fields()),
         "DEFINE FIELD id ON TABLE alien_with_explicit_attributes TYPE record<alien_with_explicit_attributes>;
 DEFINE FIELD name ON TABLE alien_with_explicit_attributes TYPE string;
 DEFINE FIELD age ON TABLE alien_with_explicit_attributes TYPE int;
@@ -84,11 +78,9 @@ async fn test_node_attributes_() -> SurrealOrmResult<()> {
         .to_raw()
         .build());
 
-    insta::assert_snapshot!(StudentWithGranularAttributes::define_fields()
-        .iter()
-        .map(|x| x.to_raw().build())
-        .collect::<Vec<_>>()
-        .join("\n"));
+    insta::assert_snapshot!(build_definitions(
+        StudentWithGranularAttributes::define_fields()
+    ));
 
     Ok(())
 }
@@ -99,11 +91,7 @@ async fn test_node_attributes_with_define_attribute() -> SurrealOrmResult<()> {
     db.use_ns("test").use_db("test").await.unwrap();
     insta::assert_snapshot!(StudentWithDefineAttr::define_table().to_raw().build());
 
-    insta::assert_snapshot!(StudentWithDefineAttr::define_fields()
-        .iter()
-        .map(|x| x.to_raw().build())
-        .collect::<Vec<_>>()
-        .join("\n"));
+    insta::assert_snapshot!(build_definitions(StudentWithDefineAttr::define_fields()));
 
     Ok(())
 }
@@ -114,11 +102,269 @@ async fn test_node_attributes_with_define_fn_attribute() -> SurrealOrmResult<()>
     db.use_ns("test").use_db("test").await.unwrap();
     insta::assert_snapshot!(StudentWithDefineFnAttr::define_table().to_raw().build());
 
-    insta::assert_snapshot!(StudentWithDefineFnAttr::define_fields()
-        .iter()
-        .map(|x| x.to_raw().build())
-        .collect::<Vec<_>>()
-        .join("\n"));
+    insta::assert_snapshot!(build_definitions(StudentWithDefineFnAttr::define_fields()));
+
+    Ok(())
+}
+
+#[test]
+fn test_node_enum_fields_are_literal_types() {
+    let define_fields = || build_definitions(Gadget::define_fields());
+
+    assert_eq!(
+        with_dialect(Dialect::V2, define_fields),
+        r#"DEFINE FIELD id ON TABLE gadget TYPE record<gadget>;
+DEFINE FIELD status ON TABLE gadget TYPE "active" | "soft_deleted" | "banned_forever";
+DEFINE FIELD previous_statuses ON TABLE gadget TYPE array<"active" | "soft_deleted" | "banned_forever">;
+DEFINE FIELD shape ON TABLE gadget TYPE option<{ type: "circle", radius: float } | { type: "rectangle", width: float, height: float } | { type: "dot" }>;
+DEFINE FIELD power ON TABLE gadget TYPE { kind: "battery", data: { capacity_mah: int } } | { kind: "solar", data: float } | { kind: "unplugged" };"#
+    );
+
+    assert_eq!(
+        with_dialect(Dialect::V1, define_fields),
+        "DEFINE FIELD id ON TABLE gadget TYPE record<gadget>;
+DEFINE FIELD status ON TABLE gadget TYPE string ASSERT $value INSIDE ['active', 'soft_deleted', 'banned_forever'];
+DEFINE FIELD previous_statuses ON TABLE gadget TYPE array<string> ASSERT $value ALLINSIDE ['active', 'soft_deleted', 'banned_forever'];
+DEFINE FIELD shape ON TABLE gadget TYPE option<object> ASSERT $value = NONE OR $value.type INSIDE ['circle', 'rectangle', 'dot'];
+DEFINE FIELD power ON TABLE gadget TYPE object ASSERT $value.kind INSIDE ['battery', 'solar', 'unplugged'];"
+    );
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Email(String);
+
+impl SurrealType for Email {
+    fn field_type() -> FieldType {
+        FieldType::String
+    }
+}
+
+type Money = f64;
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone)]
+#[orm(table = "subscriber")]
+struct Subscriber {
+    id: SurrealSimpleId<Self>,
+    email: Email,
+    backup_emails: Option<Vec<Email>>,
+    balance: Money,
+    joined_on: chrono::NaiveDate,
+    last_seen: chrono::DateTime<chrono::Utc>,
+    tracking_id: uuid::Uuid,
+    status: GadgetStatus,
+    #[serde(with = "time::serde::rfc3339")]
+    renewed_at: time::OffsetDateTime,
+}
+
+#[test]
+fn test_node_field_types_are_inferred_from_surreal_type_impls() {
+    let define_fields = || build_definitions(Subscriber::define_fields());
+
+    assert_eq!(
+        with_dialect(Dialect::V1, define_fields),
+        "DEFINE FIELD id ON TABLE subscriber TYPE record<subscriber>;
+DEFINE FIELD email ON TABLE subscriber TYPE string;
+DEFINE FIELD backup_emails ON TABLE subscriber TYPE option<array<string>>;
+DEFINE FIELD balance ON TABLE subscriber TYPE float;
+DEFINE FIELD joined_on ON TABLE subscriber TYPE string;
+DEFINE FIELD last_seen ON TABLE subscriber TYPE datetime;
+DEFINE FIELD tracking_id ON TABLE subscriber TYPE uuid;
+DEFINE FIELD status ON TABLE subscriber TYPE string ASSERT $value INSIDE ['active', 'soft_deleted', 'banned_forever'];
+DEFINE FIELD renewed_at ON TABLE subscriber TYPE string;"
+    );
+}
+
+#[tokio::test]
+async fn test_offset_datetime_fields_are_stored_as_rfc3339_strings() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    for definition in Subscriber::define_fields() {
+        if definition.build().contains("renewed_at") {
+            definition.run(db.clone()).await?;
+        }
+    }
+
+    let renewed_at = time::OffsetDateTime::parse(
+        "2024-03-01T10:30:00+01:00",
+        &time::format_description::well_known::Rfc3339,
+    )
+    .unwrap();
+    let subscriber = Subscriber {
+        id: Subscriber::create_simple_id(),
+        email: Email("oyelowo@codebreather.com".into()),
+        backup_emails: None,
+        balance: 10.0,
+        joined_on: chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        last_seen: chrono::Utc::now(),
+        tracking_id: uuid::Uuid::nil(),
+        status: GadgetStatus::Active,
+        renewed_at,
+    }
+    .create()
+    .get_one(db.clone())
+    .await?;
+    assert_eq!(subscriber.renewed_at, renewed_at);
+
+    let stored: Vec<String> = db
+        .query("SELECT VALUE renewed_at FROM $id")
+        .bind(("id", subscriber.id.to_thing()))
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    assert_eq!(stored, vec!["2024-03-01T10:30:00+01:00"]);
+
+    Ok(())
+}
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone)]
+#[orm(table = "writer")]
+struct Writer {
+    id: SurrealSimpleId<Self>,
+    name: String,
+}
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone)]
+#[orm(table = "article")]
+struct Article {
+    id: SurrealSimpleId<Self>,
+    title: String,
+    #[orm(link_one = Writer, reverse = articles)]
+    writer: LinkOne<Writer>,
+    #[orm(link_many = Writer, reverse = reviewed_articles)]
+    reviewers: LinkMany<Writer>,
+}
+
+#[tokio::test]
+async fn test_node_reverse_attributes_generate_accessors_on_linked_models() -> SurrealOrmResult<()>
+{
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    assert_eq!(
+        Writer::schema().reverse_links::<Article>().articles.build(),
+        "(SELECT * FROM article WHERE writer = $parent.id) AS articles"
+    );
+    assert_eq!(
+        Writer::schema()
+            .reverse_links::<Article>()
+            .reviewed_articles
+            .build(),
+        "(SELECT * FROM article WHERE reviewers CONTAINS $parent.id) AS reviewed_articles"
+    );
+
+    let writer = |name: &str| Writer {
+        id: Writer::create_simple_id(),
+        name: name.into(),
+    };
+    let oyelowo = writer("Oyelowo").create().get_one(db.clone()).await?;
+    let oyedayo = writer("Oyedayo").create().get_one(db.clone()).await?;
+
+    let article = |title: &str, writer: &Writer, reviewers: Vec<Writer>| Article {
+        id: Article::create_simple_id(),
+        title: title.into(),
+        writer: LinkOne::from(writer.clone()),
+        reviewers: LinkMany::from(reviewers),
+    };
+    let rust_article = article("Rust", &oyelowo, vec![oyedayo.clone()])
+        .create()
+        .get_one(db.clone())
+        .await?;
+    let surreal_article = article("Surreal", &oyedayo, vec![oyelowo.clone()])
+        .create()
+        .get_one(db.clone())
+        .await?;
+
+    let articles = oyelowo.load_articles(db.clone()).await?;
+    assert_eq!(articles.len(), 1);
+    assert_eq!(articles[0].id.to_thing(), rust_article.id.to_thing());
+
+    let reviewed_articles = oyelowo.load_reviewed_articles(db.clone()).await?;
+    assert_eq!(reviewed_articles.len(), 1);
+    assert_eq!(
+        reviewed_articles[0].id.to_thing(),
+        surreal_article.id.to_thing()
+    );
+
+    Ok(())
+}
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone)]
+#[orm(table = "book", repository)]
+struct Book {
+    id: SurrealSimpleId<Self>,
+    title: String,
+    pages: u32,
+    #[orm(link_one = Writer)]
+    writer: LinkOne<Writer>,
+}
+
+#[tokio::test]
+async fn test_node_repository_attribute_generates_repository() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    let writer = Writer {
+        id: Writer::create_simple_id(),
+        name: "Oyelowo".into(),
+    }
+    .create()
+    .get_one(db.clone())
+    .await?;
+    let book = |title: &str| Book {
+        id: Book::create_simple_id(),
+        title: title.into(),
+        pages: 100,
+        writer: LinkOne::from(writer.clone()),
+    };
+
+    let repo = BookRepo::new(db.clone());
+    let rust_book = repo.create(book("Rust")).await?.expect("Book created");
+    assert!(repo.exists(rust_book.id.clone()).await?);
+
+    let updated = repo
+        .update_partial(
+            rust_book.id.clone(),
+            Book::partial_builder().pages(250).build(),
+        )
+        .await?
+        .expect("Book updated");
+    assert_eq!(updated.title, "Rust");
+    assert_eq!(updated.pages, 250);
+
+    let with_writer = repo
+        .get_with_links(rust_book.id.clone(), vec![Book::schema().writer])
+        .await?
+        .expect("Book exists");
+    assert_eq!(
+        with_writer
+            .writer
+            .value()
+            .map(|writer| writer.name.as_str()),
+        Some("Oyelowo")
+    );
+
+    let transaction = statements::TransactionHandle::new();
+    let queued = repo.with_transaction(transaction.clone());
+    queued.create(book("Surreal"))?;
+    queued.delete(rust_book.id.clone())?;
+    assert_eq!(repo.list().await?.len(), 1);
+
+    transaction.commit_transaction().run(db.clone()).await?;
+    assert!(transaction.is_empty());
+
+    let books = repo.list().await?;
+    assert_eq!(books.len(), 1);
+    assert_eq!(books[0].title, "Surreal");
+    assert!(!repo.exists(rust_book.id.clone()).await?);
+    assert_eq!(
+        repo.list_where(Book::schema().pages.eq(100)).await?.len(),
+        1
+    );
+
+    repo.delete(books[0].id.clone()).await?;
+    assert!(repo.get(books[0].id.clone()).await?.is_none());
 
     Ok(())
 }
@@ -1 +1,78 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
 
+use pretty_assertions::assert_eq;
+use serde::{Deserialize, Serialize};
+use surreal_orm::*;
+use surreal_orm_tests::build_definitions;
+
+#[derive(Object, Serialize, Deserialize, Debug, Clone)]
+struct Audit {
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_by: Option<String>,
+}
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone)]
+#[orm(table = "invoice")]
+struct Invoice {
+    id: SurrealSimpleId<Self>,
+    amount: f64,
+    #[serde(flatten)]
+    audit: Audit,
+}
+
+#[test]
+fn test_node_flattened_object_fields_are_expanded() {
+    let define_fields = || build_definitions(Invoice::define_fields());
+
+    assert_eq!(
+        with_dialect(Dialect::V1, define_fields),
+        "DEFINE FIELD id ON TABLE invoice TYPE record<invoice>;
+DEFINE FIELD amount ON TABLE invoice TYPE float;
+DEFINE FIELD created_at ON TABLE invoice TYPE datetime;
+DEFINE FIELD updated_by ON TABLE invoice TYPE option<string>;"
+    );
+
+    assert_eq!(
+        Invoice::get_serializable_fields()
+            .iter()
+            .map(|field| field.build())
+            .collect::<Vec<_>>(),
+        vec!["id", "amount", "created_at", "updated_by"]
+    );
+
+    let invoice = Invoice::schema();
+    assert_eq!(invoice.audit().created_at.build(), "created_at");
+    assert_eq!(
+        Invoice::get_field_meta()
+            .iter()
+            .map(|meta| meta.name.build())
+            .collect::<Vec<_>>(),
+        vec!["id", "amount", "created_at", "updated_by"]
+    );
+}
+
+#[derive(Serialize)]
+struct Coordinates(f64, f64);
+
+impl SchemaGetter for Coordinates {
+    type Schema = ();
+
+    fn schema() -> Self::Schema {}
+
+    fn schema_prefixed(_prefix: impl Into<ValueLike>) -> Self::Schema {}
+}
+
+impl Object for Coordinates {}
+
+#[test]
+fn test_hand_written_object_has_no_flattened_fields() {
+    let place = Table::new("place");
+
+    assert!(Coordinates::get_serializable_fields().is_empty());
+    assert!(Coordinates::define_fields_on(place.clone()).is_empty());
+    assert!(Coordinates::get_field_meta_on(place).is_empty());
+}
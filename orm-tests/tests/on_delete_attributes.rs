@@ -0,0 +1,52 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use pretty_assertions::assert_eq;
+use serde::{Deserialize, Serialize};
+use surreal_orm::*;
+use surreal_orm_tests::build_definitions;
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone)]
+#[orm(table = "company")]
+struct Company {
+    id: SurrealSimpleId<Self>,
+    name: String,
+}
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone)]
+#[orm(table = "employee")]
+struct Employee {
+    id: SurrealSimpleId<Self>,
+    #[orm(link_one = Company, on_delete = "cascade")]
+    company: LinkOne<Company>,
+    #[orm(link_self = Employee, on_delete = "set_null")]
+    manager: LinkSelf<Employee>,
+    #[orm(link_many = Company, on_delete = "unset")]
+    clients: LinkMany<Company>,
+}
+
+#[test]
+fn test_node_on_delete_attributes_generate_events_on_linked_tables() {
+    assert_eq!(
+        build_definitions(Employee::define_referential_actions()),
+        "DEFINE EVENT employee_company_on_delete ON TABLE company WHEN $event = 'DELETE' THEN DELETE employee WHERE company = $before.id;
+DEFINE EVENT employee_manager_on_delete ON TABLE employee WHEN $event = 'DELETE' THEN UPDATE employee SET manager = NULL WHERE manager = $before.id;
+DEFINE EVENT employee_clients_on_delete ON TABLE company WHEN $event = 'DELETE' THEN UPDATE employee SET clients -= $before.id WHERE clients CONTAINS $before.id;"
+    );
+    assert!(Company::define_referential_actions().is_empty());
+
+    assert_eq!(
+        select_dangling_links::<Employee>()
+            .expect("employee has link fields")
+            .to_raw()
+            .build(),
+        "SELECT id, company, manager, clients FROM employee WHERE \
+        (company != NONE AND company != NULL AND company.id = NONE) OR \
+        (manager != NONE AND manager != NULL AND manager.id = NONE) OR \
+        (clients.*.id CONTAINS NONE);"
+    );
+    assert!(select_dangling_links::<Company>().is_none());
+}
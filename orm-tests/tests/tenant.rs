@@ -0,0 +1,324 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use pretty_assertions::assert_eq;
+use serde::{Deserialize, Serialize};
+use surreal_orm::{
+    statements::{delete, relate, select, update},
+    *,
+};
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = "tenant_account", tenant_key = org)]
+pub struct TenantAccount {
+    pub id: SurrealSimpleId<Self>,
+    pub org: String,
+    pub name: String,
+
+    #[orm(relate(model = AccountAuthoredPost, connection = "->authored->tenant_post"))]
+    #[serde(skip_serializing, default)]
+    pub posts: Relate<TenantPost>,
+}
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = "tenant_post", tenant_key = org)]
+pub struct TenantPost {
+    pub id: SurrealSimpleId<Self>,
+    pub org: String,
+    pub title: String,
+}
+
+#[derive(Edge, Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[orm(table = "authored", tenant_key = org)]
+pub struct Authored<In: Node, Out: Node> {
+    pub id: SurrealSimpleId<Self>,
+
+    #[serde(rename = "in", skip_serializing)]
+    #[orm(link_one = In)]
+    pub in_: LinkOne<In>,
+
+    #[serde(skip_serializing)]
+    #[orm(link_one = Out)]
+    pub out: LinkOne<Out>,
+
+    pub org: String,
+    pub primary: bool,
+}
+
+pub type AccountAuthoredPost = Authored<TenantAccount, TenantPost>;
+
+const TENANT_KEY_CHANGE_ERROR: &str =
+    "The tenant field `org` of the tenant-scoped table `tenant_account` cannot be changed";
+
+fn scope() -> TenantScope {
+    TenantScope::new("codebreather")
+}
+
+fn register_tenant_tables() {
+    TenantTables::register::<TenantAccount>();
+    TenantTables::register::<TenantPost>();
+    TenantTables::register::<AccountAuthoredPost>();
+}
+
+fn bound_object(bindings: BindingsList) -> sql::Object {
+    bindings
+        .into_iter()
+        .find_map(|binding| match binding.get_value() {
+            sql::Value::Object(object) => Some(object.clone()),
+            _ => None,
+        })
+        .expect("object must be bound")
+}
+
+#[test]
+fn test_typed_statements_are_scoped_to_tenant() {
+    let tenant_account::Schema { name, .. } = TenantAccount::schema();
+
+    let statement = with_tenant_scope(scope(), || {
+        TenantAccount::find_where(name.equal("Oyelowo")).limit(10)
+    });
+    assert_eq!(
+        statement.to_raw().build(),
+        "SELECT * FROM tenant_account WHERE (name = 'Oyelowo') AND (org = 'codebreather') LIMIT 10;"
+    );
+
+    let statement = with_tenant_scope(scope(), || {
+        delete::<TenantAccount>(TenantAccount::table()).where_(name.equal("Oyelowo"))
+    });
+    assert_eq!(
+        statement.to_raw().build(),
+        "DELETE tenant_account WHERE (name = 'Oyelowo') AND (org = 'codebreather');"
+    );
+    assert!(statement.get_errors().is_empty());
+}
+
+#[test]
+fn test_untyped_selects_are_scoped_by_table_and_record_id() {
+    register_tenant_tables();
+    let tenant_account::Schema { name, .. } = TenantAccount::schema();
+
+    let statement = with_tenant_scope(scope(), || {
+        select(All)
+            .from(TenantAccount::table())
+            .where_(name.equal("Oyelowo"))
+            .where_(name.equal("Oyedayo"))
+    });
+    assert_eq!(
+        statement.to_raw().build(),
+        "SELECT * FROM tenant_account WHERE (name = 'Oyedayo') AND (org = 'codebreather');"
+    );
+
+    let statement = with_tenant_scope(scope(), || {
+        select(All).from(TenantAccount::create_thing("oyelowo"))
+    });
+    assert_eq!(
+        statement.to_raw().build(),
+        "SELECT * FROM tenant_account:oyelowo WHERE org = 'codebreather';"
+    );
+
+    let statement = with_tenant_scope(scope(), || {
+        TenantAccount::find_by_id(TenantAccount::create_id("oyelowo"))
+    });
+    assert_eq!(
+        statement.to_raw().build(),
+        "SELECT * FROM tenant_account:oyelowo WHERE org = 'codebreather';"
+    );
+}
+
+#[test]
+fn test_untyped_selects_from_unknown_tables_are_refused_within_tenant_scope() {
+    register_tenant_tables();
+    TenantTables::register_table("tenant_country");
+
+    let statement = with_tenant_scope(scope(), || select(All).from(Table::new("tenant_country")));
+    assert_eq!(statement.to_raw().build(), "SELECT * FROM tenant_country;");
+    assert!(statement.get_errors().is_empty());
+
+    let statement = with_tenant_scope(scope(), || {
+        select(All).from(&[&TenantPost::table(), &Table::new("tenant_unknown")])
+    });
+    assert_eq!(
+        statement.get_errors(),
+        vec![
+            "Table `tenant_unknown` is not registered with `TenantTables` and cannot be read from \
+            while a tenant scope is active. Register its model with `TenantTables::register`"
+        ]
+    );
+
+    let statement = with_tenant_scope(scope(), || select(All).from(Param::new("accounts")));
+    assert_eq!(
+        statement.get_errors(),
+        vec![
+            "`$accounts` cannot be read from while a tenant scope is active because the table \
+            it reads from is not known. Read from a registered table or a record id instead"
+        ]
+    );
+
+    let statement = select(All).from(Table::new("tenant_unknown"));
+    assert!(statement.get_errors().is_empty());
+}
+
+#[test]
+fn test_graph_traversals_are_scoped_to_tenant() {
+    let authored::Schema { primary, .. } = AccountAuthoredPost::schema();
+
+    let traversal = with_tenant_scope(scope(), || {
+        TenantAccount::with(TenantAccount::create_id("oyelowo"))
+            .authored__(primary.equal(true))
+            .tenant_post(E)
+    });
+    let traversal = traversal.to_raw().build();
+
+    assert!(
+        traversal
+            .contains("SELECT VALUE id FROM tenant_account:oyelowo WHERE org = 'codebreather'"),
+        "The starting record is narrowed down to the tenant: {traversal}"
+    );
+    assert!(
+        traversal.contains("->authored[WHERE (primary = true) AND (org = 'codebreather')]"),
+        "The edge is filtered by the tenant: {traversal}"
+    );
+    assert!(
+        traversal.ends_with("->tenant_post[WHERE org = 'codebreather']"),
+        "The node is filtered by the tenant: {traversal}"
+    );
+}
+
+#[test]
+fn test_relate_only_connects_records_of_tenant() {
+    let statement = with_tenant_scope(scope(), || {
+        relate::<AccountAuthoredPost>(
+            TenantAccount::with(TenantAccount::create_id("oyelowo"))
+                .authored__(E)
+                .tenant_post(TenantPost::create_id("surrealdb")),
+        )
+    });
+    assert!(statement.get_errors().is_empty());
+
+    let statement = statement.to_raw().build();
+    assert!(statement
+        .contains("SELECT VALUE id FROM tenant_account:oyelowo WHERE org = 'codebreather'"));
+    assert!(statement.contains("->authored->"));
+    assert!(
+        statement.contains("SELECT VALUE id FROM tenant_post:surrealdb WHERE org = 'codebreather'")
+    );
+    assert!(statement.contains("SET org = 'codebreather'"));
+}
+
+#[test]
+fn test_content_merge_and_replace_set_tenant_field() {
+    let account = TenantAccount {
+        id: TenantAccount::create_id("oyelowo"),
+        org: "someone-else".into(),
+        name: "Oyelowo".into(),
+        posts: Default::default(),
+    };
+
+    let statement = with_tenant_scope(scope(), || account.save());
+    assert_eq!(
+        statement.fine_tune_params(),
+        "UPDATE $_param_00000001 CONTENT  $_param_00000002 WHERE org = $_param_00000003;"
+    );
+    let content = bound_object(statement.get_bindings());
+    assert_eq!(content.get("org"), Some(&sql::Value::from("codebreather")));
+
+    let change = serde_json::json!({ "name": "Oyedayo", "org": "someone-else" });
+
+    let statement = with_tenant_scope(scope(), || {
+        update::<TenantAccount>(TenantAccount::create_id("oyelowo")).merge(change.clone())
+    });
+    let merge = bound_object(statement.get_bindings());
+    assert_eq!(merge.get("org"), Some(&sql::Value::from("codebreather")));
+    assert!(statement.get_errors().is_empty());
+
+    let statement = with_tenant_scope(scope(), || {
+        update::<TenantAccount>(TenantAccount::create_id("oyelowo")).replace(change.clone())
+    });
+    let replacement = bound_object(statement.get_bindings());
+    assert_eq!(
+        replacement.get("org"),
+        Some(&sql::Value::from("codebreather"))
+    );
+    assert!(statement.get_errors().is_empty());
+}
+
+#[test]
+fn test_set_and_patch_cannot_change_tenant_field() {
+    let tenant_account::Schema { name, org, .. } = TenantAccount::schema();
+
+    let statement = with_tenant_scope(scope(), || {
+        update::<TenantAccount>(TenantAccount::create_id("oyelowo")).set(name.equal_to("Oyedayo"))
+    });
+    assert!(statement.get_errors().is_empty());
+    assert!(statement
+        .to_raw()
+        .build()
+        .contains("SET name = 'Oyedayo', org = 'codebreather'"));
+
+    let statement = with_tenant_scope(scope(), || {
+        update::<TenantAccount>(TenantAccount::create_id("oyelowo"))
+            .set([name.equal_to("Oyedayo"), org.equal_to("someone-else")])
+    });
+    assert_eq!(statement.get_errors(), vec![TENANT_KEY_CHANGE_ERROR]);
+
+    let statement = with_tenant_scope(scope(), || {
+        update::<TenantAccount>(TenantAccount::create_id("oyelowo"))
+            .patch(org.patch_replace("someone-else"))
+    });
+    assert_eq!(statement.get_errors(), vec![TENANT_KEY_CHANGE_ERROR]);
+
+    let statement = with_tenant_scope(scope(), || {
+        update::<TenantAccount>(TenantAccount::create_id("oyelowo"))
+            .patch(name.patch_replace("Oyedayo"))
+    });
+    assert!(statement.get_errors().is_empty());
+}
+
+#[test]
+fn test_statements_without_tenant_scope_are_erroneous() {
+    register_tenant_tables();
+    assert!(TenantScope::current().is_none());
+    let missing_scope_error =
+        "Table `tenant_account` is tenant-scoped by `org` but no tenant scope is active. \
+        Build the statement within `with_tenant_scope`";
+
+    let statement = update::<TenantAccount>(TenantAccount::table()).merge(serde_json::json!({}));
+    assert_eq!(statement.get_errors(), vec![missing_scope_error]);
+
+    let statement = select(All).from(TenantAccount::table());
+    assert_eq!(statement.get_errors(), vec![missing_scope_error]);
+
+    assert_eq!(TenantAccount::count_all().get_errors().len(), 1);
+    assert_eq!(
+        TenantAccount::find_by_id(TenantAccount::create_id("oyelowo"))
+            .get_errors()
+            .len(),
+        1
+    );
+    assert!(!TenantAccount::with(TenantAccount::create_id("oyelowo"))
+        .get_errors()
+        .is_empty());
+}
+
+#[test]
+fn test_scope_is_restored() {
+    with_tenant_scope(scope(), || {
+        with_tenant_scope(TenantScope::new("oyelowo"), || {
+            assert_eq!(
+                TenantScope::current().unwrap().tenant(),
+                &sql::Value::from("oyelowo")
+            );
+        });
+        assert_eq!(
+            TenantScope::current().unwrap().tenant(),
+            &sql::Value::from("codebreather")
+        );
+    });
+    assert!(TenantScope::current().is_none());
+}
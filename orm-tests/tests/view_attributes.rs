@@ -0,0 +1,133 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use pretty_assertions::assert_eq;
+use serde::{Deserialize, Serialize};
+use surreal_orm::*;
+use surrealdb::{engine::local::Mem, Surreal};
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone)]
+#[orm(table = "purchase")]
+struct Purchase {
+    id: SurrealSimpleId<Self>,
+    customer: String,
+    amount: f64,
+}
+
+#[derive(View, Serialize, Deserialize, Debug, Clone)]
+#[orm(
+    table = "customer_totals",
+    view(from = Purchase, where_ = "amount > 0", group_by = customer)
+)]
+struct CustomerTotals {
+    customer: String,
+    #[orm(expr = count!())]
+    purchases: u64,
+    #[orm(expr = "math::sum(amount)")]
+    total: f64,
+}
+
+#[tokio::test]
+async fn test_view_attributes_generate_as_select_table() -> SurrealOrmResult<()> {
+    assert_eq!(
+        CustomerTotals::view_statement().to_raw().build(),
+        "SELECT customer AS customer, count() AS purchases, math::sum(amount) AS total FROM purchase \
+        WHERE amount > 0 GROUP BY customer;"
+    );
+    assert_eq!(
+        CustomerTotals::table_definition().build(),
+        "DEFINE TABLE customer_totals AS \n\tSELECT customer AS customer, count() AS purchases, \
+        math::sum(amount) AS total FROM purchase WHERE amount > 0 GROUP BY customer;"
+    );
+    assert_eq!(
+        CustomerTotals::get_serializable_fields()
+            .iter()
+            .map(|field| field.build())
+            .collect::<Vec<_>>(),
+        vec!["customer", "purchases", "total"]
+    );
+    assert!(CustomerTotals::fields_definitions().is_empty());
+
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    CustomerTotals::table_definition().run(db.clone()).await?;
+
+    for (customer, amount) in [("Oyelowo", 10.0), ("Oyelowo", 5.0), ("Oyedayo", 7.0)] {
+        Purchase {
+            id: Purchase::create_simple_id(),
+            customer: customer.into(),
+            amount,
+        }
+        .create()
+        .get_one(db.clone())
+        .await?;
+    }
+
+    let mut totals: Vec<CustomerTotals> =
+        CustomerTotals::find_all().return_many(db.clone()).await?;
+    totals.sort_by(|a, b| a.customer.cmp(&b.customer));
+
+    assert_eq!(totals.len(), 2);
+    assert_eq!(totals[0].customer, "Oyedayo");
+    assert_eq!(totals[0].purchases, 1);
+    assert_eq!(totals[1].customer, "Oyelowo");
+    assert_eq!(totals[1].purchases, 2);
+    assert_eq!(totals[1].total, 15.0);
+
+    Ok(())
+}
+
+#[derive(View, Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+#[orm(table = "customer_largest_purchases", view(from = Purchase, group_by = customer))]
+struct CustomerLargestPurchases {
+    customer: String,
+    #[orm(expr = "math::max(amount)")]
+    largest_amount: f64,
+}
+
+#[tokio::test]
+async fn test_view_projections_are_aliased_to_the_renamed_fields() -> SurrealOrmResult<()> {
+    assert_eq!(
+        CustomerLargestPurchases::view_statement().to_raw().build(),
+        "SELECT customer AS Customer, math::max(amount) AS LargestAmount FROM purchase \
+        GROUP BY Customer;"
+    );
+    assert_eq!(
+        CustomerLargestPurchases::get_serializable_fields()
+            .iter()
+            .map(|field| field.build())
+            .collect::<Vec<_>>(),
+        vec!["Customer", "LargestAmount"]
+    );
+
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    CustomerLargestPurchases::table_definition()
+        .run(db.clone())
+        .await?;
+
+    for amount in [10.0, 5.0] {
+        Purchase {
+            id: Purchase::create_simple_id(),
+            customer: "Oyelowo".into(),
+            amount,
+        }
+        .create()
+        .get_one(db.clone())
+        .await?;
+    }
+
+    let purchases: Vec<CustomerLargestPurchases> = CustomerLargestPurchases::find_all()
+        .return_many(db.clone())
+        .await?;
+
+    assert_eq!(purchases.len(), 1);
+    assert_eq!(purchases[0].customer, "Oyelowo");
+    assert_eq!(purchases[0].largest_amount, 10.0);
+
+    Ok(())
+}
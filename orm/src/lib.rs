@@ -199,6 +199,9 @@ pub use surreal_derive::Node;
 #[doc = include_str!("docs/object_field_attributes.md")]
 pub use surreal_derive::Object;
 
+#[doc = include_str!("docs/view_description.md")]
+pub use surreal_derive::View;
+
 pub use surreal_derive::pick;
 pub use surreal_derive::Pickable;
 
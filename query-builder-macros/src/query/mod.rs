@@ -5,6 +5,7 @@
  */
 
 use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use proc_macros_helpers::get_crate_name;
 use quote::ToTokens;
 use surreal_query_builder::sql;
 use syn::parse::{Parse, ParseStream};
@@ -95,7 +96,7 @@ fn parse_args(input: TokenStream) -> Result<(Expr, Vec<String>, Vec<Binding>)> {
     };
 
     for query in &queries {
-        let sql = sql::parse(query.trim());
+        let sql = sql::parse(query_with_consts_spliced(query).trim());
         match sql {
             Ok(value) => value,
             Err(value) => {
@@ -211,6 +212,68 @@ fn validate_and_parse_sql_query(query: &str, bindings: &[Binding]) -> syn::Resul
     Ok(query.to_owned())
 }
 
+/// A part of a query string. Constants e.g the names generated by the derives are spliced
+/// into a query string as `{{path}}` e.g `"SELECT {{user::fields::EMAIL}} FROM {{User::TABLE}}"`.
+enum QueryPart {
+    Literal(String),
+    Const(syn::Path),
+}
+
+fn query_parts(query: &str) -> Vec<QueryPart> {
+    let mut parts = vec![];
+    let mut rest = query;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + len + 2;
+
+        // Anything other than a path e.g a nested object is left in the query as is
+        match syn::parse_str::<syn::Path>(rest[start + 2..start + 2 + len].trim()) {
+            Ok(path) => {
+                parts.push(QueryPart::Literal(rest[..start].to_string()));
+                parts.push(QueryPart::Const(path));
+            }
+            Err(_) => parts.push(QueryPart::Literal(rest[..end].to_string())),
+        }
+        rest = &rest[end..];
+    }
+    parts.push(QueryPart::Literal(rest.to_string()));
+
+    parts
+        .into_iter()
+        .filter(|part| !matches!(part, QueryPart::Literal(literal) if literal.is_empty()))
+        .collect()
+}
+
+/// The query checked at compile time with an identifier in place of each spliced constant
+fn query_with_consts_spliced(query: &str) -> String {
+    query_parts(query)
+        .into_iter()
+        .map(|part| match part {
+            QueryPart::Literal(literal) => literal,
+            QueryPart::Const(_) => "spliced_const".to_string(),
+        })
+        .collect()
+}
+
+fn query_tokens(query: &str) -> TokenStream {
+    let parts = query_parts(query);
+
+    if !parts.iter().any(|part| matches!(part, QueryPart::Const(_))) {
+        return quote::quote!(#query);
+    }
+
+    let crate_name = get_crate_name(false);
+    let parts = parts.iter().map(|part| match part {
+        QueryPart::Literal(literal) => quote::quote!(#literal),
+        QueryPart::Const(path) => quote::quote!(#path),
+    });
+
+    quote::quote!(#crate_name::concat_const!(#( #parts ),*))
+}
+
 pub fn query(args: TokenStream) -> TokenStream {
     let (db_con, query_strs, bindings) = parse_args(args).expect("Failed to parse arguments");
     let mut output = TokenStream::new();
@@ -219,6 +282,7 @@ pub fn query(args: TokenStream) -> TokenStream {
         .expect("Failed to validate SQL query");
 
     for (i, query_str) in query_strs.iter().enumerate() {
+        let query_str = query_tokens(query_str);
         let is_first = i == 0;
         if is_first {
             output.extend(quote::quote! {
@@ -297,6 +361,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_query_splices_constants() {
+        let input = quote! {
+            my_db, "SELECT {{user::fields::EMAIL}} FROM {{ User::TABLE }} WHERE age > $age", {
+                age: 18
+            }
+        };
+
+        assert_eq!(
+            query(input).to_string(),
+            quote! {
+                my_db.query(surreal_orm::concat_const!(
+                    "SELECT ",
+                    user::fields::EMAIL,
+                    " FROM ",
+                    User::TABLE,
+                    " WHERE age > $age"
+                ))
+                .bind(("age", 18))
+            }
+            .to_string()
+        );
+        assert_eq!(
+            query_with_consts_spliced("UPDATE {{User::TABLE}} CONTENT {{ \"nested\": true }}"),
+            "UPDATE spliced_const CONTENT {{ \"nested\": true }}"
+        );
+    }
+
     #[test]
     fn test_parse_args_unexpected_tokens() {
         let input = quote! {
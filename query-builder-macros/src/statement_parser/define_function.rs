@@ -93,7 +93,7 @@ impl FieldTypeParser {
             FieldType::Option(_) => {
                 quote!(::std::option::Option<#crate_name::ValueLike>)
             }
-            FieldType::Union(_) => quote!(#crate_name::ValueLike),
+            FieldType::Union(_) | FieldType::Literal(_) => quote!(#crate_name::ValueLike),
             FieldType::Set(_, _) => quote!(#crate_name::SetLike),
         }
     }
@@ -0,0 +1,120 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use crate::{BindingsList, Buildable, Conditional, Erroneous, ErrorList, Parametric, ReturnType};
+
+use super::{
+    expr::{and_condition, combined_condition},
+    Expr, Target,
+};
+
+/// Structured form of a `DELETE` statement.
+#[derive(Debug, Clone)]
+pub struct Delete {
+    pub(crate) target: Target,
+    pub(crate) is_only: bool,
+    pub(crate) where_: Option<Expr>,
+    pub(crate) scope: Vec<Expr>,
+    pub(crate) return_type: Option<ReturnType>,
+    pub(crate) timeout: Option<String>,
+    pub(crate) parallel: bool,
+    pub(crate) errors: ErrorList,
+}
+
+impl Delete {
+    pub(crate) fn new(target: Target) -> Self {
+        Self {
+            target,
+            is_only: false,
+            where_: None,
+            scope: vec![],
+            return_type: None,
+            timeout: None,
+            parallel: false,
+            errors: vec![],
+        }
+    }
+
+    /// The table or record being deleted.
+    pub fn target(&self) -> &Target {
+        &self.target
+    }
+
+    /// The `WHERE` condition, if any.
+    pub fn condition(&self) -> Option<&Expr> {
+        self.where_.as_ref()
+    }
+
+    /// Replaces the `WHERE` condition.
+    pub fn set_where(&mut self, condition: impl Conditional) -> &mut Self {
+        self.where_ = Some(Expr::condition(condition));
+        self
+    }
+
+    /// Adds a condition that must hold in addition to the existing `WHERE` condition.
+    pub fn and_where(&mut self, condition: impl Conditional) -> &mut Self {
+        and_condition(&mut self.where_, condition);
+        self
+    }
+
+    /// Conditions that always apply in addition to the `WHERE` condition e.g the tenant
+    /// condition. Unlike the `WHERE` condition, they are kept when `where_` is called again.
+    pub fn scope_conditions(&self) -> &[Expr] {
+        &self.scope
+    }
+
+    /// Adds a condition that is always applied in addition to the `WHERE` condition.
+    pub fn add_scope_condition(&mut self, condition: impl Conditional) -> &mut Self {
+        self.scope.push(Expr::condition(condition));
+        self
+    }
+}
+
+impl Buildable for Delete {
+    fn build(&self) -> String {
+        let mut query = format!("DELETE {}", self.target.build());
+
+        if self.is_only {
+            query = format!("{query} ONLY");
+        }
+
+        if let Some(condition) = combined_condition(self.where_.as_ref(), &self.scope) {
+            query = format!("{query} WHERE {condition}");
+        }
+
+        if let Some(return_type) = &self.return_type {
+            query = format!("{query} {return_type}");
+        }
+
+        if let Some(timeout) = &self.timeout {
+            query = format!("{query} TIMEOUT {timeout}");
+        }
+
+        if self.parallel {
+            query = format!("{query} PARALLEL");
+        }
+
+        format!("{query};")
+    }
+}
+
+impl Parametric for Delete {
+    fn get_bindings(&self) -> BindingsList {
+        let mut bindings = self.target.get_bindings();
+        bindings.extend(self.where_.iter().flat_map(Parametric::get_bindings));
+        bindings.extend(self.scope.iter().flat_map(Parametric::get_bindings));
+        bindings
+    }
+}
+
+impl Erroneous for Delete {
+    fn get_errors(&self) -> ErrorList {
+        let mut errors = self.errors.to_vec();
+        errors.extend(self.where_.iter().flat_map(Erroneous::get_errors));
+        errors.extend(self.scope.iter().flat_map(Erroneous::get_errors));
+        errors
+    }
+}
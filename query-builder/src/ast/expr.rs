@@ -0,0 +1,176 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use std::fmt::{self, Display};
+
+use surrealdb::sql;
+
+use crate::{
+    Binding, BindingsList, Buildable, Conditional, Erroneous, ErrorList, Filter, Parametric,
+};
+
+/// An expression of a statement e.g a condition, a limit or a subquery target.
+#[derive(Debug, Clone)]
+pub enum Expr {
+    /// A bound value e.g the `$_param_1` generated for `.limit(10)`.
+    Param(Binding),
+    /// Conditions that must all hold, rendered as `(a) AND (b)`.
+    And(Vec<Expr>),
+    /// An expression as rendered by the builders e.g `age > $_param_1` or a subquery,
+    /// with the bindings and errors it references. Its structure can be inspected
+    /// with [`Expr::parse`].
+    Rendered {
+        /// The rendered expression.
+        query: String,
+        /// The bindings referenced by the expression.
+        bindings: BindingsList,
+        /// Errors raised while building the expression.
+        errors: ErrorList,
+    },
+}
+
+impl Expr {
+    /// Creates an expression from a raw query string that has no bindings.
+    pub fn new(query: impl Into<String>) -> Self {
+        Self::Rendered {
+            query: query.into(),
+            bindings: vec![],
+            errors: vec![],
+        }
+    }
+
+    /// Creates an expression from any buildable value, keeping its bindings and errors.
+    /// A value rendered as a single parameter becomes an [`Expr::Param`].
+    pub fn from_buildable(value: &(impl Buildable + Parametric + Erroneous)) -> Self {
+        let query = value.build();
+        let bindings = value.get_bindings();
+        let errors = value.get_errors();
+
+        match bindings.as_slice() {
+            [binding] if errors.is_empty() && binding.get_param_dollarised() == query => {
+                Self::Param(binding.clone())
+            }
+            _ => Self::Rendered {
+                query,
+                bindings,
+                errors,
+            },
+        }
+    }
+
+    /// Creates a `WHERE` condition.
+    pub(crate) fn condition(condition: impl Conditional) -> Self {
+        Self::from_buildable(&Filter::new(condition))
+    }
+
+    /// Returns the bound value if the expression is a single parameter e.g the `$_param_1`
+    /// generated for `.limit(10)`. Returns `None` for fields, functions and other expressions.
+    pub fn value(&self) -> Option<&sql::Value> {
+        match self {
+            Self::Param(binding) => Some(binding.get_value()),
+            _ => None,
+        }
+    }
+
+    /// The conditions that must all hold for this condition to hold.
+    /// A condition that is not a conjunction is its only conjunct.
+    pub fn conjuncts(&self) -> Vec<&Expr> {
+        match self {
+            Self::And(conditions) => conditions.iter().flat_map(Expr::conjuncts).collect(),
+            _ => vec![self],
+        }
+    }
+
+    /// Parses the expression into SurrealDB's syntax tree, e.g to find the idioms a
+    /// condition reads. Parameters are left as [`sql::Param`]s which can be looked up in
+    /// the bindings of the expression. Returns `None` if the expression is not valid on
+    /// its own.
+    pub fn parse(&self) -> Option<sql::Value> {
+        match self {
+            Self::Param(binding) => Some(sql::Value::Param(sql::Param::from(
+                binding.get_param().to_string(),
+            ))),
+            _ => sql::value(&self.build()).ok(),
+        }
+    }
+
+    /// Returns true if the expression renders to nothing.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Param(_) => false,
+            Self::And(conditions) => conditions.iter().all(Expr::is_empty),
+            Self::Rendered { query, .. } => query.trim().is_empty(),
+        }
+    }
+}
+
+/// Adds a condition to an optional `WHERE` clause, joining it to the existing one with `AND`.
+pub(crate) fn and_condition(where_: &mut Option<Expr>, condition: impl Conditional) {
+    let condition = Expr::condition(condition);
+    *where_ = match where_.take() {
+        Some(existing) if !existing.is_empty() => Some(Expr::And(vec![existing, condition])),
+        _ => Some(condition),
+    };
+}
+
+/// Joins the `WHERE` condition and the scope conditions of a statement with `AND`.
+pub(crate) fn combined_condition(where_: Option<&Expr>, scope: &[Expr]) -> Option<String> {
+    let conditions = where_
+        .into_iter()
+        .chain(scope)
+        .filter(|condition| !condition.is_empty())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    match conditions.as_slice() {
+        [] => None,
+        [condition] => Some(condition.build()),
+        _ => Some(Expr::And(conditions).build()),
+    }
+}
+
+impl Buildable for Expr {
+    fn build(&self) -> String {
+        match self {
+            Self::Param(binding) => binding.get_param_dollarised(),
+            Self::And(conditions) => conditions
+                .iter()
+                .filter(|condition| !condition.is_empty())
+                .map(|condition| format!("({condition})"))
+                .collect::<Vec<_>>()
+                .join(" AND "),
+            Self::Rendered { query, .. } => query.to_string(),
+        }
+    }
+}
+
+impl Parametric for Expr {
+    fn get_bindings(&self) -> BindingsList {
+        match self {
+            Self::Param(binding) => vec![binding.clone()],
+            Self::And(conditions) => conditions.iter().flat_map(Expr::get_bindings).collect(),
+            Self::Rendered { bindings, .. } => bindings.to_vec(),
+        }
+    }
+}
+
+impl Erroneous for Expr {
+    fn get_errors(&self) -> ErrorList {
+        match self {
+            Self::Param(_) => vec![],
+            Self::And(conditions) => conditions.iter().flat_map(Expr::get_errors).collect(),
+            Self::Rendered { errors, .. } => errors.to_vec(),
+        }
+    }
+}
+
+impl Conditional for Expr {}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.build())
+    }
+}
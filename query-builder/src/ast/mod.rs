@@ -0,0 +1,59 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+//! Structured representation of the `SELECT`, `UPDATE`, `UPSERT` and `DELETE` statements
+//! produced by the builders.
+//!
+//! These statements keep their targets, conditions and limits as nodes which are rendered
+//! only when the statement is built. This makes it possible to inspect and rewrite
+//! statements before they are run, for example to enforce a tenant filter or a maximum
+//! page size across an application.
+//!
+//! Bound values are [`Expr::Param`] nodes and conditions joined with `AND` are
+//! [`Expr::And`] nodes. Other expressions e.g `age > $_param_1`, subqueries and function
+//! calls are produced by the string builders and kept as [`Expr::Rendered`]; their
+//! structure can be inspected by parsing them into SurrealDB's syntax tree with
+//! [`Expr::parse`], but they can only be replaced as a whole.
+//!
+//! `CREATE`, `INSERT`, `RELATE`, `DEFINE` and the other statements are not part of the
+//! AST yet and are rendered directly by their builders.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use surreal_query_builder as surreal_orm;
+//! use surreal_orm::{*, ast::{Mutator, Select, Visitable}, statements::select};
+//!
+//! struct MaxPageSize(u64);
+//!
+//! impl Mutator for MaxPageSize {
+//!     fn mutate_select(&mut self, select: &mut Select) {
+//!         select.enforce_limit(self.0);
+//!     }
+//! }
+//!
+//! let statement = select(All)
+//!     .from(Table::new("user"))
+//!     .limit(1000)
+//!     .mutated(&mut MaxPageSize(100));
+//!
+//! assert_eq!(statement.to_raw().build(), "SELECT * FROM user LIMIT 100;");
+//! ```
+
+mod delete;
+mod expr;
+mod select;
+mod target;
+mod update;
+mod visitor;
+
+pub use delete::Delete;
+pub use expr::Expr;
+pub(crate) use select::{ExplainMode, WithIndexType};
+pub use select::{Select, SelectKind};
+pub use target::Target;
+pub use update::Update;
+pub use visitor::{walk_delete, walk_select, walk_update, Mutator, Visitable, Visitor};
@@ -0,0 +1,347 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use surrealdb::sql;
+
+use crate::{
+    statements::Order,
+    tenant::{opaque_target_error, unregistered_table_error},
+    BindingsList, Buildable, Conditional, Erroneous, ErrorList, IndexName, NumberLike, Parametric,
+};
+
+use super::{
+    expr::{and_condition, combined_condition},
+    Expr, Target,
+};
+
+/// The flavour of a select statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectKind {
+    /// `SELECT`
+    Select,
+    /// `SELECT VALUE`
+    Value,
+    /// `SELECT DIFF`
+    Diff,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum WithIndexType {
+    NoIndex,
+    Indexes(Vec<IndexName>),
+}
+
+impl Parametric for WithIndexType {
+    fn get_bindings(&self) -> BindingsList {
+        match self {
+            WithIndexType::NoIndex => vec![],
+            WithIndexType::Indexes(indexes) => indexes
+                .iter()
+                .flat_map(|i| i.get_bindings())
+                .collect::<Vec<_>>(),
+        }
+    }
+}
+
+impl Buildable for WithIndexType {
+    fn build(&self) -> String {
+        match self {
+            WithIndexType::NoIndex => "WITH NO INDEX".into(),
+            WithIndexType::Indexes(indexes) => format!(
+                "WITH INDEX {}",
+                indexes
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum ExplainMode {
+    Simple,
+    Full,
+}
+
+/// Structured form of a `SELECT` statement.
+///
+/// Targets, the condition, the limit and the start offset are kept as separate nodes so
+/// that they can be inspected and rewritten. The remaining clauses are kept as rendered.
+#[derive(Debug, Clone)]
+pub struct Select {
+    pub(crate) kind: SelectKind,
+    pub(crate) projections: String,
+    pub(crate) targets: Vec<Target>,
+    pub(crate) only: bool,
+    pub(crate) where_: Option<Expr>,
+    pub(crate) scope: Vec<Expr>,
+    // Tables the tenant condition has been added for, so that it is only added once.
+    pub(crate) tenant_tables: Vec<String>,
+    // Tables not registered with `TenantTables` and targets whose table is not known,
+    // read from while a tenant scope is active.
+    pub(crate) unregistered_tables: Vec<String>,
+    pub(crate) opaque_targets: Vec<String>,
+    pub(crate) split: Vec<String>,
+    pub(crate) group_by: Vec<String>,
+    pub(crate) group_all: bool,
+    pub(crate) order_by: Vec<Order>,
+    pub(crate) limit: Option<Expr>,
+    pub(crate) start: Option<Expr>,
+    pub(crate) fetch: Vec<String>,
+    pub(crate) omitted_fields: Vec<String>,
+    pub(crate) with_index_type: Option<WithIndexType>,
+    pub(crate) timeout: Option<String>,
+    pub(crate) parallel: bool,
+    pub(crate) explain_mode: Option<ExplainMode>,
+    // Bindings of the clauses that are kept as rendered i.e projections, omit, order, fetch and with index.
+    pub(crate) bindings: BindingsList,
+    pub(crate) errors: ErrorList,
+}
+
+impl Select {
+    pub(crate) fn new(kind: SelectKind, projections: String) -> Self {
+        Self {
+            kind,
+            projections,
+            targets: vec![],
+            only: false,
+            where_: None,
+            scope: vec![],
+            tenant_tables: vec![],
+            unregistered_tables: vec![],
+            opaque_targets: vec![],
+            split: vec![],
+            group_by: vec![],
+            group_all: false,
+            order_by: vec![],
+            limit: None,
+            start: None,
+            fetch: vec![],
+            omitted_fields: vec![],
+            with_index_type: None,
+            timeout: None,
+            parallel: false,
+            explain_mode: None,
+            bindings: vec![],
+            errors: vec![],
+        }
+    }
+
+    /// The flavour of the select statement.
+    pub fn kind(&self) -> SelectKind {
+        self.kind
+    }
+
+    /// The targets of the `FROM` clause.
+    pub fn targets(&self) -> &[Target] {
+        &self.targets
+    }
+
+    /// Names of the tables read from, directly or through record ids.
+    pub fn tables(&self) -> Vec<&str> {
+        self.targets.iter().filter_map(Target::table_name).collect()
+    }
+
+    /// The `WHERE` condition, if any.
+    pub fn condition(&self) -> Option<&Expr> {
+        self.where_.as_ref()
+    }
+
+    /// Replaces the `WHERE` condition.
+    pub fn set_where(&mut self, condition: impl Conditional) -> &mut Self {
+        self.where_ = Some(Expr::condition(condition));
+        self
+    }
+
+    /// Adds a condition that must hold in addition to the existing `WHERE` condition.
+    pub fn and_where(&mut self, condition: impl Conditional) -> &mut Self {
+        and_condition(&mut self.where_, condition);
+        self
+    }
+
+    /// Conditions that always apply in addition to the `WHERE` condition e.g the tenant
+    /// condition. Unlike the `WHERE` condition, they are kept when `where_` is called again.
+    pub fn scope_conditions(&self) -> &[Expr] {
+        &self.scope
+    }
+
+    /// Adds a condition that is always applied in addition to the `WHERE` condition.
+    pub fn add_scope_condition(&mut self, condition: impl Conditional) -> &mut Self {
+        self.scope.push(Expr::condition(condition));
+        self
+    }
+
+    /// The `LIMIT` value, if any.
+    pub fn limit(&self) -> Option<&Expr> {
+        self.limit.as_ref()
+    }
+
+    /// Replaces the `LIMIT` value.
+    pub fn set_limit(&mut self, limit: impl Into<NumberLike>) -> &mut Self {
+        let limit: NumberLike = limit.into();
+        self.limit = Some(Expr::from_buildable(&limit));
+        self
+    }
+
+    /// Makes sure the statement returns at most `max` records. A missing limit, a limit that
+    /// is not a plain number e.g a param, or a limit larger than `max` is replaced by `max`.
+    pub fn enforce_limit(&mut self, max: u64) -> &mut Self {
+        let within_max =
+            self.limit
+                .as_ref()
+                .and_then(Expr::value)
+                .is_some_and(|value| match value {
+                    sql::Value::Number(sql::Number::Int(limit)) => {
+                        u64::try_from(*limit).is_ok_and(|limit| limit <= max)
+                    }
+                    _ => false,
+                });
+
+        if !within_max {
+            self.set_limit(max);
+        }
+        self
+    }
+
+    /// The `START` offset, if any.
+    pub fn start(&self) -> Option<&Expr> {
+        self.start.as_ref()
+    }
+
+    /// Replaces the `START` offset.
+    pub fn set_start(&mut self, start: impl Into<NumberLike>) -> &mut Self {
+        let start: NumberLike = start.into();
+        self.start = Some(Expr::from_buildable(&start));
+        self
+    }
+}
+
+impl Buildable for Select {
+    fn build(&self) -> String {
+        let select = match self.kind {
+            SelectKind::Select => "SELECT",
+            SelectKind::Value => "SELECT VALUE",
+            SelectKind::Diff => "SELECT DIFF",
+        };
+
+        let omitted_fields = if !self.omitted_fields.is_empty() {
+            format!("OMIT {} ", self.omitted_fields.join(", "))
+        } else {
+            "".to_string()
+        };
+
+        let only = if self.only { "ONLY " } else { "" };
+        let mut query = format!(
+            "{select} {} {}FROM {}{}",
+            self.projections,
+            omitted_fields,
+            only,
+            self.targets
+                .iter()
+                .map(Buildable::build)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        if let Some(with_index_type) = &self.with_index_type {
+            query = format!("{query} {}", with_index_type.build());
+        }
+
+        if let Some(condition) = combined_condition(self.where_.as_ref(), &self.scope) {
+            query = format!("{query} WHERE {condition}");
+        }
+
+        if !self.split.is_empty() {
+            query = format!("{query} SPLIT {}", &self.split.join(", "));
+        }
+
+        if !self.group_by.is_empty() {
+            query = format!("{query} GROUP BY {}", &self.group_by.join(", "));
+        } else if self.group_all {
+            query = format!("{query} GROUP ALL");
+        }
+
+        if !self.order_by.is_empty() {
+            query = format!(
+                "{query} ORDER BY {}",
+                &self
+                    .order_by
+                    .iter()
+                    .map(|o| o.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+        }
+
+        if let Some(limit_value) = &self.limit {
+            query = format!("{query} LIMIT {}", limit_value);
+        }
+
+        if let Some(start_value) = &self.start {
+            query = format!("{query} START AT {}", start_value);
+        }
+
+        if !self.fetch.is_empty() {
+            query = format!("{query} FETCH {}", self.fetch.join(", "));
+        }
+
+        if let Some(timeout_value) = &self.timeout {
+            query = format!("{query} TIMEOUT {}", timeout_value);
+        }
+
+        if self.parallel {
+            query = format!("{query} PARALLEL");
+        }
+
+        if let Some(explain_mode) = &self.explain_mode {
+            match explain_mode {
+                ExplainMode::Simple => query = format!("{query} EXPLAIN"),
+                ExplainMode::Full => query = format!("{query} EXPLAIN FULL"),
+            }
+        }
+
+        format!("{query};")
+    }
+}
+
+impl Parametric for Select {
+    fn get_bindings(&self) -> BindingsList {
+        let mut bindings = self.bindings.to_vec();
+        bindings.extend(self.targets.iter().flat_map(Parametric::get_bindings));
+        bindings.extend(self.where_.iter().flat_map(Parametric::get_bindings));
+        bindings.extend(self.scope.iter().flat_map(Parametric::get_bindings));
+        bindings.extend(self.limit.iter().flat_map(Parametric::get_bindings));
+        bindings.extend(self.start.iter().flat_map(Parametric::get_bindings));
+        bindings
+    }
+}
+
+impl Erroneous for Select {
+    fn get_errors(&self) -> ErrorList {
+        let mut errors = self.errors.to_vec();
+        errors.extend(
+            self.unregistered_tables
+                .iter()
+                .map(|table| unregistered_table_error(table)),
+        );
+        errors.extend(
+            self.opaque_targets
+                .iter()
+                .map(|target| opaque_target_error(target)),
+        );
+        errors.extend(self.targets.iter().flat_map(|t| match t {
+            Target::Expression(expression) => expression.get_errors(),
+            _ => vec![],
+        }));
+        errors.extend(self.where_.iter().flat_map(Erroneous::get_errors));
+        errors.extend(self.scope.iter().flat_map(Erroneous::get_errors));
+        errors.extend(self.limit.iter().flat_map(Erroneous::get_errors));
+        errors.extend(self.start.iter().flat_map(Erroneous::get_errors));
+        errors
+    }
+}
@@ -0,0 +1,81 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use surrealdb::sql;
+
+use crate::{Binding, BindingsList, Buildable, Parametric};
+
+use super::Expr;
+
+/// What a statement reads from or writes to.
+#[derive(Debug, Clone)]
+pub enum Target {
+    /// A whole table e.g `user`.
+    Table(sql::Table),
+    /// A specific record. The record id is bound as a parameter.
+    Record(Binding),
+    /// Any other target such as a subquery, a function call or a record range.
+    /// These are kept as rendered and are opaque to visitors.
+    Expression(Expr),
+}
+
+impl Target {
+    /// The name of the table targeted, either directly or through a record id.
+    /// Returns `None` for opaque targets.
+    pub fn table_name(&self) -> Option<&str> {
+        match self {
+            Target::Table(table) => Some(table.0.as_str()),
+            Target::Record(binding) => match binding.get_value() {
+                sql::Value::Thing(thing) => Some(thing.tb.as_str()),
+                _ => None,
+            },
+            Target::Expression(_) => None,
+        }
+    }
+
+    /// Returns true if this target is the given table or a record in it.
+    pub fn is_table(&self, table: impl Into<sql::Table>) -> bool {
+        self.table_name() == Some(table.into().0.as_str())
+    }
+}
+
+impl From<sql::Table> for Target {
+    fn from(value: sql::Table) -> Self {
+        Self::Table(value)
+    }
+}
+
+impl From<sql::Thing> for Target {
+    fn from(value: sql::Thing) -> Self {
+        Self::Record(Binding::new(value))
+    }
+}
+
+impl From<Expr> for Target {
+    fn from(value: Expr) -> Self {
+        Self::Expression(value)
+    }
+}
+
+impl Buildable for Target {
+    fn build(&self) -> String {
+        match self {
+            Target::Table(table) => table.to_string(),
+            Target::Record(binding) => binding.get_param_dollarised(),
+            Target::Expression(expression) => expression.build(),
+        }
+    }
+}
+
+impl Parametric for Target {
+    fn get_bindings(&self) -> BindingsList {
+        match self {
+            Target::Table(_) => vec![],
+            Target::Record(binding) => vec![binding.clone()],
+            Target::Expression(expression) => expression.get_bindings(),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use crate::{
+    BindingsList, Buildable, Conditional, Dialect, Erroneous, ErrorList, Parametric, ReturnType,
+};
+
+use super::{
+    expr::{and_condition, combined_condition},
+    Expr, Target,
+};
+
+/// Structured form of an `UPDATE` or `UPSERT` statement.
+///
+/// The target and the condition are kept as separate nodes so that they can be inspected
+/// and rewritten. The data clauses are kept as rendered.
+#[derive(Debug, Clone)]
+pub struct Update {
+    pub(crate) upsert: bool,
+    pub(crate) target: Target,
+    pub(crate) is_only: bool,
+    pub(crate) content: Option<String>,
+    pub(crate) merge: Option<String>,
+    pub(crate) replace: Option<String>,
+    pub(crate) set: Vec<String>,
+    pub(crate) patch_ops: Vec<String>,
+    pub(crate) where_: Option<Expr>,
+    pub(crate) scope: Vec<Expr>,
+    pub(crate) return_type: Option<ReturnType>,
+    pub(crate) timeout: Option<String>,
+    pub(crate) parallel: bool,
+    // Bindings of the data clauses.
+    pub(crate) bindings: BindingsList,
+    pub(crate) errors: ErrorList,
+}
+
+impl Update {
+    pub(crate) fn new(target: Target) -> Self {
+        Self {
+            upsert: false,
+            target,
+            is_only: false,
+            content: None,
+            merge: None,
+            replace: None,
+            set: vec![],
+            patch_ops: vec![],
+            where_: None,
+            scope: vec![],
+            return_type: None,
+            timeout: None,
+            parallel: false,
+            bindings: vec![],
+            errors: vec![],
+        }
+    }
+
+    /// Returns true if the statement creates the records that do not exist.
+    pub fn is_upsert(&self) -> bool {
+        self.upsert
+    }
+
+    /// The table or record being updated.
+    pub fn target(&self) -> &Target {
+        &self.target
+    }
+
+    /// The `WHERE` condition, if any.
+    pub fn condition(&self) -> Option<&Expr> {
+        self.where_.as_ref()
+    }
+
+    /// Replaces the `WHERE` condition.
+    pub fn set_where(&mut self, condition: impl Conditional) -> &mut Self {
+        self.where_ = Some(Expr::condition(condition));
+        self
+    }
+
+    /// Adds a condition that must hold in addition to the existing `WHERE` condition.
+    pub fn and_where(&mut self, condition: impl Conditional) -> &mut Self {
+        and_condition(&mut self.where_, condition);
+        self
+    }
+
+    /// Conditions that always apply in addition to the `WHERE` condition e.g the tenant
+    /// condition. Unlike the `WHERE` condition, they are kept when `where_` is called again.
+    pub fn scope_conditions(&self) -> &[Expr] {
+        &self.scope
+    }
+
+    /// Adds a condition that is always applied in addition to the `WHERE` condition.
+    pub fn add_scope_condition(&mut self, condition: impl Conditional) -> &mut Self {
+        self.scope.push(Expr::condition(condition));
+        self
+    }
+}
+
+impl Buildable for Update {
+    fn build(&self) -> String {
+        // `UPSERT` is a 2.x statement. In 1.x, `UPDATE` also creates a targeted record.
+        let keyword = if self.upsert && Dialect::current().is_v2() {
+            "UPSERT"
+        } else {
+            "UPDATE"
+        };
+        let mut query = format!("{keyword} {}", self.target.build());
+
+        if self.is_only {
+            query = format!("{query} ONLY");
+        }
+
+        if let Some(content) = &self.content {
+            query = format!("{query} CONTENT  {content}",);
+        } else if let Some(merge) = &self.merge {
+            query = format!("{query} MERGE {merge}");
+        } else if let Some(replace) = &self.replace {
+            query = format!("{query} REPLACE {replace}");
+        } else if !self.set.is_empty() {
+            let set_vec = self.set.join(", ");
+            query = format!("{query} SET {set_vec}");
+        } else if !self.patch_ops.is_empty() {
+            let patch_vec = self.patch_ops.join(", ");
+            query = format!("{query} PATCH [{patch_vec}]");
+        }
+
+        if let Some(condition) = combined_condition(self.where_.as_ref(), &self.scope) {
+            query = format!("{query} WHERE {condition}");
+        }
+
+        if let Some(return_type) = &self.return_type {
+            query = format!("{query} {return_type}");
+        }
+
+        if let Some(timeout) = &self.timeout {
+            query = format!("{query} TIMEOUT {timeout}");
+        }
+
+        if self.parallel {
+            query.push_str(" PARALLEL");
+        }
+
+        format!("{query};")
+    }
+}
+
+impl Parametric for Update {
+    fn get_bindings(&self) -> BindingsList {
+        let mut bindings = self.target.get_bindings();
+        bindings.extend(self.bindings.to_vec());
+        bindings.extend(self.where_.iter().flat_map(Parametric::get_bindings));
+        bindings.extend(self.scope.iter().flat_map(Parametric::get_bindings));
+        bindings
+    }
+}
+
+impl Erroneous for Update {
+    fn get_errors(&self) -> ErrorList {
+        let mut errors = self.errors.to_vec();
+        errors.extend(self.where_.iter().flat_map(Erroneous::get_errors));
+        errors.extend(self.scope.iter().flat_map(Erroneous::get_errors));
+        errors
+    }
+}
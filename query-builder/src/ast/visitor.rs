@@ -0,0 +1,298 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use super::{Delete, Expr, Select, Target, Update};
+
+/// Read-only traversal of statement nodes.
+///
+/// All methods have default implementations, so only the nodes of interest
+/// need to be handled. Overriding `visit_select`, `visit_update` or `visit_delete`
+/// stops the traversal into the children of that node unless the matching
+/// `walk_*` function is called.
+pub trait Visitor {
+    /// Visits a select statement.
+    fn visit_select(&mut self, select: &Select) {
+        walk_select(self, select);
+    }
+
+    /// Visits an update or upsert statement.
+    fn visit_update(&mut self, update: &Update) {
+        walk_update(self, update);
+    }
+
+    /// Visits a delete statement.
+    fn visit_delete(&mut self, delete: &Delete) {
+        walk_delete(self, delete);
+    }
+
+    /// Visits a statement target.
+    fn visit_target(&mut self, _target: &Target) {}
+
+    /// Visits a `WHERE` condition or a scope condition.
+    fn visit_condition(&mut self, _condition: &Expr) {}
+}
+
+/// Visits the targets and the conditions of a select statement.
+pub fn walk_select<V: Visitor + ?Sized>(visitor: &mut V, select: &Select) {
+    select
+        .targets()
+        .iter()
+        .for_each(|target| visitor.visit_target(target));
+    select
+        .condition()
+        .into_iter()
+        .chain(select.scope_conditions())
+        .for_each(|condition| visitor.visit_condition(condition));
+}
+
+/// Visits the target and the conditions of an update statement.
+pub fn walk_update<V: Visitor + ?Sized>(visitor: &mut V, update: &Update) {
+    visitor.visit_target(update.target());
+    update
+        .condition()
+        .into_iter()
+        .chain(update.scope_conditions())
+        .for_each(|condition| visitor.visit_condition(condition));
+}
+
+/// Visits the target and the conditions of a delete statement.
+pub fn walk_delete<V: Visitor + ?Sized>(visitor: &mut V, delete: &Delete) {
+    visitor.visit_target(delete.target());
+    delete
+        .condition()
+        .into_iter()
+        .chain(delete.scope_conditions())
+        .for_each(|condition| visitor.visit_condition(condition));
+}
+
+/// Rewrites statement nodes in place e.g to add a tenant condition or cap a limit.
+pub trait Mutator {
+    /// Rewrites a select statement.
+    fn mutate_select(&mut self, _select: &mut Select) {}
+
+    /// Rewrites an update or upsert statement.
+    fn mutate_update(&mut self, _update: &mut Update) {}
+
+    /// Rewrites a delete statement.
+    fn mutate_delete(&mut self, _delete: &mut Delete) {}
+}
+
+/// Statements whose structure can be visited and rewritten.
+pub trait Visitable {
+    /// Passes the statement to the visitor.
+    fn accept(&self, visitor: &mut impl Visitor);
+
+    /// Passes the statement to the mutator for rewriting.
+    fn accept_mut(&mut self, mutator: &mut impl Mutator);
+
+    /// Rewrites the statement with the mutator and returns it.
+    fn mutated(mut self, mutator: &mut impl Mutator) -> Self
+    where
+        Self: Sized,
+    {
+        self.accept_mut(mutator);
+        self
+    }
+}
+
+impl Visitable for Select {
+    fn accept(&self, visitor: &mut impl Visitor) {
+        visitor.visit_select(self);
+    }
+
+    fn accept_mut(&mut self, mutator: &mut impl Mutator) {
+        mutator.mutate_select(self);
+    }
+}
+
+impl Visitable for Update {
+    fn accept(&self, visitor: &mut impl Visitor) {
+        visitor.visit_update(self);
+    }
+
+    fn accept_mut(&mut self, mutator: &mut impl Mutator) {
+        mutator.mutate_update(self);
+    }
+}
+
+impl Visitable for Delete {
+    fn accept(&self, visitor: &mut impl Visitor) {
+        visitor.visit_delete(self);
+    }
+
+    fn accept_mut(&mut self, mutator: &mut impl Mutator) {
+        mutator.mutate_delete(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        statements::{delete, select, update},
+        *,
+    };
+
+    #[derive(Default)]
+    struct TablesCollector {
+        tables: Vec<String>,
+        conditions: usize,
+    }
+
+    impl Visitor for TablesCollector {
+        fn visit_target(&mut self, target: &Target) {
+            if let Some(table) = target.table_name() {
+                self.tables.push(table.to_string());
+            }
+        }
+
+        fn visit_condition(&mut self, _condition: &Expr) {
+            self.conditions += 1;
+        }
+    }
+
+    #[derive(Default)]
+    struct ConditionFields {
+        fields: Vec<String>,
+        limit_is_param: bool,
+    }
+
+    impl Visitor for ConditionFields {
+        fn visit_select(&mut self, select: &Select) {
+            self.limit_is_param = matches!(select.limit(), Some(Expr::Param(_)));
+            walk_select(self, select);
+        }
+
+        fn visit_condition(&mut self, condition: &Expr) {
+            self.fields.extend(
+                condition
+                    .conjuncts()
+                    .into_iter()
+                    .filter_map(Expr::parse)
+                    .filter_map(compared_field),
+            );
+        }
+    }
+
+    fn compared_field(value: sql::Value) -> Option<String> {
+        match value {
+            sql::Value::Expression(expression) => match *expression {
+                sql::Expression::Binary { l, .. } => Some(l.to_string()),
+                _ => None,
+            },
+            sql::Value::Subquery(subquery) => match *subquery {
+                sql::Subquery::Value(value) => compared_field(value),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    struct TenantScope {
+        org: &'static str,
+        max_limit: u64,
+    }
+
+    impl Mutator for TenantScope {
+        fn mutate_select(&mut self, select: &mut Select) {
+            select.and_where(Field::new("org").equal(self.org));
+            select.enforce_limit(self.max_limit);
+        }
+
+        fn mutate_update(&mut self, update: &mut Update) {
+            update.and_where(Field::new("org").equal(self.org));
+        }
+
+        fn mutate_delete(&mut self, delete: &mut Delete) {
+            delete.and_where(Field::new("org").equal(self.org));
+        }
+    }
+
+    #[test]
+    fn test_visitor_collects_targets_and_conditions() {
+        let age = Field::new("age");
+        let statement = select(All)
+            .from(vec![Table::new("user"), Table::new("alien")])
+            .where_(age.greater_than(18));
+
+        let mut collector = TablesCollector::default();
+        statement.accept(&mut collector);
+
+        assert_eq!(collector.tables, vec!["user", "alien"]);
+        assert_eq!(collector.conditions, 1);
+
+        let mut collector = TablesCollector::default();
+        delete::<TestUser>(TestUser::create_id("oyelowo")).accept(&mut collector);
+
+        assert_eq!(collector.tables, vec!["user"]);
+        assert_eq!(collector.conditions, 0);
+    }
+
+    #[test]
+    fn test_visitor_inspects_conjuncts_and_params() {
+        let age = Field::new("age");
+        let mut tenant_scope = TenantScope {
+            org: "codebreather",
+            max_limit: 100,
+        };
+        let statement = select(All)
+            .from(Table::new("user"))
+            .where_(age.greater_than(18))
+            .limit(500)
+            .mutated(&mut tenant_scope);
+
+        let mut condition_fields = ConditionFields::default();
+        statement.accept(&mut condition_fields);
+
+        assert_eq!(condition_fields.fields, vec!["age", "org"]);
+        assert!(condition_fields.limit_is_param);
+    }
+
+    #[test]
+    fn test_mutator_adds_condition_and_enforces_limit() {
+        let age = Field::new("age");
+        let mut tenant_scope = TenantScope {
+            org: "codebreather",
+            max_limit: 100,
+        };
+
+        let statement = select(All)
+            .from(Table::new("user"))
+            .where_(age.greater_than(18))
+            .limit(500)
+            .mutated(&mut tenant_scope);
+        assert_eq!(
+            statement.to_raw().build(),
+            "SELECT * FROM user WHERE (age > 18) AND (org = 'codebreather') LIMIT 100;"
+        );
+
+        let statement = select(All)
+            .from(Table::new("user"))
+            .limit(20)
+            .mutated(&mut tenant_scope);
+        assert_eq!(
+            statement.to_raw().build(),
+            "SELECT * FROM user WHERE org = 'codebreather' LIMIT 20;"
+        );
+        assert_eq!(statement.get_bindings().len(), 2);
+
+        let statement = update::<TestUser>(Table::new("user"))
+            .merge(serde_json::json!({ "age": 10 }))
+            .mutated(&mut tenant_scope);
+        assert_eq!(
+            statement.fine_tune_params(),
+            "UPDATE user MERGE $_param_00000001 WHERE org = $_param_00000002;"
+        );
+
+        let statement = delete::<TestUser>(Table::new("user"))
+            .where_(age.less_than(18))
+            .mutated(&mut tenant_scope);
+        assert_eq!(
+            statement.to_raw().build(),
+            "DELETE user WHERE (age < 18) AND (org = 'codebreather');"
+        );
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use std::{
+    cell::Cell,
+    fmt::{self, Display},
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// The SurrealQL syntax version that statements are rendered in.
+///
+/// The default is `V1` unless the `surrealdb-v2` cargo feature is enabled.
+/// It can be overridden at runtime for the whole process with [`Dialect::set_default`]
+/// or for a scope on the current thread with [`with_dialect`].
+///
+/// Examples:
+///
+/// ```rust
+/// # use surreal_query_builder as surreal_orm;
+/// use surreal_orm::{*, statements::define_table};
+///
+/// let statement = define_table(Table::from("likes")).type_relation(
+///     Table::from("user"),
+///     Table::from("post"),
+/// );
+///
+/// let v1 = with_dialect(Dialect::V1, || statement.build());
+/// let v2 = with_dialect(Dialect::V2, || statement.build());
+///
+/// assert_eq!(v1, "DEFINE TABLE likes;");
+/// assert_eq!(v2, "DEFINE TABLE likes TYPE RELATION IN user OUT post;");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Dialect {
+    /// SurrealDB 1.x syntax
+    #[cfg_attr(not(feature = "surrealdb-v2"), default)]
+    V1,
+    /// SurrealDB 2.x syntax e.g `UPSERT`, `DEFINE ACCESS`, `TYPE RELATION`, `OVERWRITE`.
+    #[cfg_attr(feature = "surrealdb-v2", default)]
+    V2,
+}
+
+const DIALECT_UNSET: u8 = 0;
+const DIALECT_V1: u8 = 1;
+const DIALECT_V2: u8 = 2;
+
+static DEFAULT_DIALECT: AtomicU8 = AtomicU8::new(DIALECT_UNSET);
+
+thread_local! {
+    static SCOPED_DIALECT: Cell<Option<Dialect>> = const { Cell::new(None) };
+}
+
+impl Dialect {
+    /// The dialect used when building statements on the current thread.
+    /// A scoped dialect set with [`with_dialect`] takes precedence over
+    /// the process-wide default.
+    pub fn current() -> Self {
+        SCOPED_DIALECT.with(Cell::get).unwrap_or_else(|| {
+            match DEFAULT_DIALECT.load(Ordering::Relaxed) {
+                DIALECT_V1 => Dialect::V1,
+                DIALECT_V2 => Dialect::V2,
+                _ => Dialect::default(),
+            }
+        })
+    }
+
+    /// Set the process-wide dialect, overriding the cargo feature default.
+    pub fn set_default(dialect: Dialect) {
+        let value = match dialect {
+            Dialect::V1 => DIALECT_V1,
+            Dialect::V2 => DIALECT_V2,
+        };
+        DEFAULT_DIALECT.store(value, Ordering::Relaxed);
+    }
+
+    /// Checks if the dialect is 2.x
+    pub fn is_v2(&self) -> bool {
+        matches!(self, Dialect::V2)
+    }
+}
+
+/// Builds statements in the closure with the specified dialect on the current thread.
+/// The previous dialect is restored afterwards.
+pub fn with_dialect<R>(dialect: Dialect, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Dialect>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_DIALECT.with(|scoped| scoped.set(self.0));
+        }
+    }
+
+    let _restore = Restore(SCOPED_DIALECT.with(|scoped| scoped.replace(Some(dialect))));
+    f()
+}
+
+/// What to do when a resource being defined already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefineGuard {
+    /// Only define the resource if it does not already exist.
+    IfNotExists,
+    /// Replace the existing resource. In 1.x, a definition always
+    /// replaces the existing one, so nothing is rendered.
+    Overwrite,
+}
+
+impl Display for DefineGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefineGuard::IfNotExists => write!(f, "IF NOT EXISTS"),
+            DefineGuard::Overwrite => write!(f, "OVERWRITE"),
+        }
+    }
+}
+
+/// Renders the leading keywords of a DEFINE statement e.g `DEFINE TABLE IF NOT EXISTS`.
+pub(crate) fn define_keyword(resource: &str, guard: Option<DefineGuard>) -> String {
+    match guard {
+        Some(DefineGuard::IfNotExists) => format!("DEFINE {resource} IF NOT EXISTS"),
+        Some(DefineGuard::Overwrite) if Dialect::current().is_v2() => {
+            format!("DEFINE {resource} OVERWRITE")
+        }
+        Some(DefineGuard::Overwrite) | None => format!("DEFINE {resource}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scoped_dialect_is_restored() {
+        let before = Dialect::current();
+        let inside = with_dialect(Dialect::V2, || {
+            with_dialect(Dialect::V1, Dialect::current);
+            Dialect::current()
+        });
+
+        assert_eq!(inside, Dialect::V2);
+        assert_eq!(Dialect::current(), before);
+    }
+
+    #[test]
+    fn test_define_keyword() {
+        with_dialect(Dialect::V1, || {
+            assert_eq!(define_keyword("TABLE", None), "DEFINE TABLE");
+            assert_eq!(
+                define_keyword("TABLE", Some(DefineGuard::IfNotExists)),
+                "DEFINE TABLE IF NOT EXISTS"
+            );
+            assert_eq!(
+                define_keyword("TABLE", Some(DefineGuard::Overwrite)),
+                "DEFINE TABLE"
+            );
+        });
+
+        with_dialect(Dialect::V2, || {
+            assert_eq!(
+                define_keyword("FIELD", Some(DefineGuard::Overwrite)),
+                "DEFINE FIELD OVERWRITE"
+            );
+        });
+    }
+}
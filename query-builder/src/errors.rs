@@ -51,6 +51,12 @@ pub enum SurrealOrmError {
     #[error("The id - {0} provided does not belong to the table {1}. Please ensure that the id provided is for the table you are trying to fetch from.")]
     IdBelongsToAnotherTable(IdString, TableString),
 
+    #[error("The id - {0} is not an array id. Composite ids are made of an array of values e.g reading:['sensor1', 1700000000].")]
+    NotArrayId(IdString),
+
+    #[error("The id - {0} is made of {1} values but its composite key is made of {2}.")]
+    CompositeIdArityMismatch(IdString, usize, usize),
+
     #[error("The following fields could not be fetched as they are not linked to a foreign table: {0}. Please ensure that all fields provided are of types 'link_self', 'link_one' or 'link_many' to allow fetching of linked values from other tables.")]
     FieldsUnfetchableNotARecordLink(String),
 
@@ -14,3 +14,49 @@ macro_rules! array {
         ]
     }};
 }
+
+/// Concatenates string constants and literals into a `&'static str` constant like
+/// `const_format::concatcp!`. Used to splice the table and field name constants generated
+/// by the derives e.g `User::TABLE` and `user::fields::EMAIL` into queries, so a rename
+/// is caught at compile time. The `query!` macro does it for constants written as `{{path}}`.
+///
+/// # Example
+/// ```rust
+/// # use surreal_query_builder as surreal_orm;
+/// use surreal_orm::concat_const;
+///
+/// const TABLE: &str = "user";
+/// const EMAIL: &str = "email";
+/// const QUERY: &str = concat_const!("SELECT ", EMAIL, " FROM ", TABLE, ";");
+///
+/// assert_eq!(QUERY, "SELECT email FROM user;");
+/// ```
+#[macro_export]
+macro_rules! concat_const {
+    ($($str:expr),+ $(,)?) => {{
+        const LEN: usize = 0 $(+ $str.len())+;
+
+        #[allow(unused_assignments)]
+        const BYTES: [u8; LEN] = {
+            let mut bytes = [0u8; LEN];
+            let mut offset = 0;
+            $(
+                let str_bytes = $str.as_bytes();
+                let mut i = 0;
+                while i < str_bytes.len() {
+                    bytes[offset + i] = str_bytes[i];
+                    i += 1;
+                }
+                offset += str_bytes.len();
+            )+
+            bytes
+        };
+
+        const CONCATENATED: &str = match ::std::str::from_utf8(&BYTES) {
+            ::std::result::Result::Ok(concatenated) => concatenated,
+            ::std::result::Result::Err(_) => ::std::panic!("Invalid UTF-8 concatenated"),
+        };
+
+        CONCATENATED
+    }};
+}
@@ -46,26 +46,35 @@
 //! This library includes augmented surrealdb types, custom types, statements, functions, operators, castings, and other utilities to
 //! to make working with surrealdb a joy.
 
+/// Structured statement nodes with a visitor and mutator API for inspecting and
+/// rewriting queries before they are run.
+pub mod ast;
 mod errors;
 
 /// Contains math constants, all the casting functions and future.
 mod data_model;
+mod dialect;
 pub mod functions;
 mod helpers;
 mod operators_macros;
+mod pretty;
 /// This module contains the different types of statements that can be used to query the
 /// database.
 pub mod statements;
+mod tenant;
 mod traits;
 mod types;
 /// For compile time validations
 pub mod validators;
 
 pub use data_model::*;
+pub use dialect::*;
 pub use errors::*;
 pub use helpers::*;
+pub use pretty::*;
 pub use statements::select::CanOrder;
 pub use statements::utils::*;
+pub use tenant::*;
 pub use traits::*;
 pub use types::*;
 
@@ -75,5 +84,6 @@ pub use surrealdb::sql;
 #[doc(hidden)]
 pub mod internal_tools {
     pub use paste::paste;
+    pub use surrealdb;
     pub use typed_builder;
 }
@@ -0,0 +1,1016 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+// A whitespace-only formatter for built SurrealQL.
+// It never changes what a query means: it only collapses and re-inserts whitespace
+// outside of strings and optionally changes the case of keywords.
+
+/// The case keywords are rendered in by the pretty printer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeywordCase {
+    /// e.g `SELECT * FROM user`. Keywords that are also common identifiers
+    /// e.g `user`, `in` or `value` are left as they were built.
+    #[default]
+    Upper,
+    /// e.g `select * from user`
+    Lower,
+    /// Keep keywords as they were built.
+    Preserve,
+}
+
+/// Options for pretty printing a query with [`crate::Buildable::build_pretty`].
+///
+/// Examples:
+///
+/// ```rust
+/// # use surreal_query_builder as surreal_orm;
+/// use surreal_orm::*;
+///
+/// let options = PrettyOptions::default()
+///     .indent_width(2)
+///     .max_line_length(100)
+///     .keyword_case(KeywordCase::Lower);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrettyOptions {
+    indent_width: usize,
+    max_line_length: usize,
+    keyword_case: KeywordCase,
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        Self {
+            indent_width: 4,
+            max_line_length: 80,
+            keyword_case: KeywordCase::default(),
+        }
+    }
+}
+
+impl PrettyOptions {
+    /// Number of spaces per indentation level. Defaults to 4.
+    pub fn indent_width(mut self, indent_width: usize) -> Self {
+        self.indent_width = indent_width;
+        self
+    }
+
+    /// Statements longer than this are broken up into one clause per line. Defaults to 80.
+    pub fn max_line_length(mut self, max_line_length: usize) -> Self {
+        self.max_line_length = max_line_length;
+        self
+    }
+
+    /// The case keywords are rendered in. Defaults to upper case.
+    pub fn keyword_case(mut self, keyword_case: KeywordCase) -> Self {
+        self.keyword_case = keyword_case;
+        self
+    }
+}
+
+/// Formats a SurrealQL query string readably.
+/// Blocks e.g in `IF/ELSE` and `FOR` statements are indented, statements
+/// that are too long are broken up into one clause per line and long subqueries
+/// are moved into their own indented lines.
+///
+/// Examples:
+///
+/// ```rust
+/// # use surreal_query_builder as surreal_orm;
+/// use surreal_orm::*;
+///
+/// let query = format_surql(
+///     "IF $age >= 18 { (SELECT * FROM user) } ELSE { (SELECT * FROM child) };",
+///     &PrettyOptions::default(),
+/// );
+///
+/// assert_eq!(
+///     query,
+///     "IF $age >= 18 {\n    (SELECT * FROM user)\n} ELSE {\n    (SELECT * FROM child)\n};"
+/// );
+/// ```
+pub fn format_surql(query: &str, options: &PrettyOptions) -> String {
+    let nodes = parse(tokenize(query));
+    Printer { options }.statements(&nodes, 0)
+}
+
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "SELECT", "CREATE", "UPDATE", "UPSERT", "DELETE", "RELATE", "INSERT", "IF", "RETURN", "DEFINE",
+    "REMOVE", "LET", "FOR", "INFO",
+];
+
+// Clauses that start on a new line when a statement does not fit on one line.
+const CLAUSE_KEYWORDS: &[&str] = &[
+    "FROM",
+    "WHERE",
+    "SPLIT",
+    "GROUP",
+    "ORDER",
+    "LIMIT",
+    "START",
+    "FETCH",
+    "TIMEOUT",
+    "PARALLEL",
+    "EXPLAIN",
+    "WITH",
+    "CONTENT",
+    "MERGE",
+    "PATCH",
+    "REPLACE",
+    "SET",
+    "UNSET",
+    "RETURN",
+    "TYPE",
+    "FLEXIBLE",
+    "VALUE",
+    "ASSERT",
+    "DEFAULT",
+    "READONLY",
+    "PERMISSIONS",
+    "FIELDS",
+    "COLUMNS",
+    "SEARCH",
+    "WHEN",
+    "THEN",
+    "DROP",
+    "SCHEMAFULL",
+    "SCHEMALESS",
+    "CHANGEFEED",
+    "COMMENT",
+    "SIGNUP",
+    "SIGNIN",
+    "AUTHENTICATE",
+    "DURATION",
+];
+
+const KEYWORDS: &[&str] = &[
+    "ACCESS",
+    "AFTER",
+    "ALGORITHM",
+    "ALL",
+    "ANALYZER",
+    "AND",
+    "ANY",
+    "AS",
+    "ASC",
+    "ASSERT",
+    "AT",
+    "AUTHENTICATE",
+    "BEFORE",
+    "BEGIN",
+    "BREAK",
+    "BY",
+    "CANCEL",
+    "CHANGEFEED",
+    "COLLATE",
+    "COLUMNS",
+    "COMMENT",
+    "COMMIT",
+    "CONTAINS",
+    "CONTENT",
+    "CONTINUE",
+    "CREATE",
+    "DATABASE",
+    "DEFAULT",
+    "DEFINE",
+    "DELETE",
+    "DESC",
+    "DIFF",
+    "DROP",
+    "DURATION",
+    "ELSE",
+    "EVENT",
+    "EXISTS",
+    "EXPLAIN",
+    "FETCH",
+    "FIELD",
+    "FIELDS",
+    "FLEXIBLE",
+    "FOR",
+    "FROM",
+    "FULL",
+    "FUNCTION",
+    "GROUP",
+    "IF",
+    "IN",
+    "INDEX",
+    "INFO",
+    "INSERT",
+    "INTO",
+    "IS",
+    "JWT",
+    "KEY",
+    "LET",
+    "LIMIT",
+    "MERGE",
+    "NAMESPACE",
+    "NONE",
+    "NORMAL",
+    "NOT",
+    "NUMERIC",
+    "ON",
+    "ONLY",
+    "OR",
+    "ORDER",
+    "OUT",
+    "OVERWRITE",
+    "PARALLEL",
+    "PARAM",
+    "PATCH",
+    "PERMISSIONS",
+    "READONLY",
+    "RECORD",
+    "RELATE",
+    "RELATION",
+    "REMOVE",
+    "REPLACE",
+    "RETURN",
+    "SCHEMAFULL",
+    "SCHEMALESS",
+    "SCOPE",
+    "SELECT",
+    "SET",
+    "SIGNIN",
+    "SIGNUP",
+    "SPLIT",
+    "START",
+    "TABLE",
+    "THEN",
+    "TIMEOUT",
+    "TRANSACTION",
+    "TOKEN",
+    "TYPE",
+    "UNIQUE",
+    "UNSET",
+    "UPDATE",
+    "UPSERT",
+    "USER",
+    "VALUE",
+    "WHEN",
+    "WHERE",
+    "WITH",
+];
+
+// Keywords that are also commonly used as table, field or param names e.g `user`,
+// `in` or `value`. Identifiers are case sensitive, so lowercase ones are never
+// uppercased.
+const IDENTIFIER_KEYWORDS: &[&str] = &[
+    "ACCESS",
+    "ALGORITHM",
+    "ANALYZER",
+    "AT",
+    "COLUMNS",
+    "COMMENT",
+    "CONTENT",
+    "DATABASE",
+    "DEFAULT",
+    "DIFF",
+    "DURATION",
+    "EVENT",
+    "FIELD",
+    "FIELDS",
+    "FUNCTION",
+    "IN",
+    "INDEX",
+    "KEY",
+    "NAMESPACE",
+    "NORMAL",
+    "NUMERIC",
+    "OUT",
+    "PARAM",
+    "RECORD",
+    "RELATION",
+    "SCOPE",
+    "START",
+    "TABLE",
+    "TIMEOUT",
+    "TOKEN",
+    "TYPE",
+    "UNIQUE",
+    "USER",
+    "VALUE",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    /// Strings and escaped identifiers. Rendered verbatim.
+    Literal(String),
+    Comment(String),
+    Whitespace {
+        newlines: usize,
+    },
+    Semicolon,
+    Punct(char),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Token(Token),
+    Group {
+        open: char,
+        close: Option<char>,
+        children: Vec<Node>,
+    },
+}
+
+impl Node {
+    fn is_whitespace(&self) -> bool {
+        matches!(self, Node::Token(Token::Whitespace { .. }))
+    }
+
+    fn as_word(&self) -> Option<&str> {
+        match self {
+            Node::Token(Token::Word(word)) => Some(word),
+            _ => None,
+        }
+    }
+}
+
+fn tokenize(query: &str) -> Vec<Token> {
+    let chars = query.chars().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut index = 0;
+
+    while let Some(&current) = chars.get(index) {
+        let next = chars.get(index + 1).copied();
+        let start = index;
+
+        if current.is_whitespace() {
+            while chars.get(index).is_some_and(|c| c.is_whitespace()) {
+                index += 1;
+            }
+            let newlines = chars[start..index].iter().filter(|c| **c == '\n').count();
+            tokens.push(Token::Whitespace { newlines });
+        } else if matches!(current, '\'' | '"' | '`' | '⟨') {
+            let close = if current == '⟨' { '⟩' } else { current };
+            index += 1;
+            while let Some(&c) = chars.get(index) {
+                index += if c == '\\' { 2 } else { 1 };
+                if c == close {
+                    break;
+                }
+            }
+            let end = index.min(chars.len());
+            tokens.push(Token::Literal(chars[start..end].iter().collect()));
+        } else if (current == '#'
+            || (current == '-' && next == Some('-'))
+            || (current == '/' && next == Some('/')))
+            && is_statement_boundary(&tokens)
+        {
+            while chars.get(index).is_some_and(|c| *c != '\n') {
+                index += 1;
+            }
+            let comment = chars[start..index].iter().collect::<String>();
+            tokens.push(Token::Comment(comment.trim_end().to_string()));
+        } else if current.is_alphanumeric() || current == '_' || current == '$' {
+            while chars
+                .get(index)
+                .is_some_and(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+            {
+                index += 1;
+            }
+            let word = chars[start..index].iter().collect::<String>();
+            match script_function_end(&chars, &word, index) {
+                // The JavaScript of a script function is not SurrealQL
+                Some(end) => {
+                    index = end;
+                    tokens.push(Token::Literal(chars[start..end].iter().collect()));
+                }
+                None => tokens.push(Token::Word(word)),
+            }
+        } else {
+            index += 1;
+            tokens.push(if current == ';' {
+                Token::Semicolon
+            } else {
+                Token::Punct(current)
+            });
+        }
+    }
+
+    tokens
+}
+
+// Comments are only recognised where a statement can start, so that e.g `a--b` or the
+// `#` of a record id in a condition are not taken for one.
+fn is_statement_boundary(tokens: &[Token]) -> bool {
+    match tokens
+        .iter()
+        .rev()
+        .find(|token| !matches!(token, Token::Whitespace { .. }))
+    {
+        None | Some(Token::Semicolon | Token::Comment(_) | Token::Punct('{' | '}')) => true,
+        Some(_) => false,
+    }
+}
+
+// The end of a script function e.g `function($name) { return `Hi ${name}`; }` whose
+// `function` keyword ends at `index`.
+fn script_function_end(chars: &[char], word: &str, index: usize) -> Option<usize> {
+    if !word.eq_ignore_ascii_case("function") {
+        return None;
+    }
+
+    let skip_whitespace = |mut index: usize| {
+        while chars.get(index).is_some_and(|c| c.is_whitespace()) {
+            index += 1;
+        }
+        index
+    };
+
+    let mut index = skip_whitespace(index);
+    if chars.get(index) != Some(&'(') {
+        return None;
+    }
+    while chars.get(index).is_some_and(|c| *c != ')') {
+        index += 1;
+    }
+    index = skip_whitespace(index + 1);
+    if chars.get(index) != Some(&'{') {
+        return None;
+    }
+
+    let mut depth = 0;
+    while let Some(&current) = chars.get(index) {
+        let next = chars.get(index + 1).copied();
+        match current {
+            '\'' | '"' | '`' => {
+                index += 1;
+                while let Some(&c) = chars.get(index) {
+                    index += if c == '\\' { 2 } else { 1 };
+                    if c == current {
+                        break;
+                    }
+                }
+                continue;
+            }
+            '/' if next == Some('/') => {
+                while chars.get(index).is_some_and(|c| *c != '\n') {
+                    index += 1;
+                }
+                continue;
+            }
+            '/' if next == Some('*') => {
+                index += 2;
+                while index < chars.len() && !(chars[index - 1] == '*' && chars[index] == '/') {
+                    index += 1;
+                }
+            }
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index + 1);
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    None
+}
+
+fn closing(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        _ => '}',
+    }
+}
+
+fn parse(tokens: Vec<Token>) -> Vec<Node> {
+    let mut stack: Vec<(char, Vec<Node>)> = vec![];
+    let mut current = vec![];
+
+    for token in tokens {
+        match token {
+            Token::Punct(open @ ('(' | '[' | '{')) => {
+                stack.push((open, std::mem::take(&mut current)));
+            }
+            Token::Punct(close @ (')' | ']' | '}')) => match stack.pop() {
+                Some((open, parent)) if closing(open) == close => {
+                    let children = std::mem::replace(&mut current, parent);
+                    current.push(Node::Group {
+                        open,
+                        close: Some(close),
+                        children,
+                    });
+                }
+                unmatched => {
+                    stack.extend(unmatched);
+                    current.push(Node::Token(Token::Punct(close)));
+                }
+            },
+            token => current.push(Node::Token(token)),
+        }
+    }
+
+    while let Some((open, parent)) = stack.pop() {
+        let children = std::mem::replace(&mut current, parent);
+        current.push(Node::Group {
+            open,
+            close: None,
+            children,
+        });
+    }
+
+    current
+}
+
+fn trim(nodes: &[Node]) -> &[Node] {
+    let start = nodes
+        .iter()
+        .position(|node| !node.is_whitespace())
+        .unwrap_or(nodes.len());
+    let end = nodes
+        .iter()
+        .rposition(|node| !node.is_whitespace())
+        .map_or(start, |end| end + 1);
+    &nodes[start..end]
+}
+
+fn trim_end_in_place(text: &mut String) {
+    text.truncate(text.trim_end().len());
+}
+
+/// Splits statements on `;`. Returns each statement and whether it was terminated.
+fn split_statements(nodes: &[Node]) -> Vec<(&[Node], bool)> {
+    let mut statements = vec![];
+    let mut start = 0;
+    for (index, node) in nodes.iter().enumerate() {
+        if let Node::Token(Token::Semicolon) = node {
+            statements.push((&nodes[start..index], true));
+            start = index + 1;
+        }
+    }
+    if !trim(&nodes[start..]).is_empty() {
+        statements.push((&nodes[start..], false));
+    }
+    statements
+}
+
+/// A brace group is a block unless it looks like an object i.e `{ key: value }`.
+fn is_block(children: &[Node]) -> bool {
+    !matches!(
+        trim(children),
+        [] | [
+            Node::Token(Token::Word(_) | Token::Literal(_)),
+            Node::Token(Token::Punct(':')),
+            Node::Token(Token::Whitespace { .. }),
+            ..
+        ]
+    )
+}
+
+fn has_block(nodes: &[Node]) -> bool {
+    nodes.iter().any(|node| {
+        matches!(node, Node::Group { open: '{', close: Some('}'), children } if is_block(children))
+    })
+}
+
+fn is_subquery(children: &[Node]) -> bool {
+    trim(children)
+        .first()
+        .and_then(Node::as_word)
+        .is_some_and(|word| STATEMENT_KEYWORDS.contains(&word.to_uppercase().as_str()))
+}
+
+/// Splits a statement before each of its top level clauses.
+fn split_clauses(nodes: &[Node]) -> Vec<&[Node]> {
+    let mut clauses = vec![];
+    let mut start = 0;
+    let mut in_permissions = false;
+
+    for (index, node) in nodes.iter().enumerate().skip(1) {
+        let Some(word) = node.as_word() else {
+            continue;
+        };
+        let clause_head = nodes[start].as_word();
+        let is_clause = match word {
+            // `PERMISSIONS FOR select WHERE ...` keeps the condition with its permission
+            "FOR" => in_permissions,
+            "WHERE" => clause_head != Some("FOR"),
+            // `SELECT VALUE` is not the `VALUE` clause of a field definition
+            "VALUE" => clause_head != Some("SELECT"),
+            word => CLAUSE_KEYWORDS.contains(&word),
+        };
+        let previous = nodes[..index]
+            .iter()
+            .rev()
+            .find(|node| !node.is_whitespace());
+        let is_path = matches!(previous, Some(Node::Token(Token::Punct(':' | '.'))));
+
+        if is_clause && !is_path {
+            clauses.push(trim(&nodes[start..index]));
+            start = index;
+            in_permissions |= word == "PERMISSIONS";
+        }
+    }
+    clauses.push(trim(&nodes[start..]));
+    clauses
+}
+
+struct Printer<'a> {
+    options: &'a PrettyOptions,
+}
+
+impl Printer<'_> {
+    fn indent(&self, depth: usize) -> String {
+        " ".repeat(self.options.indent_width * depth)
+    }
+
+    fn fits(&self, text: &str, depth: usize) -> bool {
+        !text.contains('\n')
+            && self.options.indent_width * depth + text.chars().count()
+                <= self.options.max_line_length
+    }
+
+    fn keyword(&self, word: &str) -> String {
+        match self.options.keyword_case {
+            KeywordCase::Upper => {
+                let upper = word.to_ascii_uppercase();
+                let is_keyword = word.chars().all(|c| c.is_ascii_lowercase())
+                    && KEYWORDS.contains(&upper.as_str())
+                    && !IDENTIFIER_KEYWORDS.contains(&upper.as_str());
+                if is_keyword {
+                    upper
+                } else {
+                    word.to_string()
+                }
+            }
+            KeywordCase::Lower => {
+                let is_keyword =
+                    word.chars().all(|c| c.is_ascii_uppercase()) && KEYWORDS.contains(&word);
+                if is_keyword {
+                    word.to_lowercase()
+                } else {
+                    word.to_string()
+                }
+            }
+            KeywordCase::Preserve => word.to_string(),
+        }
+    }
+
+    fn node_text(&self, nodes: &[Node], index: usize) -> String {
+        match &nodes[index] {
+            Node::Token(Token::Word(word)) => {
+                let previous = index.checked_sub(1).map(|previous| &nodes[previous]);
+                // Leave record ids, field paths and function names e.g `type::thing` alone.
+                let is_path = matches!(previous, Some(Node::Token(Token::Punct(':' | '.'))))
+                    || matches!(nodes.get(index + 1), Some(Node::Token(Token::Punct(':'))));
+                if is_path {
+                    word.to_string()
+                } else {
+                    self.keyword(word)
+                }
+            }
+            Node::Token(Token::Literal(literal)) => literal.to_string(),
+            Node::Token(Token::Comment(comment)) => format!("{comment}\n"),
+            Node::Token(Token::Whitespace { .. }) => " ".to_string(),
+            Node::Token(Token::Semicolon) => ";".to_string(),
+            Node::Token(Token::Punct(punct)) => punct.to_string(),
+            Node::Group {
+                open,
+                close,
+                children,
+            } => format!(
+                "{open}{}{}",
+                self.flat(children),
+                close.map(String::from).unwrap_or_default()
+            ),
+        }
+    }
+
+    fn flat(&self, nodes: &[Node]) -> String {
+        (0..nodes.len())
+            .map(|index| self.node_text(nodes, index))
+            .collect()
+    }
+
+    /// The flat text of the nodes with block bodies left out,
+    /// since blocks are always rendered on their own lines.
+    fn flat_without_blocks(&self, nodes: &[Node]) -> String {
+        (0..nodes.len())
+            .map(|index| match &nodes[index] {
+                Node::Group {
+                    open: '{',
+                    close: Some('}'),
+                    children,
+                } if is_block(children) => "{}".to_string(),
+                _ => self.node_text(nodes, index),
+            })
+            .collect()
+    }
+
+    /// Renders `;` separated statements, one after the other.
+    fn statements(&self, nodes: &[Node], depth: usize) -> String {
+        let mut lines: Vec<String> = vec![];
+
+        for (statement, terminated) in split_statements(nodes) {
+            let blank_line_before = statement
+                .iter()
+                .take_while(|node| node.is_whitespace())
+                .any(|node| matches!(node, Node::Token(Token::Whitespace { newlines }) if *newlines > 1));
+            if blank_line_before && !lines.is_empty() {
+                lines.push(String::new());
+            }
+
+            let mut statement = trim(statement);
+            while let [Node::Token(Token::Comment(comment)), rest @ ..] = statement {
+                lines.push(format!("{}{comment}", self.indent(depth)));
+                statement = trim(rest);
+            }
+
+            if statement.is_empty() {
+                continue;
+            }
+
+            let mut rendered = self.statement(statement, depth);
+            if terminated {
+                rendered.push(';');
+            }
+            lines.push(rendered);
+        }
+
+        lines.join("\n")
+    }
+
+    fn statement(&self, nodes: &[Node], depth: usize) -> String {
+        if !self.fits(&self.flat_without_blocks(nodes), depth) {
+            return split_clauses(nodes)
+                .into_iter()
+                .enumerate()
+                .map(|(index, clause)| {
+                    let clause_depth = if index == 0 { depth } else { depth + 1 };
+                    self.clause(clause, clause_depth)
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+
+        self.clause(nodes, depth)
+    }
+
+    fn clause(&self, nodes: &[Node], depth: usize) -> String {
+        let is_too_long = !self.fits(&self.flat_without_blocks(nodes), depth);
+        if !is_too_long && !has_block(nodes) {
+            return format!("{}{}", self.indent(depth), self.flat(nodes));
+        }
+
+        let mut rendered = self.indent(depth);
+        for (index, node) in nodes.iter().enumerate() {
+            match node {
+                Node::Group {
+                    open: '{',
+                    close: Some('}'),
+                    children,
+                } if is_block(children) => {
+                    trim_end_in_place(&mut rendered);
+                    if !rendered.is_empty() {
+                        rendered.push(' ');
+                    }
+                    rendered.push_str("{\n");
+                    let body = self.statements(children, depth + 1);
+                    if !body.is_empty() {
+                        rendered.push_str(&body);
+                        rendered.push('\n');
+                    }
+                    rendered.push_str(&self.indent(depth));
+                    rendered.push('}');
+                }
+                Node::Group {
+                    open: '(',
+                    close: Some(')'),
+                    children,
+                } if is_too_long && is_subquery(children) => {
+                    rendered.push_str("(\n");
+                    rendered.push_str(&self.statement(trim(children), depth + 1));
+                    rendered.push('\n');
+                    rendered.push_str(&self.indent(depth));
+                    rendered.push(')');
+                }
+                Node::Token(Token::Word(word))
+                    if is_too_long && index > 0 && matches!(word.as_str(), "AND" | "OR") =>
+                {
+                    trim_end_in_place(&mut rendered);
+                    rendered.push('\n');
+                    rendered.push_str(&self.indent(depth + 1));
+                    rendered.push_str(&self.keyword(word));
+                }
+                _ => rendered.push_str(&self.node_text(nodes, index)),
+            }
+        }
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_statement_is_kept_on_one_line() {
+        let query = format_surql(
+            "SELECT * FROM user WHERE age > 18;",
+            &PrettyOptions::default(),
+        );
+
+        assert_eq!(query, "SELECT * FROM user WHERE age > 18;");
+    }
+
+    #[test]
+    fn test_long_statement_is_split_into_clauses() {
+        let query = format_surql(
+            "SELECT * FROM user:oyelowo WHERE (city IS 'Prince Edward Island') AND (city IS 'NewFoundland') OR (city ~ 'Toronto') ORDER BY age NUMERIC ASC LIMIT 153 START AT 10 PARALLEL;",
+            &PrettyOptions::default().max_line_length(100),
+        );
+
+        assert_eq!(
+            query,
+            "SELECT *
+    FROM user:oyelowo
+    WHERE (city IS 'Prince Edward Island') AND (city IS 'NewFoundland') OR (city ~ 'Toronto')
+    ORDER BY age NUMERIC ASC
+    LIMIT 153
+    START AT 10
+    PARALLEL;"
+        );
+    }
+
+    #[test]
+    fn test_long_condition_is_split_on_and_or() {
+        let query = format_surql(
+            "SELECT * FROM user WHERE (city IS 'Prince Edward Island') AND (city IS 'NewFoundland') OR (city ~ 'Toronto');",
+            &PrettyOptions::default().max_line_length(60),
+        );
+
+        assert_eq!(
+            query,
+            "SELECT *
+    FROM user
+    WHERE (city IS 'Prince Edward Island')
+        AND (city IS 'NewFoundland')
+        OR (city ~ 'Toronto');"
+        );
+    }
+
+    #[test]
+    fn test_if_else_blocks_are_indented() {
+        let query = format_surql(
+            "IF (age >= 18) AND (age <= 120) \n\t{ (SELECT * FROM user WHERE age > 34) }\nELSE IF name ~ 'Oyelowo Oyedayo' \n\t{ (SELECT * FROM book) }\nELSE\n\t{ (SELECT * FROM fruit) };",
+            &PrettyOptions::default(),
+        );
+
+        assert_eq!(
+            query,
+            "IF (age >= 18) AND (age <= 120) {
+    (SELECT * FROM user WHERE age > 34)
+} ELSE IF name ~ 'Oyelowo Oyedayo' {
+    (SELECT * FROM book)
+} ELSE {
+    (SELECT * FROM fruit)
+};"
+        );
+    }
+
+    #[test]
+    fn test_for_loop_and_nested_blocks() {
+        let query = format_surql(
+            "FOR $name IN (SELECT VALUE name FROM person) { CREATE gamer CONTENT { name: $name }; IF $name = 'Oyelowo' { BREAK; }; };",
+            &PrettyOptions::default().indent_width(2),
+        );
+
+        assert_eq!(
+            query,
+            "FOR $name IN (SELECT VALUE name FROM person) {
+  CREATE gamer CONTENT { name: $name };
+  IF $name = 'Oyelowo' {
+    BREAK;
+  };
+};"
+        );
+    }
+
+    #[test]
+    fn test_long_subquery_is_moved_to_its_own_lines() {
+        let query = format_surql(
+            "DELETE user WHERE id IN (SELECT VALUE in FROM likes WHERE out = post:1 AND created_at < time::now());",
+            &PrettyOptions::default().max_line_length(60),
+        );
+
+        assert_eq!(
+            query,
+            "DELETE user
+    WHERE id IN (
+        SELECT VALUE in
+            FROM likes
+            WHERE out = post:1 AND created_at < time::now()
+    );"
+        );
+    }
+
+    #[test]
+    fn test_define_statement_is_split_into_clauses() {
+        let query = format_surql(
+            "DEFINE FIELD email ON TABLE user TYPE string VALUE string::lowercase($value) ASSERT string::is::email($value) PERMISSIONS FOR select FULL FOR create, update WHERE $auth.id = id;",
+            &PrettyOptions::default(),
+        );
+
+        assert_eq!(
+            query,
+            "DEFINE FIELD email ON TABLE user
+    TYPE string
+    VALUE string::lowercase($value)
+    ASSERT string::is::email($value)
+    PERMISSIONS
+    FOR SELECT FULL
+    FOR CREATE, UPDATE WHERE $auth.id = id;"
+        );
+    }
+
+    #[test]
+    fn test_blank_lines_comments_and_strings_are_preserved() {
+        let query = format_surql(
+            "-- Begin\nBEGIN TRANSACTION;\n\nCREATE user SET bio = 'IF { ; } ELSE';\n\nCOMMIT TRANSACTION;\n\t",
+            &PrettyOptions::default(),
+        );
+
+        assert_eq!(
+            query,
+            "-- Begin\nBEGIN TRANSACTION;\n\nCREATE user SET bio = 'IF { ; } ELSE';\n\nCOMMIT TRANSACTION;"
+        );
+    }
+
+    #[test]
+    fn test_keyword_case() {
+        let query = format_surql(
+            "SELECT VALUE type::thing('user', 'IF') FROM user:SELECT WHERE in.ORDER IS NONE;",
+            &PrettyOptions::default().keyword_case(KeywordCase::Lower),
+        );
+
+        assert_eq!(
+            query,
+            "select value type::thing('user', 'IF') from user:SELECT where in.ORDER is none;"
+        );
+    }
+
+    #[test]
+    fn test_upper_keyword_case_uppercases_keywords_but_not_identifiers() {
+        let query = format_surql(
+            "select * from user where in = person:1 and age > 18 order by age limit 10;",
+            &PrettyOptions::default().keyword_case(KeywordCase::Upper),
+        );
+
+        assert_eq!(
+            query,
+            "SELECT * FROM user WHERE in = person:1 AND age > 18 ORDER BY age LIMIT 10;"
+        );
+    }
+
+    #[test]
+    fn test_comment_markers_within_statements_are_not_comments() {
+        let query = format_surql(
+            "SELECT * FROM user WHERE age = a--b AND url = //host;\n-- Done",
+            &PrettyOptions::default(),
+        );
+
+        assert_eq!(
+            query,
+            "SELECT * FROM user WHERE age = a--b AND url = //host;\n-- Done"
+        );
+    }
+
+    #[test]
+    fn test_script_functions_are_kept_verbatim() {
+        let query = "DEFINE FUNCTION fn::greet($name: string) { RETURN function($name) {\n  \
+            // Greets the user\n  if (name) { return `Hi ${name}`; }\n  let i = 2; i--; \
+            return '}' + i;\n}; };";
+        let pretty = format_surql(query, &PrettyOptions::default());
+
+        assert_eq!(
+            pretty,
+            "DEFINE FUNCTION fn::greet($name: string) {\n    RETURN function($name) {\n  \
+            // Greets the user\n  if (name) { return `Hi ${name}`; }\n  let i = 2; i--; \
+            return '}' + i;\n};\n};"
+        );
+        assert_eq!(format_surql(&pretty, &PrettyOptions::default()), pretty);
+    }
+
+    #[test]
+    fn test_hash_comments_are_preserved() {
+        let query = format_surql(
+            "# Seed users\nCREATE user SET name = '# not a comment';\n# Then read them\nSELECT * FROM user;",
+            &PrettyOptions::default(),
+        );
+
+        assert_eq!(
+            query,
+            "# Seed users\nCREATE user SET name = '# not a comment';\n# Then read them\nSELECT * FROM user;"
+        );
+    }
+}
@@ -9,7 +9,7 @@ use std::marker::PhantomData;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    derive_binding_and_errors_from_value,
+    tenant::{derive_tenant_binding_and_errors, tenant_set_assignment},
     traits::{
         BindingsList, Buildable, Erroneous, Node, Parametric, Queryable, ReturnableDefault,
         ReturnableStandard,
@@ -139,7 +139,7 @@ where
     ///     });
     /// ```
     pub fn content(mut self, content: T) -> CreateStatement<T> {
-        let (binding, errors) = derive_binding_and_errors_from_value(&content);
+        let (binding, errors) = derive_tenant_binding_and_errors::<T>(&content);
         self.content = binding.get_param_dollarised();
         self.bindings.push(binding);
         self.errors.extend(errors);
@@ -208,6 +208,15 @@ where
         self.errors.extend(errors);
         self.set.extend(settable);
 
+        match tenant_set_assignment::<T>() {
+            Ok(Some((assignment, binding))) => {
+                self.set.push(assignment);
+                self.bindings.push(binding);
+            }
+            Ok(None) => {}
+            Err(error) => self.errors.push(error),
+        }
+
         CreateStatement(self)
     }
 }
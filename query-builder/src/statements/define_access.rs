@@ -0,0 +1,395 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+// DEFINE ACCESS statement
+// Replaces DEFINE SCOPE and DEFINE TOKEN in SurrealDB 2.x. It defines how users and
+// third-party tokens are authenticated on a namespace or database.
+//
+// Statement syntax
+// DEFINE ACCESS [ OVERWRITE | IF NOT EXISTS ] @name
+//   ON [ NAMESPACE | DATABASE ]
+//   TYPE [
+//     JWT [ ALGORITHM @algorithm KEY @key | URL @url ]
+//     | RECORD
+//       [ SIGNUP @expression ]
+//       [ SIGNIN @expression ]
+//       [ WITH JWT [ ALGORITHM @algorithm KEY @key | URL @url ] ]
+//   ]
+//   [ AUTHENTICATE @expression ]
+//   [ DURATION [ FOR TOKEN @duration ] [, FOR SESSION @duration ] ]
+use std::fmt::{self, Display};
+
+use surrealdb::sql;
+
+use crate::{
+    define_keyword,
+    traits::{Binding, BindingsList, Buildable, Erroneous, Parametric, Queryable},
+    types::{DurationLike, Idiomx, TokenType},
+    DefineGuard, ErrorList,
+};
+
+use super::{NamespaceOrDatabase, Subquery};
+
+/// Define a new access method. This is the 2.x replacement for `DEFINE SCOPE` and `DEFINE TOKEN`.
+///
+/// Requirements
+/// To DEFINE ACCESS ... ON NAMESPACE ... you must have root or namespace level access.
+/// To DEFINE ACCESS ... ON DATABASE ... you must have root, namespace, or database level access.
+///
+/// Examples:
+///
+/// ```rust
+/// # use surreal_query_builder as surreal_orm;
+/// use surreal_orm::{*, statements::{define_access, select}};
+/// use std::time::Duration;
+///
+/// # let user = Table::new("user");
+/// # let email = Field::new("email");
+/// let statement = define_access("account")
+///     .on_database()
+///     .type_record()
+///     .signup(Raw::new(
+///         "CREATE user SET email = $email, pass = crypto::argon2::generate($pass)",
+///     ))
+///     .signin(select(All).from(user).where_(email.equal(Param::new("email"))))
+///     .duration_for_session(Duration::from_secs(60 * 60 * 24));
+///
+/// assert!(!statement.build().is_empty());
+///
+/// let statement = define_access("token")
+///     .on_namespace()
+///     .type_jwt()
+///     .algorithm(TokenType::HS512)
+///     .key("sNSYneezcr8kqphfOC6NwwraUHJCVAt0XjsRSNmssBaBRh3WyMa9TRfq8ST7fsU2H2kGiOpU4GbAF1bCiXmM1b3JGgleBzz7rsrz6VvYEM4q3CLkcO8CMBIlhwhzWmy8");
+///
+/// assert!(!statement.build().is_empty());
+/// ```
+pub fn define_access(access_name: impl Into<Idiomx>) -> DefineAccessStatement {
+    let access_name: Idiomx = access_name.into();
+
+    DefineAccessStatement {
+        name: access_name.to_string(),
+        guard: None,
+        target: None,
+        access_type: None,
+        signup: None,
+        signin: None,
+        jwt: None,
+        authenticate: None,
+        duration_for_token: None,
+        duration_for_session: None,
+        bindings: vec![],
+    }
+}
+
+/// Define the API for the Access builder
+pub struct DefineAccessStatement {
+    name: String,
+    guard: Option<DefineGuard>,
+    target: Option<NamespaceOrDatabase>,
+    access_type: Option<AccessType>,
+    signup: Option<String>,
+    signin: Option<String>,
+    jwt: Option<JwtVerification>,
+    authenticate: Option<String>,
+    duration_for_token: Option<String>,
+    duration_for_session: Option<String>,
+    bindings: BindingsList,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AccessType {
+    Jwt,
+    Record,
+}
+
+enum JwtVerification {
+    Key {
+        algorithm: Option<TokenType>,
+        key: Option<String>,
+    },
+    Url(String),
+}
+
+impl DefineAccessStatement {
+    /// Only define the access method if it does not already exist.
+    pub fn if_not_exists(mut self) -> Self {
+        self.guard = Some(DefineGuard::IfNotExists);
+        self
+    }
+
+    /// Overwrite the access method definition if it already exists.
+    pub fn overwrite(mut self) -> Self {
+        self.guard = Some(DefineGuard::Overwrite);
+        self
+    }
+
+    /// Define the access method on the namespace
+    pub fn on_namespace(mut self) -> Self {
+        self.target = Some(NamespaceOrDatabase::Namespace);
+        self
+    }
+
+    /// Define the access method on the current database
+    pub fn on_database(mut self) -> Self {
+        self.target = Some(NamespaceOrDatabase::Database);
+        self
+    }
+
+    /// Authenticate with a third-party JWT.
+    pub fn type_jwt(mut self) -> Self {
+        self.access_type = Some(AccessType::Jwt);
+        self
+    }
+
+    /// Authenticate records i.e users stored in a table. This is what scopes were used for in 1.x.
+    pub fn type_record(mut self) -> Self {
+        self.access_type = Some(AccessType::Record);
+        self
+    }
+
+    /// Set the signup expression of a record access method
+    pub fn signup(mut self, subquery: impl Into<Subquery>) -> Self {
+        let subquery: Subquery = subquery.into();
+        self.bindings.extend(subquery.get_bindings());
+        self.signup = Some(subquery.build());
+        self
+    }
+
+    /// Set the signin expression of a record access method
+    pub fn signin(mut self, subquery: impl Into<Subquery>) -> Self {
+        let subquery: Subquery = subquery.into();
+        self.bindings.extend(subquery.get_bindings());
+        self.signin = Some(subquery.build());
+        self
+    }
+
+    /// Specify the cryptographic signature algorithm used to verify the token
+    pub fn algorithm(mut self, algorithm: TokenType) -> Self {
+        self.jwt = match self.jwt.take() {
+            Some(JwtVerification::Key { key, .. }) => Some(JwtVerification::Key {
+                algorithm: Some(algorithm),
+                key,
+            }),
+            _ => Some(JwtVerification::Key {
+                algorithm: Some(algorithm),
+                key: None,
+            }),
+        };
+        self
+    }
+
+    /// Specify the key used to verify the authenticity of the token
+    pub fn key(mut self, key: impl Into<sql::Strand>) -> Self {
+        let binding = Binding::new(key.into());
+        let key = binding.get_param_dollarised();
+        self.bindings.push(binding);
+        self.jwt = match self.jwt.take() {
+            Some(JwtVerification::Key { algorithm, .. }) => Some(JwtVerification::Key {
+                algorithm,
+                key: Some(key),
+            }),
+            _ => Some(JwtVerification::Key {
+                algorithm: None,
+                key: Some(key),
+            }),
+        };
+        self
+    }
+
+    /// Fetch the keys used to verify the token from a JWKS url
+    pub fn url(mut self, url: impl Into<sql::Strand>) -> Self {
+        let binding = Binding::new(url.into());
+        self.jwt = Some(JwtVerification::Url(binding.get_param_dollarised()));
+        self.bindings.push(binding);
+        self
+    }
+
+    /// Set an expression that is run whenever the access method is used to authenticate.
+    pub fn authenticate(mut self, subquery: impl Into<Subquery>) -> Self {
+        let subquery: Subquery = subquery.into();
+        self.bindings.extend(subquery.get_bindings());
+        self.authenticate = Some(subquery.build());
+        self
+    }
+
+    /// Set how long the token issued by the access method is valid
+    pub fn duration_for_token(mut self, duration: impl Into<DurationLike>) -> Self {
+        let duration: DurationLike = duration.into();
+        self.bindings.extend(duration.get_bindings());
+        self.duration_for_token = Some(duration.build());
+        self
+    }
+
+    /// Set how long the session established with the access method is valid
+    pub fn duration_for_session(mut self, duration: impl Into<DurationLike>) -> Self {
+        let duration: DurationLike = duration.into();
+        self.bindings.extend(duration.get_bindings());
+        self.duration_for_session = Some(duration.build());
+        self
+    }
+}
+
+impl Display for JwtVerification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JwtVerification::Key { algorithm, key } => {
+                write!(f, "JWT")?;
+                if let Some(algorithm) = algorithm {
+                    write!(f, " ALGORITHM {algorithm}")?;
+                }
+                if let Some(key) = key {
+                    write!(f, " KEY {key}")?;
+                }
+                Ok(())
+            }
+            JwtVerification::Url(url) => write!(f, "JWT URL {url}"),
+        }
+    }
+}
+
+impl Buildable for DefineAccessStatement {
+    fn build(&self) -> String {
+        let mut query = format!("{} {}", define_keyword("ACCESS", self.guard), self.name);
+
+        if let Some(target) = &self.target {
+            query = format!("{query} ON {target}");
+        }
+
+        let is_record = self.access_type == Some(AccessType::Record)
+            || self.signup.is_some()
+            || self.signin.is_some();
+
+        if is_record {
+            query = format!("{query} TYPE RECORD");
+
+            if let Some(signup) = &self.signup {
+                query = format!("{query} \n\tSIGNUP {signup}");
+            }
+
+            if let Some(signin) = &self.signin {
+                query = format!("{query} \n\tSIGNIN {signin}");
+            }
+
+            if let Some(jwt) = &self.jwt {
+                query = format!("{query} \n\tWITH {jwt}");
+            }
+        } else if let Some(jwt) = &self.jwt {
+            query = format!("{query} TYPE {jwt}");
+        } else if self.access_type == Some(AccessType::Jwt) {
+            query = format!("{query} TYPE JWT");
+        }
+
+        if let Some(authenticate) = &self.authenticate {
+            query = format!("{query} \n\tAUTHENTICATE {authenticate}");
+        }
+
+        let durations = [
+            self.duration_for_token
+                .as_ref()
+                .map(|duration| format!("FOR TOKEN {duration}")),
+            self.duration_for_session
+                .as_ref()
+                .map(|duration| format!("FOR SESSION {duration}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        if !durations.is_empty() {
+            query = format!("{query} DURATION {}", durations.join(", "));
+        }
+
+        query += ";";
+        query
+    }
+}
+
+impl Display for DefineAccessStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.build())
+    }
+}
+
+impl Parametric for DefineAccessStatement {
+    fn get_bindings(&self) -> BindingsList {
+        self.bindings.to_vec()
+    }
+}
+
+impl Queryable for DefineAccessStatement {}
+
+impl Erroneous for DefineAccessStatement {
+    fn get_errors(&self) -> ErrorList {
+        let mut errors = vec![];
+        if self.target.is_none() {
+            errors.push(format!(
+                "Access method {} must be defined on a namespace or database",
+                self.name
+            ));
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        functions::crypto,
+        statements::{define_access, select},
+        *,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_define_access_record() {
+        let user = Table::new("user");
+        let email = Field::new("email");
+        let pass = Field::new("pass");
+        let pass_param = Param::new("pass_param");
+
+        let statement = define_access("account")
+            .if_not_exists()
+            .on_database()
+            .type_record()
+            .signup(Raw::new(
+                "CREATE user SET email = $email, pass = crypto::argon2::generate($pass)",
+            ))
+            .signin(
+                select(All).from(user).where_(
+                    cond(email.equal("oyelowo@codebreather.com"))
+                        .and(crypto::argon2::compare!(pass, pass_param)),
+                ),
+            )
+            .duration_for_token(Duration::from_secs(60 * 15))
+            .duration_for_session(Duration::from_secs(60 * 60 * 12));
+
+        insta::assert_snapshot!(statement.fine_tune_params());
+        insta::assert_snapshot!(statement.to_raw().build());
+        assert_eq!(statement.get_bindings().len(), 4);
+        assert!(statement.get_errors().is_empty());
+    }
+
+    #[test]
+    fn test_define_access_jwt() {
+        let statement = define_access("oyelowo_token")
+            .on_namespace()
+            .type_jwt()
+            .algorithm(TokenType::HS512)
+            .key("abrakradabra");
+
+        insta::assert_snapshot!(statement.fine_tune_params());
+        insta::assert_snapshot!(statement.to_raw().build());
+        assert_eq!(statement.get_bindings().len(), 1);
+    }
+
+    #[test]
+    fn test_define_access_without_target_is_erroneous() {
+        let statement = define_access("oyelowo_token").type_jwt();
+
+        assert_eq!(statement.get_errors().len(), 1);
+    }
+}
@@ -6,7 +6,10 @@
 
 use std::fmt::{self, Display};
 
-use crate::{BindingsList, Buildable, Erroneous, ErrorList, Parametric, Queryable};
+use crate::{
+    define_keyword, BindingsList, Buildable, DefineGuard, Erroneous, ErrorList, Parametric,
+    Queryable,
+};
 
 #[allow(missing_docs)]
 #[derive(Debug, Clone)]
@@ -100,6 +103,7 @@ impl Display for AnalyzerFilter {
 #[derive(Clone, Debug)]
 pub struct DefineAnalyzerStatement {
     name: String,
+    guard: Option<DefineGuard>,
     tokenizers: Vec<Tokenizer>,
     filters: Vec<AnalyzerFilter>,
     bindings: BindingsList,
@@ -121,6 +125,7 @@ pub struct DefineAnalyzerStatement {
 pub fn define_analyzer(name: &str) -> DefineAnalyzerStatement {
     DefineAnalyzerStatement {
         name: name.to_string(),
+        guard: None,
         tokenizers: vec![],
         filters: vec![],
         bindings: vec![],
@@ -129,6 +134,18 @@ pub fn define_analyzer(name: &str) -> DefineAnalyzerStatement {
 }
 
 impl DefineAnalyzerStatement {
+    /// Only define the analyzer if it does not already exist.
+    pub fn if_not_exists(mut self) -> Self {
+        self.guard = Some(DefineGuard::IfNotExists);
+        self
+    }
+
+    /// Overwrite the analyzer definition if it already exists. Only rendered in the 2.x dialect.
+    pub fn overwrite(mut self) -> Self {
+        self.guard = Some(DefineGuard::Overwrite);
+        self
+    }
+
     /// Add a tokenizer to the analyzer
     pub fn tokenizers<I>(mut self, tokenizers: I) -> Self
     where
@@ -164,7 +181,7 @@ impl Parametric for DefineAnalyzerStatement {
 
 impl Buildable for DefineAnalyzerStatement {
     fn build(&self) -> String {
-        let mut query = format!("DEFINE ANALYZER {}", self.name);
+        let mut query = format!("{} {}", define_keyword("ANALYZER", self.guard), self.name);
 
         if !self.tokenizers.is_empty() {
             let tokenizers_str = self
@@ -7,8 +7,10 @@
 use std::fmt::Display;
 
 use crate::{
+    define_keyword,
     traits::{BindingsList, Buildable, Erroneous, Parametric, Queryable},
     types::Database,
+    DefineGuard,
 };
 
 /// Define a new database statement.
@@ -40,6 +42,7 @@ use crate::{
 pub fn define_database(database: impl Into<Database>) -> DefineDatabaseStatement {
     DefineDatabaseStatement {
         database: database.into().into(),
+        guard: None,
         bindings: vec![],
     }
 }
@@ -47,12 +50,31 @@ pub fn define_database(database: impl Into<Database>) -> DefineDatabaseStatement
 /// A statement for defining a database.
 pub struct DefineDatabaseStatement {
     database: String,
+    guard: Option<DefineGuard>,
     bindings: BindingsList,
 }
 
+impl DefineDatabaseStatement {
+    /// Only define the database if it does not already exist.
+    pub fn if_not_exists(mut self) -> Self {
+        self.guard = Some(DefineGuard::IfNotExists);
+        self
+    }
+
+    /// Overwrite the database definition if it already exists. Only rendered in the 2.x dialect.
+    pub fn overwrite(mut self) -> Self {
+        self.guard = Some(DefineGuard::Overwrite);
+        self
+    }
+}
+
 impl Buildable for DefineDatabaseStatement {
     fn build(&self) -> String {
-        format!("DEFINE DATABASE {};", self.database)
+        format!(
+            "{} {};",
+            define_keyword("DATABASE", self.guard),
+            self.database
+        )
     }
 }
 
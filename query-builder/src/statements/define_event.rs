@@ -7,7 +7,8 @@
 use std::fmt::{self, Display};
 
 use crate::{
-    BindingsList, Buildable, Conditional, Erroneous, Event, Filter, Parametric, Queryable, Table,
+    define_keyword, BindingsList, Buildable, Conditional, DefineGuard, Erroneous, Event, Filter,
+    Parametric, Queryable, Table,
 };
 
 // DEFINE EVENT statement
@@ -28,6 +29,7 @@ use crate::{
 
 pub struct EventBuilder {
     event: String,
+    guard: Option<DefineGuard>,
     on_table: Option<String>,
     when: Option<String>,
     then_string: Option<String>,
@@ -91,6 +93,7 @@ impl EventBuilder {
     fn new(event_name: impl Into<Event>) -> Self {
         Self {
             event: event_name.into().to_string(),
+            guard: None,
             on_table: None,
             when: None,
             then_string: None,
@@ -98,6 +101,18 @@ impl EventBuilder {
         }
     }
 
+    /// Only define the event if it does not already exist.
+    pub fn if_not_exists(mut self) -> Self {
+        self.guard = Some(DefineGuard::IfNotExists);
+        self
+    }
+
+    /// Overwrite the event definition if it already exists. Only rendered in the 2.x dialect.
+    pub fn overwrite(mut self) -> Self {
+        self.guard = Some(DefineGuard::Overwrite);
+        self
+    }
+
     /// Set the event table
     pub fn on_table(mut self, table: impl Into<Table>) -> Self {
         self.on_table = Some(table.into().to_string());
@@ -129,7 +144,11 @@ pub struct DefineEventStatement(EventBuilder);
 // DEFINE EVENT @name ON [ TABLE ] @table WHEN @expression THEN @expression
 impl Buildable for DefineEventStatement {
     fn build(&self) -> String {
-        let mut query = format!("DEFINE EVENT {}", &self.0.event);
+        let mut query = format!(
+            "{} {}",
+            define_keyword("EVENT", self.0.guard),
+            &self.0.event
+        );
         if let Some(table) = &self.0.on_table {
             query = format!("{query} ON TABLE {table}");
         }
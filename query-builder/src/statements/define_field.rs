@@ -7,8 +7,8 @@
 use std::fmt::{self, Display};
 
 use crate::{
-    BindingsList, Buildable, Conditional, Erroneous, Field, FieldType, Filter, Parametric,
-    Queryable, Table, ValueLike,
+    define_keyword, BindingsList, Buildable, Conditional, DefineGuard, Dialect, Erroneous, Field,
+    FieldType, Filter, Parametric, Queryable, Table, ValueLike,
 };
 
 use super::for_permission::Permissions;
@@ -35,8 +35,9 @@ use super::for_permission::Permissions;
 #[derive(Clone, Debug)]
 pub struct DefineFieldStatement {
     field_name: String,
+    guard: Option<DefineGuard>,
     table: Option<String>,
-    type_: Option<String>,
+    type_: Option<FieldType>,
     value: Option<String>,
     assert: Option<String>,
     permissions_none: Option<bool>,
@@ -81,6 +82,7 @@ pub fn define_field(fieldable: impl Into<Field>) -> DefineFieldStatement {
     let field: Field = fieldable.into();
     DefineFieldStatement {
         field_name: field.to_string(),
+        guard: None,
         table: None,
         type_: None,
         value: None,
@@ -93,6 +95,18 @@ pub fn define_field(fieldable: impl Into<Field>) -> DefineFieldStatement {
 }
 
 impl DefineFieldStatement {
+    /// Only define the field if it does not already exist.
+    pub fn if_not_exists(mut self) -> Self {
+        self.guard = Some(DefineGuard::IfNotExists);
+        self
+    }
+
+    /// Overwrite the field definition if it already exists. Only rendered in the 2.x dialect.
+    pub fn overwrite(mut self) -> Self {
+        self.guard = Some(DefineGuard::Overwrite);
+        self
+    }
+
     /// Set the table where the field is defined.
     pub fn on_table(mut self, table: impl Into<Table>) -> Self {
         let table: Table = table.into();
@@ -102,8 +116,7 @@ impl DefineFieldStatement {
 
     /// Set the data type of the field.
     pub fn type_(mut self, field_type: impl Into<FieldType>) -> Self {
-        let field_type: FieldType = field_type.into();
-        self.type_ = Some(field_type.to_string());
+        self.type_ = Some(field_type.into());
         self
     }
 
@@ -234,22 +247,43 @@ impl Parametric for DefineFieldStatement {
 
 impl Buildable for DefineFieldStatement {
     fn build(&self) -> String {
-        let mut query = format!("DEFINE FIELD {}", &self.field_name);
+        let mut query = format!(
+            "{} {}",
+            define_keyword("FIELD", self.guard),
+            &self.field_name
+        );
 
         if let Some(table) = &self.table {
             query = format!("{query} ON TABLE {table}");
         }
 
+        // Literal types are a 2.x feature. In 1.x, the base type is checked
+        // against the literal values instead.
+        let mut literal_assertion = None;
         if let Some(field_type) = &self.type_ {
-            query = format!("{query} TYPE {field_type}");
+            if field_type.has_literals() && !Dialect::current().is_v2() {
+                literal_assertion = field_type.literal_assertion();
+                query = format!("{query} TYPE {}", field_type.without_literals());
+            } else {
+                query = format!("{query} TYPE {field_type}");
+            }
         }
 
         if let Some(value) = &self.value {
             query = format!("{query} VALUE $value OR {value}");
         }
 
-        if let Some(assertion) = &self.assert {
-            query = format!("{query} ASSERT {assertion}");
+        match (&self.assert, literal_assertion) {
+            (Some(assertion), Some(literal_assertion)) => {
+                query = format!("{query} ASSERT ({assertion}) AND ({literal_assertion})");
+            }
+            (Some(assertion), None) => {
+                query = format!("{query} ASSERT {assertion}");
+            }
+            (None, Some(literal_assertion)) => {
+                query = format!("{query} ASSERT {literal_assertion}");
+            }
+            (None, None) => {}
         }
 
         if let Some(true) = self.permissions_none {
@@ -274,8 +308,9 @@ impl Display for DefineFieldStatement {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{cond, value, Operatable, ToRaw, NONE};
+    use crate::{cond, value, with_dialect, Dialect, Operatable, ToRaw, NONE};
     use crate::{statements::for_permission, CrudType::*};
+    use std::str::FromStr;
 
     #[test]
     fn test_define_field_statement_full() {
@@ -337,4 +372,32 @@ mod tests {
         insta::assert_snapshot!(statement.fine_tune_params());
         assert_eq!(statement.get_bindings().len(), 0);
     }
+
+    #[test]
+    fn test_define_field_statement_literal_type() {
+        let status = Field::new("status");
+        let statement = define_field(status)
+            .on_table(Table::from("user"))
+            .type_(FieldType::from_str("\"active\" | \"banned\"").unwrap());
+
+        assert_eq!(
+            with_dialect(Dialect::V2, || statement.build()),
+            "DEFINE FIELD status ON TABLE user TYPE \"active\" | \"banned\";"
+        );
+        assert_eq!(
+            with_dialect(Dialect::V1, || statement.build()),
+            "DEFINE FIELD status ON TABLE user TYPE string ASSERT $value INSIDE ['active', 'banned'];"
+        );
+
+        let statement = statement.assert(value().is_not(NONE));
+        assert_eq!(
+            with_dialect(Dialect::V1, || statement.build()),
+            "DEFINE FIELD status ON TABLE user TYPE string \
+                ASSERT ($value IS NOT NONE) AND ($value INSIDE ['active', 'banned']);"
+        );
+        assert_eq!(
+            with_dialect(Dialect::V2, || statement.build()),
+            "DEFINE FIELD status ON TABLE user TYPE \"active\" | \"banned\" ASSERT $value IS NOT NONE;"
+        );
+    }
 }
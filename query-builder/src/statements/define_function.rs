@@ -5,7 +5,8 @@
  */
 
 use crate::{
-    BindingsList, Block, Buildable, Erroneous, ErrorList, FieldType, Param, Parametric, Queryable,
+    define_keyword, BindingsList, Block, Buildable, DefineGuard, Erroneous, ErrorList, FieldType,
+    Param, Parametric, Queryable,
 };
 
 /// Represents a surrealdb define function statement argument
@@ -21,6 +22,7 @@ pub struct FunctionArgument {
 #[derive(Debug, Clone)]
 pub struct DefineFunctionStatement {
     name: String,
+    guard: Option<DefineGuard>,
     args: Vec<FunctionArgument>,
     body: Option<Block>,
     bindings: BindingsList,
@@ -35,6 +37,7 @@ impl DefineFunctionStatement {
     pub fn new(name: String) -> Self {
         Self {
             name,
+            guard: None,
             args: vec![],
             body: None,
             bindings: vec![],
@@ -42,6 +45,18 @@ impl DefineFunctionStatement {
         }
     }
 
+    /// Only define the function if it does not already exist.
+    pub fn if_not_exists(mut self) -> Self {
+        self.guard = Some(DefineGuard::IfNotExists);
+        self
+    }
+
+    /// Overwrite the function definition if it already exists. Only rendered in the 2.x dialect.
+    pub fn overwrite(mut self) -> Self {
+        self.guard = Some(DefineGuard::Overwrite);
+        self
+    }
+
     /// Sets the arguments for the function
     pub fn arguments(mut self, args: Vec<FunctionArgument>) -> Self {
         self.args = args;
@@ -62,6 +77,7 @@ impl DefineFunctionStatement {
 pub fn define_function(name: impl Into<String>) -> DefineFunctionStatement {
     DefineFunctionStatement {
         name: name.into(),
+        guard: None,
         args: vec![],
         body: None,
         bindings: vec![],
@@ -85,7 +101,11 @@ impl Queryable for DefineFunctionStatement {}
 
 impl Buildable for DefineFunctionStatement {
     fn build(&self) -> String {
-        let mut build = format!("DEFINE FUNCTION fn::{}(", self.name);
+        let mut build = format!(
+            "{} fn::{}(",
+            define_keyword("FUNCTION", self.guard),
+            self.name
+        );
         build.push_str(
             &self
                 .args
@@ -31,9 +31,10 @@
 use std::fmt::{self, Display};
 
 use crate::{
+    define_keyword,
     traits::{BindingsList, Buildable, Erroneous, Parametric, Queryable},
     types::{Field, TableIndex},
-    ErrorList, NumberLike, TableLike, ValueLike,
+    DefineGuard, ErrorList, NumberLike, TableLike, ValueLike,
 };
 
 /// Define a new database index.
@@ -71,6 +72,7 @@ pub fn define_index(index_name: impl Into<TableIndex>) -> DefineIndexStatement {
 
     DefineIndexStatement {
         index_name,
+        guard: None,
         table: None,
         fields: vec![],
         columns: vec![],
@@ -157,6 +159,7 @@ impl Parametric for Columns {
 /// A statement for defining a database Index.
 pub struct DefineIndexStatement {
     index_name: String,
+    guard: Option<DefineGuard>,
     table: Option<String>,
     fields: Vec<Field>,
     columns: Vec<Field>,
@@ -167,6 +170,18 @@ pub struct DefineIndexStatement {
 }
 
 impl DefineIndexStatement {
+    /// Only define the index if it does not already exist.
+    pub fn if_not_exists(mut self) -> Self {
+        self.guard = Some(DefineGuard::IfNotExists);
+        self
+    }
+
+    /// Overwrite the index definition if it already exists. Only rendered in the 2.x dialect.
+    pub fn overwrite(mut self) -> Self {
+        self.guard = Some(DefineGuard::Overwrite);
+        self
+    }
+
     /// Set the table where the index is defined.
     pub fn on_table(mut self, table: impl Into<TableLike>) -> Self {
         let table: TableLike = table.into();
@@ -232,7 +247,11 @@ impl DefineIndexStatement {
 
 impl Buildable for DefineIndexStatement {
     fn build(&self) -> String {
-        let mut query = format!("DEFINE INDEX {}", self.index_name);
+        let mut query = format!(
+            "{} {}",
+            define_keyword("INDEX", self.guard),
+            self.index_name
+        );
 
         if let Some(table) = &self.table {
             query = format!("{query} ON TABLE {table}");
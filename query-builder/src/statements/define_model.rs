@@ -1,6 +1,6 @@
 use crate::{
-    statements::Permissions, BindingsList, Buildable, Erroneous, LiteralLike, Parametric,
-    Queryable, StrandLike, TableLike,
+    define_keyword, statements::Permissions, BindingsList, Buildable, DefineGuard, Erroneous,
+    LiteralLike, Parametric, Queryable, StrandLike, TableLike,
 };
 use std::fmt::{self, Display};
 
@@ -17,6 +17,7 @@ use std::fmt::{self, Display};
 #[derive(Clone, Debug)]
 pub struct DefineModelStatement {
     model_name: String,
+    guard: Option<DefineGuard>,
     version: String,
     comment: Option<String>,
     permissions_none: Option<bool>,
@@ -57,6 +58,7 @@ pub fn define_model(name: impl Into<ModelName>) -> DefineModelStatement {
 
     DefineModelStatement {
         model_name: name.build(),
+        guard: None,
         version: String::new(),
         comment: None,
         permissions_none: None,
@@ -70,6 +72,18 @@ pub fn define_model(name: impl Into<ModelName>) -> DefineModelStatement {
 pub type ModelVersion = LiteralLike;
 
 impl DefineModelStatement {
+    /// Only define the model if it does not already exist.
+    pub fn if_not_exists(mut self) -> Self {
+        self.guard = Some(DefineGuard::IfNotExists);
+        self
+    }
+
+    /// Overwrite the model definition if it already exists. Only rendered in the 2.x dialect.
+    pub fn overwrite(mut self) -> Self {
+        self.guard = Some(DefineGuard::Overwrite);
+        self
+    }
+
     /// Set the version of the model.
     pub fn version(mut self, version: impl Into<ModelVersion>) -> Self {
         let version: ModelVersion = version.into();
@@ -170,7 +184,11 @@ impl Parametric for DefineModelStatement {
 
 impl Buildable for DefineModelStatement {
     fn build(&self) -> String {
-        let mut query = format!("DEFINE MODEL ml::{}", &self.model_name);
+        let mut query = format!(
+            "{} ml::{}",
+            define_keyword("MODEL", self.guard),
+            &self.model_name
+        );
 
         if !self.version.is_empty() {
             query = format!("{query}<{version}>", version = self.version);
@@ -7,8 +7,10 @@
 use std::fmt::Display;
 
 use crate::{
+    define_keyword,
     traits::{BindingsList, Buildable, Erroneous, Parametric, Queryable},
     types::Namespace,
+    DefineGuard,
 };
 
 /// Define a new namespace .
@@ -36,6 +38,7 @@ use crate::{
 pub fn define_namespace(namespace: impl Into<Namespace>) -> DefineNamespaceStatement {
     DefineNamespaceStatement {
         namespace: namespace.into().into(),
+        guard: None,
         bindings: vec![],
     }
 }
@@ -43,12 +46,31 @@ pub fn define_namespace(namespace: impl Into<Namespace>) -> DefineNamespaceState
 /// Define namespace
 pub struct DefineNamespaceStatement {
     namespace: String,
+    guard: Option<DefineGuard>,
     bindings: BindingsList,
 }
 
+impl DefineNamespaceStatement {
+    /// Only define the namespace if it does not already exist.
+    pub fn if_not_exists(mut self) -> Self {
+        self.guard = Some(DefineGuard::IfNotExists);
+        self
+    }
+
+    /// Overwrite the namespace definition if it already exists. Only rendered in the 2.x dialect.
+    pub fn overwrite(mut self) -> Self {
+        self.guard = Some(DefineGuard::Overwrite);
+        self
+    }
+}
+
 impl Buildable for DefineNamespaceStatement {
     fn build(&self) -> String {
-        format!("DEFINE NAMESPACE {};", self.namespace)
+        format!(
+            "{} {};",
+            define_keyword("NAMESPACE", self.guard),
+            self.namespace
+        )
     }
 }
 
@@ -7,7 +7,8 @@
 use std::{fmt::Display, ops::Deref};
 
 use crate::{
-    BindingsList, Buildable, Erroneous, ErrorList, Param, Parametric, Queryable, ValueLike,
+    define_keyword, BindingsList, Buildable, DefineGuard, Erroneous, ErrorList, Param, Parametric,
+    Queryable, ValueLike,
 };
 
 // DEFINE PARAM statement
@@ -56,6 +57,7 @@ pub fn define_param(param_name: impl Deref<Target = Param>) -> DefineParamStatem
     let param_name: &Param = param_name.deref();
     let define_param_statement = DefineParamStatement {
         name: param_name.to_string(),
+        guard: None,
         value: None,
         bindings: vec![],
         errors: vec![],
@@ -68,12 +70,25 @@ pub struct DefineParamStatementBuilder(DefineParamStatement);
 /// Define param statement
 pub struct DefineParamStatement {
     name: String,
+    guard: Option<DefineGuard>,
     value: Option<String>,
     bindings: BindingsList,
     errors: ErrorList,
 }
 
 impl DefineParamStatementBuilder {
+    /// Only define the param if it does not already exist.
+    pub fn if_not_exists(mut self) -> Self {
+        self.0.guard = Some(DefineGuard::IfNotExists);
+        self
+    }
+
+    /// Overwrite the param definition if it already exists. Only rendered in the 2.x dialect.
+    pub fn overwrite(mut self) -> Self {
+        self.0.guard = Some(DefineGuard::Overwrite);
+        self
+    }
+
     /// Set the value of the parameter.
     pub fn value(mut self, value: impl Into<ValueLike>) -> DefineParamStatement {
         let value: ValueLike = value.into();
@@ -86,7 +101,7 @@ impl DefineParamStatementBuilder {
 
 impl Buildable for DefineParamStatement {
     fn build(&self) -> String {
-        let mut query = format!("DEFINE PARAM {}", self.name);
+        let mut query = format!("{} {}", define_keyword("PARAM", self.guard), self.name);
 
         if let Some(value) = &self.value {
             query = format!("{query} VALUE {value}");
@@ -7,8 +7,9 @@
 use std::fmt::{self, Display};
 
 use crate::{
+    define_keyword,
     statements::{for_permission::Permissions, select::SelectStatement},
-    BindingsList, Buildable, Erroneous, Parametric, Queryable, Table,
+    BindingsList, Buildable, DefineGuard, Dialect, Erroneous, Parametric, Queryable, Table, Tables,
 };
 
 // DEFINE TABLE statement
@@ -32,10 +33,16 @@ use crate::{
 // 		| FOR update @expression
 // 		| FOR delete @expression
 // 	] ]
+//
+// 2.x syntax additionally supports
+// DEFINE TABLE [ OVERWRITE | IF NOT EXISTS ] @name
+// 	[ TYPE [ ANY | NORMAL | RELATION [ IN | FROM ] @table [ OUT | TO ] @table ] ]
 
 /// Define the API for the Table builder
 pub struct DefineTableStatement {
     table: String,
+    guard: Option<DefineGuard>,
+    table_type: Option<TableType>,
     drop: Option<bool>,
     flexible: Option<bool>,
     schema_type: Option<SchemaType>,
@@ -93,6 +100,8 @@ pub fn define_table(table: impl Into<Table>) -> DefineTableStatement {
     let table: Table = table.into();
     DefineTableStatement {
         table: table.to_string(),
+        guard: None,
+        table_type: None,
         drop: None,
         flexible: None,
         schema_type: None,
@@ -105,6 +114,54 @@ pub fn define_table(table: impl Into<Table>) -> DefineTableStatement {
 }
 
 impl DefineTableStatement {
+    /// Only define the table if it does not already exist.
+    pub fn if_not_exists(mut self) -> Self {
+        self.guard = Some(DefineGuard::IfNotExists);
+        self
+    }
+
+    /// Overwrite the table definition if it already exists. Only rendered in the 2.x dialect.
+    pub fn overwrite(mut self) -> Self {
+        self.guard = Some(DefineGuard::Overwrite);
+        self
+    }
+
+    /// Allow the table to store both normal records and relations. Only rendered in the 2.x dialect.
+    pub fn type_any(mut self) -> Self {
+        self.table_type = Some(TableType::Any);
+        self
+    }
+
+    /// Only allow normal records i.e not relations in the table. Only rendered in the 2.x dialect.
+    pub fn type_normal(mut self) -> Self {
+        self.table_type = Some(TableType::Normal);
+        self
+    }
+
+    /// Only allow relations from the `in_` tables to the `out` tables.
+    /// Only rendered in the 2.x dialect.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    /// # use surreal_query_builder as surreal_orm;
+    /// use surreal_orm::{*, statements::define_table};
+    ///
+    /// let statement = define_table(Table::from("likes"))
+    ///     .type_relation([Table::from("user"), Table::from("admin")], Table::from("post"));
+    ///
+    /// assert_eq!(
+    ///     with_dialect(Dialect::V2, || statement.build()),
+    ///     "DEFINE TABLE likes TYPE RELATION IN user | admin OUT post;"
+    /// );
+    /// ```
+    pub fn type_relation(mut self, in_: impl Into<Tables>, out: impl Into<Tables>) -> Self {
+        let in_: Vec<Table> = in_.into().into();
+        let out: Vec<Table> = out.into().into();
+        self.table_type = Some(TableType::Relation { in_, out });
+        self
+    }
+
     /// Drop the table if it exists and create a new one with the same name.
     pub fn drop(mut self) -> Self {
         self.drop = Some(true);
@@ -250,7 +307,13 @@ impl DefineTableStatement {
 // 	] ]
 impl Buildable for DefineTableStatement {
     fn build(&self) -> String {
-        let mut query = format!("DEFINE TABLE {}", &self.table);
+        let mut query = format!("{} {}", define_keyword("TABLE", self.guard), &self.table);
+
+        if let Some(table_type) = &self.table_type {
+            if Dialect::current().is_v2() {
+                query = format!("{query} TYPE {table_type}");
+            }
+        }
 
         if self.drop.unwrap_or_default() {
             query = format!("{query} DROP");
@@ -307,6 +370,39 @@ enum SchemaType {
     Schemaless,
 }
 
+enum TableType {
+    Any,
+    Normal,
+    Relation { in_: Vec<Table>, out: Vec<Table> },
+}
+
+impl Display for TableType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |tables: &[Table]| {
+            tables
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" | ")
+        };
+
+        match self {
+            TableType::Any => write!(f, "ANY"),
+            TableType::Normal => write!(f, "NORMAL"),
+            TableType::Relation { in_, out } => {
+                write!(f, "RELATION")?;
+                if !in_.is_empty() {
+                    write!(f, " IN {}", join(in_))?;
+                }
+                if !out.is_empty() {
+                    write!(f, " OUT {}", join(out))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -398,4 +494,30 @@ mod tests {
         );
         assert_eq!(statement.get_bindings().len(), 8);
     }
+
+    #[test]
+    fn test_define_table_type_relation_is_dialect_specific() {
+        let statement = define_table(Table::from("likes"))
+            .if_not_exists()
+            .type_relation(
+                Table::from("user"),
+                [Table::from("post"), Table::from("comment")],
+            )
+            .schemafull();
+
+        insta::assert_snapshot!(with_dialect(Dialect::V1, || statement.build()));
+        insta::assert_snapshot!(with_dialect(Dialect::V2, || statement.build()));
+    }
+
+    #[test]
+    fn test_define_table_type_normal_overwrite() {
+        let statement = define_table(Table::from("user"))
+            .overwrite()
+            .type_normal()
+            .schemaless()
+            .permissions_full();
+
+        insta::assert_snapshot!(with_dialect(Dialect::V1, || statement.build()));
+        insta::assert_snapshot!(with_dialect(Dialect::V2, || statement.build()));
+    }
 }
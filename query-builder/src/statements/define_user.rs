@@ -7,8 +7,10 @@
 use std::fmt::{self, Display};
 
 use crate::{
+    define_keyword,
     traits::{Binding, BindingsList, Buildable, Erroneous, Parametric, Queryable},
     types::Idiomx,
+    DefineGuard,
 };
 
 pub struct Password(pub(crate) String);
@@ -133,6 +135,7 @@ pub enum UserCredential {
 /// Define a new database user.
 pub struct DefineUserStatement {
     name: String,
+    guard: Option<DefineGuard>,
     user_type: Option<UserType>,
     credential: Option<UserCredential>,
     role: Option<UserRole>,
@@ -140,11 +143,24 @@ pub struct DefineUserStatement {
 }
 
 impl DefineUserStatement {
+    /// Only define the user if it does not already exist.
+    pub fn if_not_exists(mut self) -> Self {
+        self.guard = Some(DefineGuard::IfNotExists);
+        self
+    }
+
+    /// Overwrite the user definition if it already exists. Only rendered in the 2.x dialect.
+    pub fn overwrite(mut self) -> Self {
+        self.guard = Some(DefineGuard::Overwrite);
+        self
+    }
+
     // Set the user name
     fn new(name: impl Into<Idiomx>) -> Self {
         let binding = Binding::new(name.into()).with_description("user name");
         Self {
             name: binding.get_param_dollarised(),
+            guard: None,
             user_type: None,
             credential: None,
             role: None,
@@ -199,7 +215,7 @@ impl DefineUserStatement {
 
 impl Buildable for DefineUserStatement {
     fn build(&self) -> String {
-        let mut query = format!("DEFINE USER {}", self.name);
+        let mut query = format!("{} {}", define_keyword("USER", self.guard), self.name);
 
         if let Some(user_type) = &self.user_type {
             query.push_str(&format!(" ON {user_type}"));
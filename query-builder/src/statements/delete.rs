@@ -4,13 +4,18 @@
  * Copyright (c) 2023 Oyelowo Oyedayo
  */
 
-use std::marker::PhantomData;
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
 
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
+    ast::{Delete, Mutator, Target, Visitable, Visitor},
+    tenant::tenant_condition,
     traits::{BindingsList, Buildable, Erroneous, Model, Parametric, Queryable},
-    types::{DurationLike, Filter, ReturnType},
+    types::{DurationLike, ReturnType},
     Binding, Conditional, ErrorList, ReturnableDefault, ReturnableStandard, ToRaw,
 };
 
@@ -47,17 +52,15 @@ where
 {
     let table_name = T::table();
     let targettables: TargettablesForUpdate = targettables.into();
-    let mut bindings = vec![];
     let mut errors = vec![];
-    let param = match targettables {
+    let target = match targettables {
         TargettablesForUpdate::Table(table) => {
-            let table = table.to_string();
-            if table != table_name.to_string() {
+            if table.to_string() != table_name.to_string() {
                 errors.push(format!(
                     "table name -{table} does not match the surreal model struct type which belongs to {table} table"
                 ));
             }
-            table
+            Target::Table(table)
         }
         TargettablesForUpdate::SurrealId(id) => {
             if !id
@@ -68,22 +71,21 @@ where
                     "id - {id} does not belong to {table_name} table from the surreal model struct provided"
                 ));
             }
-            let binding = Binding::new(id);
-            let param = binding.get_param_dollarised();
-            bindings.push(binding);
-            param
+            Target::Record(Binding::new(id))
         }
     };
 
+    let mut delete = Delete::new(target);
+    match tenant_condition::<T>() {
+        Ok(Some(condition)) => {
+            delete.add_scope_condition(condition);
+        }
+        Ok(None) => {}
+        Err(error) => errors.push(error),
+    }
+
     DeleteStatement::<T> {
-        target: param,
-        is_only: false,
-        where_: None,
-        return_type: None,
-        timeout: None,
-        parallel: false,
-        bindings,
-        errors,
+        delete: Delete { errors, ..delete },
         __model_return_type: PhantomData,
     }
 }
@@ -119,17 +121,30 @@ pub struct DeleteStatement<T>
 where
     T: Serialize + DeserializeOwned + Model,
 {
-    target: String,
-    is_only: bool,
-    where_: Option<String>,
-    return_type: Option<ReturnType>,
-    timeout: Option<String>,
-    parallel: bool,
-    bindings: BindingsList,
-    errors: ErrorList,
+    delete: Delete,
     __model_return_type: PhantomData<T>,
 }
 
+impl<T> Deref for DeleteStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    type Target = Delete;
+
+    fn deref(&self) -> &Self::Target {
+        &self.delete
+    }
+}
+
+impl<T> DerefMut for DeleteStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.delete
+    }
+}
+
 impl<T> Queryable for DeleteStatement<T> where T: Serialize + DeserializeOwned + Model {}
 
 impl<T> Erroneous for DeleteStatement<T>
@@ -137,7 +152,7 @@ where
     T: Serialize + DeserializeOwned + Model,
 {
     fn get_errors(&self) -> ErrorList {
-        self.errors.to_vec()
+        self.delete.get_errors()
     }
 }
 
@@ -164,14 +179,7 @@ where
     ///         .and(age.less_than(80)));
     /// ```
     pub fn where_(mut self, condition: impl Conditional + Clone) -> Self {
-        self.update_bindings(condition.get_bindings());
-        let condition = Filter::new(condition);
-        self.where_ = Some(condition.build());
-        self
-    }
-
-    fn update_bindings(&mut self, bindings: BindingsList) -> &mut Self {
-        self.bindings.extend(bindings);
+        self.set_where(condition);
         self
     }
 
@@ -255,29 +263,20 @@ where
     T: Serialize + DeserializeOwned + Model,
 {
     fn build(&self) -> String {
-        let mut query = format!("DELETE {}", self.target);
-
-        if self.is_only {
-            query = format!("{query} ONLY");
-        }
-
-        if let Some(condition) = &self.where_ {
-            query = format!("{query} WHERE {}", condition);
-        }
-
-        if let Some(return_type) = &self.return_type {
-            query = format!("{query} {return_type}");
-        }
-
-        if let Some(timeout) = &self.timeout {
-            query = format!("{query} TIMEOUT {timeout}");
-        }
+        self.delete.build()
+    }
+}
 
-        if self.parallel {
-            query = format!("{query} PARALLEL");
-        }
+impl<T> Visitable for DeleteStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn accept(&self, visitor: &mut impl Visitor) {
+        self.delete.accept(visitor);
+    }
 
-        format!("{query};")
+    fn accept_mut(&mut self, mutator: &mut impl Mutator) {
+        self.delete.accept_mut(mutator);
     }
 }
 
@@ -295,7 +294,7 @@ where
     T: Serialize + DeserializeOwned + Model,
 {
     fn get_bindings(&self) -> BindingsList {
-        self.bindings.to_vec()
+        self.delete.get_bindings()
     }
 }
 
@@ -17,6 +17,7 @@ use serde::{de::DeserializeOwned, Serialize};
 use surrealdb::sql;
 
 use crate::{
+    tenant::stamp_tenant,
     traits::{Binding, BindingsList, Buildable, Erroneous, Node, Parametric, Queryable},
     types::Updateables,
     ErrorList, ReturnType, ReturnableDefault, ReturnableStandard,
@@ -218,13 +219,16 @@ where
     let mut serialized_field_names = T::get_serializable_fields();
     serialized_field_names.sort_by_key(|a| a.build());
 
-    let value = sql::to_value(node).ok().map_or_else(
+    let mut value = sql::to_value(node).ok().map_or_else(
         || {
             errors.push("Unable to convert node to json".to_string());
             sql::Value::Null
         },
         |v| v,
     );
+    if let Err(error) = stamp_tenant::<T>(&mut value) {
+        errors.push(error);
+    }
 
     let (field_names, bindings): (Vec<String>, BindingsList) = serialized_field_names
         .iter()
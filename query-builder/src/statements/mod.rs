@@ -10,6 +10,7 @@ pub(crate) mod break_;
 pub(crate) mod continue_;
 pub(crate) mod create;
 pub(crate) mod create_v2;
+pub(crate) mod define_access;
 pub(crate) mod define_analyzer;
 pub(crate) mod define_database;
 pub(crate) mod define_event;
@@ -55,6 +56,7 @@ pub(crate) mod subquery;
 pub(crate) mod throw;
 pub(crate) mod transaction;
 pub(crate) mod update;
+pub(crate) mod upsert;
 pub(crate) mod use_;
 pub(crate) mod utils_block;
 pub(crate) mod utils_chain;
@@ -63,6 +65,7 @@ pub use break_::{break_, BreakStatement};
 pub use continue_::{continue_, ContinueStatement};
 pub use create::{create, create_only, CreateStatement};
 pub use create_v2::{create_v2, CreateStatementV2};
+pub use define_access::{define_access, DefineAccessStatement};
 pub use define_analyzer::{
     define_analyzer, AnalyzerFilter, DefineAnalyzerStatement, SnowballLanguage, Tokenizer,
 };
@@ -104,7 +107,7 @@ pub use remove_user::{remove_user, RemoveUserStatement, UserPermissionScope};
 pub use return_::{return_, ReturnStatement};
 pub use select::{
     order, select, select_diff, select_value, Order, Orderables, SelectStatement,
-    SelectStatementMini, Selectables, Splittables,
+    SelectStatementMini, Selectables, Splittables, TargettablesForSelect,
 };
 pub use show::{show_changes_for_table, ShowChangesStatement};
 pub use sleep::{sleep, SleepStatement};
@@ -112,8 +115,10 @@ pub use subquery::Subquery;
 pub use throw::{throw, ThrowStatement};
 pub use transaction::{
     begin_transaction, transaction_deprecated, BeginTransactionStatement, TransactionCompletion,
+    TransactionHandle,
 };
 pub use update::{update, update_only, UpdateStatement};
+pub use upsert::{upsert, upsert_only, UpsertStatement, UpsertStatementInit};
 pub use use_::{use_, UseStatement};
 
 /// helpers for statements
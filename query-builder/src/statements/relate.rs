@@ -18,8 +18,8 @@ use std::marker::PhantomData;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    derive_binding_and_errors_from_value,
-    traits::{BindingsList, Buildable, Edge, Erroneous, ErrorList, Parametric, Queryable},
+    tenant::{derive_tenant_binding_and_errors, tenant_set_assignment},
+    traits::{Binding, BindingsList, Buildable, Edge, Erroneous, ErrorList, Parametric, Queryable},
     types::{DurationLike, ReturnType},
     ReturnableDefault, ReturnableStandard, Setter, ToRaw,
 };
@@ -54,12 +54,18 @@ pub fn relate<T>(connection: impl Buildable + Parametric + Erroneous) -> RelateS
 where
     T: Serialize + DeserializeOwned + Edge,
 {
-    let errors = connection.get_errors();
+    let mut errors = connection.get_errors();
+    let tenant_assignment = tenant_set_assignment::<T>().unwrap_or_else(|error| {
+        errors.push(error);
+        None
+    });
+
     RelateStatement {
         relation: connection.build(),
         is_only: false,
         content_param: None,
         set: vec![],
+        tenant_assignment,
         return_type: None,
         timeout: None,
         parallel: false,
@@ -114,6 +120,8 @@ where
     is_only: bool,
     content_param: Option<String>,
     set: Vec<String>,
+    // Sets the tenant field of tenant-scoped edges created without `content`.
+    tenant_assignment: Option<(String, Binding)>,
     return_type: Option<ReturnType>,
     timeout: Option<String>,
     parallel: bool,
@@ -128,7 +136,7 @@ where
 {
     /// Set a serailizable surrealdb edge model. It must implement the Edge trait.
     pub fn content(mut self, content: T) -> Self {
-        let (binding, errors) = derive_binding_and_errors_from_value(&content);
+        let (binding, errors) = derive_tenant_binding_and_errors::<T>(&content);
         self.content_param = Some(binding.get_param_dollarised().to_owned());
         self.bindings.push(binding);
         self.errors.extend(errors);
@@ -282,7 +290,11 @@ where
     T: Serialize + DeserializeOwned + Edge,
 {
     fn get_bindings(&self) -> BindingsList {
-        self.bindings.to_vec()
+        let mut bindings = self.bindings.to_vec();
+        if let (None, Some((_, binding))) = (&self.content_param, &self.tenant_assignment) {
+            bindings.push(binding.clone());
+        }
+        bindings
     }
 }
 
@@ -301,8 +313,13 @@ where
             query = format!("{query} CONTENT {param} ");
         }
 
-        if !&self.set.is_empty() {
-            let set_vec = self.set.join(", ");
+        let mut set = self.set.clone();
+        if let (None, Some((assignment, _))) = (&self.content_param, &self.tenant_assignment) {
+            set.push(assignment.to_string());
+        }
+
+        if !set.is_empty() {
+            let set_vec = set.join(", ");
             query = format!("{query} SET {set_vec} ");
         }
 
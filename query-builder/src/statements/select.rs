@@ -27,17 +27,21 @@
 use std::{
     fmt::{Display, Formatter, Result as FmtResult},
     marker::PhantomData,
-    ops::Deref,
+    ops::{Deref, DerefMut},
 };
 
 use serde::{de::DeserializeOwned, Serialize};
 use surrealdb::{engine::local::Db, sql, Surreal};
 
 use crate::{
-    Aliasable, All, Binding, BindingsList, Buildable, Conditional, DurationLike, Erroneous,
-    ErrorList, Field, Filter, Function, IndexName, Model, NumberLike, Parametric, Queryable,
-    ReturnableSelect, ReturnableStandard, SurrealId, SurrealOrmResult, SurrealSimpleId,
-    SurrealUlid, SurrealUuid, Table, ToRaw, ValueLike,
+    ast::{
+        ExplainMode, Expr, Mutator, Select, SelectKind, Target, Visitable, Visitor, WithIndexType,
+    },
+    tenant::{scope_select, scope_select_model},
+    Aliasable, All, Binding, BindingsList, Buildable, CompositeKey, Conditional, DurationLike,
+    Erroneous, ErrorList, Field, Function, IndexName, Model, NumberLike, Param, Parametric,
+    Queryable, ReturnableSelect, ReturnableStandard, SurrealCompositeId, SurrealId,
+    SurrealOrmResult, SurrealSimpleId, SurrealUlid, SurrealUuid, Table, ToRaw, ValueLike,
 };
 
 use super::Subquery;
@@ -292,6 +296,7 @@ pub enum TargettablesForSelect {
     SurrealIds(Vec<sql::Thing>),
     // Should already be bound
     Subquery(Subquery),
+    Param(Param),
     Function(Function),
     RecordRange(ValueLike),
 }
@@ -495,6 +500,25 @@ impl<T: Model> From<&[&SurrealUlid<T>]> for TargettablesForSelect {
     }
 }
 
+// from SurrealCompositeId
+impl<T: Model, K: CompositeKey> From<&SurrealCompositeId<T, K>> for TargettablesForSelect {
+    fn from(value: &SurrealCompositeId<T, K>) -> Self {
+        Self::SurrealId(value.to_thing())
+    }
+}
+
+impl<T: Model, K: CompositeKey> From<SurrealCompositeId<T, K>> for TargettablesForSelect {
+    fn from(value: SurrealCompositeId<T, K>) -> Self {
+        Self::SurrealId(value.to_thing())
+    }
+}
+
+impl<T: Model, K: CompositeKey> From<Vec<SurrealCompositeId<T, K>>> for TargettablesForSelect {
+    fn from(value: Vec<SurrealCompositeId<T, K>>) -> Self {
+        Self::SurrealIds(value.iter().map(|t| t.to_thing()).collect::<Vec<_>>())
+    }
+}
+
 impl From<&Table> for TargettablesForSelect {
     fn from(value: &Table) -> Self {
         Self::Table(value.into())
@@ -564,6 +588,12 @@ impl From<Function> for TargettablesForSelect {
     }
 }
 
+impl From<Param> for TargettablesForSelect {
+    fn from(value: Param) -> Self {
+        Self::Param(value)
+    }
+}
+
 /// Single field or multiple fields to split by
 #[derive(Clone, Debug)]
 pub enum Splittables {
@@ -761,77 +791,24 @@ impl Deref for Selectables {
     }
 }
 
+/// Select statement initializer
 #[derive(Debug, Clone)]
-enum SelectionType {
-    Select,
-    SelectValue,
-    SelectDiff,
-}
+pub struct SelectStatementInit(Select);
 
-#[derive(Debug, Clone)]
-enum WithIndexType {
-    NoIndex,
-    Indexes(Vec<IndexName>),
-}
+impl Deref for SelectStatementInit {
+    type Target = Select;
 
-impl Parametric for WithIndexType {
-    fn get_bindings(&self) -> BindingsList {
-        match self {
-            WithIndexType::NoIndex => vec![],
-            WithIndexType::Indexes(indexes) => indexes
-                .iter()
-                .flat_map(|i| i.get_bindings())
-                .collect::<Vec<_>>(),
-        }
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
-impl Buildable for WithIndexType {
-    fn build(&self) -> String {
-        match self {
-            WithIndexType::NoIndex => "WITH NO INDEX".into(),
-            WithIndexType::Indexes(indexes) => format!(
-                "WITH INDEX {}",
-                indexes
-                    .iter()
-                    .map(|i| i.build())
-                    .collect::<Vec<_>>()
-                    .join(",")
-            ),
-        }
+impl DerefMut for SelectStatementInit {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
     }
 }
 
-#[derive(Debug, Clone)]
-enum ExplainMode {
-    Simple,
-    Full,
-}
-
-/// Select statement initializer
-#[derive(Debug, Clone)]
-pub struct SelectStatementInit {
-    selection_type: SelectionType,
-    projections: String,
-    targets: Vec<String>,
-    only: bool,
-    where_: Option<String>,
-    split: Vec<String>,
-    group_by: Vec<String>,
-    group_all: bool,
-    order_by: Vec<Order>,
-    limit: Option<String>,
-    start: Option<String>,
-    fetch: Vec<String>,
-    omitted_fields: Vec<String>,
-    with_index_type: Option<WithIndexType>,
-    timeout: Option<String>,
-    parallel: bool,
-    explain_mode: Option<ExplainMode>,
-    bindings: BindingsList,
-    errors: ErrorList,
-}
-
 impl Aliasable for SelectStatement {
     fn build_aliasable(&self) -> String {
         format!("({})", self.build().trim_end_matches(';'))
@@ -850,13 +827,23 @@ impl Conditional for SelectStatement {
 
 impl Erroneous for SelectStatement {
     fn get_errors(&self) -> Vec<String> {
-        self.0.errors.to_vec()
+        self.0 .0.get_errors()
     }
 }
 
 impl Parametric for SelectStatement {
     fn get_bindings(&self) -> BindingsList {
-        self.0.bindings.to_vec()
+        self.0 .0.get_bindings()
+    }
+}
+
+impl Visitable for SelectStatement {
+    fn accept(&self, visitor: &mut impl Visitor) {
+        self.0 .0.accept(visitor);
+    }
+
+    fn accept_mut(&mut self, mutator: &mut impl Mutator) {
+        self.0 .0.accept_mut(mutator);
     }
 }
 
@@ -950,40 +937,24 @@ impl Parametric for SelectStatement {
 pub fn select(selectables: impl Into<Selectables>) -> SelectStatementInit {
     let selectables: Selectables = selectables.into();
 
-    SelectStatementInit {
-        selection_type: SelectionType::Select,
-        projections: selectables.build(),
-        targets: vec![],
-        only: false,
-        where_: None,
-        split: vec![],
-        group_by: vec![],
-        group_all: false,
-        order_by: vec![],
-        limit: None,
-        with_index_type: None,
-        explain_mode: None,
-        start: None,
-        fetch: vec![],
-        omitted_fields: vec![],
-        timeout: None,
-        parallel: false,
+    SelectStatementInit(Select {
         bindings: selectables.get_bindings(),
         errors: selectables.get_errors(),
-    }
+        ..Select::new(SelectKind::Select, selectables.build())
+    })
 }
 
 /// Just like normal select statement but useful for selecting a single value out of the returned object.
 pub fn select_value(selectable_value: impl Into<Field>) -> SelectStatementInit {
     let mut select_statement = select(selectable_value);
-    select_statement.selection_type = SelectionType::SelectValue;
+    select_statement.kind = SelectKind::Value;
     select_statement
 }
 
 /// Selects diff
 pub fn select_diff(selectable_value: impl Into<Field>) -> SelectStatementInit {
     let mut select_statement = select(selectable_value);
-    select_statement.selection_type = SelectionType::SelectDiff;
+    select_statement.kind = SelectKind::Diff;
     select_statement
 }
 
@@ -1032,51 +1003,41 @@ impl SelectStatementInit {
         targettables: impl Into<TargettablesForSelect>,
     ) -> SelectStatement {
         let targets: TargettablesForSelect = targettables.into();
-        let mut targets_bindings = vec![];
+        // Params, functions and record ranges do not tell the table they read from.
+        let opaque_targets = match &targets {
+            TargettablesForSelect::Param(param) => vec![param.build()],
+            TargettablesForSelect::Function(function) => vec![function.build()],
+            TargettablesForSelect::RecordRange(range) => vec![range.build()],
+            _ => vec![],
+        };
 
-        let target_names = match targets {
-            TargettablesForSelect::Table(table) => {
-                vec![table.to_string()]
+        let targets = match targets {
+            TargettablesForSelect::Table(table) => vec![Target::Table(table)],
+            TargettablesForSelect::Tables(tables) => {
+                tables.into_iter().map(Target::Table).collect::<Vec<_>>()
             }
-            TargettablesForSelect::Tables(tables) => tables
-                .into_iter()
-                .map(|t| t.to_string())
-                .collect::<Vec<_>>(),
             // Should already be bound
             TargettablesForSelect::Subquery(query) => {
-                targets_bindings.extend(query.get_bindings());
-                vec![query.build()]
-            }
-            TargettablesForSelect::SurrealId(id) => {
-                let binding = Binding::new(id.to_owned());
-                let param = binding.get_param_dollarised();
-                targets_bindings.push(binding);
-                vec![param]
+                vec![Target::Expression(Expr::from_buildable(&query))]
             }
-
-            TargettablesForSelect::SurrealIds(ids) => {
-                let mut params = vec![];
-
-                ids.into_iter().for_each(|id| {
-                    let binding = Binding::new(id.to_owned());
-                    let param = binding.get_param_dollarised();
-                    targets_bindings.push(binding);
-                    params.push(param);
-                });
-                params
+            TargettablesForSelect::SurrealId(id) => vec![Target::Record(Binding::new(id))],
+            TargettablesForSelect::SurrealIds(ids) => ids
+                .into_iter()
+                .map(|id| Target::Record(Binding::new(id)))
+                .collect::<Vec<_>>(),
+            TargettablesForSelect::Param(param) => {
+                vec![Target::Expression(Expr::from_buildable(&param))]
             }
             TargettablesForSelect::Function(function) => {
-                targets_bindings.extend(function.get_bindings());
-                vec![function.build()]
+                vec![Target::Expression(Expr::from_buildable(&function))]
             }
             TargettablesForSelect::RecordRange(r) => {
-                targets_bindings.extend(r.get_bindings());
-                vec![r.build()]
+                vec![Target::Expression(Expr::from_buildable(&r))]
             }
         };
 
-        self.update_bindings(targets_bindings);
-        self.targets.extend(target_names);
+        self.targets.extend(targets);
+        scope_select(&mut self.0, opaque_targets);
         self.into()
     }
 
@@ -1096,14 +1057,6 @@ impl SelectStatementInit {
         });
         self
     }
-
-    fn update_bindings(&mut self, bindings: BindingsList) -> &mut Self {
-        // let mut updated_params = vec![];
-        // updated_params.extend(self.________params_accumulator.to_vec());
-        // updated_params.extend(parametric_value.get_bindings());
-        self.bindings.extend(bindings);
-        self
-    }
 }
 
 // impl<T: IntoIterator<Item = IndexName>> From<T> for Field {
@@ -1153,9 +1106,13 @@ impl SelectStatement {
     ///                     .or(city.like("Toronto"))
     ///                 );
     pub fn where_(mut self, condition: impl Conditional + Clone) -> Self {
-        self.update_bindings(condition.get_bindings());
-        let condition = Filter::new(condition);
-        self.0.where_ = Some(condition.build());
+        self.0.set_where(condition);
+        self
+    }
+
+    /// Restricts the records of `T` to the current tenant if `T` is tenant-scoped.
+    pub(crate) fn tenant_scoped<T: Model>(mut self) -> Self {
+        scope_select_model::<T>(&mut self.0);
         self
     }
 
@@ -1389,9 +1346,7 @@ impl SelectStatement {
     ///     .limit(50);
     /// ```
     pub fn limit(mut self, limit: impl Into<NumberLike>) -> Self {
-        let limit: NumberLike = limit.into();
-        self.0.limit = Some(limit.build());
-        self.update_bindings(limit.get_bindings());
+        self.0.set_limit(limit);
         self
     }
 
@@ -1415,9 +1370,7 @@ impl SelectStatement {
     ///     .limit(50);
     /// ```
     pub fn start(mut self, start: impl Into<NumberLike>) -> Self {
-        let start: NumberLike = start.into();
-        self.0.start = Some(start.build());
-        self.update_bindings(start.get_bindings());
+        self.0.set_start(start);
         self
     }
 
@@ -1546,98 +1499,7 @@ impl Display for SelectStatement {
 
 impl Buildable for SelectStatement {
     fn build(&self) -> String {
-        let statement = &self.0;
-        let select = match statement.selection_type {
-            SelectionType::Select => "SELECT",
-            SelectionType::SelectValue => "SELECT VALUE",
-            SelectionType::SelectDiff => "SELECT DIFF",
-        };
-
-        let omitted_fields = if !statement.omitted_fields.is_empty() {
-            format!("OMIT {} ", statement.omitted_fields.join(", "))
-        } else {
-            "".to_string()
-        };
-
-        let only = if statement.only { "ONLY " } else { "" };
-        let mut query = format!(
-            "{select} {} {}FROM {}{}",
-            statement.projections,
-            omitted_fields,
-            only,
-            statement.targets.join(", ")
-        );
-
-        if let Some(with_index_type) = &statement.with_index_type {
-            match with_index_type {
-                WithIndexType::NoIndex => query = format!("{query} WITH NO INDEX"),
-                WithIndexType::Indexes(indexes) => {
-                    query = format!(
-                        "{query} WITH INDEX {}",
-                        indexes
-                            .iter()
-                            .map(|i| i.to_string())
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    )
-                }
-            }
-        }
-
-        if let Some(condition) = &statement.where_ {
-            query = format!("{query} WHERE {condition}");
-        }
-
-        if !statement.split.is_empty() {
-            query = format!("{query} SPLIT {}", &statement.split.join(", "));
-        }
-
-        if !statement.group_by.is_empty() {
-            query = format!("{query} GROUP BY {}", &statement.group_by.join(", "));
-        } else if statement.group_all {
-            query = format!("{query} GROUP ALL");
-        }
-
-        if !statement.order_by.is_empty() {
-            query = format!(
-                "{query} ORDER BY {}",
-                &statement
-                    .order_by
-                    .iter()
-                    .map(|o| o.to_string())
-                    .collect::<Vec<_>>()
-                    .join(", "),
-            );
-        }
-
-        if let Some(ref limit_value) = statement.limit {
-            query = format!("{query} LIMIT {}", limit_value);
-        }
-
-        if let Some(ref start_value) = statement.start {
-            query = format!("{query} START AT {}", start_value);
-        }
-
-        if !statement.fetch.is_empty() {
-            query = format!("{query} FETCH {}", statement.fetch.join(", "));
-        }
-
-        if let Some(timeout_value) = &statement.timeout {
-            query = format!("{query} TIMEOUT {}", timeout_value);
-        }
-
-        if statement.parallel {
-            query = format!("{query} PARALLEL");
-        }
-
-        if let Some(explain_mode) = &statement.explain_mode {
-            match explain_mode {
-                ExplainMode::Simple => query = format!("{query} EXPLAIN"),
-                ExplainMode::Full => query = format!("{query} EXPLAIN FULL"),
-            }
-        }
-
-        format!("{query};")
+        self.0 .0.build()
     }
 }
 
@@ -1658,17 +1520,13 @@ impl<T: Model> SelectStatementMini<T> {
 
     /// Starts the result at the offset
     pub fn start(mut self, start: impl Into<NumberLike>) -> Self {
-        let start: NumberLike = start.into();
-        self.0 .0.start = Some(start.build());
-        self.0.update_bindings(start.get_bindings());
+        self.0 .0.set_start(start);
         self
     }
 
     /// Limits the number of results returned
     pub fn limit(mut self, limit: impl Into<NumberLike>) -> Self {
-        let limit: NumberLike = limit.into();
-        self.0 .0.limit = Some(limit.build());
-        self.0.update_bindings(limit.get_bindings());
+        self.0 .0.set_limit(limit);
         self
     }
 
@@ -1688,7 +1546,14 @@ where
     }
 }
 
-impl<T> Erroneous for SelectStatementMini<T> where T: Serialize + DeserializeOwned + Model {}
+impl<T> Erroneous for SelectStatementMini<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn get_errors(&self) -> ErrorList {
+        self.0.get_errors()
+    }
+}
 
 impl<T> Parametric for SelectStatementMini<T>
 where
@@ -1736,7 +1601,11 @@ impl SelectStatementCount {
     }
 }
 
-impl Erroneous for SelectStatementCount {}
+impl Erroneous for SelectStatementCount {
+    fn get_errors(&self) -> ErrorList {
+        self.0.get_errors()
+    }
+}
 
 impl Parametric for SelectStatementCount {
     fn get_bindings(&self) -> crate::BindingsList {
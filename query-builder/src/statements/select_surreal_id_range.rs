@@ -4,11 +4,11 @@
  * Copyright (c) 2023 Oyelowo Oyedayo
  */
 
-use std::ops::Bound;
+use std::ops::{Bound, RangeBounds};
 
 use crate::{
-    statements::select::TargettablesForSelect, Binding, Model, SurrealId, SurrealSimpleId,
-    SurrealUlid, SurrealUuid, ValueLike,
+    statements::select::TargettablesForSelect, Binding, CompositeKey, Model, SurrealId,
+    SurrealSimpleId, SurrealUlid, SurrealUuid, ValueLike,
 };
 use surrealdb::sql;
 
@@ -332,8 +332,35 @@ create_range!(SurrealUuid);
 create_range!(SurrealUlid);
 ///////////////////////////////////////////
 
+/// Selects the records of `T` whose array id is within the range of keys
+/// e.g `reading:['sensor1', 1700000000]..['sensor1', 1800000000]`.
+pub(crate) fn composite_id_range<T, K>(range: impl RangeBounds<K>) -> TargettablesForSelect
+where
+    T: Model,
+    K: CompositeKey + Clone,
+{
+    let to_id_bound = |bound: Bound<&K>| match bound {
+        Bound::Included(key) => Bound::Included(sql::Id::from(key.clone().into_array())),
+        Bound::Excluded(key) => Bound::Excluded(sql::Id::from(key.clone().into_array())),
+        Bound::Unbounded => Bound::Unbounded,
+    };
+
+    let range = sql::Range {
+        tb: T::table().to_string(),
+        beg: to_id_bound(range.start_bound()),
+        end: to_id_bound(range.end_bound()),
+    };
+    let binding = Binding::new(range);
+    TargettablesForSelect::RecordRange(ValueLike {
+        string: binding.get_param_dollarised(),
+        bindings: vec![binding],
+        errors: vec![],
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use super::composite_id_range;
     use crate::{statements::select, *};
 
     macro_rules! gen_test {
@@ -472,4 +499,22 @@ mod tests {
         let id2 = TestUser::create_id("oyedayo");
         gen_test!(id1..id2);
     }
+
+    #[test]
+    fn test_range_for_composite_id() {
+        let start = ("sensor1".to_string(), 1700000000);
+        let end = ("sensor1".to_string(), 1800000000);
+
+        gen_test!(composite_id_range::<TestUser, _>(
+            start.clone()..end.clone()
+        ));
+        gen_test!(composite_id_range::<TestUser, _>(start.clone()..));
+        gen_test!(composite_id_range::<TestUser, _>(..=end.clone()));
+
+        let statement = select(All).from(composite_id_range::<TestUser, _>(start..end));
+        assert_eq!(
+            statement.to_raw().build(),
+            "SELECT * FROM user:['sensor1', 1700000000]..['sensor1', 1800000000];"
+        );
+    }
 }
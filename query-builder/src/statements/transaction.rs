@@ -4,13 +4,17 @@
  * Copyright (c) 2023 Oyelowo Oyedayo
  */
 
-use std::fmt::{self, Display};
+use std::{
+    fmt::{self, Display},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
 
 use surrealdb::sql;
 
 use crate::{
     traits::{BindingsList, Parametric},
     traits::{Buildable, Erroneous, Queryable},
+    SurrealOrmError, SurrealOrmResult,
 };
 /// Creates a Transaction statement starting with BEGIN TRANSACTION and ends with COMMIT
 /// TRANSACTION or END TRANSACTION.
@@ -158,13 +162,82 @@ impl BeginTransactionStatement {
     }
 }
 
+/// A shared handle queueing statements into a transaction. Clones of the handle queue into
+/// the same transaction, so one transaction can be passed to several repositories and
+/// completed once.
+///
+/// # Example
+/// ```rust
+/// # use surreal_query_builder as surreal_orm;
+/// use surreal_orm::{*, statements::{select, TransactionHandle}};
+///
+/// let transaction = TransactionHandle::new();
+/// transaction.queue(select(All).from(Table::new("user")))?;
+/// transaction.clone().queue(select(All).from(Table::new("post")))?;
+///
+/// assert_eq!(
+///     transaction.commit_transaction().build(),
+///     "BEGIN TRANSACTION;\n\nSELECT * FROM user;\n\nSELECT * FROM post;\n\nCOMMIT TRANSACTION;\n\t"
+/// );
+/// assert!(transaction.is_empty());
+/// # Ok::<(), SurrealOrmError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TransactionHandle(Arc<Mutex<TransactionData>>);
+
+impl TransactionHandle {
+    /// Creates an empty transaction
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a statement into the transaction. A statement with errors is not queued
+    /// and its errors are returned instead, as they would be when running it.
+    pub fn queue(&self, query: impl Queryable) -> SurrealOrmResult<()> {
+        let errors = query.get_errors();
+        if !errors.is_empty() {
+            return Err(SurrealOrmError::QueryBuilder(errors.join(". \n")));
+        }
+
+        let mut data = self.data();
+        data.bindings.extend(query.get_bindings());
+        data.queries.push(query.build());
+        Ok(())
+    }
+
+    /// Whether no statement is queued
+    pub fn is_empty(&self) -> bool {
+        self.data().queries.is_empty()
+    }
+
+    /// Takes the queued statements as a committed transaction, leaving the handle empty
+    pub fn commit_transaction(&self) -> TransactionCompletion {
+        self.take().commit_transaction()
+    }
+
+    /// Takes the queued statements as a cancelled transaction, leaving the handle empty
+    pub fn cancel_transaction(&self) -> TransactionCompletion {
+        self.take().cancel_transaction()
+    }
+
+    fn take(&self) -> QueryTransaction {
+        QueryTransaction {
+            data: std::mem::take(&mut *self.data()),
+        }
+    }
+
+    fn data(&self) -> MutexGuard<'_, TransactionData> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
 #[derive(Debug, Clone)]
 enum TranctionCompletionType {
     CommitTransaction,
     CancelTransaction,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct TransactionData {
     transaction_completion_type: Option<TranctionCompletionType>,
     queries: Vec<String>,
@@ -262,6 +335,7 @@ mod tests {
 
         insta::assert_snapshot!(transaction.fine_tune_params());
         insta::assert_snapshot!(transaction.to_raw().build());
+        insta::assert_snapshot!(transaction.to_raw().build_pretty(&PrettyOptions::default()));
     }
 
     #[test]
@@ -301,4 +375,23 @@ mod tests {
         insta::assert_snapshot!(transaction.fine_tune_params());
         insta::assert_snapshot!(transaction.to_raw().build());
     }
+
+    #[test]
+    fn test_transaction_handle_does_not_queue_erroneous_statements() {
+        let transaction = TransactionHandle::new();
+        let statement = with_tenant_scope(TenantScope::new("codebreather"), || {
+            select(All).from(Table::new("unregistered_table"))
+        });
+
+        assert!(matches!(
+            transaction.queue(statement),
+            Err(SurrealOrmError::QueryBuilder(_))
+        ));
+        assert!(transaction.is_empty());
+
+        transaction
+            .queue(select(All).from(Table::new("user")))
+            .unwrap();
+        assert!(!transaction.is_empty());
+    }
 }
@@ -16,15 +16,23 @@
 // 	[ TIMEOUT @duration ]
 // 	[ PARALLEL ]
 // ;
-use std::marker::PhantomData;
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
 
 use serde::{de::DeserializeOwned, Serialize};
 use surrealdb::sql;
 
 use crate::{
-    derive_binding_and_errors_from_value, Binding, BindingsList, Buildable, Conditional,
-    DurationLike, Erroneous, ErrorList, Filter, Model, Parametric, PatchOp, Queryable, ReturnType,
-    ReturnableDefault, ReturnableStandard, Setter, SurrealId, SurrealSimpleId, SurrealUlid,
+    ast::{Mutator, Target, Update, Visitable, Visitor},
+    tenant::{
+        derive_tenant_binding_and_errors, tenant_condition, tenant_key_change_error,
+        tenant_set_assignment,
+    },
+    Binding, BindingsList, Buildable, CompositeKey, Conditional, DurationLike, Erroneous,
+    ErrorList, Model, Parametric, PatchOp, Queryable, ReturnType, ReturnableDefault,
+    ReturnableStandard, Setter, SurrealCompositeId, SurrealId, SurrealSimpleId, SurrealUlid,
     SurrealUuid, ToRaw,
 };
 
@@ -89,17 +97,15 @@ where
 {
     let table_name = T::table();
     let targettables: TargettablesForUpdate = targettables.into();
-    let mut bindings = vec![];
     let mut errors = vec![];
-    let param = match targettables {
+    let target = match targettables {
         TargettablesForUpdate::Table(table) => {
-            let table = table.to_string();
-            if table != table_name.to_string() {
+            if table.to_string() != table_name.to_string() {
                 errors.push(format!(
                     "table name -{table} does not match the surreal model struct type which belongs to {table} table"
                 ));
             }
-            table
+            Target::Table(table)
         }
         TargettablesForUpdate::SurrealId(id) => {
             if !id
@@ -110,27 +116,21 @@ where
                     "id - {id} does not belong to {table_name} table from the surreal model struct provided"
                 ));
             }
-            let binding = Binding::new(id);
-            let param = binding.get_param_dollarised();
-            bindings.push(binding);
-            param
+            Target::Record(Binding::new(id))
         }
     };
 
+    let mut update = Update::new(target);
+    match tenant_condition::<T>() {
+        Ok(Some(condition)) => {
+            update.add_scope_condition(condition);
+        }
+        Ok(None) => {}
+        Err(error) => errors.push(error),
+    }
+
     UpdateStatementInit {
-        target: param,
-        is_only: false,
-        content: None,
-        merge: None,
-        replace: None,
-        patch_ops: vec![],
-        set: vec![],
-        where_: None,
-        return_type: None,
-        timeout: None,
-        parallel: false,
-        bindings,
-        errors,
+        update: Update { errors, ..update },
         __model_return_type: PhantomData,
     }
 }
@@ -208,29 +208,37 @@ pub struct UpdateStatementInit<T>
 where
     T: Serialize + DeserializeOwned + Model,
 {
-    target: String,
-    is_only: bool,
-    content: Option<String>,
-    merge: Option<String>,
-    replace: Option<String>,
-    set: Vec<String>,
-    patch_ops: Vec<String>,
-    where_: Option<String>,
-    return_type: Option<ReturnType>,
-    timeout: Option<String>,
-    bindings: BindingsList,
-    errors: ErrorList,
-    parallel: bool,
+    update: Update,
     __model_return_type: PhantomData<T>,
 }
 
+impl<T> Deref for UpdateStatementInit<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    type Target = Update;
+
+    fn deref(&self) -> &Self::Target {
+        &self.update
+    }
+}
+
+impl<T> DerefMut for UpdateStatementInit<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.update
+    }
+}
+
 impl<T> Queryable for UpdateStatement<T> where T: Serialize + DeserializeOwned + Model {}
 impl<T> Erroneous for UpdateStatement<T>
 where
     T: Serialize + DeserializeOwned + Model,
 {
     fn get_errors(&self) -> ErrorList {
-        self.0.errors.to_vec()
+        self.0.update.get_errors()
     }
 }
 
@@ -352,6 +360,26 @@ where
     }
 }
 
+impl<T, K> From<SurrealCompositeId<T, K>> for TargettablesForUpdate
+where
+    T: Model,
+    K: CompositeKey,
+{
+    fn from(value: SurrealCompositeId<T, K>) -> Self {
+        Self::SurrealId(value.to_thing())
+    }
+}
+
+impl<T, K> From<&SurrealCompositeId<T, K>> for TargettablesForUpdate
+where
+    T: Model,
+    K: CompositeKey,
+{
+    fn from(value: &SurrealCompositeId<T, K>) -> Self {
+        Self::SurrealId(value.to_thing())
+    }
+}
+
 impl From<sql::Table> for TargettablesForUpdate {
     fn from(value: sql::Table) -> Self {
         Self::Table(value)
@@ -362,34 +390,43 @@ impl<T> UpdateStatementInit<T>
 where
     T: Serialize + DeserializeOwned + Model,
 {
+    /// Binds a record with the tenant field set to the current tenant.
+    fn bind_record(&mut self, record: &impl Serialize) -> Binding {
+        let (binding, errors) = derive_tenant_binding_and_errors::<T>(record);
+        // A missing tenant scope has already been reported when the statement was created.
+        for error in errors {
+            if !self.errors.contains(&error) {
+                self.errors.push(error);
+            }
+        }
+        self.bindings.push(binding.clone());
+        binding
+    }
+
     /// Caution! Overrides all data even with default. Use with care. You may prefer `merge` with Updater instead e.g `UserUpdater`.
     /// Specify the full record data using the CONTENT keyword. The content must be serializable
     /// and implement Model trait.
     pub fn content(mut self, content: T) -> UpdateStatement<T> {
-        let (binding, errors) = derive_binding_and_errors_from_value(&content);
+        let binding = self.bind_record(&content);
         self.content = Some(binding.get_param_dollarised());
-        self.bindings.push(binding);
-        self.errors.extend(errors);
         self.into()
     }
 
     /// merge-update only specific fields by using the MERGE keyword and specifying only the fields which are to be updated.
+    /// The tenant field of a tenant-scoped table is always merged as the current tenant.
     pub fn merge(mut self, merge: impl Serialize) -> UpdateStatement<T> {
-        let (binding, errors) = derive_binding_and_errors_from_value(&merge);
+        let binding = self.bind_record(&merge);
         self.merge = Some(binding.get_param_dollarised());
-        self.bindings.push(binding);
-        self.errors.extend(errors);
         self.into()
     }
 
     /// Caution!
     /// Fully replaces weapon table with completely new object and data. This will remove all fields
     /// that are not present in the new object. This is a destructive operation.
+    /// The tenant field of a tenant-scoped table is kept as the current tenant.
     pub fn replace(mut self, replacement: impl Serialize) -> UpdateStatement<T> {
-        let (binding, errors) = derive_binding_and_errors_from_value(&replacement);
+        let binding = self.bind_record(&replacement);
         self.replace = Some(binding.get_param_dollarised());
-        self.bindings.push(binding);
-        self.errors.extend(errors);
         self.into()
     }
 
@@ -426,8 +463,16 @@ where
     ///
     /// // remove value from an array. Generates  -=
     /// .set(friends_names.remove("Oyedayo"))
+    /// ```
+    ///
+    /// The tenant field of a tenant-scoped table cannot be set and is always set to the
+    /// current tenant.
     pub fn set(mut self, settables: impl Into<Vec<Setter>>) -> UpdateStatement<T> {
         let settable: Vec<Setter> = settables.into();
+        self.errors
+            .extend(tenant_key_change_error::<T>(|tenant_key| {
+                settable.iter().any(|setter| setter.assigns(tenant_key))
+            }));
 
         let (settable, bindings) = settable.into_iter().fold(
             (Vec::new(), Vec::new()),
@@ -440,6 +485,12 @@ where
 
         self.bindings.extend(bindings);
         self.set.extend(settable);
+        // Sets the tenant field of records created by an upsert. A missing tenant scope has
+        // already been reported when the statement was created.
+        if let Ok(Some((assignment, binding))) = tenant_set_assignment::<T>() {
+            self.set.push(assignment);
+            self.bindings.push(binding);
+        }
         self.into()
     }
 
@@ -461,6 +512,10 @@ where
     /// name.patch_change("@@ -1,4 +1,4 @@\n te\n-s\n+x\n t\n");
     pub fn patch(mut self, patch_op: impl Into<Vec<PatchOp>>) -> UpdateStatement<T> {
         let patch_op: Vec<PatchOp> = patch_op.into();
+        self.errors
+            .extend(tenant_key_change_error::<T>(|tenant_key| {
+                patch_op.iter().any(|patch_op| patch_op.patches(tenant_key))
+            }));
         for patch_op in patch_op {
             self.bindings.extend(patch_op.get_bindings());
             self.errors.extend(patch_op.get_errors());
@@ -505,14 +560,7 @@ where
     /// .where_(cond(age.greater_than_or_equal(18)).and(age.less_than_or_equal(90)))
     /// ```
     pub fn where_(mut self, condition: impl Conditional) -> Self {
-        self.update_bindings(condition.get_bindings());
-        let condition = Filter::new(condition);
-        self.0.where_ = Some(condition.build());
-        self
-    }
-
-    fn update_bindings(&mut self, bindings: BindingsList) -> &mut Self {
-        self.0.bindings.extend(bindings);
+        self.0.set_where(condition);
         self
     }
 
@@ -596,44 +644,20 @@ where
     T: Serialize + DeserializeOwned + Model,
 {
     fn build(&self) -> String {
-        let statement = &self.0;
-        let mut query = format!("UPDATE {}", statement.target);
-
-        if statement.is_only {
-            query = format!("{query} ONLY");
-        }
-
-        if let Some(content) = &statement.content {
-            query = format!("{query} CONTENT  {content}",);
-        } else if let Some(merge) = &statement.merge {
-            query = format!("{query} MERGE {merge}");
-        } else if let Some(replace) = &statement.replace {
-            query = format!("{query} REPLACE {replace}");
-        } else if !statement.set.is_empty() {
-            let set_vec = statement.set.join(", ");
-            query = format!("{query} SET {set_vec}");
-        } else if !statement.patch_ops.is_empty() {
-            let patch_vec = statement.patch_ops.join(", ");
-            query = format!("{query} PATCH [{patch_vec}]");
-        }
-
-        if let Some(condition) = &statement.where_ {
-            query = format!("{query} WHERE {condition}");
-        }
-
-        if let Some(return_type) = &statement.return_type {
-            query = format!("{query} {return_type}");
-        }
-
-        if let Some(timeout) = &statement.timeout {
-            query = format!("{query} TIMEOUT {timeout}");
-        }
+        self.0.update.build()
+    }
+}
 
-        if statement.parallel {
-            query.push_str(" PARALLEL");
-        }
+impl<T> Visitable for UpdateStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn accept(&self, visitor: &mut impl Visitor) {
+        self.0.update.accept(visitor);
+    }
 
-        format!("{query};")
+    fn accept_mut(&mut self, mutator: &mut impl Mutator) {
+        self.0.update.accept_mut(mutator);
     }
 }
 
@@ -651,7 +675,7 @@ where
     T: Serialize + DeserializeOwned + Model,
 {
     fn get_bindings(&self) -> BindingsList {
-        self.0.bindings.to_vec()
+        self.0.update.get_bindings()
     }
 }
 
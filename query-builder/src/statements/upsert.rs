@@ -0,0 +1,226 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+// Statement syntax
+// UPSERT [ ONLY ] @targets
+// 	[ CONTENT @value
+// 	  | MERGE @value
+// 	  | PATCH @value
+// 	  | REPLACE @value
+// 	  | SET @field = @value ...
+// 	]
+// 	[ WHERE @condition ]
+// 	[ RETURN [ NONE | BEFORE | AFTER | DIFF | @projections ... ]
+// 	[ TIMEOUT @duration ]
+// 	[ PARALLEL ]
+// ;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    ast::{Mutator, Visitable, Visitor},
+    BindingsList, Buildable, Conditional, DurationLike, Erroneous, ErrorList, Model, Parametric,
+    PatchOp, Queryable, ReturnType, ReturnableDefault, ReturnableStandard, Setter,
+};
+
+use super::update::{
+    update, update_only, TargettablesForUpdate, UpdateStatement, UpdateStatementInit,
+};
+
+/// Creates a new UPSERT statement.
+/// The UPSERT statement updates records that exist and creates the ones that do not.
+///
+/// `UPSERT` is a 2.x statement. In the 1.x dialect, it is rendered as an `UPDATE`
+/// which also creates the record when a specific record id is targeted.
+///
+/// # Examples
+///
+/// ```rust, ignore
+/// # use surreal_query_builder as surreal_orm;
+/// use surreal_orm::{*, statements::upsert};
+///
+/// upsert::<User>(user_id)
+///     .set(object_partial!(User { score: 5 }))
+///     .where_(age.greater_than(18));
+///
+/// upsert::<User>(user_id)
+///     .merge(UserDocument {
+///         hobbies: vec!["music production", "problem solving", "rust"],
+///         ...
+///     });
+/// ```
+pub fn upsert<T>(targettables: impl Into<TargettablesForUpdate>) -> UpsertStatementInit<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    let mut statement = update(targettables);
+    statement.upsert = true;
+    UpsertStatementInit(statement)
+}
+
+/// Creates a new UPSERT statement and returns a single object.
+/// The UPSERT statement updates records that exist and creates the ones that do not.
+pub fn upsert_only<T>(targettables: impl Into<TargettablesForUpdate>) -> UpsertStatementInit<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    let mut statement = update_only(targettables);
+    statement.upsert = true;
+    UpsertStatementInit(statement)
+}
+
+/// Upsert statement initializer
+#[derive(Debug, Clone)]
+pub struct UpsertStatementInit<T>(UpdateStatementInit<T>)
+where
+    T: Serialize + DeserializeOwned + Model;
+
+impl<T> UpsertStatementInit<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    /// Caution! Overrides all data even with default. Use with care.
+    /// Specify the full record data using the CONTENT keyword.
+    pub fn content(self, content: T) -> UpsertStatement<T> {
+        UpsertStatement(self.0.content(content))
+    }
+
+    /// merge-update only specific fields by using the MERGE keyword and specifying only the fields which are to be updated.
+    pub fn merge(self, merge: impl Serialize) -> UpsertStatement<T> {
+        UpsertStatement(self.0.merge(merge))
+    }
+
+    /// Caution! Fully replaces the record with completely new object and data.
+    pub fn replace(self, replacement: impl Serialize) -> UpsertStatement<T> {
+        UpsertStatement(self.0.replace(replacement))
+    }
+
+    /// Specify fields to set using the SET clause. Works just like in `update`.
+    pub fn set(self, settables: impl Into<Vec<Setter>>) -> UpsertStatement<T> {
+        UpsertStatement(self.0.set(settables))
+    }
+
+    /// Specify the patch operations to be applied to the record using the PATCH keyword.
+    pub fn patch(self, patch_op: impl Into<Vec<PatchOp>>) -> UpsertStatement<T> {
+        UpsertStatement(self.0.patch(patch_op))
+    }
+}
+
+/// A builder for upsert statements.
+pub struct UpsertStatement<T>(UpdateStatement<T>)
+where
+    T: Serialize + DeserializeOwned + Model;
+
+impl<T> UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    /// Adds a condition to the `WHERE` clause of the query.
+    pub fn where_(self, condition: impl Conditional) -> Self {
+        Self(self.0.where_(condition))
+    }
+
+    /// Sets the return type for the query.
+    pub fn return_type(self, return_type: impl Into<ReturnType>) -> Self {
+        Self(self.0.return_type(return_type))
+    }
+
+    /// Sets the timeout duration for the query.
+    pub fn timeout(self, duration: impl Into<DurationLike>) -> Self {
+        Self(self.0.timeout(duration))
+    }
+
+    /// Indicates that the query should be executed in parallel.
+    pub fn parallel(self) -> Self {
+        Self(self.0.parallel())
+    }
+}
+
+impl<T> Buildable for UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn build(&self) -> String {
+        self.0.build()
+    }
+}
+
+impl<T> Visitable for UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn accept(&self, visitor: &mut impl Visitor) {
+        self.0.accept(visitor);
+    }
+
+    fn accept_mut(&mut self, mutator: &mut impl Mutator) {
+        self.0.accept_mut(mutator);
+    }
+}
+
+impl<T> std::fmt::Display for UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.build())
+    }
+}
+
+impl<T> Parametric for UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn get_bindings(&self) -> BindingsList {
+        self.0.get_bindings()
+    }
+}
+
+impl<T> Erroneous for UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn get_errors(&self) -> ErrorList {
+        self.0.get_errors()
+    }
+}
+
+impl<T> Queryable for UpsertStatement<T> where T: Serialize + DeserializeOwned + Model {}
+
+impl<T> ReturnableDefault<T> for UpsertStatement<T> where T: Serialize + DeserializeOwned + Model {}
+
+impl<T> ReturnableStandard<T> for UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model + Send + Sync,
+{
+    fn set_return_type(self, return_type: ReturnType) -> Self {
+        Self(self.0.set_return_type(return_type))
+    }
+
+    fn get_return_type(&self) -> ReturnType {
+        self.0.get_return_type()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{with_dialect, Dialect, Field, Operatable, TestUser, ToRaw};
+
+    #[test]
+    fn test_upsert_is_rendered_per_dialect() {
+        let age = Field::new("age");
+        let statement = upsert::<TestUser>(TestUser::create_id("oyelowo"))
+            .merge(serde_json::json!({ "name": "Oyelowo" }))
+            .where_(age.greater_than(18))
+            .parallel();
+
+        insta::assert_snapshot!(with_dialect(Dialect::V1, || statement.fine_tune_params()));
+        insta::assert_snapshot!(with_dialect(Dialect::V2, || statement.fine_tune_params()));
+        assert!(with_dialect(Dialect::V2, || statement.to_raw().build())
+            .starts_with("UPSERT user:oyelowo MERGE"));
+        assert_eq!(statement.get_bindings().len(), 3);
+    }
+}
@@ -0,0 +1,319 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use std::{cell::RefCell, collections::BTreeMap, sync::RwLock};
+
+use serde::Serialize;
+use surrealdb::sql;
+
+use crate::{
+    ast::Select, derive_binding_and_errors_from_value, Binding, Buildable, Field, Filter, Model,
+    Operatable, Table,
+};
+
+thread_local! {
+    static SCOPED_TENANT: RefCell<Option<TenantScope>> = const { RefCell::new(None) };
+}
+
+/// Tables registered with [`TenantTables`] and their tenant key, if they are tenant-scoped.
+static TENANT_TABLES: RwLock<BTreeMap<String, Option<String>>> = RwLock::new(BTreeMap::new());
+
+/// The tenant that statements on tenant-scoped tables are restricted to.
+///
+/// A table is tenant-scoped with the `#[orm(tenant_key = org)]` attribute. While a scope
+/// is active on the current thread, `select`, `update`, `upsert`, `delete`, the
+/// `SurrealCrud` methods and graph traversals targeting the table get a
+/// `org = <tenant>` condition, and `create`, `insert` and `relate` set the `org` field.
+/// Building any of these for a tenant-scoped table without an active scope fails
+/// when the statement is run.
+///
+/// Records selected by id, param or subquery, e.g `User::with(user_id)`, are narrowed
+/// down to the ones of the tenant with `(SELECT VALUE id FROM <records> WHERE org = <tenant>)`.
+///
+/// Untyped selects such as `select(All).from(User::table())` only know the table they read
+/// from, so its model has to be registered with [`TenantTables`] up front. While a scope is
+/// active, untyped selects from tables that are not registered, params, functions and record
+/// ranges fail when the statement is run.
+///
+/// Examples:
+///
+/// ```rust, ignore
+/// # use surreal_orm::{*, statements::select};
+/// #[derive(Node, Serialize, Deserialize, Debug, Clone)]
+/// #[serde(rename_all = "camelCase")]
+/// #[orm(table = user, tenant_key = org)]
+/// pub struct User {
+///     pub id: SurrealSimpleId<Self>,
+///     pub org: String,
+///     pub age: u8,
+/// }
+///
+/// TenantTables::register::<User>();
+/// let scope = TenantScope::new("codebreather");
+///
+/// let statement = with_tenant_scope(scope, || {
+///     User::find_where(User::schema().age.greater_than(18))
+/// });
+///
+/// assert_eq!(
+///     statement.to_raw().build(),
+///     "SELECT * FROM user WHERE (age > 18) AND (org = 'codebreather');"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct TenantScope {
+    tenant: sql::Value,
+}
+
+impl TenantScope {
+    /// Creates a scope for the given tenant.
+    pub fn new(tenant: impl Into<sql::Value>) -> Self {
+        Self {
+            tenant: tenant.into(),
+        }
+    }
+
+    /// The tenant of the scope.
+    pub fn tenant(&self) -> &sql::Value {
+        &self.tenant
+    }
+
+    /// The tenant scope active on the current thread, if any.
+    pub fn current() -> Option<Self> {
+        SCOPED_TENANT.with(|scoped| scoped.borrow().clone())
+    }
+
+    fn condition(&self, tenant_key: &Field) -> Filter {
+        Filter::new(tenant_key.equal(self.tenant.clone()))
+    }
+}
+
+/// Builds statements in the closure restricted to the tenant on the current thread.
+/// The previous scope is restored afterwards.
+pub fn with_tenant_scope<R>(scope: TenantScope, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<TenantScope>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_TENANT.with(|scoped| *scoped.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _restore = Restore(SCOPED_TENANT.with(|scoped| scoped.replace(Some(scope))));
+    f()
+}
+
+/// The tables untyped statements can read from while a tenant scope is active.
+///
+/// Typed statements, e.g `User::find_where`, know the model they are built for and are
+/// scoped without it. Register every model, tenant-scoped or not, whose table is read from
+/// with untyped statements, usually once at startup. Reading from a table that is not
+/// registered while a tenant scope is active fails instead of returning the records of
+/// every tenant.
+///
+/// ```rust, ignore
+/// TenantTables::register::<User>();
+/// TenantTables::register::<Country>();
+/// TenantTables::register_table("user_stats");
+/// ```
+pub struct TenantTables;
+
+impl TenantTables {
+    /// Registers the table of the model with its tenant key, if it is tenant-scoped.
+    pub fn register<T: Model>() {
+        Self::insert(
+            T::table().to_string(),
+            T::tenant_key().map(|tenant_key| tenant_key.build()),
+        );
+    }
+
+    /// Registers a table that is not tenant-scoped e.g a view.
+    pub fn register_table(table: impl Into<Table>) {
+        Self::insert(table.into().to_string(), None);
+    }
+
+    /// Whether the table has been registered.
+    pub fn is_registered(table: impl Into<Table>) -> bool {
+        Self::get(&table.into().to_string()).is_some()
+    }
+
+    fn insert(table: String, tenant_key: Option<String>) {
+        TENANT_TABLES
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(table, tenant_key);
+    }
+
+    /// The tenant key of a registered table. `Some(None)` if it is not tenant-scoped.
+    fn get(table: &str) -> Option<Option<Field>> {
+        TENANT_TABLES
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(table)
+            .map(|tenant_key| tenant_key.as_deref().map(Field::new))
+    }
+}
+
+/// Adds the tenant condition of `table` to the select unless it has already been added.
+fn scope_select_table(select: &mut Select, table: &str, tenant_key: &Field) {
+    if select.tenant_tables.iter().any(|scoped| scoped == table) {
+        return;
+    }
+    select.tenant_tables.push(table.to_string());
+
+    match TenantScope::current() {
+        Some(scope) => {
+            select.add_scope_condition(scope.condition(tenant_key));
+        }
+        None => select.errors.push(missing_scope_error(table, tenant_key)),
+    }
+}
+
+/// Adds the tenant condition for every registered tenant-scoped table the select reads
+/// from. While a tenant scope is active, tables that are not registered and `opaque_targets`
+/// i.e targets whose table is not known are refused unless the select is scoped to a model.
+pub(crate) fn scope_select(select: &mut Select, opaque_targets: Vec<String>) {
+    let tables = select
+        .tables()
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    let is_scoped = TenantScope::current().is_some();
+
+    for table in tables {
+        match TenantTables::get(&table) {
+            Some(Some(tenant_key)) => scope_select_table(select, &table, &tenant_key),
+            Some(None) => {}
+            None if is_scoped && !select.unregistered_tables.contains(&table) => {
+                select.unregistered_tables.push(table)
+            }
+            None => {}
+        }
+    }
+
+    if is_scoped {
+        select.opaque_targets.extend(opaque_targets);
+    }
+}
+
+/// Scopes the select to `T`. Its table and the targets whose table is not known are
+/// restricted to the tenant of `T` if it is tenant-scoped and allowed as they are otherwise.
+pub(crate) fn scope_select_model<T: Model>(select: &mut Select) {
+    let table = T::table().to_string();
+    select
+        .unregistered_tables
+        .retain(|unregistered| unregistered != &table);
+    select.opaque_targets.clear();
+
+    if let Some(tenant_key) = T::tenant_key() {
+        scope_select_table(select, &table, &tenant_key);
+    }
+}
+
+/// The error for a select reading from a table that is not registered with [`TenantTables`]
+/// while a tenant scope is active.
+pub(crate) fn unregistered_table_error(table: &str) -> String {
+    format!(
+        "Table `{table}` is not registered with `TenantTables` and cannot be read from \
+        while a tenant scope is active. Register its model with `TenantTables::register`"
+    )
+}
+
+/// The error for a select reading from a target whose table is not known while a tenant
+/// scope is active.
+pub(crate) fn opaque_target_error(target: &str) -> String {
+    format!(
+        "`{target}` cannot be read from while a tenant scope is active because the table \
+        it reads from is not known. Read from a registered table or a record id instead"
+    )
+}
+
+fn missing_scope_error(table: &str, tenant_key: &Field) -> String {
+    format!(
+        "Table `{table}` is tenant-scoped by `{}` but no tenant scope is active. \
+        Build the statement within `with_tenant_scope`",
+        tenant_key.build()
+    )
+}
+
+/// The condition restricting `T` to the current tenant.
+/// Returns `None` if `T` is not tenant-scoped.
+pub(crate) fn tenant_condition<T: Model>() -> Result<Option<Filter>, String> {
+    let Some(tenant_key) = T::tenant_key() else {
+        return Ok(None);
+    };
+
+    let table = T::table().to_string();
+    let scope = TenantScope::current().ok_or_else(|| missing_scope_error(&table, &tenant_key))?;
+    Ok(Some(scope.condition(&tenant_key)))
+}
+
+/// The tenant field of `T` and the current tenant it should be set to.
+/// Returns `None` if `T` is not tenant-scoped.
+pub(crate) fn tenant_assignment<T: Model>() -> Result<Option<(Field, sql::Value)>, String> {
+    let Some(tenant_key) = T::tenant_key() else {
+        return Ok(None);
+    };
+
+    let table = T::table().to_string();
+    let scope = TenantScope::current().ok_or_else(|| missing_scope_error(&table, &tenant_key))?;
+    Ok(Some((tenant_key, scope.tenant)))
+}
+
+/// The `SET` assignment of the tenant field of `T` to the current tenant together
+/// with the binding of the tenant. Returns `None` if `T` is not tenant-scoped.
+pub(crate) fn tenant_set_assignment<T: Model>() -> Result<Option<(String, Binding)>, String> {
+    Ok(tenant_assignment::<T>()?.map(|(tenant_key, tenant)| {
+        let binding = Binding::new(tenant);
+        let assignment = format!(
+            "{} = {}",
+            tenant_key.build(),
+            binding.get_param_dollarised()
+        );
+        (assignment, binding)
+    }))
+}
+
+/// Sets the tenant field of a serialized record of `T` to the current tenant.
+pub(crate) fn stamp_tenant<T: Model>(record: &mut sql::Value) -> Result<(), String> {
+    if let (Some((tenant_key, tenant)), sql::Value::Object(object)) =
+        (tenant_assignment::<T>()?, record)
+    {
+        object.insert(tenant_key.build(), tenant);
+    }
+    Ok(())
+}
+
+/// Binds a record of `T` with its tenant field set to the current tenant.
+pub(crate) fn derive_tenant_binding_and_errors<T: Model>(
+    record: &impl Serialize,
+) -> (Binding, Vec<String>) {
+    let (binding, mut errors) = derive_binding_and_errors_from_value(record);
+    if T::tenant_key().is_none() {
+        return (binding, errors);
+    }
+
+    let mut value = binding.get_value().clone();
+    if let Err(error) = stamp_tenant::<T>(&mut value) {
+        errors.push(error);
+    }
+    (Binding::new(value), errors)
+}
+
+/// The error for a statement that changes the tenant field of `T`. Returns `None` if
+/// `T` is not tenant-scoped or the tenant field is left alone.
+pub(crate) fn tenant_key_change_error<T: Model>(
+    changes_field: impl Fn(&Field) -> bool,
+) -> Option<String> {
+    let tenant_key = T::tenant_key()?;
+    changes_field(&tenant_key).then(|| {
+        format!(
+            "The tenant field `{}` of the tenant-scoped table `{}` cannot be changed",
+            tenant_key.build(),
+            T::table()
+        )
+    })
+}
@@ -14,6 +14,11 @@ pub trait DbResources {
         HashMap::default()
     }
 
+    /// Returns the old names of the renamed tables keyed by their new names.
+    fn tables_old_names(&self) -> HashMap<Table, Table> {
+        HashMap::default()
+    }
+
     /// Returns a list of fields.
     fn analyzers(&self) -> Vec<Raw> {
         vec![]
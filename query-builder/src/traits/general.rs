@@ -4,7 +4,7 @@
  * Copyright (c) 2023 Oyelowo Oyedayo
  */
 
-use crate::Field;
+use crate::{format_surql, Field, PrettyOptions};
 
 use super::{Erroneous, Parametric};
 
@@ -23,6 +23,12 @@ pub trait Buildable {
         })
         .to_string()
     }
+
+    /// Build a readable, multi-line query string. Blocks are indented and long
+    /// statements are broken up into one clause per line.
+    fn build_pretty(&self, options: &PrettyOptions) -> String {
+        format_surql(&self.build(), options)
+    }
 }
 
 /// Denoted by `.*`. Used for accessing all nested fields and arrays and links
@@ -14,7 +14,9 @@ pub(crate) mod pickable;
 pub(crate) mod raw;
 pub(crate) mod setter;
 pub(crate) mod statements;
+pub(crate) mod surreal_type;
 pub(crate) mod table_resources;
+pub(crate) mod view;
 
 pub use binding::*;
 pub use db_resources::*;
@@ -26,4 +28,6 @@ pub use pickable::*;
 pub use raw::*;
 pub use setter::*;
 pub use statements::*;
+pub use surreal_type::*;
 pub use table_resources::*;
+pub use view::*;
@@ -4,18 +4,22 @@
  * Copyright (c) 2023 Oyelowo Oyedayo
  */
 
+use std::ops::RangeBounds;
+
 use crate::{
     count,
     statements::{
         create::{create, CreateStatement},
         delete::{delete, DeleteStatementMini},
-        select::{select, SelectStatementCount},
+        select::{select, SelectStatementCount, TargettablesForSelect},
+        select_surreal_id_range::composite_id_range,
         select_value,
         update::{update, UpdateStatement},
         SelectStatementMini,
     },
-    Alias, All, Conditional, Field, Filter, NodeClause, Raw, SurrealId, SurrealOrmResult,
-    SurrealSimpleId, SurrealUlid, SurrealUuid, Table, ValueLike,
+    Alias, All, CompositeId, CompositeKey, Conditional, Field, FieldType, Filter, NodeClause, Raw,
+    SurrealCompositeId, SurrealId, SurrealOrmResult, SurrealSimpleId, SurrealUlid, SurrealUuid,
+    Table, ValueLike,
 };
 use serde::{de::DeserializeOwned, Serialize};
 use surrealdb::sql::{self, Thing};
@@ -72,6 +76,37 @@ pub trait Model: Sized {
     /// Get old name of field
     fn get_field_meta() -> Vec<FieldMetadata>;
 
+    /// The previous name of the model's table. Set with the `#[orm(old_table = client)]`
+    /// table attribute so the migrator moves the records of the old table instead of
+    /// removing it.
+    fn old_table() -> Option<Table> {
+        None
+    }
+
+    /// The field that scopes the records of the model to a tenant.
+    /// Set with the `#[orm(tenant_key = org)]` table attribute.
+    fn tenant_key() -> Option<Field> {
+        None
+    }
+
+    /// The indexes of the model. Generated from the `#[orm(index)]` and `#[orm(unique)]`
+    /// field attributes and the `#[orm(index(name = "idx", fields(a, b), unique))]` table attribute.
+    fn define_indexes() -> Vec<Raw> {
+        vec![]
+    }
+
+    /// The events of the model. Generated from the
+    /// `#[orm(event(name = "ev", when = "$event = 'UPDATE'", then = path::to::fn))]` table attribute.
+    fn define_events() -> Vec<Raw> {
+        vec![]
+    }
+
+    /// The events on the linked tables implementing the `on_delete` actions of the link fields
+    /// of the model e.g `#[orm(link_one = Company, on_delete = "cascade")]`.
+    fn define_referential_actions() -> Vec<Raw> {
+        vec![]
+    }
+
     /// Create a new SurrealId from a string
     fn create_thing(id: impl Into<sql::Id>) -> Thing {
         Thing::from((Self::table().to_string(), id.into()))
@@ -98,6 +133,22 @@ pub trait Model: Sized {
         SurrealSimpleId::new()
     }
 
+    /// Create a new array id from a tuple of values e.g `reading:['sensor1', 1700000000]`
+    fn create_composite_id<K: CompositeKey>(key: K) -> SurrealCompositeId<Self, K> {
+        SurrealCompositeId::new(key)
+    }
+
+    /// Selects the records whose array id is within the range of keys e.g
+    /// `select(All).from(Reading::id_range((sensor.clone(), start)..(sensor, end)))`.
+    /// Only available for models with a `SurrealCompositeId` id.
+    fn id_range<R>(range: R) -> TargettablesForSelect
+    where
+        Self::Id: CompositeId,
+        R: RangeBounds<<Self::Id as CompositeId>::Key>,
+    {
+        composite_id_range::<Self, _>(range)
+    }
+
     /// Map a Thing to a SurrealId
     fn from_thing(thing: sql::Thing) -> SurrealOrmResult<SurrealId<Self, sql::Id>> {
         SurrealId::try_from(thing)
@@ -118,12 +169,24 @@ pub trait SurrealCrud: Sized + Serialize + DeserializeOwned + Model {
 
     /// Finds a record by id.
     fn find_by_id(id: impl Into<Thing>) -> SelectStatementMini<Self> {
-        select(All).from(id.into()).into()
+        select(All).from(id.into()).tenant_scoped::<Self>().into()
+    }
+
+    /// Finds all records.
+    fn find_all() -> SelectStatementMini<Self> {
+        select(All)
+            .from(Self::table())
+            .tenant_scoped::<Self>()
+            .into()
     }
 
     /// Finds records by filtering.
     fn find_where(filter: impl Conditional + Clone) -> SelectStatementMini<Self> {
-        select(All).from(Self::table()).where_(filter).into()
+        select(All)
+            .from(Self::table())
+            .where_(filter)
+            .tenant_scoped::<Self>()
+            .into()
     }
 
     /// Count filtered records.
@@ -131,15 +194,20 @@ pub trait SurrealCrud: Sized + Serialize + DeserializeOwned + Model {
         let selection = select_value(Field::new("count")).from(
             select(count!(Filter::new(filter)))
                 .from(Self::table())
-                .group_all(),
+                .group_all()
+                .tenant_scoped::<Self>(),
         );
         selection.into()
     }
 
     /// Count all records.
     fn count_all() -> SelectStatementCount {
-        let selection = select_value(Field::new("count"))
-            .from(select(count!()).from(Self::table()).group_all());
+        let selection = select_value(Field::new("count")).from(
+            select(count!())
+                .from(Self::table())
+                .group_all()
+                .tenant_scoped::<Self>(),
+        );
         selection.into()
     }
 
@@ -290,6 +358,24 @@ pub trait Edge: Model + Serialize + SchemaGetter {
 
 /// Object is a trait signifying a nested object in the graph
 pub trait Object: Serialize + SchemaGetter {
+    /// The serializable fields of the object. Used to expand an object flattened
+    /// with `#[serde(flatten)]` into the fields of its parent node or edge.
+    /// Generated by the `Object` derive. Defaults to none so that hand-written
+    /// implementations keep compiling but cannot be flattened.
+    fn get_serializable_fields() -> Vec<Field> {
+        vec![]
+    }
+
+    /// The field definitions of the object on the table of the node or edge it is flattened into
+    fn define_fields_on(_table: Table) -> Vec<Raw> {
+        vec![]
+    }
+
+    /// The field metadata of the object on the table of the node or edge it is flattened into
+    fn get_field_meta_on(_table: Table) -> Vec<FieldMetadata> {
+        vec![]
+    }
+
     // For merge update of object
     // type PartialBuilder;
 
@@ -301,6 +387,15 @@ pub trait Object: Serialize + SchemaGetter {
     // fn schema() -> Self::Schema;
 }
 
+/// An enum stored as a literal type e.g `"active" | "banned"` for a unit enum or
+/// `{ type: "circle", radius: float } | { type: "square", side: float }`
+/// for an internally tagged enum. Implemented by deriving `Object` on the enum.
+/// Used in a node, edge or object with the `nest_enum` field attribute.
+pub trait ObjectEnum: Serialize {
+    /// The database type of the enum
+    fn field_type() -> FieldType;
+}
+
 /// Trait for getting the schema of a node for generating graph strings
 pub trait SchemaGetter {
     /// The type of the schema
@@ -312,6 +407,16 @@ pub trait SchemaGetter {
     fn schema_prefixed(prefix: impl Into<ValueLike>) -> Self::Schema;
 }
 
+/// The reverse links declared by the model `M` with the `reverse` attribute of its links to
+/// this model. Implemented by deriving `Node` or `Edge` on `M` and accessed through the schema
+/// e.g `User::schema().reverse_links::<Post>().posts`
+pub trait ReverseLinks<M> {
+    /// The struct with a field per reverse link e.g `PostReverseLinksOnUser`
+    type Links;
+    /// returns the reverse links aliased by their names
+    fn reverse_links() -> Self::Links;
+}
+
 /// Trait for updating a model/table. Useful when you want to skip optional fields
 pub trait PartialUpdater {
     /// Partial state of the original model/table struct
@@ -32,6 +32,8 @@ impl Display for OpType {
 #[derive(Clone, Debug)]
 pub struct PatchOpInit {
     path: String,
+    // The JSON pointer of the path e.g `/name/first`
+    pointer: String,
     op: OpType,
     value: Option<String>,
     bindings: BindingsList,
@@ -64,6 +66,14 @@ impl Buildable for PatchOp {
     }
 }
 
+impl PatchOp {
+    /// Checks if the operation patches the field or a field nested in it.
+    pub(crate) fn patches(&self, field: &Field) -> bool {
+        let field = format!("/{}", field.build().replace('.', "/"));
+        self.pointer == field || self.pointer.starts_with(&format!("{field}/"))
+    }
+}
+
 impl From<PatchOp> for Vec<PatchOp> {
     fn from(patch_op: PatchOp) -> Self {
         vec![patch_op]
@@ -219,11 +229,12 @@ fn patch(path: impl Into<Field>) -> PatchOpInit {
         errors.push("The path you have provided is invalid. Make sure that there are no clauses or conditions included. Valid path include e.g name, name(E).first, name(E).first(E).second, etc.".to_string());
     }
 
-    let path = format!("/{}", path.join("/"));
-    let path_binding = Binding::new(sql::Value::from(path));
+    let pointer = format!("/{}", path.join("/"));
+    let path_binding = Binding::new(sql::Value::from(pointer.clone()));
 
     PatchOpInit {
         path: path_binding.get_param_dollarised(),
+        pointer,
         op: OpType::Add,
         value: None,
         bindings: vec![path_binding],
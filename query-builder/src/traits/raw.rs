@@ -6,7 +6,9 @@
 
 use std::fmt::{Display, Formatter};
 
-use super::{BindingsList, Buildable, Erroneous, Parametric, Queryable};
+use crate::Aliasable;
+
+use super::{BindingsList, Buildable, Conditional, Erroneous, Parametric, Queryable};
 
 /// A raw query which can usually be converted into from a `Parametric` query.
 /// This is useful for debugging purposes.
@@ -35,6 +37,10 @@ impl Erroneous for Raw {}
 
 impl Queryable for Raw {}
 
+impl Conditional for Raw {}
+
+impl Aliasable for Raw {}
+
 impl Buildable for Raw {
     fn build(&self) -> String {
         self.0.to_string()
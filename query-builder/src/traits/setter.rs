@@ -30,6 +30,21 @@ impl From<Setter> for Vec<Setter> {
     }
 }
 
+impl Setter {
+    /// Checks if the setter assigns to the field or a field nested in it.
+    pub(crate) fn assigns(&self, field: &Field) -> bool {
+        let field = field.build();
+        self.query_string
+            .split_whitespace()
+            .next()
+            .is_some_and(|target| {
+                target == field
+                    || target.starts_with(&format!("{field}."))
+                    || target.starts_with(&format!("{field}["))
+            })
+    }
+}
+
 impl Parametric for Setter {
     fn get_bindings(&self) -> BindingsList {
         self.bindings.to_vec()
@@ -0,0 +1,299 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque},
+    rc::Rc,
+    sync::Arc,
+};
+
+use surrealdb::sql;
+
+use crate::{
+    CompositeKey, FieldType, Geometry, GeometryType, LinkMany, LinkOne, LinkSelf, Model, Node,
+    SurrealCompositeId, SurrealId, SurrealSimpleId, SurrealUlid, SurrealUuid,
+};
+
+/// The database type a rust type is stored as. The `Node`, `Edge` and `Object` derives use it
+/// to infer the type of a field that is not a primitive nor a collection and has no explicit
+/// `ty` attribute. Implement it once for your own newtypes instead of annotating every field.
+///
+/// `time::OffsetDateTime` is a `string` because its default serialization is a tuple and not a
+/// datetime. The derives refuse its fields unless they are serialized as RFC 3339 strings with
+/// `#[serde(with = "time::serde::rfc3339")]` or `#[serde(with = "time::serde::rfc3339::option")]`.
+///
+/// # Example
+/// ```
+/// # use surreal_query_builder::{FieldType, SurrealType};
+/// struct Email(String);
+///
+/// impl SurrealType for Email {
+///     fn field_type() -> FieldType {
+///         FieldType::String
+///     }
+/// }
+///
+/// assert_eq!(<Option<Vec<Email>>>::field_type().to_string(), "option<array<string>>");
+/// ```
+pub trait SurrealType {
+    /// The database type of the rust type
+    fn field_type() -> FieldType;
+}
+
+macro_rules! impl_surreal_type {
+    ($field_type:expr => $($ty:ty),+ $(,)?) => {
+        $(
+            impl SurrealType for $ty {
+                fn field_type() -> FieldType {
+                    $field_type
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_surreal_type_for_wrapper {
+    ($($wrapper:ident),+) => {
+        $(
+            impl<T: SurrealType + ?Sized> SurrealType for $wrapper<T> {
+                fn field_type() -> FieldType {
+                    T::field_type()
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_surreal_type_for_collection {
+    ($field_type:ident => $($collection:ident),+) => {
+        $(
+            impl<T: SurrealType> SurrealType for $collection<T> {
+                fn field_type() -> FieldType {
+                    FieldType::$field_type(::std::boxed::Box::new(T::field_type()), None)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_surreal_type_for_geometry {
+    ($($geometry_type:ident => $($ty:ident),+);+ $(;)?) => {
+        $($(
+            impl<T: geo::CoordNum> SurrealType for geo::$ty<T> {
+                fn field_type() -> FieldType {
+                    FieldType::Geometry(vec![GeometryType::$geometry_type])
+                }
+            }
+        )+)+
+    };
+}
+
+impl_surreal_type!(FieldType::Bool => bool);
+impl_surreal_type!(FieldType::Int => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_surreal_type!(FieldType::Float => f32, f64);
+impl_surreal_type!(FieldType::String => String, str, char, sql::Strand);
+impl_surreal_type!(FieldType::Number => sql::Number);
+impl_surreal_type!(FieldType::Duration => std::time::Duration, chrono::Duration, sql::Duration);
+impl_surreal_type!(FieldType::Uuid => uuid::Uuid, sql::Uuid);
+impl_surreal_type!(FieldType::Bytes => sql::Bytes);
+impl_surreal_type!(FieldType::Object => sql::Object, serde_json::Map<String, serde_json::Value>);
+impl_surreal_type!(FieldType::Any => sql::Value, serde_json::Value);
+impl_surreal_type!(FieldType::Record(vec![]) => sql::Thing);
+impl_surreal_type!(FieldType::Array(Box::new(FieldType::Any), None) => sql::Array);
+impl_surreal_type!(FieldType::Geometry(vec![GeometryType::Feature]) => sql::Geometry, Geometry);
+impl_surreal_type!(FieldType::Datetime => sql::Datetime);
+// Naive dates and times are serialized as strings by chrono
+impl_surreal_type!(FieldType::String => chrono::NaiveDate, chrono::NaiveTime, chrono::NaiveDateTime);
+
+impl<Tz: chrono::TimeZone> SurrealType for chrono::DateTime<Tz> {
+    fn field_type() -> FieldType {
+        FieldType::Datetime
+    }
+}
+
+impl<T: SurrealType + ?Sized> SurrealType for &T {
+    fn field_type() -> FieldType {
+        T::field_type()
+    }
+}
+
+impl<T: SurrealType + ?Sized> SurrealType for &mut T {
+    fn field_type() -> FieldType {
+        T::field_type()
+    }
+}
+
+impl_surreal_type_for_wrapper!(Box, Rc, Arc, RefCell);
+
+impl<T: SurrealType + ToOwned + ?Sized> SurrealType for Cow<'_, T> {
+    fn field_type() -> FieldType {
+        T::field_type()
+    }
+}
+
+impl<T: SurrealType> SurrealType for Option<T> {
+    fn field_type() -> FieldType {
+        FieldType::Option(Box::new(T::field_type()))
+    }
+}
+
+impl_surreal_type_for_collection!(Array => Vec, VecDeque, LinkedList);
+impl_surreal_type_for_collection!(Set => HashSet, BTreeSet);
+
+impl<T: SurrealType> SurrealType for [T] {
+    fn field_type() -> FieldType {
+        FieldType::Array(Box::new(T::field_type()), None)
+    }
+}
+
+impl<T: SurrealType, const N: usize> SurrealType for [T; N] {
+    fn field_type() -> FieldType {
+        FieldType::Array(Box::new(T::field_type()), Some(N as u64))
+    }
+}
+
+impl<K, V> SurrealType for HashMap<K, V> {
+    fn field_type() -> FieldType {
+        FieldType::Object
+    }
+}
+
+impl<K, V> SurrealType for BTreeMap<K, V> {
+    fn field_type() -> FieldType {
+        FieldType::Object
+    }
+}
+
+impl_surreal_type_for_geometry!(
+    Point => Point;
+    LineString => LineString;
+    Polygon => Polygon;
+    MultiPoint => MultiPoint;
+    MultiLine => MultiLineString;
+    MultiPolygon => MultiPolygon;
+    Collection => GeometryCollection;
+    Feature => Geometry;
+);
+
+impl<V: Node> SurrealType for LinkOne<V> {
+    fn field_type() -> FieldType {
+        FieldType::Record(vec![V::table()])
+    }
+}
+
+impl<V: Node> SurrealType for LinkSelf<V> {
+    fn field_type() -> FieldType {
+        FieldType::Record(vec![V::table()])
+    }
+}
+
+impl<V: Node> SurrealType for LinkMany<V> {
+    fn field_type() -> FieldType {
+        FieldType::Array(Box::new(FieldType::Record(vec![V::table()])), None)
+    }
+}
+
+impl<T: Model, Id: Into<sql::Id>> SurrealType for SurrealId<T, Id> {
+    fn field_type() -> FieldType {
+        FieldType::Record(vec![T::table()])
+    }
+}
+
+impl<T: Model> SurrealType for SurrealSimpleId<T> {
+    fn field_type() -> FieldType {
+        FieldType::Record(vec![T::table()])
+    }
+}
+
+impl<T: Model> SurrealType for SurrealUuid<T> {
+    fn field_type() -> FieldType {
+        FieldType::Record(vec![T::table()])
+    }
+}
+
+impl<T: Model> SurrealType for SurrealUlid<T> {
+    fn field_type() -> FieldType {
+        FieldType::Record(vec![T::table()])
+    }
+}
+
+impl<T: Model, K: CompositeKey> SurrealType for SurrealCompositeId<T, K> {
+    fn field_type() -> FieldType {
+        FieldType::Record(vec![T::table()])
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl_surreal_type!(FieldType::Decimal => rust_decimal::Decimal);
+
+#[cfg(feature = "url")]
+impl_surreal_type!(FieldType::String => url::Url);
+
+// Only valid for fields serialized with `time::serde::rfc3339`, which the derives check
+#[cfg(feature = "time")]
+impl_surreal_type!(FieldType::String => time::OffsetDateTime);
+
+#[cfg(feature = "bytes")]
+impl_surreal_type!(FieldType::Bytes => bytes::Bytes, bytes::BytesMut);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Email;
+
+    impl SurrealType for Email {
+        fn field_type() -> FieldType {
+            FieldType::String
+        }
+    }
+
+    type Emails = Vec<Email>;
+
+    #[test]
+    fn test_surreal_type_of_primitives_and_std_types() {
+        assert_eq!(bool::field_type().to_string(), "bool");
+        assert_eq!(u32::field_type().to_string(), "int");
+        assert_eq!(f64::field_type().to_string(), "float");
+        assert_eq!(<&str>::field_type().to_string(), "string");
+        assert_eq!(<Cow<'_, str>>::field_type().to_string(), "string");
+        assert_eq!(
+            <Option<Vec<Box<String>>>>::field_type().to_string(),
+            "option<array<string>>"
+        );
+        assert_eq!(<[u8; 4]>::field_type().to_string(), "array<int, 4>");
+        assert_eq!(<BTreeSet<i64>>::field_type().to_string(), "set<int>");
+        assert_eq!(<HashMap<String, u8>>::field_type().to_string(), "object");
+        assert_eq!(std::time::Duration::field_type().to_string(), "duration");
+    }
+
+    #[test]
+    fn test_surreal_type_of_third_party_types() {
+        assert_eq!(
+            <chrono::DateTime<chrono::Utc>>::field_type().to_string(),
+            "datetime"
+        );
+        assert_eq!(chrono::NaiveDate::field_type().to_string(), "string");
+        assert_eq!(uuid::Uuid::field_type().to_string(), "uuid");
+        assert_eq!(geo::Point::field_type().to_string(), "geometry<point>");
+        assert_eq!(
+            geo::MultiLineString::field_type().to_string(),
+            "geometry<multiline>"
+        );
+        assert_eq!(sql::Geometry::field_type().to_string(), "geometry<feature>");
+    }
+
+    #[test]
+    fn test_surreal_type_of_newtypes_and_aliases() {
+        assert_eq!(Emails::field_type().to_string(), "array<string>");
+        assert_eq!(
+            <Option<Arc<Email>>>::field_type().to_string(),
+            "option<string>"
+        );
+    }
+}
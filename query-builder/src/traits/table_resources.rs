@@ -1,24 +1,29 @@
-use crate::{Field, Model, Raw, Table};
+use crate::{Field, Model, Raw, Table, View};
 
 /// Provides the ability to gather all the resources
-/// of a table in a single place. Fields definitions,
-/// and table definitions are using automatically
-/// generated methods from the `Model` trait.
-/// Events and indexes definitions are manually
-/// implemented using the statements or query macro
-/// provided by the crate.
+/// of a table in a single place. Fields, indexes,
+/// events and table definitions are using automatically
+/// generated methods from the `Model` trait. They can
+/// also be manually implemented using the statements
+/// or query macro provided by the crate.
 pub trait TableResources
 where
     Self: Model,
 {
-    /// Returns a list of fields definitions.
+    /// Returns a list of events definitions.
     fn events_definitions() -> Vec<Raw> {
-        vec![]
+        Self::define_events()
     }
 
     /// Returns a list of fields' indexes definitions.
     fn indexes_definitions() -> Vec<Raw> {
-        vec![]
+        Self::define_indexes()
+    }
+
+    /// Returns a list of the events on linked tables implementing
+    /// the `on_delete` actions of the link fields.
+    fn referential_actions_definitions() -> Vec<Raw> {
+        Self::define_referential_actions()
     }
 
     /// Returns a list of fields definitions.
@@ -32,6 +37,19 @@ where
     }
 }
 
+/// Provides the definition of a view to `create_table_resources!`.
+/// A view only has its `DEFINE TABLE ... AS SELECT` definition.
+/// Implemented by the `View` derive.
+pub trait ViewResources
+where
+    Self: View,
+{
+    /// Returns the view's table definition.
+    fn view_definition() -> Raw {
+        Self::define_table()
+    }
+}
+
 /// A trait for field changes.
 #[derive(Debug, Clone)]
 pub struct FieldChangeMeta {
@@ -46,7 +64,7 @@ pub struct FieldChangeMeta {
 /// A trait for table changes.
 #[macro_export]
 macro_rules! create_table_resources {
-    ($($struct_table: ident),*) => {
+    ($($struct_table: ident),* $(,)? $(; views: $($view: ident),+ $(,)?)?) => {
         fn tables(&self) -> ::std::vec::Vec<$crate::Raw> {
             ::std::vec![
                 $(
@@ -55,6 +73,13 @@ macro_rules! create_table_resources {
                     <$struct_table as $crate::TableResources>::indexes_definitions(),
                     <$struct_table as $crate::TableResources>::events_definitions(),
                 )*
+                $($(
+                    ::std::vec![<$view as $crate::ViewResources>::view_definition()],
+                )+)?
+                // Defined last as they are defined on the linked tables
+                $(
+                    <$struct_table as $crate::TableResources>::referential_actions_definitions(),
+                )*
             ].into_iter().flatten().collect::<::std::vec::Vec<$crate::Raw>>()
         }
 
@@ -67,6 +92,15 @@ macro_rules! create_table_resources {
             meta
         }
 
+        fn tables_old_names(&self) -> ::std::collections::HashMap<$crate::Table, $crate::Table> {
+            let mut old_names = ::std::collections::HashMap::<$crate::Table, $crate::Table>::new();
+            $(
+                if let ::std::option::Option::Some(old_table) = <$struct_table as $crate::Model>::old_table() {
+                    old_names.insert(<$struct_table as $crate::Model>::table(), old_table);
+                }
+            )*
+            old_names
+        }
 
     };
 }
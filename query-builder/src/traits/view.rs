@@ -0,0 +1,68 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use serde::{de::DeserializeOwned, Serialize};
+use surrealdb::sql::Thing;
+
+use crate::{
+    count,
+    statements::{
+        select::{select, SelectStatementCount},
+        select_value, SelectStatement,
+    },
+    All, Conditional, Field, Filter, Raw, Table,
+};
+
+/// A read-only table whose records are computed by the database from a `SELECT` on another
+/// table e.g `DEFINE TABLE customer_totals AS SELECT customer, count() AS orders FROM order
+/// GROUP BY customer`. It is derived with `#[derive(View)]`.
+///
+/// Unlike a `Model`, it has no `SurrealCrud` methods creating, updating or deleting records.
+pub trait View: Sized + Serialize + DeserializeOwned {
+    /// The name of the view table
+    fn table() -> Table;
+
+    /// The `SELECT` statement computing the records of the view
+    fn view_statement() -> SelectStatement;
+
+    /// Get view's table definition statement
+    fn define_table() -> Raw;
+
+    /// The names of the fields projected into the view
+    fn get_serializable_fields() -> Vec<Field>;
+
+    /// Finds a record by id.
+    fn find_by_id(id: impl Into<Thing>) -> SelectStatement {
+        select(All).from(id.into())
+    }
+
+    /// Finds all records.
+    fn find_all() -> SelectStatement {
+        select(All).from(Self::table())
+    }
+
+    /// Finds records by filtering.
+    fn find_where(filter: impl Conditional + Clone) -> SelectStatement {
+        select(All).from(Self::table()).where_(filter)
+    }
+
+    /// Count filtered records.
+    fn count_where(filter: impl Conditional + Clone) -> SelectStatementCount {
+        let selection = select_value(Field::new("count")).from(
+            select(count!(Filter::new(filter)))
+                .from(Self::table())
+                .group_all(),
+        );
+        selection.into()
+    }
+
+    /// Count all records.
+    fn count_all() -> SelectStatementCount {
+        let selection =
+            select_value(Field::new("count")).from(select(count!()).from(Self::table()).group_all());
+        selection.into()
+    }
+}
@@ -7,9 +7,10 @@
 use std::ops::Deref;
 
 use crate::{
-    statements::{LetStatement, Subquery},
-    Arrow, Binding, BindingsList, Buildable, Conditional, Erroneous, ErrorList, Model, Operatable,
-    Operation, Param, Parametric, Setter, Table,
+    statements::{select_value, LetStatement, Subquery, TargettablesForSelect},
+    tenant::tenant_condition,
+    Arrow, Binding, BindingsList, Buildable, Conditional, Erroneous, ErrorList, Field, Model,
+    Operatable, Operation, Param, Parametric, Setter, Table,
 };
 
 use super::{Filter, NumberLike, SurrealId};
@@ -156,6 +157,12 @@ impl NodeClause {
     pub fn with_field(self, field_name: String) -> Self {
         Self(self.0.with_field(field_name))
     }
+
+    /// Restricts the node to the current tenant if `T` is tenant-scoped.
+    /// Used in the macro after `with_table` for traversals into the node.
+    pub fn tenant_scoped<T: Model>(self) -> Self {
+        Self(self.0.tenant_scoped::<T>())
+    }
 }
 
 impl<T, Id> From<SurrealId<T, Id>> for NodeClause
@@ -254,6 +261,18 @@ impl EdgeClause {
     pub fn with_table(self, table: impl Into<String>) -> Self {
         Self(self.0.with_table(table))
     }
+
+    /// Restricts the edge to the current tenant if `T` is tenant-scoped.
+    /// Used in the macro after `with_table` for traversals through the edge.
+    ///
+    /// Empty edges are left as they are because RELATE only takes a bare edge table. They
+    /// can only be reached from nodes that are themselves scoped to the tenant.
+    pub fn tenant_scoped<T: Model>(self) -> Self {
+        if matches!(self.0.kind, ClauseType::Empty) {
+            return self;
+        }
+        Self(self.0.tenant_scoped::<T>())
+    }
 }
 
 impl<T> From<T> for EdgeClause
@@ -426,6 +445,50 @@ impl Clause {
         self
     }
 
+    /// Restricts the clause to the records of the current tenant if `T` is tenant-scoped.
+    /// The tenant condition is added to empty, `*` and `WHERE` clauses. Ids, params and
+    /// subqueries are narrowed down to `(SELECT VALUE id FROM <records> WHERE <tenant condition>)`
+    /// which still works as either side of a RELATE statement.
+    fn tenant_scoped<T: Model>(mut self) -> Self {
+        let condition = match tenant_condition::<T>() {
+            Ok(Some(condition)) => condition,
+            Ok(None) => return self,
+            Err(error) => {
+                self.errors.push(error);
+                return self;
+            }
+        };
+
+        let kind = match &self.kind {
+            ClauseType::Empty | ClauseType::All => ClauseType::Where(condition),
+            ClauseType::Where(filter) => ClauseType::Where(filter.clone().and(condition)),
+            ClauseType::Id(id) => ClauseType::Subquery(tenant_records::<T>(id.clone())),
+            ClauseType::Param(param) => ClauseType::Subquery(tenant_records::<T>(param.clone())),
+            ClauseType::Subquery(subquery) => {
+                ClauseType::Subquery(tenant_records::<T>(subquery.clone()))
+            }
+            ClauseType::AnyEdgeFilter(edge_filter) => {
+                ClauseType::AnyEdgeFilter(edge_filter.clone().and_where(condition))
+            }
+            ClauseType::Last | ClauseType::Index(_) => {
+                self.errors.push(format!(
+                    "Records of the tenant-scoped table `{}` cannot be picked by position. \
+                    Use a WHERE clause instead",
+                    T::table()
+                ));
+                return self;
+            }
+        };
+
+        let clause = Clause::new(kind);
+        Self {
+            arrow: self.arrow,
+            model_or_field_name: self.model_or_field_name,
+            errors: [self.errors, clause.errors.clone()].concat(),
+            ..clause
+        }
+    }
+
     fn update_errors(mut self, table: &str) -> Self {
         let mut errors = vec![];
         if let ClauseType::Id(id) = &self.kind {
@@ -440,6 +503,14 @@ impl Clause {
     }
 }
 
+/// The ids of the records in `targets` that belong to the current tenant.
+fn tenant_records<T: Model>(targets: impl Into<TargettablesForSelect>) -> Subquery {
+    select_value(Field::new("id"))
+        .from(targets)
+        .tenant_scoped::<T>()
+        .into()
+}
+
 impl std::fmt::Display for Clause {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.build())
@@ -630,6 +701,18 @@ impl AnyEdgeFilter {
         self.where_ = Some(condition.build());
         self
     }
+
+    /// Adds a condition that must hold in addition to the existing WHERE condition.
+    fn and_where(mut self, condition: Filter) -> Self {
+        self.bindings.extend(condition.get_bindings());
+        self.errors.extend(condition.get_errors());
+
+        self.where_ = Some(match self.where_ {
+            Some(where_) => format!("({where_}) AND ({})", condition.build()),
+            None => condition.build(),
+        });
+        self
+    }
 }
 
 impl Buildable for AnyEdgeFilter {
@@ -0,0 +1,303 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use std::{fmt::Display, marker::PhantomData};
+
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{self, Thing};
+
+use crate::{Erroneous, Model, SurrealId, SurrealOrmError};
+
+/// A tuple of values that make up an array record id e.g `('sensor1', 1700000000)`
+/// for `reading:['sensor1', 1700000000]`.
+///
+/// Implemented for tuples of up to 8 elements that can be converted to `sql::Value`.
+/// Object record ids e.g `reading:{ sensor: 'sensor1', at: 1700000000 }` are out of scope.
+/// Ids of the wrong shape or with a different number of values are rejected.
+pub trait CompositeKey {
+    /// The number of values the key is made of.
+    const ARITY: usize;
+
+    /// Converts the key into the array of the record id.
+    fn into_array(self) -> sql::Array;
+}
+
+macro_rules! impl_composite_key {
+    ($($element:ident),+) => {
+        impl<$($element),+> CompositeKey for ($($element,)+)
+        where
+            $($element: Into<sql::Value>),+
+        {
+            const ARITY: usize = [$(stringify!($element)),+].len();
+
+            #[allow(non_snake_case)]
+            fn into_array(self) -> sql::Array {
+                let ($($element,)+) = self;
+                sql::Array::from(vec![$($element.into()),+])
+            }
+        }
+    };
+}
+
+impl_composite_key!(A);
+impl_composite_key!(A, B);
+impl_composite_key!(A, B, C);
+impl_composite_key!(A, B, C, D);
+impl_composite_key!(A, B, C, D, E);
+impl_composite_key!(A, B, C, D, E, F);
+impl_composite_key!(A, B, C, D, E, F, G);
+impl_composite_key!(A, B, C, D, E, F, G, H);
+
+/// An id with a typed composite key.
+/// Used to tie a model's id to its key type for range selections e.g `Reading::id_range`.
+pub trait CompositeId {
+    /// The tuple the id is made of.
+    type Key: CompositeKey + Clone;
+}
+
+/// A record id made of an array of values e.g `reading:['sensor1', 1700000000]`.
+///
+/// Array ids keep records with the same leading values together, which makes range
+/// selections over them efficient. This is the recommended pattern for time series.
+///
+/// Examples:
+///
+/// ```rust, ignore
+/// # use surreal_orm::{*, statements::select};
+/// #[derive(Node, Serialize, Deserialize, Debug, Clone)]
+/// #[orm(table = reading)]
+/// pub struct Reading {
+///     pub id: SurrealCompositeId<Self, (String, i64)>,
+///     pub temperature: f64,
+/// }
+///
+/// let id = Reading::create_composite_id(("sensor1".to_string(), 1700000000));
+/// assert_eq!(id.to_raw(), "reading:['sensor1', 1700000000]");
+///
+/// let statement = select(All).from(Reading::id_range(
+///     ("sensor1".to_string(), 1700000000)..("sensor1".to_string(), 1800000000),
+/// ));
+/// assert_eq!(
+///     statement.to_raw().build(),
+///     "SELECT * FROM reading:['sensor1', 1700000000]..['sensor1', 1800000000];"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct SurrealCompositeId<T: Model, K: CompositeKey>(SurrealId<T, sql::Array>, PhantomData<K>);
+
+impl<T, K> SurrealCompositeId<T, K>
+where
+    T: Model,
+    K: CompositeKey,
+{
+    /// Creates a new composite id from the key
+    pub fn new(key: K) -> Self {
+        Self(SurrealId::new(key.into_array()), PhantomData)
+    }
+
+    /// The values the id is made of
+    pub fn key(&self) -> Option<&sql::Array> {
+        match &self.0.id {
+            sql::Id::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    /// Converts the surreal id to a thing
+    pub fn to_thing(&self) -> Thing {
+        self.0.to_thing()
+    }
+
+    /// Converts the surreal id to a raw string
+    pub fn to_raw(&self) -> String {
+        self.0.to_raw()
+    }
+}
+
+impl<T, K> CompositeId for SurrealCompositeId<T, K>
+where
+    T: Model,
+    K: CompositeKey + Clone,
+{
+    type Key = K;
+}
+
+impl<T, K> Serialize for SurrealCompositeId<T, K>
+where
+    T: Model,
+    K: CompositeKey,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T, K> Deserialize<'de> for SurrealCompositeId<T, K>
+where
+    T: Model,
+    K: CompositeKey,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let id = SurrealId::deserialize(deserializer)?;
+        check_key::<K>(&id.to_thing()).map_err(serde::de::Error::custom)?;
+        Ok(Self(id, PhantomData))
+    }
+}
+
+impl<T, K> Display for SurrealCompositeId<T, K>
+where
+    T: Model,
+    K: CompositeKey,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T, K> TryFrom<sql::Thing> for SurrealCompositeId<T, K>
+where
+    T: Model,
+    K: CompositeKey,
+{
+    type Error = SurrealOrmError;
+
+    fn try_from(value: sql::Thing) -> Result<Self, Self::Error> {
+        check_key::<K>(&value)?;
+        Ok(Self(SurrealId::try_from(value)?, PhantomData))
+    }
+}
+
+/// Checks that the id is an array id with as many values as the key.
+fn check_key<K: CompositeKey>(thing: &Thing) -> Result<(), SurrealOrmError> {
+    match &thing.id {
+        sql::Id::Array(array) if array.len() == K::ARITY => Ok(()),
+        sql::Id::Array(array) => Err(SurrealOrmError::CompositeIdArityMismatch(
+            thing.to_string(),
+            array.len(),
+            K::ARITY,
+        )),
+        _ => Err(SurrealOrmError::NotArrayId(thing.to_string())),
+    }
+}
+
+impl<T, K> Erroneous for SurrealCompositeId<T, K>
+where
+    T: Model,
+    K: CompositeKey,
+{
+    fn get_errors(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+impl<T, K> From<SurrealCompositeId<T, K>> for sql::Thing
+where
+    T: Model,
+    K: CompositeKey,
+{
+    fn from(id: SurrealCompositeId<T, K>) -> Self {
+        id.to_thing()
+    }
+}
+
+impl<T, K> From<&SurrealCompositeId<T, K>> for sql::Thing
+where
+    T: Model,
+    K: CompositeKey,
+{
+    fn from(id: &SurrealCompositeId<T, K>) -> Self {
+        id.to_thing()
+    }
+}
+
+impl<T, K> From<SurrealCompositeId<T, K>> for sql::Value
+where
+    T: Model,
+    K: CompositeKey,
+{
+    fn from(id: SurrealCompositeId<T, K>) -> Self {
+        sql::Value::Thing(id.to_thing())
+    }
+}
+
+impl<T, K> From<&SurrealCompositeId<T, K>> for sql::Value
+where
+    T: Model,
+    K: CompositeKey,
+{
+    fn from(id: &SurrealCompositeId<T, K>) -> Self {
+        sql::Value::Thing(id.to_thing())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestUser;
+
+    #[test]
+    fn test_composite_id_renders_as_array_id() {
+        let id = TestUser::create_composite_id(("sensor1", 1700000000, true));
+        assert_eq!(id.to_raw(), "user:['sensor1', 1700000000, true]");
+        assert_eq!(id.key().map(|key| key.len()), Some(3));
+
+        let id = SurrealCompositeId::<TestUser, (String,)>::new(("oyelowo".to_string(),));
+        assert_eq!(id.to_string(), "user:['oyelowo']");
+    }
+
+    #[test]
+    fn test_composite_id_from_thing() {
+        let id = SurrealCompositeId::<TestUser, (String, i64)>::try_from(sql::Thing::from((
+            "user".to_string(),
+            sql::Id::from(sql::Array::from(vec![
+                sql::Value::from("sensor1"),
+                sql::Value::from(1),
+            ])),
+        )))
+        .expect("array id of the user table");
+        assert_eq!(id.to_raw(), "user:['sensor1', 1]");
+
+        let not_array = SurrealCompositeId::<TestUser, (String, i64)>::try_from(
+            TestUser::create_thing("oyelowo"),
+        );
+        assert!(not_array.is_err());
+    }
+
+    #[test]
+    fn test_composite_id_deserialization_rejects_malformed_ids() {
+        let deserialize = |thing: &str| {
+            let thing = sql::thing(thing).expect("valid thing");
+            let value = serde_json::to_value(thing).expect("serializable thing");
+            serde_json::from_value::<SurrealCompositeId<TestUser, (String, i64)>>(value)
+        };
+
+        let id = deserialize("user:['sensor1', 1]").expect("id matches the key");
+        assert_eq!(id.to_raw(), "user:['sensor1', 1]");
+
+        let wrong_arity = deserialize("user:['sensor1', 1, true]").unwrap_err();
+        assert_eq!(
+            wrong_arity.to_string(),
+            "The id - user:['sensor1', 1, true] is made of 3 values but its composite key is made of 2."
+        );
+
+        let wrong_shape = deserialize("user:oyelowo").unwrap_err();
+        assert!(wrong_shape.to_string().contains("is not an array id"));
+
+        let wrong_arity = SurrealCompositeId::<TestUser, (String, i64)>::try_from(
+            sql::thing("user:['sensor1']").expect("valid thing"),
+        );
+        assert!(matches!(
+            wrong_arity,
+            Err(SurrealOrmError::CompositeIdArityMismatch(_, 1, 2))
+        ));
+    }
+}
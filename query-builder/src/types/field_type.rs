@@ -54,15 +54,16 @@ use std::{
 
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while1},
-    character::complete::space0,
-    combinator::{all_consuming, cut, opt, value},
+    bytes::complete::{escaped_transform, tag, take_while1},
+    character::complete::{char, none_of, space0},
+    combinator::{all_consuming, cut, map, opt, value},
     error::context,
     multi::{separated_list0, separated_list1},
-    sequence::{preceded, tuple},
+    sequence::{delimited, preceded, tuple},
     IResult, Parser,
 };
 use serde::{Deserialize, Serialize};
+use surrealdb::sql;
 
 /// Geometry types supported by surrealdb
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
@@ -119,6 +120,71 @@ impl Display for GeometryType {
     }
 }
 
+/// A literal type. Only values equal to the literal are allowed
+/// e.g `"active"`, `10`, `true` or `{ type: "circle", radius: float }`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LiteralType {
+    /// A string literal e.g `"active"`
+    String(String),
+    /// An integer literal e.g `10`
+    Int(i64),
+    /// A boolean literal e.g `true`
+    Bool(bool),
+    /// An object with the given field types e.g `{ type: "circle", radius: float }`.
+    /// Used for tagged enums.
+    Object(Vec<(String, FieldType)>),
+}
+
+impl LiteralType {
+    /// The type the literal belongs to e.g `string` for `"active"`
+    pub fn base_type(&self) -> FieldType {
+        match self {
+            LiteralType::String(_) => FieldType::String,
+            LiteralType::Int(_) => FieldType::Int,
+            LiteralType::Bool(_) => FieldType::Bool,
+            LiteralType::Object(_) => FieldType::Object,
+        }
+    }
+
+    /// The value of a string, int or bool literal
+    pub fn scalar_value(&self) -> Option<sql::Value> {
+        match self {
+            LiteralType::String(string) => Some(string.as_str().into()),
+            LiteralType::Int(int) => Some((*int).into()),
+            LiteralType::Bool(bool) => Some((*bool).into()),
+            LiteralType::Object(_) => None,
+        }
+    }
+}
+
+impl Display for LiteralType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LiteralType::String(string) => write!(
+                f,
+                "\"{}\"",
+                string.replace('\\', "\\\\").replace('"', "\\\"")
+            ),
+            LiteralType::Int(int) => write!(f, "{int}"),
+            LiteralType::Bool(bool) => write!(f, "{bool}"),
+            LiteralType::Object(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(key, field_type)| {
+                        if !key.is_empty() && key.chars().all(is_valid_id_char) {
+                            format!("{key}: {field_type}")
+                        } else {
+                            format!("\"{key}\": {field_type}")
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "{{ {fields} }}")
+            }
+        }
+    }
+}
+
 #[allow(missing_docs)]
 #[derive(Clone, Default, Debug, PartialEq)]
 pub enum FieldType {
@@ -159,6 +225,10 @@ pub enum FieldType {
     Set(Box<FieldType>, Option<u64>), // set<string, 10>, set<string>, set
     /// a list
     Array(Box<FieldType>, Option<u64>), // array<string, 10>, array<string>, array
+    /// Only the given value is allowed. Usually used in a union e.g `"active" | "banned"`.
+    /// Literal types are a 2.x feature. In 1.x, the base type is used together with
+    /// an `ASSERT $value INSIDE [...]` check.
+    Literal(LiteralType),
 }
 
 impl FromStr for FieldType {
@@ -246,6 +316,7 @@ impl Display for FieldType {
                     write!(f, "array<{}>", ft)
                 }
             }
+            FieldType::Literal(literal) => write!(f, "{literal}"),
         }
     }
 }
@@ -271,6 +342,7 @@ impl FieldType {
             "geometry<point>",
             "option<string>",
             "string | number",
+            "\"active\" | \"banned\"",
         ]
     }
 
@@ -429,6 +501,118 @@ impl FieldType {
     pub fn is_empty_geometry(&self) -> bool {
         matches!(self, FieldType::Geometry(ref_tables) if ref_tables.is_empty())
     }
+
+    /// Returns true if the field type is a literal type
+    pub fn is_literal(&self) -> bool {
+        matches!(self, FieldType::Literal(_))
+    }
+
+    /// Returns true if the field type is or contains a literal type
+    pub fn has_literals(&self) -> bool {
+        match self {
+            FieldType::Literal(_) => true,
+            FieldType::Option(ft) | FieldType::Array(ft, _) | FieldType::Set(ft, _) => {
+                ft.has_literals()
+            }
+            FieldType::Union(fts) => fts.iter().any(FieldType::has_literals),
+            _ => false,
+        }
+    }
+
+    /// Replaces the literal types with their base types for dialects without literal types
+    /// e.g `"active" | "banned"` becomes `string`.
+    pub(crate) fn without_literals(&self) -> FieldType {
+        match self {
+            FieldType::Literal(literal) => literal.base_type(),
+            FieldType::Option(ft) => FieldType::Option(Box::new(ft.without_literals())),
+            FieldType::Array(ft, size) => FieldType::Array(Box::new(ft.without_literals()), *size),
+            FieldType::Set(ft, size) => FieldType::Set(Box::new(ft.without_literals()), *size),
+            FieldType::Union(fts) => {
+                let mut base_types: Vec<FieldType> = vec![];
+                for ft in fts.iter().map(FieldType::without_literals) {
+                    if !base_types.contains(&ft) {
+                        base_types.push(ft);
+                    }
+                }
+                match base_types.len() {
+                    1 => base_types.remove(0),
+                    _ => FieldType::Union(base_types),
+                }
+            }
+            ft => ft.clone(),
+        }
+    }
+
+    /// The check standing in for the literal types of the field in dialects without
+    /// literal types e.g `$value INSIDE ['active', 'banned']`.
+    /// Object literals are checked by a field they all share with scalar literals
+    /// e.g the tag of a tagged enum.
+    pub(crate) fn literal_assertion(&self) -> Option<String> {
+        match self {
+            FieldType::Option(ft) => ft
+                .literal_assertion()
+                .map(|assertion| format!("$value = NONE OR {assertion}")),
+            FieldType::Array(ft, _) | FieldType::Set(ft, _) => ft
+                .literal_values()
+                .map(|values| format!("$value ALLINSIDE {values}")),
+            ft => ft
+                .literal_values()
+                .map(|values| format!("$value INSIDE {values}"))
+                .or_else(|| ft.object_literal_tag_assertion()),
+        }
+    }
+
+    fn literals(&self) -> Option<Vec<&LiteralType>> {
+        match self {
+            FieldType::Literal(literal) => Some(vec![literal]),
+            FieldType::Union(fts) => fts
+                .iter()
+                .map(|ft| match ft {
+                    FieldType::Literal(literal) => Some(literal),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    fn literal_values(&self) -> Option<sql::Array> {
+        self.literals()?
+            .into_iter()
+            .map(LiteralType::scalar_value)
+            .collect::<Option<Vec<_>>>()
+            .map(sql::Array::from)
+    }
+
+    fn object_literal_tag_assertion(&self) -> Option<String> {
+        let objects = self
+            .literals()?
+            .into_iter()
+            .map(|literal| match literal {
+                LiteralType::Object(fields) => Some(fields),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let (tag, _) = objects.first()?.iter().find(|(key, _)| {
+            objects.iter().all(|fields| {
+                fields
+                    .iter()
+                    .any(|(k, ft)| k == key && ft.literal_values().is_some())
+            })
+        })?;
+
+        let mut tags: Vec<sql::Value> = vec![];
+        for fields in &objects {
+            let values = fields
+                .iter()
+                .find(|(key, _)| key == tag)
+                .and_then(|(_, ft)| ft.literal_values())?;
+            tags.extend(values.0);
+        }
+
+        Some(format!("$value.{tag} INSIDE {}", sql::Array::from(tags)))
+    }
 }
 
 /// Parses a field type
@@ -468,6 +652,7 @@ fn parse_single_field_type(input: &str) -> IResult<&str, FieldType> {
         parse_option_field_type,
         parse_array_type,
         parse_set_type,
+        parse_literal_type,
         // parse_union_type,
     ))(input)
 }
@@ -618,6 +803,63 @@ fn parse_geometry_type(input: &str) -> IResult<&str, FieldType> {
     Ok((input, FieldType::Geometry(rt.unwrap_or(vec![]))))
 }
 
+fn parse_string_literal(input: &str) -> IResult<&str, String> {
+    let double_quoted = delimited(
+        char('"'),
+        opt(escaped_transform(
+            none_of("\\\""),
+            '\\',
+            alt((value("\\", tag("\\")), value("\"", tag("\"")))),
+        )),
+        char('"'),
+    );
+    let single_quoted = delimited(
+        char('\''),
+        opt(escaped_transform(
+            none_of("\\'"),
+            '\\',
+            alt((value("\\", tag("\\")), value("'", tag("'")))),
+        )),
+        char('\''),
+    );
+    map(
+        alt((double_quoted, single_quoted)),
+        Option::unwrap_or_default,
+    )(input)
+}
+
+fn parse_object_literal_field(input: &str) -> IResult<&str, (String, FieldType)> {
+    let (input, key) = alt((
+        parse_string_literal,
+        map(parse_identifier, |key: &str| key.to_string()),
+    ))(input)?;
+    let (input, _) = tuple((space0, tag(":"), space0))(input)?;
+    let (input, field_type) = parse_top_level_field_type(input)?;
+    let (input, _) = space0(input)?;
+    Ok((input, (key, field_type)))
+}
+
+fn parse_object_literal(input: &str) -> IResult<&str, LiteralType> {
+    let (input, _) = tuple((tag("{"), space0))(input)?;
+    let (input, fields) =
+        separated_list0(tuple((tag(","), space0)), parse_object_literal_field)(input)?;
+    let (input, _) = opt(tuple((tag(","), space0)))(input)?;
+    let (input, _) = tag("}")(input)?;
+    Ok((input, LiteralType::Object(fields)))
+}
+
+fn parse_literal_type(input: &str) -> IResult<&str, FieldType> {
+    let (input, literal) = alt((
+        map(parse_string_literal, LiteralType::String),
+        map(nom::character::complete::i64, LiteralType::Int),
+        value(LiteralType::Bool(true), tag("true")),
+        value(LiteralType::Bool(false), tag("false")),
+        parse_object_literal,
+    ))(input)?;
+    let (input, _) = space0(input)?;
+    Ok((input, FieldType::Literal(literal)))
+}
+
 struct ListItem {
     item_type: FieldType,
     size: Option<u64>,
@@ -1340,4 +1582,95 @@ mod tests {
             )))
         ])
     );
+
+    test_parse_db_field_type!(
+        literal_string_union,
+        "\"active\" | 'banned' | \"soft \\\"deleted\\\"\"",
+        FieldType::Union(vec![
+            FieldType::Literal(LiteralType::String("active".into())),
+            FieldType::Literal(LiteralType::String("banned".into())),
+            FieldType::Literal(LiteralType::String("soft \"deleted\"".into())),
+        ])
+    );
+
+    test_parse_db_field_type!(
+        literal_int_and_bool,
+        "option<-1 | 10 | true>",
+        FieldType::Option(Box::new(FieldType::Union(vec![
+            FieldType::Literal(LiteralType::Int(-1)),
+            FieldType::Literal(LiteralType::Int(10)),
+            FieldType::Literal(LiteralType::Bool(true)),
+        ])))
+    );
+
+    test_parse_db_field_type!(
+        literal_object_union,
+        "{ type: \"circle\", radius: float } | { type: \"square\", side: option<float> }",
+        FieldType::Union(vec![
+            FieldType::Literal(LiteralType::Object(vec![
+                (
+                    "type".into(),
+                    FieldType::Literal(LiteralType::String("circle".into()))
+                ),
+                ("radius".into(), FieldType::Float),
+            ])),
+            FieldType::Literal(LiteralType::Object(vec![
+                (
+                    "type".into(),
+                    FieldType::Literal(LiteralType::String("square".into()))
+                ),
+                ("side".into(), FieldType::Option(Box::new(FieldType::Float))),
+            ])),
+        ])
+    );
+
+    #[test]
+    fn test_literal_display_roundtrip() {
+        let types = [
+            "\"active\" | \"banned\"",
+            "array<\"a\" | 1 | false>",
+            "{ type: \"circle\", radius: float } | { type: \"square\" }",
+            "{ t: \"point\", c: { x: int, y: int } }",
+        ];
+
+        for field_type in types {
+            assert_eq!(
+                FieldType::from_str(field_type).unwrap().to_string(),
+                field_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_literal_fallback_for_dialects_without_literal_types() {
+        let status = FieldType::from_str("\"active\" | \"banned\"").unwrap();
+        assert_eq!(status.without_literals(), FieldType::String);
+        assert_eq!(
+            status.literal_assertion().unwrap(),
+            "$value INSIDE ['active', 'banned']"
+        );
+
+        let statuses = FieldType::from_str("option<array<\"active\" | \"banned\">>").unwrap();
+        assert_eq!(
+            statuses.without_literals().to_string(),
+            "option<array<string>>"
+        );
+        assert_eq!(
+            statuses.literal_assertion().unwrap(),
+            "$value = NONE OR $value ALLINSIDE ['active', 'banned']"
+        );
+
+        let shape = FieldType::from_str(
+            "{ type: \"circle\", radius: float } | { type: \"square\", side: float }",
+        )
+        .unwrap();
+        assert_eq!(shape.without_literals(), FieldType::Object);
+        assert_eq!(
+            shape.literal_assertion().unwrap(),
+            "$value.type INSIDE ['circle', 'square']"
+        );
+
+        assert_eq!(FieldType::String.literal_assertion(), None);
+        assert!(!FieldType::from_str("array<string>").unwrap().has_literals());
+    }
 }
@@ -100,6 +100,12 @@ impl From<&Table> for Table {
 /// A collection of tables
 pub struct Tables(Vec<Table>);
 
+impl From<Table> for Tables {
+    fn from(value: Table) -> Self {
+        Self(vec![value])
+    }
+}
+
 impl From<Vec<Table>> for Tables {
     fn from(value: Vec<Table>) -> Self {
         Self(value)
@@ -9,6 +9,7 @@ pub(crate) mod arithmetic;
 pub(crate) mod arrow;
 pub(crate) mod bracket;
 pub(crate) mod clause;
+pub(crate) mod composite_id;
 pub(crate) mod crud_type;
 pub(crate) mod data_types;
 pub(crate) mod expression;
@@ -27,6 +28,7 @@ pub(crate) mod ordinal;
 pub(crate) mod param;
 pub(crate) mod params_standard;
 pub(crate) mod projection;
+pub(crate) mod referential_action;
 pub(crate) mod return_type;
 pub(crate) mod surreal_id;
 pub(crate) mod token_target;
@@ -36,6 +38,7 @@ pub use alias::*;
 pub use arrow::*;
 pub use bracket::*;
 pub use clause::*;
+pub use composite_id::*;
 pub use crud_type::*;
 pub use data_types::*;
 pub use field::*;
@@ -52,6 +55,7 @@ pub use ordinal::*;
 pub use param::*;
 pub use params_standard::*;
 pub use projection::*;
+pub use referential_action::*;
 pub use return_type::*;
 pub use surreal_id::*;
 pub use token_target::*;
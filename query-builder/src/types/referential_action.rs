@@ -0,0 +1,177 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2024 Oyelowo Oyedayo
+ */
+
+use crate::{
+    statements::{define_event, select, SelectStatement},
+    Field, Model, Raw, Table, ToRaw,
+};
+
+/// The action taken on the records linking to a record when the record is deleted.
+/// Set with the `on_delete` attribute of a `link_one`, `link_self` or `link_many` field
+/// e.g `#[orm(link_one = Company, on_delete = "cascade")]`.
+/// It is implemented as a `DEFINE EVENT` on the linked table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnDelete {
+    /// Deletes the linking records
+    Cascade,
+    /// Fails the deletion while records link to the record
+    Restrict,
+    /// Sets the link to `NULL`. Removes the link from the array of a `link_many` field.
+    SetNull,
+    /// Sets the link to `NONE`. Removes the link from the array of a `link_many` field.
+    Unset,
+}
+
+impl OnDelete {
+    /// The `DEFINE EVENT` on `linked_table` applying the action to the records of `table`
+    /// whose `field` links to a deleted record.
+    ///
+    /// # Example
+    /// ```
+    /// # use surreal_query_builder::{Field, OnDelete, Table, Buildable};
+    /// let event = OnDelete::Cascade.define_event(
+    ///     Table::new("employee"),
+    ///     Field::new("company"),
+    ///     Table::new("company"),
+    ///     false,
+    /// );
+    ///
+    /// assert_eq!(
+    ///     event.build(),
+    ///     "DEFINE EVENT employee_company_on_delete ON TABLE company WHEN $event = 'DELETE' \
+    ///     THEN DELETE employee WHERE company = $before.id;"
+    /// );
+    /// ```
+    pub fn define_event(
+        self,
+        table: Table,
+        field: Field,
+        linked_table: Table,
+        is_link_many: bool,
+    ) -> Raw {
+        let links_deleted = if is_link_many {
+            format!("{field} CONTAINS $before.id")
+        } else {
+            format!("{field} = $before.id")
+        };
+
+        let action = match (self, is_link_many) {
+            (OnDelete::Cascade, _) => format!("DELETE {table} WHERE {links_deleted}"),
+            (OnDelete::Restrict, _) => format!(
+                "IF count((SELECT VALUE id FROM {table} WHERE {links_deleted})) > 0 \
+                {{ THROW 'Cannot delete a record of {linked_table} linked from {table}.{field}' }}"
+            ),
+            (OnDelete::SetNull | OnDelete::Unset, true) => {
+                format!("UPDATE {table} SET {field} -= $before.id WHERE {links_deleted}")
+            }
+            (OnDelete::SetNull, false) => {
+                format!("UPDATE {table} SET {field} = NULL WHERE {links_deleted}")
+            }
+            (OnDelete::Unset, false) => {
+                format!("UPDATE {table} SET {field} = NONE WHERE {links_deleted}")
+            }
+        };
+
+        define_event(format!("{table}_{field}_on_delete"))
+            .on_table(linked_table)
+            .when(Raw::new("$event = 'DELETE'"))
+            .then(Raw::new(action))
+            .to_raw()
+    }
+}
+
+/// Selects the ids and links of the records of a model with `link_one`, `link_self` or
+/// `link_many` fields pointing to records that no longer exist.
+/// Returns `None` when the model has no link fields.
+///
+/// # Example
+/// ```ignore
+/// let dangling = select_dangling_links::<Employee>().unwrap();
+/// // SELECT id, company, projects FROM employee WHERE (company != NONE AND company != NULL
+/// // AND company.id = NONE) OR (projects.*.id CONTAINS NONE);
+/// ```
+pub fn select_dangling_links<T: Model>() -> Option<SelectStatement> {
+    let link_one_fields = T::get_link_one_and_self_fields();
+    let link_many_fields = T::get_link_many_fields();
+
+    let conditions = link_one_fields
+        .iter()
+        .map(|field| format!("({field} != NONE AND {field} != NULL AND {field}.id = NONE)"))
+        .chain(
+            link_many_fields
+                .iter()
+                .map(|field| format!("({field}.*.id CONTAINS NONE)")),
+        )
+        .collect::<Vec<_>>();
+
+    if conditions.is_empty() {
+        return None;
+    }
+
+    let projections = [Field::new("id")]
+        .into_iter()
+        .chain(link_one_fields)
+        .chain(link_many_fields)
+        .collect::<Vec<_>>();
+
+    Some(
+        select(projections)
+            .from(T::table())
+            .where_(Raw::new(conditions.join(" OR "))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Buildable;
+
+    fn on_delete_event(on_delete: OnDelete, is_link_many: bool) -> String {
+        let field = if is_link_many { "projects" } else { "company" };
+        on_delete
+            .define_event(
+                Table::new("employee"),
+                Field::new(field),
+                Table::new("company"),
+                is_link_many,
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_on_delete_link_one_events() {
+        assert_eq!(
+            on_delete_event(OnDelete::Restrict, false),
+            "DEFINE EVENT employee_company_on_delete ON TABLE company WHEN $event = 'DELETE' \
+            THEN IF count((SELECT VALUE id FROM employee WHERE company = $before.id)) > 0 \
+            { THROW 'Cannot delete a record of company linked from employee.company' };"
+        );
+        assert_eq!(
+            on_delete_event(OnDelete::SetNull, false),
+            "DEFINE EVENT employee_company_on_delete ON TABLE company WHEN $event = 'DELETE' \
+            THEN UPDATE employee SET company = NULL WHERE company = $before.id;"
+        );
+        assert_eq!(
+            on_delete_event(OnDelete::Unset, false),
+            "DEFINE EVENT employee_company_on_delete ON TABLE company WHEN $event = 'DELETE' \
+            THEN UPDATE employee SET company = NONE WHERE company = $before.id;"
+        );
+    }
+
+    #[test]
+    fn test_on_delete_link_many_events() {
+        assert_eq!(
+            on_delete_event(OnDelete::Cascade, true),
+            "DEFINE EVENT employee_projects_on_delete ON TABLE company WHEN $event = 'DELETE' \
+            THEN DELETE employee WHERE projects CONTAINS $before.id;"
+        );
+        assert_eq!(
+            on_delete_event(OnDelete::Unset, true),
+            "DEFINE EVENT employee_projects_on_delete ON TABLE company WHEN $event = 'DELETE' \
+            THEN UPDATE employee SET projects -= $before.id WHERE projects CONTAINS $before.id;"
+        );
+    }
+}
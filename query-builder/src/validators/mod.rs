@@ -3,6 +3,8 @@ use crate::LinkOne;
 use crate::LinkSelf;
 use crate::Node;
 use crate::Object;
+use crate::ObjectEnum;
+use crate::SurrealType;
 // pub use crate::assert_fields;
 pub use num_traits::{Float, Num, PrimInt as Int};
 // pub use static_assertions::assert_fields;
@@ -336,7 +338,8 @@ pub fn assert_value_is_uuid<T: IsUuid>(_value: T) {}
 /// Validate that type is a Datetime at compile time
 pub trait IsDatetime {}
 
-impl IsDatetime for chrono::DateTime<chrono::Utc> {}
+impl<Tz: chrono::TimeZone> IsDatetime for chrono::DateTime<Tz> {}
+impl IsDatetime for crate::sql::Datetime {}
 
 /// Validate that type is a Datetime at compile time
 pub fn assert_type_is_datetime<T: IsDatetime>() {
@@ -518,6 +521,33 @@ pub fn assert_type_is_object<T: IsObject>() {
     // This function doesn't need to do anything; it's just here to enforce the type constraint.
 }
 
+/// Validate that type is a surrealdb orm enum at compile time
+pub trait IsObjectEnum {}
+
+impl<T: ObjectEnum> IsObjectEnum for T {}
+
+/// Validate that type is an enum deriving `Object` at compile time
+/// # Example
+/// ```rust,ignore
+/// # use surreal_query_builder as surreal_orm;
+/// use surreal_orm::validators::assert_type_is_enum;
+/// assert_type_is_enum::<AnEnum>();
+/// ```
+pub fn assert_type_is_enum<T: IsObjectEnum>() {
+    // This function doesn't need to do anything; it's just here to enforce the type constraint.
+}
+
+/// Validate that the database type of a type is known at compile time
+/// # Example
+/// ```
+/// # use surreal_query_builder as surreal_orm;
+/// use surreal_orm::validators::assert_type_is_surreal_type;
+/// assert_type_is_surreal_type::<Option<chrono::DateTime<chrono::Utc>>>();
+/// ```
+pub fn assert_type_is_surreal_type<T: SurrealType + ?Sized>() {
+    // This function doesn't need to do anything; it's just here to enforce the type constraint.
+}
+
 /// Validate that type is a surrealdb Node at compile time
 pub trait IsNode {}
 